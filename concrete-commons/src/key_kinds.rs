@@ -1,12 +1,16 @@
 //! This module contains types to manage the different kinds of secret keys.
 
 /// This type is a marker for keys using binary elements as scalar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BinaryKeyKind;
 /// This type is a marker for keys using ternary elements as scalar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TernaryKeyKind;
 /// This type is a marker for keys using normaly sampled elements as scalar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct GaussianKeyKind;
 /// This type is a marker for keys using uniformly sampled elements as scalar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct UniformKeyKind;
 
 /// In concrete, secret keys can be based on different kinds of scalar values (put aside the
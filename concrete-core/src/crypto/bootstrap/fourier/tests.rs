@@ -236,6 +236,100 @@ fn test_external_product_generic<T: UnsignedTorus + npe::Cross>() {
     }
 }
 
+fn test_external_product_key_kind<T, K, G>(generate_key: G)
+where
+    T: UnsignedTorus + npe::Cross,
+    K: npe::KeyDispersion,
+    G: Fn(GlweDimension, PolynomialSize, &mut SecretRandomGenerator) -> GlweSecretKey<K, Vec<T>>,
+{
+    // fix different polynomial degrees
+    let degrees = vec![512, 1024];
+    for polynomial_size in degrees {
+        // fix a set of parameters, with a decomposition coarse enough for the rounding of the
+        // mask times the key to dominate the output noise
+        let rlwe_dimension = GlweDimension(2);
+        let lwe_dimension = LweDimension(1);
+        let level = DecompositionLevelCount(3);
+        let base_log = DecompositionBaseLog(4);
+        let std_dev_bsk = LogStandardDev(-25.);
+        let std_dev_rlwe = LogStandardDev(-25.);
+
+        // We instantiate the random generators.
+        let mut random_generator = RandomGenerator::new(None);
+        let mut secret_generator = SecretRandomGenerator::new(None);
+        let mut encryption_generator = EncryptionRandomGenerator::new(None);
+
+        // the glwe secret key of kind K
+        let rlwe_sk = generate_key(
+            rlwe_dimension,
+            PolynomialSize(polynomial_size),
+            &mut secret_generator,
+        );
+
+        // We create a lwe secret key with one bit set to one
+        let lwe_sk = LweSecretKey::binary_from_container(vec![T::ONE]);
+
+        // create the polynomial to encrypt
+        let mut messages = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size));
+        random_generator.fill_tensor_with_random_uniform(&mut messages);
+        let mut new_messages = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size));
+
+        // generation of the key in the coefficient and fourier domains
+        let mut coef_bsk = StandardBootstrapKey::allocate(
+            T::ZERO,
+            rlwe_dimension.to_glwe_size(),
+            PolynomialSize(polynomial_size),
+            level,
+            base_log,
+            lwe_dimension,
+        );
+        coef_bsk.fill_with_new_key(&lwe_sk, &rlwe_sk, std_dev_bsk, &mut encryption_generator);
+        let mut fourier_bsk = FourierBootstrapKey::allocate(
+            Complex64::new(0., 0.),
+            rlwe_dimension.to_glwe_size(),
+            PolynomialSize(polynomial_size),
+            level,
+            base_log,
+            lwe_dimension,
+        );
+        fourier_bsk.fill_with_forward_fourier(&coef_bsk);
+
+        // encrypt, compute the external product and decrypt
+        let mut ciphertext = GlweCiphertext::allocate(
+            T::ZERO,
+            PolynomialSize(polynomial_size),
+            rlwe_dimension.to_glwe_size(),
+        );
+        let mut res = GlweCiphertext::allocate(
+            T::ZERO,
+            PolynomialSize(polynomial_size),
+            rlwe_dimension.to_glwe_size(),
+        );
+        rlwe_sk.encrypt_glwe(
+            &mut ciphertext,
+            &messages,
+            std_dev_rlwe,
+            &mut encryption_generator,
+        );
+        let rgsw = fourier_bsk.ggsw_iter().next().unwrap();
+        fourier_bsk.external_product(&mut res, &rgsw, &ciphertext);
+        rlwe_sk.decrypt_glwe(&mut new_messages, &res);
+
+        // call the NPE to find the theoritical amount of noise after the external product
+        let output_variance = <T as npe::Cross>::external_product_with_key_kind::<K>(
+            rlwe_dimension.0,
+            level.0,
+            base_log.0,
+            polynomial_size,
+            std_dev_bsk.get_variance(),
+            std_dev_rlwe.get_variance(),
+        );
+
+        // test
+        assert_noise_distribution(&new_messages, &messages, Variance(output_variance));
+    }
+}
+
 fn test_cmux_0<T: UnsignedTorus + npe::Cross>() {
     // fix different polynomial degrees
     let degrees = vec![512, 1024, 2048];
@@ -666,3 +760,23 @@ pub fn test_sample_extract_u32() {
 pub fn test_sample_extract_u64() {
     test_sample_extract::<u64>();
 }
+
+#[test]
+pub fn test_external_product_ternary_key_u32() {
+    test_external_product_key_kind::<u32, _, _>(GlweSecretKey::generate_ternary)
+}
+
+#[test]
+pub fn test_external_product_ternary_key_u64() {
+    test_external_product_key_kind::<u64, _, _>(GlweSecretKey::generate_ternary)
+}
+
+#[test]
+pub fn test_external_product_gaussian_key_u32() {
+    test_external_product_key_kind::<u32, _, _>(GlweSecretKey::generate_gaussian)
+}
+
+#[test]
+pub fn test_external_product_gaussian_key_u64() {
+    test_external_product_key_kind::<u64, _, _>(GlweSecretKey::generate_gaussian)
+}
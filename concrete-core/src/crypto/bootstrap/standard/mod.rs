@@ -7,7 +7,7 @@ use crate::math::tensor::{AsMutTensor, AsRefSlice, AsRefTensor, Tensor};
 use crate::math::torus::UnsignedTorus;
 use crate::{ck_dim_div, ck_dim_eq, tensor_traits, zip, zip_args};
 use concrete_commons::dispersion::DispersionParameter;
-use concrete_commons::key_kinds::BinaryKeyKind;
+use concrete_commons::key_kinds::{BinaryKeyKind, KeyKind};
 use concrete_commons::numeric::Numeric;
 use concrete_commons::parameters::{
     DecompositionBaseLog, DecompositionLevelCount, GlweSize, LweDimension, PolynomialSize,
@@ -167,16 +167,17 @@ impl<Cont> StandardBootstrapKey<Cont> {
     ///     &mut encryption_generator,
     /// );
     /// ```
-    pub fn fill_with_new_key<LweCont, RlweCont, Scalar>(
+    pub fn fill_with_new_key<LweCont, RlweCont, GlweKind, Scalar>(
         &mut self,
         lwe_secret_key: &LweSecretKey<BinaryKeyKind, LweCont>,
-        glwe_secret_key: &GlweSecretKey<BinaryKeyKind, RlweCont>,
+        glwe_secret_key: &GlweSecretKey<GlweKind, RlweCont>,
         noise_parameters: impl DispersionParameter,
         generator: &mut EncryptionRandomGenerator,
     ) where
         Self: AsMutTensor<Element = Scalar>,
         LweSecretKey<BinaryKeyKind, LweCont>: AsRefTensor<Element = Scalar>,
-        GlweSecretKey<BinaryKeyKind, RlweCont>: AsRefTensor<Element = Scalar>,
+        GlweSecretKey<GlweKind, RlweCont>: AsRefTensor<Element = Scalar>,
+        GlweKind: KeyKind,
         Scalar: UnsignedTorus,
     {
         ck_dim_eq!(self.key_size().0 => lwe_secret_key.key_size().0);
@@ -249,16 +250,17 @@ impl<Cont> StandardBootstrapKey<Cont> {
     /// );
     /// ```
     #[cfg(feature = "multithread")]
-    pub fn par_fill_with_new_key<LweCont, RlweCont, Scalar>(
+    pub fn par_fill_with_new_key<LweCont, RlweCont, GlweKind, Scalar>(
         &mut self,
         lwe_secret_key: &LweSecretKey<BinaryKeyKind, LweCont>,
-        glwe_secret_key: &GlweSecretKey<BinaryKeyKind, RlweCont>,
+        glwe_secret_key: &GlweSecretKey<GlweKind, RlweCont>,
         noise_parameters: impl DispersionParameter + Sync + Send,
         generator: &mut EncryptionRandomGenerator,
     ) where
         Self: AsMutTensor<Element = Scalar>,
         LweSecretKey<BinaryKeyKind, LweCont>: AsRefTensor<Element = Scalar>,
-        GlweSecretKey<BinaryKeyKind, RlweCont>: AsRefTensor<Element = Scalar>,
+        GlweSecretKey<GlweKind, RlweCont>: AsRefTensor<Element = Scalar>,
+        GlweKind: KeyKind,
         Scalar: UnsignedTorus + Sync + Send,
         RlweCont: Sync,
     {
@@ -324,16 +326,17 @@ impl<Cont> StandardBootstrapKey<Cont> {
     ///     &mut encryption_generator,
     /// );
     /// ```
-    pub fn fill_with_new_trivial_key<LweCont, RlweCont, Scalar>(
+    pub fn fill_with_new_trivial_key<LweCont, RlweCont, GlweKind, Scalar>(
         &mut self,
         lwe_secret_key: &LweSecretKey<BinaryKeyKind, LweCont>,
-        rlwe_secret_key: &GlweSecretKey<BinaryKeyKind, RlweCont>,
+        rlwe_secret_key: &GlweSecretKey<GlweKind, RlweCont>,
         noise_parameters: impl DispersionParameter,
         generator: &mut EncryptionRandomGenerator,
     ) where
         Self: AsMutTensor<Element = Scalar>,
         LweSecretKey<BinaryKeyKind, LweCont>: AsRefTensor<Element = Scalar>,
-        GlweSecretKey<BinaryKeyKind, RlweCont>: AsRefTensor<Element = Scalar>,
+        GlweSecretKey<GlweKind, RlweCont>: AsRefTensor<Element = Scalar>,
+        GlweKind: KeyKind,
         Scalar: UnsignedTorus,
     {
         ck_dim_eq!(self.key_size().0 => lwe_secret_key.key_size().0);
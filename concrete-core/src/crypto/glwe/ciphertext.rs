@@ -1,10 +1,14 @@
 use super::{GlweBody, GlweMask};
+use crate::crypto::encoding::PlaintextList;
 use crate::crypto::lwe::LweCiphertext;
+use crate::crypto::secret::GlweSecretKey;
 use crate::math::polynomial::{MonomialDegree, PolynomialList};
 use crate::math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor, Tensor};
 use crate::math::torus::UnsignedTorus;
 use crate::tensor_traits;
-use concrete_commons::parameters::{GlweDimension, GlweSize, PolynomialSize};
+use concrete_commons::key_kinds::KeyKind;
+use concrete_commons::numeric::{CastInto, Numeric};
+use concrete_commons::parameters::{GlweDimension, GlweSize, PlaintextCount, PolynomialSize};
 use serde::{Deserialize, Serialize};
 
 /// An GLWE ciphertext.
@@ -451,4 +455,63 @@ impl<Cont> GlweCiphertext<Cont> {
             lwe_mask_poly.rotate_left(negated_count);
         }
     }
+
+    /// Computes the errors of the coefficients of the ciphertext, i.e. the differences between
+    /// the coefficients of its phase and the encoded messages, as torus elements in [-1/2, 1/2[.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use concrete_commons::dispersion::LogStandardDev;
+    /// use concrete_commons::parameters::{GlweDimension, PolynomialSize};
+    /// use concrete_core::crypto::encoding::PlaintextList;
+    /// use concrete_core::crypto::glwe::GlweCiphertext;
+    /// use concrete_core::crypto::secret::generators::{
+    ///     EncryptionRandomGenerator, SecretRandomGenerator,
+    /// };
+    /// use concrete_core::crypto::secret::GlweSecretKey;
+    ///
+    /// let mut secret_generator = SecretRandomGenerator::new(None);
+    /// let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    /// let poly_size = PolynomialSize(4);
+    /// let glwe_dim = GlweDimension(2);
+    /// let secret_key = GlweSecretKey::generate_binary(glwe_dim, poly_size, &mut secret_generator);
+    /// let encoded = PlaintextList::from_container(vec![100000 as u32, 200000, 300000, 400000]);
+    /// let mut ciphertext = GlweCiphertext::allocate(0u32, poly_size, glwe_dim.to_glwe_size());
+    /// secret_key.encrypt_glwe(
+    ///     &mut ciphertext,
+    ///     &encoded,
+    ///     LogStandardDev::from_log_standard_dev(-20.),
+    ///     &mut encryption_generator,
+    /// );
+    /// let errors = ciphertext.compute_errors(&secret_key, &encoded);
+    /// assert_eq!(errors.len(), 4);
+    /// assert!(errors.iter().all(|error| error.abs() < f64::powi(2., -13)));
+    /// ```
+    pub fn compute_errors<Kind, KeyCont, EncCont, Scalar>(
+        &self,
+        key: &GlweSecretKey<Kind, KeyCont>,
+        encoded: &PlaintextList<EncCont>,
+    ) -> Vec<f64>
+    where
+        Self: AsRefTensor<Element = Scalar>,
+        GlweSecretKey<Kind, KeyCont>: AsRefTensor<Element = Scalar>,
+        PlaintextList<EncCont>: AsRefTensor<Element = Scalar>,
+        Kind: KeyKind,
+        Scalar: UnsignedTorus,
+    {
+        let mut phases =
+            PlaintextList::allocate(<Scalar as Numeric>::ZERO, PlaintextCount(self.poly_size.0));
+        key.decrypt_glwe(&mut phases, self);
+        phases
+            .plaintext_iter()
+            .zip(encoded.plaintext_iter())
+            .map(|(phase, message)| {
+                let error: f64 = <Scalar::Signed as CastInto<f64>>::cast_into(
+                    phase.0.wrapping_sub(message.0).into_signed(),
+                );
+                error / f64::powi(2., <Scalar as Numeric>::BITS as i32)
+            })
+            .collect()
+    }
 }
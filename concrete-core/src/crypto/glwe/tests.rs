@@ -1,12 +1,18 @@
-use crate::crypto::encoding::PlaintextList;
-use crate::crypto::glwe::GlweList;
+use concrete_npe as npe;
+
+use crate::crypto::encoding::{Plaintext, PlaintextList};
+use crate::crypto::glwe::{GlweCiphertext, GlweList};
+use crate::crypto::lwe::LweCiphertext;
 use crate::crypto::secret::generators::{EncryptionRandomGenerator, SecretRandomGenerator};
 use crate::crypto::secret::GlweSecretKey;
+use crate::math::polynomial::{MonomialDegree, Polynomial};
 use crate::math::random::RandomGenerator;
+use crate::math::tensor::{AsMutTensor, AsRefSlice, AsRefTensor};
 use crate::math::torus::UnsignedTorus;
 use crate::test_tools;
-use crate::test_tools::assert_delta_std_dev;
-use concrete_commons::dispersion::LogStandardDev;
+use crate::test_tools::{assert_delta_std_dev, assert_noise_distribution};
+use concrete_commons::dispersion::{DispersionParameter, LogStandardDev, Variance};
+use concrete_commons::parameters::{GlweDimension, LweSize, PlaintextCount, PolynomialSize};
 
 fn test_glwe<T: UnsignedTorus>() {
     // random settings
@@ -54,3 +60,220 @@ fn test_glwe_encrypt_decrypt_u32() {
 fn test_glwe_encrypt_decrypt_u64() {
     test_glwe::<u64>();
 }
+
+fn test_glwe_polynomial_mult<T: UnsignedTorus + npe::RLWE>() {
+    //! multiply a GLWE by a polynomial with small signed coefficients
+    let dimension = GlweDimension(2);
+    let polynomial_size = PolynomialSize(1024);
+    let noise_parameter = LogStandardDev::from_log_standard_dev(-25.);
+    let mut random_generator = RandomGenerator::new(None);
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    let sk = GlweSecretKey::generate_binary(dimension, polynomial_size, &mut secret_generator);
+
+    // encrypt random messages
+    let messages =
+        PlaintextList::from_tensor(random_generator.random_uniform_tensor(polynomial_size.0));
+    let mut ciphertext =
+        GlweCiphertext::allocate(T::ZERO, polynomial_size, dimension.to_glwe_size());
+    sk.encrypt_glwe(
+        &mut ciphertext,
+        &messages,
+        noise_parameter,
+        &mut encryption_generator,
+    );
+
+    // a polynomial with coefficients in [-8,8]
+    let weights: Vec<T> = (0..polynomial_size.0)
+        .map(|_| {
+            test_tools::random_uint_between(T::ZERO..T::cast_from(17.))
+                .wrapping_sub(T::cast_from(8.))
+        })
+        .collect();
+    let weights = Polynomial::from_container(weights);
+
+    // multiply the ciphertext and the messages
+    let mut tmp = Polynomial::allocate(T::ZERO, polynomial_size);
+    for mut polynomial in ciphertext.as_mut_polynomial_list().polynomial_iter_mut() {
+        tmp.as_mut_tensor().fill_with_copy(polynomial.as_tensor());
+        polynomial.fill_with_wrapping_mul(&tmp, &weights);
+    }
+    let mut expected = Polynomial::allocate(T::ZERO, polynomial_size);
+    expected.fill_with_wrapping_mul(
+        &Polynomial::from_container(messages.as_tensor().as_slice()),
+        &weights,
+    );
+
+    // decrypt
+    let mut decryptions = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size.0));
+    sk.decrypt_glwe(&mut decryptions, &ciphertext);
+
+    // the slots all have the same variance
+    let output_variances = <T as npe::RLWE>::polynomial_mult(
+        &vec![noise_parameter.get_variance(); polynomial_size.0],
+        weights.as_tensor().as_slice(),
+    );
+    assert_noise_distribution(
+        &expected,
+        &decryptions,
+        Variance::from_variance(output_variances[0]),
+    );
+}
+
+#[test]
+fn test_glwe_polynomial_mult_u32() {
+    test_glwe_polynomial_mult::<u32>();
+}
+
+#[test]
+fn test_glwe_polynomial_mult_u64() {
+    test_glwe_polynomial_mult::<u64>();
+}
+
+fn test_glwe_monomial_mult_x_sample_extract<T: UnsignedTorus>() {
+    //! rotate a GLWE with a monomial then sample extract all its slots
+    let dimension = GlweDimension(2);
+    let polynomial_size = PolynomialSize(1024);
+    let noise_parameter = LogStandardDev::from_log_standard_dev(-25.);
+    let mut random_generator = RandomGenerator::new(None);
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    let sk = GlweSecretKey::generate_binary(dimension, polynomial_size, &mut secret_generator);
+
+    // encrypt random messages
+    let messages =
+        PlaintextList::from_tensor(random_generator.random_uniform_tensor(polynomial_size.0));
+    let mut ciphertext =
+        GlweCiphertext::allocate(T::ZERO, polynomial_size, dimension.to_glwe_size());
+    sk.encrypt_glwe(
+        &mut ciphertext,
+        &messages,
+        noise_parameter,
+        &mut encryption_generator,
+    );
+
+    // rotate the ciphertext and the messages
+    let degree =
+        MonomialDegree(test_tools::random_uint_between(0..2 * polynomial_size.0 as u64) as usize);
+    ciphertext
+        .as_mut_polynomial_list()
+        .update_with_wrapping_monic_monomial_mul(degree);
+    let mut expected = Polynomial::from_container(messages.as_tensor().as_slice().to_vec());
+    expected.update_with_wrapping_monic_monomial_mul(degree);
+    let variances = npe::rlwe::monomial_mult(
+        &vec![noise_parameter.get_variance(); polynomial_size.0],
+        degree.0,
+    );
+
+    // sample extract and decrypt every slot
+    let lwe_sk = sk.into_lwe_secret_key();
+    let mut lwe = LweCiphertext::allocate(T::ZERO, LweSize(dimension.0 * polynomial_size.0 + 1));
+    let mut decryptions = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size.0));
+    for (i, decryption) in decryptions.plaintext_iter_mut().enumerate() {
+        ciphertext.fill_lwe_with_sample_extraction(&mut lwe, MonomialDegree(i));
+        let mut plaintext = Plaintext(T::ZERO);
+        lwe_sk.decrypt_lwe(&mut plaintext, &lwe);
+        *decryption = plaintext;
+    }
+
+    // the rotation and the extraction do not change the variances
+    assert_noise_distribution(
+        &expected,
+        &decryptions,
+        Variance::from_variance(npe::rlwe::sample_extract(&variances, 0)),
+    );
+}
+
+#[test]
+fn test_glwe_monomial_mult_x_sample_extract_u32() {
+    test_glwe_monomial_mult_x_sample_extract::<u32>();
+}
+
+#[test]
+fn test_glwe_monomial_mult_x_sample_extract_u64() {
+    test_glwe_monomial_mult_x_sample_extract::<u64>();
+}
+
+fn test_glwe_add_different_keys<T: UnsignedTorus>() {
+    //! add two GLWE encrypted under different keys, the result being encrypted under the
+    //! concatenation of the keys
+    let (dimension_0, dimension_1) = (GlweDimension(1), GlweDimension(2));
+    let polynomial_size = PolynomialSize(1024);
+    let (noise_0, noise_1) = (
+        LogStandardDev::from_log_standard_dev(-25.),
+        LogStandardDev::from_log_standard_dev(-24.),
+    );
+    let mut random_generator = RandomGenerator::new(None);
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    let sk_0 = GlweSecretKey::generate_binary(dimension_0, polynomial_size, &mut secret_generator);
+    let sk_1 = GlweSecretKey::generate_binary(dimension_1, polynomial_size, &mut secret_generator);
+
+    // encrypt random messages under each key
+    let messages_0 =
+        PlaintextList::from_tensor(random_generator.random_uniform_tensor(polynomial_size.0));
+    let messages_1 =
+        PlaintextList::from_tensor(random_generator.random_uniform_tensor(polynomial_size.0));
+    let mut ciphertext_0 =
+        GlweCiphertext::allocate(T::ZERO, polynomial_size, dimension_0.to_glwe_size());
+    let mut ciphertext_1 =
+        GlweCiphertext::allocate(T::ZERO, polynomial_size, dimension_1.to_glwe_size());
+    sk_0.encrypt_glwe(
+        &mut ciphertext_0,
+        &messages_0,
+        noise_0,
+        &mut encryption_generator,
+    );
+    sk_1.encrypt_glwe(
+        &mut ciphertext_1,
+        &messages_1,
+        noise_1,
+        &mut encryption_generator,
+    );
+
+    // concatenate the masks and add the bodies
+    let mut container: Vec<T> = ciphertext_0.get_mask().as_tensor().as_slice().to_vec();
+    container.extend_from_slice(ciphertext_1.get_mask().as_tensor().as_slice());
+    container.extend(
+        ciphertext_0
+            .get_body()
+            .as_tensor()
+            .iter()
+            .zip(ciphertext_1.get_body().as_tensor().iter())
+            .map(|(b_0, b_1)| b_0.wrapping_add(*b_1)),
+    );
+    let ciphertext = GlweCiphertext::from_container(container, polynomial_size);
+    let mut key: Vec<T> = sk_0.as_tensor().as_slice().to_vec();
+    key.extend_from_slice(sk_1.as_tensor().as_slice());
+    let sk = GlweSecretKey::binary_from_container(key, polynomial_size);
+
+    // decrypt with the concatenated key
+    let mut decryptions = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size.0));
+    sk.decrypt_glwe(&mut decryptions, &ciphertext);
+    let expected: Vec<T> = messages_0
+        .as_tensor()
+        .iter()
+        .zip(messages_1.as_tensor().iter())
+        .map(|(m_0, m_1)| m_0.wrapping_add(*m_1))
+        .collect();
+
+    let variances = npe::rlwe::add_different_keys(
+        &vec![noise_0.get_variance(); polynomial_size.0],
+        &vec![noise_1.get_variance(); polynomial_size.0],
+    );
+    assert_noise_distribution(
+        &PlaintextList::from_container(expected),
+        &decryptions,
+        Variance::from_variance(variances[0]),
+    );
+}
+
+#[test]
+fn test_glwe_add_different_keys_u32() {
+    test_glwe_add_different_keys::<u32>();
+}
+
+#[test]
+fn test_glwe_add_different_keys_u64() {
+    test_glwe_add_different_keys::<u64>();
+}
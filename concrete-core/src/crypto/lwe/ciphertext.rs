@@ -10,7 +10,7 @@ use crate::crypto::glwe::GlweCiphertext;
 use crate::math::polynomial::MonomialDegree;
 use crate::math::torus::UnsignedTorus;
 use concrete_commons::key_kinds::KeyKind;
-use concrete_commons::numeric::{Numeric, UnsignedInteger};
+use concrete_commons::numeric::{CastInto, Numeric, UnsignedInteger};
 use concrete_commons::parameters::{LweDimension, LweSize};
 
 /// A ciphertext encrypted using the LWE scheme.
@@ -600,6 +600,54 @@ impl<Cont> LweCiphertext<Cont> {
     {
         glwe.fill_lwe_with_sample_extraction(self, n_th);
     }
+
+    /// Computes the error of the ciphertext, i.e. the difference between its phase and the
+    /// encoded message, as a torus element in [-1/2, 1/2[.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use concrete_commons::dispersion::LogStandardDev;
+    /// use concrete_commons::parameters::LweDimension;
+    /// use concrete_core::crypto::encoding::Plaintext;
+    /// use concrete_core::crypto::lwe::LweCiphertext;
+    /// use concrete_core::crypto::secret::generators::{
+    ///     EncryptionRandomGenerator, SecretRandomGenerator,
+    /// };
+    /// use concrete_core::crypto::secret::LweSecretKey;
+    ///
+    /// let mut secret_generator = SecretRandomGenerator::new(None);
+    /// let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    /// let secret_key = LweSecretKey::generate_binary(LweDimension(256), &mut secret_generator);
+    /// let encoded = Plaintext(1u32 << 30);
+    /// let mut ciphertext = LweCiphertext::allocate(0u32, LweDimension(256).to_lwe_size());
+    /// secret_key.encrypt_lwe(
+    ///     &mut ciphertext,
+    ///     &encoded,
+    ///     LogStandardDev::from_log_standard_dev(-20.),
+    ///     &mut encryption_generator,
+    /// );
+    /// let error = ciphertext.compute_error(&secret_key, &encoded);
+    /// assert!(error.abs() < f64::powi(2., -13));
+    /// ```
+    pub fn compute_error<Kind, KeyCont, Scalar>(
+        &self,
+        key: &LweSecretKey<Kind, KeyCont>,
+        encoded: &Plaintext<Scalar>,
+    ) -> f64
+    where
+        Self: AsRefTensor<Element = Scalar>,
+        LweSecretKey<Kind, KeyCont>: AsRefTensor<Element = Scalar>,
+        Kind: KeyKind,
+        Scalar: UnsignedTorus,
+    {
+        let mut phase = Plaintext(<Scalar as Numeric>::ZERO);
+        key.decrypt_lwe(&mut phase, self);
+        let error: f64 = <Scalar::Signed as CastInto<f64>>::cast_into(
+            phase.0.wrapping_sub(encoded.0).into_signed(),
+        );
+        error / f64::powi(2., <Scalar as Numeric>::BITS as i32)
+    }
 }
 
 /// The mask of an LWE encrypted ciphertext.
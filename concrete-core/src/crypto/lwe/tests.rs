@@ -12,6 +12,7 @@ use crate::test_tools::{
     random_uint_between,
 };
 use concrete_commons::dispersion::{DispersionParameter, LogStandardDev, Variance};
+use concrete_commons::key_kinds::UniformKeyKind;
 use concrete_commons::numeric::{CastFrom, Numeric, SignedInteger};
 use concrete_commons::parameters::{
     CiphertextCount, CleartextCount, DecompositionBaseLog, DecompositionLevelCount, LweDimension,
@@ -110,6 +111,108 @@ fn test_keyswitch_u64() {
     test_keyswitch::<u64>();
 }
 
+fn test_keyswitch_key_kind<T, K, G>(generate_key: G)
+where
+    T: UnsignedTorus + RandomGenerable<UniformMsb> + npe::LWE,
+    K: npe::KeyDispersion,
+    G: Fn(LweDimension, &mut SecretRandomGenerator) -> LweSecretKey<K, Vec<T>>,
+{
+    //! key switch some LWE samples encrypted under a key of kind K, with a decomposition coarse
+    //! enough for the rounding of the input mask times the key to dominate the output noise
+    let mut random_generator = RandomGenerator::new(None);
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+
+    // fix a set of parameters
+    let n_bit_msg = 8; // bit precision of the plaintext
+    let nb_ct = CiphertextCount(100); // number of messages to encrypt
+    let base_log = DecompositionBaseLog(3); // a parameter of the gadget matrix
+    let level_count = DecompositionLevelCount(4); // a parameter of the gadget matrix
+    let messages = PlaintextList::from_tensor(
+        random_generator.random_uniform_n_msb_tensor(nb_ct.0, n_bit_msg),
+    );
+    let std_input = LogStandardDev::from_log_standard_dev(-25.);
+    let std_ksk = LogStandardDev::from_log_standard_dev(-25.);
+
+    // the keys
+    let dimension_after = LweDimension(600);
+    let sk_after = LweSecretKey::generate_binary(dimension_after, &mut secret_generator);
+    let dimension_before = LweDimension(1024);
+    let sk_before = generate_key(dimension_before, &mut secret_generator);
+
+    // key switching key generation
+    let mut ciphertexts_before = LweList::allocate(T::ZERO, dimension_before.to_lwe_size(), nb_ct);
+    let mut ciphertexts_after = LweList::allocate(T::ZERO, dimension_after.to_lwe_size(), nb_ct);
+    let mut ksk = LweKeyswitchKey::allocate(
+        T::ZERO,
+        level_count,
+        base_log,
+        dimension_before,
+        dimension_after,
+    );
+    ksk.fill_with_keyswitch_key(&sk_before, &sk_after, std_ksk, &mut encryption_generator);
+
+    // encrypt, key switch and decrypt
+    sk_before.encrypt_lwe_list(
+        &mut ciphertexts_before,
+        &messages,
+        std_input,
+        &mut encryption_generator,
+    );
+    ksk.keyswitch_list(&mut ciphertexts_after, &ciphertexts_before);
+    let mut dec_messages = PlaintextList::allocate(T::ZERO, PlaintextCount(nb_ct.0));
+    sk_after.decrypt_lwe_list(&mut dec_messages, &ciphertexts_after);
+
+    // calls the NPE to find out the amount of noise after KS
+    let output_variance = <T as npe::LWE>::key_switch_with_key_kind::<K>(
+        dimension_before.0,
+        level_count.0,
+        base_log.0,
+        std_ksk.get_variance(),
+        std_input.get_variance(),
+    );
+
+    assert_noise_distribution(
+        &messages,
+        &dec_messages,
+        Variance::from_variance(output_variance),
+    );
+}
+
+#[test]
+fn test_keyswitch_ternary_key_u32() {
+    test_keyswitch_key_kind::<u32, _, _>(LweSecretKey::generate_ternary);
+}
+
+#[test]
+fn test_keyswitch_ternary_key_u64() {
+    test_keyswitch_key_kind::<u64, _, _>(LweSecretKey::generate_ternary);
+}
+
+#[test]
+fn test_keyswitch_gaussian_key_u32() {
+    test_keyswitch_key_kind::<u32, _, _>(LweSecretKey::generate_gaussian);
+}
+
+#[test]
+fn test_keyswitch_gaussian_key_u64() {
+    test_keyswitch_key_kind::<u64, _, _>(LweSecretKey::generate_gaussian);
+}
+
+#[test]
+fn test_keyswitch_uniform_key_noise() {
+    //! the decomposition is never exact, so the rounding of the input mask times a uniform key
+    //! makes the output noise larger than the one of a uniform torus element
+    let output_variance = <u64 as npe::LWE>::key_switch_with_key_kind::<UniformKeyKind>(
+        630,
+        7,
+        9,
+        f64::powi(2., -60),
+        f64::powi(2., -60),
+    );
+    assert!(output_variance > 1. / 12.);
+}
+
 fn test_encrypt_decrypt<T: UnsignedTorus>() {
     //! encrypts a bunch of messages and decrypts them
     //! warning: std_dev is not randomized
@@ -163,6 +266,55 @@ fn test_encrypt_decrypt_u64() {
     test_encrypt_decrypt::<u64>()
 }
 
+fn test_restored_generators<T: UnsignedTorus>() {
+    //! generates a key and encrypts a message with generators restored from exported states
+    //! and checks that the very same key and ciphertext are obtained
+    let dimension = random_lwe_dimension(1000);
+    let std_dev = LogStandardDev::from_log_standard_dev(-25.);
+    let mut random_generator = RandomGenerator::new(None);
+    let message = Plaintext(random_generator.random_uniform::<T>());
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+
+    // export the states before any use
+    let secret_state = secret_generator.export_state();
+    let encryption_state = encryption_generator.export_state();
+
+    let sk = LweSecretKey::generate_binary(dimension, &mut secret_generator);
+    let mut ciphertext = LweCiphertext::allocate(T::ZERO, dimension.to_lwe_size());
+    sk.encrypt_lwe(
+        &mut ciphertext,
+        &message,
+        std_dev,
+        &mut encryption_generator,
+    );
+
+    // restore the generators and do it again
+    let mut secret_generator = SecretRandomGenerator::from_state(&secret_state);
+    let mut encryption_generator = EncryptionRandomGenerator::from_state(&encryption_state);
+    let restored_sk = LweSecretKey::generate_binary(dimension, &mut secret_generator);
+    let mut restored_ciphertext = LweCiphertext::allocate(T::ZERO, dimension.to_lwe_size());
+    restored_sk.encrypt_lwe(
+        &mut restored_ciphertext,
+        &message,
+        std_dev,
+        &mut encryption_generator,
+    );
+
+    assert_eq!(sk, restored_sk);
+    assert_eq!(ciphertext, restored_ciphertext);
+}
+
+#[test]
+fn test_restored_generators_u32() {
+    test_restored_generators::<u32>()
+}
+
+#[test]
+fn test_restored_generators_u64() {
+    test_restored_generators::<u64>()
+}
+
 fn test_multisum_npe<T>()
 where
     T: UnsignedTorus + RandomGenerable<UniformMsb> + npe::LWE + CastFrom<usize>,
@@ -1,4 +1,6 @@
-use crate::math::random::{Gaussian, RandomGenerable, RandomGenerator, Uniform};
+use crate::math::random::{
+    Gaussian, RandomGenerable, RandomGenerator, RandomGeneratorState, Uniform,
+};
 use crate::math::tensor::AsMutTensor;
 
 use concrete_commons::dispersion::DispersionParameter;
@@ -8,6 +10,16 @@ use concrete_commons::parameters::{
 };
 #[cfg(feature = "multithread")]
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The state of an [`EncryptionRandomGenerator`], from which it can be restored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptionRandomGeneratorState {
+    /// The state of the generator of the mask elements.
+    pub mask: RandomGeneratorState,
+    /// The state of the generator of the noise elements.
+    pub noise: RandomGeneratorState,
+}
 
 /// A random number generator which can be used to encrypt messages.
 pub struct EncryptionRandomGenerator {
@@ -26,6 +38,37 @@ impl EncryptionRandomGenerator {
         }
     }
 
+    /// Creates a new encryption generator whose mask and noise generators are both seeded.
+    ///
+    /// Every encryption performed with this generator is reproducible, which is only meant for
+    /// test fixtures: anyone knowing the noise seed can remove the noise of the ciphertexts.
+    pub fn new_deterministic(mask_seed: u128, noise_seed: u128) -> EncryptionRandomGenerator {
+        EncryptionRandomGenerator {
+            mask: RandomGenerator::new(Some(mask_seed)),
+            noise: RandomGenerator::new(Some(noise_seed)),
+        }
+    }
+
+    /// Restores a generator from a state exported with
+    /// [`EncryptionRandomGenerator::export_state`].
+    pub fn from_state(state: &EncryptionRandomGeneratorState) -> EncryptionRandomGenerator {
+        EncryptionRandomGenerator {
+            mask: RandomGenerator::from_state(&state.mask),
+            noise: RandomGenerator::from_state(&state.noise),
+        }
+    }
+
+    /// Exports the state of the generator, e.g. to checkpoint a long key generation.
+    ///
+    /// The state contains the seed of the noise generator, and must be kept as secret as the
+    /// secret keys.
+    pub fn export_state(&self) -> EncryptionRandomGeneratorState {
+        EncryptionRandomGeneratorState {
+            mask: self.mask.export_state(),
+            noise: self.noise.export_state(),
+        }
+    }
+
     // Allows to seed the noise generator. For testing purpose only.
     #[allow(dead_code)]
     pub(crate) fn seed_noise_generator(&mut self, seed: u128) {
@@ -1,4 +1,4 @@
-use crate::math::random::{Gaussian, RandomGenerable, RandomGenerator};
+use crate::math::random::{Gaussian, RandomGenerable, RandomGenerator, RandomGeneratorState};
 use crate::math::tensor::Tensor;
 use crate::math::torus::UnsignedTorus;
 use concrete_commons::dispersion::DispersionParameter;
@@ -12,6 +12,20 @@ impl SecretRandomGenerator {
         SecretRandomGenerator(RandomGenerator::new(seed))
     }
 
+    /// Restores a generator from a state exported with
+    /// [`SecretRandomGenerator::export_state`].
+    pub fn from_state(state: &RandomGeneratorState) -> SecretRandomGenerator {
+        SecretRandomGenerator(RandomGenerator::from_state(state))
+    }
+
+    /// Exports the state of the generator, e.g. to checkpoint a long key generation.
+    ///
+    /// The state allows to regenerate the secret keys sampled afterwards, and must be kept as
+    /// secret as them.
+    pub fn export_state(&self) -> RandomGeneratorState {
+        self.0.export_state()
+    }
+
     /// Returns the number of remaining bytes, if the generator is bounded.
     pub fn remaining_bytes(&self) -> Option<usize> {
         self.0.remaining_bytes()
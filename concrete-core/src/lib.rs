@@ -117,6 +117,9 @@ pub mod crypto;
 pub mod math;
 pub mod utils;
 
+#[cfg(test)]
+mod noise_calibration;
+
 #[doc(hidden)]
 #[cfg(test)]
 pub mod test_tools {
@@ -138,7 +141,7 @@ pub mod test_tools {
         std::cmp::min(d0, d1)
     }
 
-    fn torus_modular_distance<T: UnsignedInteger>(first: T, other: T) -> f64 {
+    pub fn torus_modular_distance<T: UnsignedInteger>(first: T, other: T) -> f64 {
         let d0 = first.wrapping_sub(other);
         let d1 = other.wrapping_sub(first);
         if d0 < d1 {
@@ -31,9 +31,7 @@ macro_rules! implement_gaussian {
                             n_bytes,
                         )
                     };
-                    uniform_rand_bytes
-                        .iter_mut()
-                        .for_each(|a| *a = generator.generate_next());
+                    generator.fill_bytes(uniform_rand_bytes);
                     let size = <$T>::BITS as i32;
                     let mut u: $T = uniform_rand[0].cast_into();
                     u *= <$T>::TWO.powi(-size + 1);
@@ -5,6 +5,8 @@ use crate::math::random::{
 use crate::math::tensor::{AsMutSlice, AsMutTensor, Tensor};
 use concrete_commons::numeric::{FloatingPoint, Numeric};
 use concrete_csprng::RandomGenerator as RandomGeneratorImpl;
+pub use concrete_csprng::RandomGeneratorState;
+use rand_core::{CryptoRng, RngCore};
 #[cfg(feature = "multithread")]
 use rayon::prelude::*;
 
@@ -56,6 +58,10 @@ impl RandomGenerator {
         self.0.generate_next()
     }
 
+    pub(crate) fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
     /// Generates a new generator, optionally seeding it with the given value.
     ///
     /// # Example
@@ -69,6 +75,69 @@ impl RandomGenerator {
         RandomGenerator(RandomGeneratorImpl::new(seed))
     }
 
+    /// Generates a new generator, seeded with bytes drawn from another cryptographically secure
+    /// generator.
+    ///
+    /// This allows tests to derive every generator they use from a single source of randomness.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_core::math::random::RandomGenerator;
+    /// use rand_core::SeedableRng;
+    /// let mut source = concrete_csprng::RandomGenerator::from_seed([0; 16]);
+    /// let mut generator = RandomGenerator::from_rng(&mut source);
+    /// let mut other_source = concrete_csprng::RandomGenerator::from_seed([0; 16]);
+    /// let mut other_generator = RandomGenerator::from_rng(&mut other_source);
+    /// assert_eq!(
+    ///     generator.random_uniform::<u64>(),
+    ///     other_generator.random_uniform::<u64>()
+    /// );
+    /// ```
+    pub fn from_rng<R: RngCore + CryptoRng>(rng: &mut R) -> RandomGenerator {
+        let mut seed = [0; 16];
+        rng.fill_bytes(&mut seed);
+        RandomGenerator::new(Some(u128::from_le_bytes(seed)))
+    }
+
+    /// Restores a generator from a state exported with [`RandomGenerator::export_state`].
+    ///
+    /// The restored generator yields the very same values as the exported one would have.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_core::math::random::RandomGenerator;
+    /// let mut generator = RandomGenerator::new(None);
+    /// let state = generator.export_state();
+    /// let mut restored = RandomGenerator::from_state(&state);
+    /// assert_eq!(
+    ///     generator.random_uniform::<u64>(),
+    ///     restored.random_uniform::<u64>()
+    /// );
+    /// ```
+    pub fn from_state(state: &RandomGeneratorState) -> RandomGenerator {
+        RandomGenerator(RandomGeneratorImpl::from_state(state))
+    }
+
+    /// Exports the state of the generator, e.g. to checkpoint a long computation.
+    ///
+    /// The state contains the secret seed of the generator, and must be kept as secret as the
+    /// values sampled with the generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use concrete_core::math::random::RandomGenerator;
+    /// let mut generator = RandomGenerator::new(Some(0));
+    /// let _ = generator.random_uniform::<u64>();
+    /// let state = generator.export_state();
+    /// assert_eq!(state.key, 0);
+    /// ```
+    pub fn export_state(&self) -> RandomGeneratorState {
+        self.0.export_state()
+    }
+
     /// Returns the number of bytes that can still be generated, if the generator is bounded.
     ///
     /// # Example
@@ -99,6 +168,52 @@ impl RandomGenerator {
         self.0.is_bounded()
     }
 
+    /// Moves the generator forward to the byte at the given offset from the start of its
+    /// keystream.
+    ///
+    /// The generators created with `RandomGenerator::new` start at the beginning of their
+    /// keystream. The generator must be unbounded, and the offset must not be behind the bytes
+    /// already generated or forked, otherwise the method panics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use concrete_core::math::random::RandomGenerator;
+    /// let mut reference = RandomGenerator::new(Some(0));
+    /// let _ = reference.random_uniform::<u64>();
+    /// let value = reference.random_uniform::<u64>();
+    /// let mut generator = RandomGenerator::new(Some(0));
+    /// generator.seek(8);
+    /// assert_eq!(generator.random_uniform::<u64>(), value);
+    /// ```
+    pub fn seek(&mut self, offset: usize) {
+        self.0.seek(offset)
+    }
+
+    /// Returns a generator bounded to the `length` bytes following the byte at the given offset
+    /// from the start of the keystream, and moves the current generator past it.
+    ///
+    /// This allows to generate a part of a seeded keystream without generating what comes before,
+    /// e.g. the mask of a single ciphertext of a list. If the current generator is bounded, or if
+    /// the window starts behind the bytes already generated or forked, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use concrete_core::math::random::RandomGenerator;
+    /// let mut reference = RandomGenerator::new(Some(0));
+    /// let values: Vec<u64> = (0..10).map(|_| reference.random_uniform()).collect();
+    /// let mut generator = RandomGenerator::new(Some(0));
+    /// let mut window = generator.generator_at(8 * 5, 8).unwrap();
+    /// assert_eq!(window.remaining_bytes(), Some(8));
+    /// assert_eq!(window.random_uniform::<u64>(), values[5]);
+    /// assert_eq!(generator.random_uniform::<u64>(), values[6]);
+    /// assert!(generator.generator_at(0, 8).is_none());
+    /// ```
+    pub fn generator_at(&mut self, offset: usize, length: usize) -> Option<RandomGenerator> {
+        self.0.generator_at(offset, length).map(Self)
+    }
+
     /// Tries to fork the current generator into `n_child` generator bounded to `bytes_per_child`.
     /// If `n_child*bytes_per_child` exceeds the bound of the current generator, the method
     /// returns `None`.
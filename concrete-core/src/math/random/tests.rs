@@ -83,3 +83,22 @@ fn test_distribution_u32() {
 fn test_distribution_u64() {
     test_distribution::<u64>();
 }
+
+#[test]
+fn test_from_rng() {
+    //! generators seeded from identical sources yield identical values
+    use rand_core::SeedableRng;
+    let seed = RandomGenerator::new(None)
+        .random_uniform::<u128>()
+        .to_le_bytes();
+    let mut first =
+        RandomGenerator::from_rng(&mut concrete_csprng::RandomGenerator::from_seed(seed));
+    let mut second =
+        RandomGenerator::from_rng(&mut concrete_csprng::RandomGenerator::from_seed(seed));
+    for _ in 0..100 {
+        assert_eq!(
+            first.random_uniform::<u64>(),
+            second.random_uniform::<u64>()
+        );
+    }
+}
@@ -10,8 +10,8 @@ macro_rules! implement_uniform {
         impl RandomGenerable<Uniform> for $T {
             #[allow(unused)]
             fn generate_one(generator: &mut RandomGenerator, distribution: Uniform) -> Self {
-                let mut buf = [0; $bytes];
-                buf.iter_mut().for_each(|a| *a = generator.generate_next());
+                let mut buf = [0u8; $bytes];
+                generator.fill_bytes(&mut buf);
                 unsafe { *(buf.as_ptr() as *const $T) }
             }
         }
@@ -0,0 +1,602 @@
+//! Calibration of the noise formulas of `concrete-npe` against measurements.
+//!
+//! The `noise_calibration` test sweeps the key switch, the external product, the cmux and the
+//! bootstrap over a grid of parameters, measures the mean and the variance of the error of
+//! thousands of samples, and compares them with the predictions of `concrete-npe`. A csv report
+//! is written, and the test fails when a prediction lies outside the confidence interval of the
+//! measurement widened by the tolerance, which catches the regressions of the kernels as well as
+//! the ones of the formulas. The test is long and thus ignored by default:
+//!
+//! ```bash
+//! cargo test --release -p concrete-core noise_calibration -- --ignored --nocapture
+//! ```
+//!
+//! The following environment variables tune the calibration:
+//! * `NPE_CALIBRATION_SAMPLES` - the number of samples per point (default 4096)
+//! * `NPE_CALIBRATION_BOOTSTRAP_SAMPLES` - the number of samples per bootstrap point (default 256)
+//! * `NPE_CALIBRATION_TOLERANCE` - the relative tolerance on the variance (default 0.1)
+//! * `NPE_CALIBRATION_Z` - the number of standard deviations of the confidence intervals
+//!   (default 4)
+//! * `NPE_CALIBRATION_REPORT` - the path of the csv report (default in the temporary directory)
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use concrete_npe as npe;
+
+use concrete_commons::dispersion::{DispersionParameter, LogStandardDev};
+use concrete_commons::numeric::{CastFrom, CastInto, Numeric};
+use concrete_commons::parameters::{
+    CiphertextCount, DecompositionBaseLog, DecompositionLevelCount, GlweDimension, LweDimension,
+    LweSize, PlaintextCount, PolynomialSize,
+};
+
+use crate::crypto::bootstrap::{Bootstrap, FourierBootstrapKey, StandardBootstrapKey};
+use crate::crypto::encoding::{Plaintext, PlaintextList};
+use crate::crypto::glwe::GlweCiphertext;
+use crate::crypto::lwe::{LweCiphertext, LweKeyswitchKey, LweList};
+use crate::crypto::secret::generators::{EncryptionRandomGenerator, SecretRandomGenerator};
+use crate::crypto::secret::{GlweSecretKey, LweSecretKey};
+use crate::math::fft::Complex64;
+use crate::math::random::{RandomGenerable, RandomGenerator, UniformMsb};
+use crate::math::tensor::{AsMutTensor, AsRefSlice, AsRefTensor};
+use crate::math::torus::UnsignedTorus;
+use crate::test_tools::torus_modular_distance;
+
+/// The settings of a calibration
+#[derive(Debug, Clone, Copy)]
+struct CalibrationConfig {
+    nb_samples: usize,
+    nb_bootstrap_samples: usize,
+    tolerance: f64,
+    z: f64,
+}
+
+impl CalibrationConfig {
+    fn from_env() -> CalibrationConfig {
+        fn read<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        }
+        CalibrationConfig {
+            nb_samples: read("NPE_CALIBRATION_SAMPLES", 4096),
+            nb_bootstrap_samples: read("NPE_CALIBRATION_BOOTSTRAP_SAMPLES", 256),
+            tolerance: read("NPE_CALIBRATION_TOLERANCE", 0.1),
+            z: read("NPE_CALIBRATION_Z", 4.),
+        }
+    }
+}
+
+/// The empirical mean and variance of a set of errors
+#[derive(Debug, Clone, Copy)]
+struct Measurement {
+    nb_samples: usize,
+    mean: f64,
+    variance: f64,
+}
+
+impl Measurement {
+    fn from_errors(errors: &[f64]) -> Measurement {
+        let nb_samples = errors.len();
+        let mean = errors.iter().sum::<f64>() / nb_samples as f64;
+        let variance =
+            errors.iter().map(|e| f64::powi(e - mean, 2)).sum::<f64>() / (nb_samples - 1) as f64;
+        Measurement {
+            nb_samples,
+            mean,
+            variance,
+        }
+    }
+
+    /// The confidence interval of the variance, with the normal approximation of the chi-square
+    /// distribution
+    fn variance_interval(&self, z: f64) -> (f64, f64) {
+        let half_width = z * f64::sqrt(2. / (self.nb_samples - 1) as f64);
+        (
+            f64::max(self.variance * (1. - half_width), 0.),
+            self.variance * (1. + half_width),
+        )
+    }
+
+    /// The confidence interval of the mean
+    fn mean_interval(&self, z: f64) -> (f64, f64) {
+        let half_width = z * f64::sqrt(self.variance / self.nb_samples as f64);
+        (self.mean - half_width, self.mean + half_width)
+    }
+}
+
+/// A point of the calibration i.e. a line of the report
+#[derive(Debug, Clone)]
+struct CalibrationPoint {
+    operation: &'static str,
+    torus_bits: usize,
+    parameters: String,
+    predicted_variance: f64,
+    measurement: Measurement,
+}
+
+impl CalibrationPoint {
+    fn new<T: UnsignedTorus>(
+        operation: &'static str,
+        parameters: String,
+        predicted_variance: f64,
+        errors: &[f64],
+    ) -> CalibrationPoint {
+        CalibrationPoint {
+            operation,
+            torus_bits: <T as Numeric>::BITS,
+            parameters,
+            predicted_variance,
+            measurement: Measurement::from_errors(errors),
+        }
+    }
+
+    /// The variance is flagged when the prediction is outside of the confidence interval of the
+    /// measurement widened by the tolerance
+    fn is_variance_flagged(&self, config: &CalibrationConfig) -> bool {
+        let (low, high) = self.measurement.variance_interval(config.z);
+        self.predicted_variance < low / (1. + config.tolerance)
+            || self.predicted_variance > high * (1. + config.tolerance)
+    }
+
+    /// The mean is flagged when 0 is outside of the confidence interval of the measurement
+    /// widened by the tolerance times the predicted standard deviation
+    fn is_mean_flagged(&self, config: &CalibrationConfig) -> bool {
+        let (low, high) = self.measurement.mean_interval(config.z);
+        let margin = config.tolerance * f64::sqrt(self.predicted_variance);
+        low - margin > 0. || high + margin < 0.
+    }
+
+    fn is_flagged(&self, config: &CalibrationConfig) -> bool {
+        self.is_variance_flagged(config) || self.is_mean_flagged(config)
+    }
+
+    const CSV_HEADER: &'static str = "operation,torus_bits,parameters,nb_samples,\
+        predicted_variance,measured_variance,variance_low,variance_high,log2_ratio,\
+        measured_mean,mean_low,mean_high,variance_flagged,mean_flagged";
+
+    fn to_csv(&self, config: &CalibrationConfig) -> String {
+        let (variance_low, variance_high) = self.measurement.variance_interval(config.z);
+        let (mean_low, mean_high) = self.measurement.mean_interval(config.z);
+        format!(
+            "{},{},{},{},{:e},{:e},{:e},{:e},{:.4},{:e},{:e},{:e},{},{}",
+            self.operation,
+            self.torus_bits,
+            self.parameters,
+            self.measurement.nb_samples,
+            self.predicted_variance,
+            self.measurement.variance,
+            variance_low,
+            variance_high,
+            f64::log2(self.measurement.variance / self.predicted_variance),
+            self.measurement.mean,
+            mean_low,
+            mean_high,
+            self.is_variance_flagged(config),
+            self.is_mean_flagged(config),
+        )
+    }
+}
+
+/// Computes the signed errors between the decrypted values and the messages
+fn signed_errors<T, First, Second>(decryptions: &First, messages: &Second) -> Vec<f64>
+where
+    T: UnsignedTorus,
+    First: AsRefTensor<Element = T>,
+    Second: AsRefTensor<Element = T>,
+{
+    decryptions
+        .as_tensor()
+        .iter()
+        .zip(messages.as_tensor().iter())
+        .map(|(d, m)| torus_modular_distance(*d, *m))
+        .collect()
+}
+
+fn calibrate_keyswitch<T>(
+    config: &CalibrationConfig,
+    dimension_before: LweDimension,
+    base_log: DecompositionBaseLog,
+    level_count: DecompositionLevelCount,
+    std_ksk: LogStandardDev,
+) -> CalibrationPoint
+where
+    T: UnsignedTorus + RandomGenerable<UniformMsb> + npe::LWE,
+{
+    let mut random_generator = RandomGenerator::new(None);
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    let nb_ct = CiphertextCount(config.nb_samples);
+    let std_input = LogStandardDev::from_log_standard_dev(-25.);
+    let dimension_after = LweDimension(600);
+
+    // keys
+    let sk_before = LweSecretKey::generate_binary(dimension_before, &mut secret_generator);
+    let sk_after = LweSecretKey::generate_binary(dimension_after, &mut secret_generator);
+    let mut ksk = LweKeyswitchKey::allocate(
+        T::ZERO,
+        level_count,
+        base_log,
+        dimension_before,
+        dimension_after,
+    );
+    ksk.fill_with_keyswitch_key(&sk_before, &sk_after, std_ksk, &mut encryption_generator);
+
+    // encrypt, key switch and decrypt
+    let messages =
+        PlaintextList::from_tensor(random_generator.random_uniform_n_msb_tensor(nb_ct.0, 8));
+    let mut ciphertexts_before = LweList::allocate(T::ZERO, dimension_before.to_lwe_size(), nb_ct);
+    let mut ciphertexts_after = LweList::allocate(T::ZERO, dimension_after.to_lwe_size(), nb_ct);
+    sk_before.encrypt_lwe_list(
+        &mut ciphertexts_before,
+        &messages,
+        std_input,
+        &mut encryption_generator,
+    );
+    ksk.keyswitch_list(&mut ciphertexts_after, &ciphertexts_before);
+    let mut decryptions = PlaintextList::allocate(T::ZERO, PlaintextCount(nb_ct.0));
+    sk_after.decrypt_lwe_list(&mut decryptions, &ciphertexts_after);
+
+    let predicted_variance = <T as npe::LWE>::key_switch(
+        dimension_before.0,
+        level_count.0,
+        base_log.0,
+        std_ksk.get_variance(),
+        std_input.get_variance(),
+    );
+    CalibrationPoint::new::<T>(
+        "keyswitch",
+        format!(
+            "n={};base_log={};level={};log2_std_ksk={}",
+            dimension_before.0,
+            base_log.0,
+            level_count.0,
+            f64::log2(std_ksk.get_standard_dev())
+        ),
+        predicted_variance,
+        &signed_errors(&decryptions, &messages),
+    )
+}
+
+/// Measures the external product, or the cmux if `cmux` is set, inside a bootstrap i.e. with a
+/// GGSW encrypting 1 (external product) or 0 (cmux)
+fn calibrate_external_product<T>(
+    config: &CalibrationConfig,
+    cmux: bool,
+    rlwe_dimension: GlweDimension,
+    polynomial_size: PolynomialSize,
+    base_log: DecompositionBaseLog,
+    level: DecompositionLevelCount,
+    std_bsk: LogStandardDev,
+) -> CalibrationPoint
+where
+    T: UnsignedTorus + npe::Cross,
+{
+    let mut random_generator = RandomGenerator::new(None);
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    let std_rlwe = LogStandardDev::from_log_standard_dev(-25.);
+    let lwe_dimension = LweDimension(1);
+
+    let mut errors = Vec::with_capacity(config.nb_samples);
+    while errors.len() < config.nb_samples {
+        // keys
+        let rlwe_sk =
+            GlweSecretKey::generate_binary(rlwe_dimension, polynomial_size, &mut secret_generator);
+        let lwe_sk = LweSecretKey::binary_from_container(vec![if cmux { T::ZERO } else { T::ONE }]);
+        let mut coef_bsk = StandardBootstrapKey::allocate(
+            T::ZERO,
+            rlwe_dimension.to_glwe_size(),
+            polynomial_size,
+            level,
+            base_log,
+            lwe_dimension,
+        );
+        coef_bsk.fill_with_new_key(&lwe_sk, &rlwe_sk, std_bsk, &mut encryption_generator);
+        let mut fourier_bsk = FourierBootstrapKey::allocate(
+            Complex64::new(0., 0.),
+            rlwe_dimension.to_glwe_size(),
+            polynomial_size,
+            level,
+            base_log,
+            lwe_dimension,
+        );
+        fourier_bsk.fill_with_forward_fourier(&coef_bsk);
+        let ggsw = fourier_bsk.ggsw_iter().next().unwrap();
+
+        // encrypt two polynomials
+        let mut m0 = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size.0));
+        random_generator.fill_tensor_with_random_uniform(&mut m0);
+        let mut m1 = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size.0));
+        random_generator.fill_tensor_with_random_uniform(&mut m1);
+        let mut ct0 =
+            GlweCiphertext::allocate(T::ZERO, polynomial_size, rlwe_dimension.to_glwe_size());
+        let mut ct1 =
+            GlweCiphertext::allocate(T::ZERO, polynomial_size, rlwe_dimension.to_glwe_size());
+        rlwe_sk.encrypt_glwe(&mut ct0, &m0, std_rlwe, &mut encryption_generator);
+        rlwe_sk.encrypt_glwe(&mut ct1, &m1, std_rlwe, &mut encryption_generator);
+
+        // compute and decrypt
+        let mut decryptions = PlaintextList::allocate(T::ZERO, PlaintextCount(polynomial_size.0));
+        if cmux {
+            fourier_bsk.cmux(&mut ct0, &mut ct1, &ggsw);
+            rlwe_sk.decrypt_glwe(&mut decryptions, &ct0);
+            errors.extend(signed_errors(&decryptions, &m0));
+        } else {
+            let mut res =
+                GlweCiphertext::allocate(T::ZERO, polynomial_size, rlwe_dimension.to_glwe_size());
+            fourier_bsk.external_product(&mut res, &ggsw, &ct0);
+            rlwe_sk.decrypt_glwe(&mut decryptions, &res);
+            errors.extend(signed_errors(&decryptions, &m0));
+        }
+    }
+
+    let predicted_variance = if cmux {
+        <T as npe::Cross>::cmux(
+            std_rlwe.get_variance(),
+            std_rlwe.get_variance(),
+            std_bsk.get_variance(),
+            rlwe_dimension.0,
+            polynomial_size.0,
+            base_log.0,
+            level.0,
+        )
+    } else {
+        <T as npe::Cross>::external_product(
+            rlwe_dimension.0,
+            level.0,
+            base_log.0,
+            polynomial_size.0,
+            std_bsk.get_variance(),
+            std_rlwe.get_variance(),
+        )
+    };
+    CalibrationPoint::new::<T>(
+        if cmux { "cmux" } else { "external_product" },
+        format!(
+            "k={};N={};base_log={};level={};log2_std_bsk={}",
+            rlwe_dimension.0,
+            polynomial_size.0,
+            base_log.0,
+            level.0,
+            f64::log2(std_bsk.get_standard_dev())
+        ),
+        predicted_variance,
+        &errors,
+    )
+}
+
+/// Measures the bootstrap of a message far enough from the edges of the accumulator for the
+/// drift not to change the output, see `test_bootstrap_noise`
+fn calibrate_bootstrap<T>(
+    config: &CalibrationConfig,
+    lwe_dimension: LweDimension,
+    polynomial_size: PolynomialSize,
+    base_log: DecompositionBaseLog,
+    level: DecompositionLevelCount,
+    std_bsk: LogStandardDev,
+) -> CalibrationPoint
+where
+    T: UnsignedTorus + npe::Cross,
+{
+    let mut secret_generator = SecretRandomGenerator::new(None);
+    let mut encryption_generator = EncryptionRandomGenerator::new(None);
+    let rlwe_dimension = GlweDimension(1);
+
+    // keys
+    let rlwe_sk =
+        GlweSecretKey::generate_binary(rlwe_dimension, polynomial_size, &mut secret_generator);
+    let lwe_sk = LweSecretKey::generate_binary(lwe_dimension, &mut secret_generator);
+    let mut coef_bsk = StandardBootstrapKey::allocate(
+        T::ZERO,
+        rlwe_dimension.to_glwe_size(),
+        polynomial_size,
+        level,
+        base_log,
+        lwe_dimension,
+    );
+    coef_bsk.fill_with_new_key(&lwe_sk, &rlwe_sk, std_bsk, &mut encryption_generator);
+    let mut fourier_bsk = FourierBootstrapKey::allocate(
+        Complex64::new(0., 0.),
+        rlwe_dimension.to_glwe_size(),
+        polynomial_size,
+        level,
+        base_log,
+        lwe_dimension,
+    );
+    fourier_bsk.fill_with_forward_fourier(&coef_bsk);
+    let flattened_key = LweSecretKey::binary_from_container(rlwe_sk.as_tensor().as_slice());
+
+    // a constant accumulator
+    let cst = T::ONE << (<T as Numeric>::BITS - 3);
+    let mut accumulator =
+        GlweCiphertext::allocate(T::ZERO, polynomial_size, rlwe_dimension.to_glwe_size());
+    accumulator
+        .get_mut_body()
+        .as_mut_tensor()
+        .fill_with_element(cst);
+
+    // a message in the first half of the torus, far from its edges
+    let val = (polynomial_size.0 as f64
+        - (5. * f64::sqrt(npe::cross::drift_index_lut(lwe_dimension.0))))
+        * (1. / (2. * polynomial_size.0 as f64))
+        * (<T as Numeric>::MAX.cast_into() + 1_f64);
+    let message = Plaintext(T::cast_from(val));
+
+    let mut errors = Vec::with_capacity(config.nb_bootstrap_samples);
+    let mut lwe_in = LweCiphertext::allocate(T::ZERO, lwe_dimension.to_lwe_size());
+    let mut lwe_out =
+        LweCiphertext::allocate(T::ZERO, LweSize(rlwe_dimension.0 * polynomial_size.0 + 1));
+    let mut decryption = Plaintext(T::ZERO);
+    for _ in 0..config.nb_bootstrap_samples {
+        lwe_sk.encrypt_lwe(&mut lwe_in, &message, std_bsk, &mut encryption_generator);
+        fourier_bsk.bootstrap(&mut lwe_out, &lwe_in, &accumulator);
+        flattened_key.decrypt_lwe(&mut decryption, &lwe_out);
+        errors.push(torus_modular_distance(decryption.0, cst));
+    }
+
+    let predicted_variance = <T as npe::Cross>::bootstrap(
+        lwe_dimension.0,
+        rlwe_dimension.0,
+        level.0,
+        base_log.0,
+        polynomial_size.0,
+        std_bsk.get_variance(),
+    );
+    CalibrationPoint::new::<T>(
+        "bootstrap",
+        format!(
+            "n={};k={};N={};base_log={};level={};log2_std_bsk={}",
+            lwe_dimension.0,
+            rlwe_dimension.0,
+            polynomial_size.0,
+            base_log.0,
+            level.0,
+            f64::log2(std_bsk.get_standard_dev())
+        ),
+        predicted_variance,
+        &errors,
+    )
+}
+
+/// Sweeps the operations over the grid of parameters
+fn sweep<T>(config: &CalibrationConfig) -> Vec<CalibrationPoint>
+where
+    T: UnsignedTorus + RandomGenerable<UniformMsb> + npe::LWE + npe::Cross,
+{
+    let mut points = Vec::new();
+    for &dimension in &[512, 1024] {
+        for &(base_log, level) in &[(2, 8), (3, 4), (4, 3), (7, 2)] {
+            points.push(calibrate_keyswitch::<T>(
+                config,
+                LweDimension(dimension),
+                DecompositionBaseLog(base_log),
+                DecompositionLevelCount(level),
+                LogStandardDev::from_log_standard_dev(-25.),
+            ));
+        }
+    }
+    for &cmux in &[false, true] {
+        for &rlwe_dimension in &[1, 2] {
+            for &polynomial_size in &[512, 1024] {
+                for &(base_log, level) in &[(4, 3), (7, 3), (10, 2)] {
+                    points.push(calibrate_external_product::<T>(
+                        config,
+                        cmux,
+                        GlweDimension(rlwe_dimension),
+                        PolynomialSize(polynomial_size),
+                        DecompositionBaseLog(base_log),
+                        DecompositionLevelCount(level),
+                        LogStandardDev::from_log_standard_dev(-25.),
+                    ));
+                }
+            }
+        }
+    }
+    for &lwe_dimension in &[256, 630] {
+        for &polynomial_size in &[512, 1024] {
+            for &(base_log, level) in &[(7, 3), (10, 2)] {
+                points.push(calibrate_bootstrap::<T>(
+                    config,
+                    LweDimension(lwe_dimension),
+                    PolynomialSize(polynomial_size),
+                    DecompositionBaseLog(base_log),
+                    DecompositionLevelCount(level),
+                    LogStandardDev::from_log_standard_dev(-29.),
+                ));
+            }
+        }
+    }
+    points
+}
+
+fn write_report(
+    path: &Path,
+    points: &[CalibrationPoint],
+    config: &CalibrationConfig,
+) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    writeln!(file, "{}", CalibrationPoint::CSV_HEADER)?;
+    for point in points.iter() {
+        writeln!(file, "{}", point.to_csv(config))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_measurement_intervals() {
+    use rand::Rng;
+    use rand_distr::Normal;
+
+    // errors of known mean and variance
+    let mut rng = rand::thread_rng();
+    let normal = Normal::new(0., f64::powi(2., -20)).unwrap();
+    let errors: Vec<f64> = (0..10000).map(|_| rng.sample(normal)).collect();
+    let measurement = Measurement::from_errors(&errors);
+
+    let (low, high) = measurement.variance_interval(5.);
+    assert!(low < f64::powi(2., -40) && f64::powi(2., -40) < high);
+    let (low, high) = measurement.mean_interval(5.);
+    assert!(low < 0. && 0. < high);
+
+    // a formula off by a factor 2 is flagged
+    let config = CalibrationConfig {
+        nb_samples: errors.len(),
+        nb_bootstrap_samples: 0,
+        tolerance: 0.1,
+        z: 5.,
+    };
+    let mut point =
+        CalibrationPoint::new::<u64>("test", String::new(), f64::powi(2., -40), errors.as_slice());
+    assert!(!point.is_flagged(&config));
+    point.predicted_variance = f64::powi(2., -39);
+    assert!(point.is_variance_flagged(&config));
+}
+
+#[test]
+fn test_calibration_keyswitch() {
+    let config = CalibrationConfig {
+        nb_samples: 512,
+        nb_bootstrap_samples: 0,
+        tolerance: 0.1,
+        z: 5.,
+    };
+    let point = calibrate_keyswitch::<u64>(
+        &config,
+        LweDimension(256),
+        DecompositionBaseLog(3),
+        DecompositionLevelCount(4),
+        LogStandardDev::from_log_standard_dev(-25.),
+    );
+    assert!(!point.is_flagged(&config), "{}", point.to_csv(&config));
+}
+
+#[test]
+#[ignore]
+fn noise_calibration() {
+    let config = CalibrationConfig::from_env();
+    let path = std::env::var("NPE_CALIBRATION_REPORT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir().join("npe_calibration.csv"));
+
+    let mut points = sweep::<u32>(&config);
+    points.extend(sweep::<u64>(&config));
+    write_report(&path, &points, &config).unwrap();
+    println!("noise calibration report written to {}", path.display());
+
+    let flagged: Vec<String> = points
+        .iter()
+        .filter(|point| point.is_flagged(&config))
+        .map(|point| point.to_csv(&config))
+        .collect();
+    assert!(
+        flagged.is_empty(),
+        "{} formulas off by more than the tolerance:\n{}\n{}",
+        flagged.len(),
+        CalibrationPoint::CSV_HEADER,
+        flagged.join("\n")
+    );
+}
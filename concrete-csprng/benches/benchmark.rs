@@ -30,5 +30,29 @@ fn bounded_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, unbounded_benchmark, bounded_benchmark);
+fn fill_bytes_benchmark(c: &mut Criterion) {
+    let mut bytes = vec![0u8; N_GEN];
+    let mut hardware = RandomGenerator::new_hardware(None).unwrap();
+    c.bench_function("fill_bytes hardware", |b| {
+        b.iter(|| hardware.fill_bytes(&mut bytes))
+    });
+    let mut software = RandomGenerator::new_software(None);
+    c.bench_function("fill_bytes software", |b| {
+        b.iter(|| software.fill_bytes(&mut bytes))
+    });
+    #[cfg(feature = "chacha")]
+    {
+        let mut chacha = RandomGenerator::new_chacha(None);
+        c.bench_function("fill_bytes chacha", |b| {
+            b.iter(|| chacha.fill_bytes(&mut bytes))
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    unbounded_benchmark,
+    bounded_benchmark,
+    fill_bytes_benchmark
+);
 criterion_main!(benches);
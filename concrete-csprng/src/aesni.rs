@@ -14,6 +14,8 @@ use std::mem::transmute;
 pub struct Generator {
     // The set of round keys used for the aes encryption
     round_keys: [__m128i; 11],
+    // The secret key, kept to export the state of the generator
+    key: AesKey,
 }
 
 impl AesBatchedGenerator for Generator {
@@ -22,9 +24,9 @@ impl AesBatchedGenerator for Generator {
             && is_x86_feature_detected!("rdseed")
             && is_x86_feature_detected!("sse2")
         {
-            let round_keys =
-                generate_round_keys(key.unwrap_or_else(generate_initialization_vector));
-            Generator { round_keys }
+            let key = key.unwrap_or_else(generate_initialization_vector);
+            let round_keys = generate_round_keys(key);
+            Generator { round_keys, key }
         } else {
             panic!(
                 "One of the `aes`, `rdseed`, or `sse2` instructions set was not fount. It is \
@@ -33,6 +35,10 @@ impl AesBatchedGenerator for Generator {
         }
     }
 
+    fn get_key(&self) -> AesKey {
+        self.key
+    }
+
     fn generate_batch(&mut self, AesCtr(aes_ctr): AesCtr) -> [u8; 128] {
         si128arr_to_u8arr(aes_encrypt_many(
             &u128_to_si128(aes_ctr),
@@ -0,0 +1,217 @@
+//! A module implementing a `chacha20-counter` random number generator, in portable software.
+//!
+//! This module implements a cryptographically secure pseudorandom number generator
+//! (CS-PRNG), using the chacha20 streamcipher. The block function follows the
+//! [RFC 8439](https://www.rfc-editor.org/rfc/rfc8439). The 128 bits secret key of the generator
+//! is used as in the original definition of chacha for 16 bytes keys: it is repeated twice in the
+//! state, along with the `expand 16-byte k` constants. The last four words of the state, which
+//! contain the block counter and the nonce in the RFC, are filled with a 128 bits block counter.
+use crate::counter::{AesBatchedGenerator, AesCtr, AesKey};
+use crate::software::rdseed;
+use std::convert::TryInto;
+
+// The constants of the state for 32 bytes keys: `expand 32-byte k`.
+#[cfg(test)]
+const SIGMA: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+// The constants of the state for 16 bytes keys: `expand 16-byte k`.
+const TAU: [u32; 4] = [0x6170_7865, 0x3120_646e, 0x7962_2d36, 0x6b20_6574];
+
+#[derive(Clone)]
+pub struct Generator {
+    // The words of the key, as laid out in the state
+    key_words: [u32; 8],
+    // The secret key, kept to export the state of the generator
+    key: AesKey,
+}
+
+impl AesBatchedGenerator for Generator {
+    fn new(key: Option<AesKey>) -> Generator {
+        let key = key.unwrap_or_else(|| AesKey(rdseed()));
+        let mut key_words = [0u32; 8];
+        for (i, bytes) in key.0.to_le_bytes().chunks(4).enumerate() {
+            let word = u32::from_le_bytes(bytes.try_into().unwrap());
+            key_words[i] = word;
+            key_words[i + 4] = word;
+        }
+        Generator { key_words, key }
+    }
+
+    fn get_key(&self) -> AesKey {
+        self.key
+    }
+
+    fn generate_batch(&mut self, AesCtr(aes_ctr): AesCtr) -> [u8; 128] {
+        // The counter counts blocks of 16 bytes, while chacha blocks are 64 bytes long. When the
+        // counter is not aligned on a chacha block, a third block is needed to fill the batch.
+        let first_block = aes_ctr / 4;
+        let offset = (aes_ctr % 4) as usize * 16;
+        let n_blocks = if offset == 0 { 2 } else { 3 };
+        let mut blocks = [0u8; 192];
+        for (i, output) in blocks.chunks_mut(64).take(n_blocks).enumerate() {
+            output.copy_from_slice(&chacha20_block(
+                &TAU,
+                &self.key_words,
+                &counter_words(first_block + i as u128),
+            ));
+        }
+        let mut batch = [0u8; 128];
+        batch.copy_from_slice(&blocks[offset..offset + 128]);
+        batch
+    }
+}
+
+// Splits a 128 bits block counter into the four last words of the state.
+fn counter_words(block: u128) -> [u32; 4] {
+    [
+        block as u32,
+        (block >> 32) as u32,
+        (block >> 64) as u32,
+        (block >> 96) as u32,
+    ]
+}
+
+// The quarter round of section 2.2 of the RFC, applied on four words of the state.
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+// The block function of section 2.3 of the RFC, which outputs the serialized block.
+fn chacha20_block(constants: &[u32; 4], key: &[u32; 8], counter: &[u32; 4]) -> [u8; 64] {
+    let mut initial_state = [0u32; 16];
+    initial_state[..4].copy_from_slice(constants);
+    initial_state[4..12].copy_from_slice(key);
+    initial_state[12..].copy_from_slice(counter);
+    let mut state = initial_state;
+    for _ in 0..10 {
+        // column rounds
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        // diagonal rounds
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+    let mut output = [0u8; 64];
+    for (bytes, (word, initial_word)) in output
+        .chunks_mut(4)
+        .zip(state.iter().zip(initial_state.iter()))
+    {
+        bytes.copy_from_slice(&word.wrapping_add(*initial_word).to_le_bytes());
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Test vector of section 2.3.2 of the RFC 8439.
+    const KEY: [u32; 8] = [
+        0x0302_0100,
+        0x0706_0504,
+        0x0b0a_0908,
+        0x0f0e_0d0c,
+        0x1312_1110,
+        0x1716_1514,
+        0x1b1a_1918,
+        0x1f1e_1d1c,
+    ];
+    const COUNTER: [u32; 4] = [0x0000_0001, 0x0900_0000, 0x4a00_0000, 0x0000_0000];
+    const BLOCK: [u8; 64] = [
+        0x10, 0xf1, 0xe7, 0xe4, 0xd1, 0x3b, 0x59, 0x15, 0x50, 0x0f, 0xdd, 0x1f, 0xa3, 0x20, 0x71,
+        0xc4, 0xc7, 0xd1, 0xf4, 0xc7, 0x33, 0xc0, 0x68, 0x03, 0x04, 0x22, 0xaa, 0x9a, 0xc3, 0xd4,
+        0x6c, 0x4e, 0xd2, 0x82, 0x64, 0x46, 0x07, 0x9f, 0xaa, 0x09, 0x14, 0xc2, 0xd7, 0x05, 0xd9,
+        0x8b, 0x02, 0xa2, 0xb5, 0x12, 0x9c, 0xd1, 0xde, 0x16, 0x4e, 0xb9, 0xcb, 0xd0, 0x83, 0xe8,
+        0xa2, 0x50, 0x3c, 0x4e,
+    ];
+
+    // Test vector #1 of appendix A.1 of the RFC 8439, with a null key and a null counter.
+    const NULL_KEY_BLOCK: [u8; 64] = [
+        0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86, 0xbd,
+        0x28, 0xbd, 0xd2, 0x19, 0xb8, 0xa0, 0x8d, 0xed, 0x1a, 0xa8, 0x36, 0xef, 0xcc, 0x8b, 0x77,
+        0x0d, 0xc7, 0xda, 0x41, 0x59, 0x7c, 0x51, 0x57, 0x48, 0x8d, 0x77, 0x24, 0xe0, 0x3f, 0xb8,
+        0xd8, 0x4a, 0x37, 0x6a, 0x43, 0xb8, 0xf4, 0x15, 0x18, 0xa1, 0x1c, 0xc3, 0x87, 0xb6, 0x69,
+        0xb2, 0xee, 0x65, 0x86,
+    ];
+
+    #[test]
+    fn test_quarter_round() {
+        // Checks the quarter round against the test vector of section 2.1.1 of the RFC 8439.
+        let mut state = [0u32; 16];
+        state[..4].copy_from_slice(&[0x1111_1111, 0x0102_0304, 0x9b8d_6f43, 0x0123_4567]);
+        quarter_round(&mut state, 0, 1, 2, 3);
+        assert_eq!(
+            state[..4],
+            [0xea2a_92f4, 0xcb1c_f8ce, 0x4581_472e, 0x5881_c4bb]
+        );
+    }
+
+    #[test]
+    fn test_block_function() {
+        // Checks the block function against the test vectors of the RFC 8439.
+        assert_eq!(chacha20_block(&SIGMA, &KEY, &COUNTER)[..], BLOCK[..]);
+        assert_eq!(
+            chacha20_block(&SIGMA, &[0; 8], &[0; 4])[..],
+            NULL_KEY_BLOCK[..]
+        );
+    }
+
+    #[test]
+    fn test_key_expansion() {
+        // Checks that the 16 bytes key is repeated twice in the state.
+        let generator = Generator::new(Some(AesKey(u128::from_le_bytes([
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f,
+        ]))));
+        assert_eq!(generator.key_words[..4], KEY[..4]);
+        assert_eq!(generator.key_words[4..], KEY[..4]);
+    }
+
+    #[test]
+    fn test_unaligned_batches() {
+        // Checks that batches starting in the middle of a chacha block are consistent with the
+        // aligned ones.
+        let mut generator = Generator::new(None);
+        for aes_ctr in 0..64u128 {
+            let aligned = aes_ctr - aes_ctr % 4;
+            let offset = (aes_ctr % 4) as usize * 16;
+            let mut expected = generator.generate_batch(AesCtr(aligned)).to_vec();
+            expected.extend_from_slice(&generator.generate_batch(AesCtr(aligned + 8)));
+            assert_eq!(
+                generator.generate_batch(AesCtr(aes_ctr))[..],
+                expected[offset..offset + 128]
+            );
+        }
+    }
+
+    #[test]
+    fn test_uniformity() {
+        // Checks that the PRNG generates uniform numbers
+        let precision = 10f64.powi(-4);
+        let n_samples = 1_000_000_usize;
+        let mut generator = Generator::new(None);
+        let mut counts = [0usize; 256];
+        let expected_prob: f64 = 1. / 256.;
+        for counter in 0..n_samples {
+            let batch = generator.generate_batch(AesCtr(8 * counter as u128));
+            for byte in batch.iter() {
+                counts[*byte as usize] += 1;
+            }
+        }
+        counts
+            .iter()
+            .map(|a| (*a as f64) / ((n_samples * 128) as f64))
+            .for_each(|a| assert!((a - expected_prob) < precision))
+    }
+}
@@ -1,6 +1,9 @@
+#[cfg(feature = "chacha")]
+use crate::chacha;
 use crate::{aesni, software};
 #[cfg(feature = "multithread")]
 use rayon::{iter::IndexedParallelIterator, prelude::*};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 #[cfg(test)]
@@ -17,19 +20,24 @@ mod test_aes;
 
 /// A trait for batched generators, i.e. generators that creates 128 bytes of random values at a
 /// time.
+///
+/// Whatever the underlying cipher, the counter counts blocks of 16 bytes, so that a batch covers
+/// 8 successive values of the counter.
 pub trait AesBatchedGenerator: Clone {
     /// Instantiate a new generator from a secret key.
     fn new(key: Option<AesKey>) -> Self;
+    /// Returns the secret key of the generator.
+    fn get_key(&self) -> AesKey;
     /// Generates the batch corresponding to the given counter.
     fn generate_batch(&mut self, ctr: AesCtr) -> [u8; 128];
 }
 
 /// Represents the counter used by the AES block cipher to generate a set of values.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct AesCtr(pub u128);
 
 /// Represents the counter used to index on the batch-generated bytes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ByteCtr(pub u8);
 
 /// Represents a key used in the AES ciphertext.
@@ -48,7 +56,7 @@ pub enum ShouldGenerateBatch {
 /// able to store both the last AES counter used, and the index of the last outputted byte in the
 /// batch. This structure contains both, and provides operations to manipulate those two values
 /// correctly.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct State {
     aes_ctr: AesCtr,
     byte_ctr: ByteCtr,
@@ -62,6 +70,13 @@ impl State {
         State { aes_ctr, byte_ctr }
     }
 
+    /// Generates the state of the byte at the given offset from the start of the keystream.
+    pub fn from_byte_offset(offset: usize) -> Self {
+        let mut state = State::default();
+        state.shift(offset);
+        state
+    }
+
     /// Generates a new state from an AES counter value.
     pub fn from_aes_counter(aes_ctr: AesCtr) -> Self {
         State {
@@ -219,6 +234,10 @@ pub type SoftAesCtrGenerator = AesCtrGenerator<software::Generator>;
 /// A generator that uses the hardware implementation.
 pub type HardAesCtrGenerator = AesCtrGenerator<aesni::Generator>;
 
+/// A generator that uses the chacha20 implementation.
+#[cfg(feature = "chacha")]
+pub type ChaChaCtrGenerator = AesCtrGenerator<chacha::Generator>;
+
 /// A csprng which operates in batch mode.
 #[derive(Clone)]
 pub struct AesCtrGenerator<G: AesBatchedGenerator> {
@@ -251,6 +270,11 @@ impl<G: AesBatchedGenerator> AesCtrGenerator<G> {
         }
     }
 
+    /// Returns the secret key of the current generator.
+    pub fn get_key(&self) -> AesKey {
+        self.generator.get_key()
+    }
+
     /// Returns the state of the current generator.
     pub fn get_state(&self) -> &State {
         &self.state
@@ -294,6 +318,81 @@ impl<G: AesBatchedGenerator> AesCtrGenerator<G> {
         output
     }
 
+    /// Fills a slice with the next random bytes.
+    ///
+    /// The bytes are the same as the ones yielded by successive calls to `generate_next`, but
+    /// they are copied from the batches a whole chunk at a time.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Some(remaining) = self.remaining_bytes() {
+            if dest.len() > remaining {
+                panic!("Tried to generate a byte outside the generator bound.");
+            }
+        }
+        let mut filled = 0;
+        while filled < dest.len() {
+            let index = self.state.get_batch_index();
+            let n_bytes = usize::min(128 - index, dest.len() - filled);
+            dest[filled..filled + n_bytes].copy_from_slice(&self.batch[index..index + n_bytes]);
+            filled += n_bytes;
+            if let ShouldGenerateBatch::GenerateBatch = self.state.shift(n_bytes) {
+                self.batch = self.generator.generate_batch(self.state.get_aes_counter());
+            }
+        }
+    }
+
+    /// Moves the generator forward to the byte at the given offset from the start of the
+    /// keystream.
+    ///
+    /// The offset is counted from the state of the generators created without a state, and the
+    /// cost of the move does not depend on it. The skipped bytes are never yielded.
+    ///
+    /// # Panics
+    ///
+    /// If the generator is bounded, since it could then yield bytes reserved to other generators,
+    /// or if the offset is behind the current state, since the generator would then yield bytes
+    /// already yielded or forked.
+    pub fn seek(&mut self, offset: usize) {
+        if self.is_bounded() {
+            panic!("Tried to seek a bounded generator.");
+        }
+        let state = State::from_byte_offset(offset);
+        if state < self.state {
+            panic!("Tried to seek a generator backward.");
+        }
+        self.state = state;
+        self.batch = self.generator.generate_batch(self.state.get_aes_counter());
+    }
+
+    /// Returns a generator bounded to the `length` bytes following the byte at the given offset
+    /// from the start of the keystream.
+    ///
+    /// The cost of the creation does not depend on the offset. The current generator is moved
+    /// past the window, and the bytes between its state and the window are never yielded. If the
+    /// current generator is bounded, or if the window starts behind its current state, `None` is
+    /// returned, since the window could contain bytes already yielded or reserved to other
+    /// generators.
+    pub fn generator_at(&mut self, offset: usize, length: usize) -> Option<AesCtrGenerator<G>> {
+        if self.is_bounded() {
+            return None;
+        }
+        let state = State::from_byte_offset(offset);
+        if state < self.state {
+            return None;
+        }
+        let mut bound = state.clone();
+        bound.shift(length);
+        let mut generator = self.generator.clone();
+        let batch = generator.generate_batch(state.get_aes_counter());
+        self.state = bound.clone();
+        self.batch = self.generator.generate_batch(self.state.get_aes_counter());
+        Some(AesCtrGenerator {
+            generator,
+            state,
+            bound: Some(bound),
+            batch,
+        })
+    }
+
     /// Tries to fork the current generator into `n_child` generators each able to yield
     /// `child_bytes` random bytes.
     ///
@@ -304,12 +403,8 @@ impl<G: AesBatchedGenerator> AesCtrGenerator<G> {
         n_child: ChildCount,
         child_bytes: BytesPerChild,
     ) -> Option<impl Iterator<Item = AesCtrGenerator<G>>> {
-        if !self.is_fork_in_bound(n_child, child_bytes) {
-            return None;
-        }
-        let output = (0..n_child.0).map(self.get_creation_closure(child_bytes));
-        self.regenerate_batch(n_child, child_bytes);
-        Some(output)
+        let factory = self.try_fork_factory(n_child, child_bytes)?;
+        Some((0..n_child.0).map(move |i| factory.create_child(i)))
     }
 
     /// Tries to fork the current generator into `n_child` generators each able to yield
@@ -330,14 +425,35 @@ impl<G: AesBatchedGenerator> AesCtrGenerator<G> {
     where
         G: Send + Sync,
     {
+        let factory = self.try_fork_factory(n_child, child_bytes)?;
+        Some(
+            (0..n_child.0)
+                .into_par_iter()
+                .map(move |i| factory.create_child(i)),
+        )
+    }
+
+    /// Tries to fork the current generator into `n_child` generators each able to yield
+    /// `child_bytes` random bytes, returning a factory that creates the children on demand.
+    ///
+    /// If the total number of bytes to be generated exceeds the bound of the current generator,
+    /// `None` is returned. Otherwise, the state of the current generator is shifted past the
+    /// bytes of the children.
+    pub fn try_fork_factory(
+        &mut self,
+        n_child: ChildCount,
+        child_bytes: BytesPerChild,
+    ) -> Option<ChildrenFactory<G>> {
         if !self.is_fork_in_bound(n_child, child_bytes) {
             return None;
         }
-        let output = (0..n_child.0)
-            .into_par_iter()
-            .map(self.get_creation_closure(child_bytes));
+        let factory = ChildrenFactory {
+            generator: self.generator.clone(),
+            state: self.state.clone(),
+            child_bytes,
+        };
         self.regenerate_batch(n_child, child_bytes);
-        Some(output)
+        Some(factory)
     }
 
     fn regenerate_batch(&mut self, n_child: ChildCount, child_bytes: BytesPerChild) {
@@ -357,27 +473,34 @@ impl<G: AesBatchedGenerator> AesCtrGenerator<G> {
         }
         true
     }
+}
 
-    fn get_creation_closure(
-        &self,
-        child_bytes: BytesPerChild,
-    ) -> impl Fn(usize) -> AesCtrGenerator<G> {
-        let state = self.state.clone();
-        let generator = self.generator.clone();
-        move |i| {
-            let mut new_state = state.clone();
-            new_state.shift(child_bytes.0 * i);
-            let mut new_bound = new_state.clone();
-            new_bound.shift(child_bytes.0);
-            new_bound.normalize_with(&new_state);
-            let mut new_generator = generator.clone();
-            let batch = new_generator.generate_batch(new_state.aes_ctr);
-            AesCtrGenerator {
-                generator: new_generator,
-                state: new_state,
-                bound: Some(new_bound),
-                batch,
-            }
+/// A factory creating the children of a forked generator.
+///
+/// The `i`-th child yields the `child_bytes` bytes following the `i * child_bytes` first bytes of
+/// the parent generator, at the time of the fork.
+#[derive(Clone)]
+pub struct ChildrenFactory<G: AesBatchedGenerator> {
+    generator: G,
+    state: State,
+    child_bytes: BytesPerChild,
+}
+
+impl<G: AesBatchedGenerator> ChildrenFactory<G> {
+    /// Creates the `i`-th child of the fork.
+    pub fn create_child(&self, i: usize) -> AesCtrGenerator<G> {
+        let mut new_state = self.state.clone();
+        new_state.shift(self.child_bytes.0 * i);
+        let mut new_bound = new_state.clone();
+        new_bound.shift(self.child_bytes.0);
+        new_bound.normalize_with(&new_state);
+        let mut new_generator = self.generator.clone();
+        let batch = new_generator.generate_batch(new_state.aes_ctr);
+        AesCtrGenerator {
+            generator: new_generator,
+            state: new_state,
+            bound: Some(new_bound),
+            batch,
         }
     }
 }
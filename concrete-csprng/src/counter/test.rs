@@ -431,3 +431,143 @@ fn test_randomized_remaining_bytes() {
         assert_eq!(forking_generator.remaining_bytes(), None);
     }
 }
+
+#[test]
+fn test_randomized_fill_bytes() {
+    // Checks that filling slices yields the same bytes as generating them one at a time.
+    for _ in 0..1000 {
+        let state = State::from_aes_counter(AesCtr(rand::thread_rng().gen::<u64>() as u128));
+        let key = AesKey(rand::thread_rng().gen());
+        let mut generator = SoftAesCtrGenerator::new(Some(key), Some(state.clone()), None);
+        let mut filling_generator = SoftAesCtrGenerator::new(Some(key), Some(state), None);
+        for _ in 0..5 {
+            let n_bytes = rand::thread_rng().gen::<usize>() % 500;
+            let expected: Vec<u8> = (0..n_bytes).map(|_| generator.generate_next()).collect();
+            let mut output = vec![0u8; n_bytes];
+            filling_generator.fill_bytes(&mut output);
+            assert_eq!(expected, output);
+            assert_eq!(filling_generator.get_state(), generator.get_state());
+        }
+        assert_eq!(filling_generator.generate_next(), generator.generate_next());
+    }
+}
+
+#[test]
+fn test_fill_bytes_bounded() {
+    // Checks that a bounded generator can fill exactly as many bytes as its bound allows.
+    let mut generator = SoftAesCtrGenerator::new(Some(AesKey(0)), None, None);
+    let mut child = generator
+        .try_fork(ChildCount(1), BytesPerChild(300))
+        .unwrap()
+        .next()
+        .unwrap();
+    let mut output = vec![0u8; 300];
+    child.fill_bytes(&mut output);
+    assert_eq!(child.remaining_bytes(), Some(0));
+}
+
+#[test]
+#[should_panic]
+fn test_fill_bytes_bounded_panic() {
+    // Checks that a bounded generator panics when filling more bytes than its bound allows.
+    let mut generator = SoftAesCtrGenerator::new(Some(AesKey(0)), None, None);
+    let mut child = generator
+        .try_fork(ChildCount(1), BytesPerChild(300))
+        .unwrap()
+        .next()
+        .unwrap();
+    let mut output = vec![0u8; 301];
+    child.fill_bytes(&mut output);
+}
+
+#[test]
+fn test_randomized_seek() {
+    // Checks that seeking forward gives the same bytes as generating up to the offset.
+    let key = AesKey(rand::thread_rng().gen());
+    let mut reference = SoftAesCtrGenerator::new(Some(key), None, None);
+    let output: Vec<u8> = (0..200_000).map(|_| reference.generate_next()).collect();
+    let mut generator = SoftAesCtrGenerator::new(Some(key), None, None);
+    let mut offset = 0;
+    for _ in 0..100 {
+        offset += rand::thread_rng().gen::<usize>() % 1000;
+        generator.seek(offset);
+        for byte in output[offset..offset + 1000].iter() {
+            assert_eq!(*byte, generator.generate_next());
+        }
+        offset += 1000;
+    }
+}
+
+#[test]
+fn test_randomized_generator_at() {
+    // Checks that a window generator yields the bytes of its window, and nothing more, and that
+    // the parent generator resumes after the window.
+    let key = AesKey(rand::thread_rng().gen());
+    let mut reference = SoftAesCtrGenerator::new(Some(key), None, None);
+    let output: Vec<u8> = (0..300_000).map(|_| reference.generate_next()).collect();
+    let mut generator = SoftAesCtrGenerator::new(Some(key), None, None);
+    let mut offset = 0;
+    for _ in 0..100 {
+        offset += rand::thread_rng().gen::<usize>() % 1000;
+        let length = rand::thread_rng().gen::<usize>() % 1000;
+        let mut window = generator.generator_at(offset, length).unwrap();
+        assert_eq!(window.remaining_bytes(), Some(length));
+        let mut bytes = vec![0u8; length];
+        window.fill_bytes(&mut bytes);
+        assert_eq!(bytes[..], output[offset..offset + length]);
+        assert_eq!(window.remaining_bytes(), Some(0));
+        assert!(window.generator_at(0, 1).is_none());
+        offset += length;
+        assert_eq!(generator.generate_next(), output[offset]);
+        offset += 1;
+    }
+}
+
+#[test]
+fn test_generator_at_overlap() {
+    // Checks that the windows can not overlap the bytes already yielded or forked.
+    let mut generator = SoftAesCtrGenerator::new(Some(AesKey(0)), None, None);
+    for _ in 0..100 {
+        generator.generate_next();
+    }
+    assert!(generator.generator_at(50, 100).is_none());
+    assert!(generator.generator_at(100, 100).is_some());
+    assert!(generator.generator_at(150, 100).is_none());
+    let _ = generator
+        .try_fork(ChildCount(2), BytesPerChild(100))
+        .unwrap()
+        .count();
+    assert!(generator.generator_at(300, 100).is_none());
+    assert!(generator.generator_at(400, 100).is_some());
+}
+
+#[test]
+#[should_panic]
+fn test_generator_at_bound_panic() {
+    // Checks that a window generator panics when exceeding its window.
+    let mut generator = SoftAesCtrGenerator::new(Some(AesKey(0)), None, None);
+    let mut window = generator.generator_at(1000, 200).unwrap();
+    for _ in 0..201 {
+        window.generate_next();
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_seek_bounded_panic() {
+    // Checks that seeking a bounded generator panics.
+    let mut generator = SoftAesCtrGenerator::new(Some(AesKey(0)), None, None);
+    let mut window = generator.generator_at(1000, 200).unwrap();
+    window.seek(1100);
+}
+
+#[test]
+#[should_panic]
+fn test_seek_backward_panic() {
+    // Checks that seeking behind the bytes already yielded panics.
+    let mut generator = SoftAesCtrGenerator::new(Some(AesKey(0)), None, None);
+    for _ in 0..100 {
+        generator.generate_next();
+    }
+    generator.seek(50);
+}
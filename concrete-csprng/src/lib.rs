@@ -4,18 +4,75 @@
 //!
 //! This crate contains a reasonably fast cryptographically secure pseudo-random number generator.
 //! The implementation is based on the AES blockcipher used in counter (CTR) mode, as presented
-//! in the ISO/IEC 18033-4 document.
+//! in the ISO/IEC 18033-4 document. With the `chacha` feature, a generator based on the chacha20
+//! streamcipher of the RFC 8439 can be selected instead, which is faster than the software AES
+//! implementation on machines lacking the AES instructions.
+//!
+//! The generator implements the `RngCore`, `CryptoRng` and `SeedableRng` traits of `rand_core`,
+//! and can be used wherever the `rand` ecosystem expects a cryptographically secure generator:
+//!
+//! ```rust
+//! use concrete_csprng::RandomGenerator;
+//! use rand_core::{RngCore, SeedableRng};
+//!
+//! let mut generator = RandomGenerator::from_seed([0; 16]);
+//! let mut bytes = [0u8; 1000];
+//! generator.fill_bytes(&mut bytes);
+//! let value = generator.next_u64();
+//! ```
 
+use rand_core::{CryptoRng, RngCore, SeedableRng};
 #[cfg(feature = "multithread")]
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display, Formatter, Result};
 
 mod aesni;
+#[cfg(feature = "chacha")]
+mod chacha;
 mod counter;
 mod software;
-use crate::counter::{AesKey, BytesPerChild, ChildCount, HardAesCtrGenerator, SoftAesCtrGenerator};
+#[cfg(feature = "chacha")]
+use crate::counter::ChaChaCtrGenerator;
+pub use crate::counter::{AesCtr, ByteCtr, State};
+use crate::counter::{
+    AesKey, BytesPerChild, ChildCount, ChildrenFactory, HardAesCtrGenerator, SoftAesCtrGenerator,
+};
 pub use software::set_soft_rdseed_secret;
 
+/// The complete state of a random generator, from which the generator can be restored.
+///
+/// Restoring a generator from an exported state yields the very same bytes as the original
+/// generator would have yielded, whether the software or the hardware implementation is used.
+///
+/// # Note
+///
+/// The state contains the secret key of the generator: anyone knowing it can predict every
+/// byte the generator outputs. It must be stored with the same care as the secrets generated with
+/// the generator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RandomGeneratorState {
+    /// The cipher the generator is based on.
+    pub cipher: Cipher,
+    /// The secret key of the cipher.
+    pub key: u128,
+    /// The position of the next byte in the stream.
+    pub state: State,
+    /// The position of the first byte the generator can not yield, if the generator is bounded.
+    pub bound: Option<State>,
+}
+
+/// The cipher a random generator is based on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cipher {
+    /// The AES block cipher, in counter mode. The software and hardware implementations yield the
+    /// same bytes.
+    Aes,
+    /// The chacha20 streamcipher.
+    #[cfg(feature = "chacha")]
+    ChaCha20,
+}
+
 /// The pseudorandom number generator.
 ///
 /// If the correct instructions sets are available on the machine, an hardware accelerated version
@@ -27,6 +84,9 @@ pub enum RandomGenerator {
     Software(SoftAesCtrGenerator),
     #[doc(hidden)]
     Hardware(HardAesCtrGenerator),
+    #[doc(hidden)]
+    #[cfg(feature = "chacha")]
+    ChaCha(ChaChaCtrGenerator),
 }
 
 impl RandomGenerator {
@@ -64,11 +124,133 @@ impl RandomGenerator {
         )))
     }
 
+    /// Builds a new chacha20 random generator, optionally seeding it with a given value.
+    ///
+    /// # Notes
+    ///
+    /// This method necessitates the "chacha" feature.
+    #[cfg(feature = "chacha")]
+    pub fn new_chacha(seed: Option<u128>) -> RandomGenerator {
+        RandomGenerator::ChaCha(ChaChaCtrGenerator::new(seed.map(AesKey), None, None))
+    }
+
+    /// Builds a random generator from an exported state, selecting the hardware implementation if
+    /// available.
+    ///
+    /// # Note
+    ///
+    /// If using the `slow` feature, this function will return the non-accelerated variant, even
+    /// though the right instructions are available. A state exported from a chacha20 generator
+    /// always gives a chacha20 generator.
+    pub fn from_state(state: &RandomGeneratorState) -> RandomGenerator {
+        #[cfg(feature = "chacha")]
+        if state.cipher == Cipher::ChaCha20 {
+            return RandomGenerator::ChaCha(ChaChaCtrGenerator::new(
+                Some(AesKey(state.key)),
+                Some(state.state.clone()),
+                state.bound.clone(),
+            ));
+        }
+        let generator = if cfg!(feature = "slow") {
+            RandomGenerator::software_from_state(state)
+        } else {
+            RandomGenerator::hardware_from_state(state)
+                .or_else(|| RandomGenerator::software_from_state(state))
+        };
+        generator.expect("The state of a chacha20 generator was not handled.")
+    }
+
+    /// Tries to build a software random generator from an exported state.
+    ///
+    /// Returns `None` if the state was not exported from an AES generator.
+    pub fn software_from_state(state: &RandomGeneratorState) -> Option<RandomGenerator> {
+        if state.cipher != Cipher::Aes {
+            return None;
+        }
+        Some(RandomGenerator::Software(SoftAesCtrGenerator::new(
+            Some(AesKey(state.key)),
+            Some(state.state.clone()),
+            state.bound.clone(),
+        )))
+    }
+
+    /// Tries to build a hardware random generator from an exported state.
+    ///
+    /// Returns `None` if the state was not exported from an AES generator, or if the right
+    /// instructions are not available.
+    pub fn hardware_from_state(state: &RandomGeneratorState) -> Option<RandomGenerator> {
+        if state.cipher != Cipher::Aes {
+            return None;
+        }
+        if !is_x86_feature_detected!("aes")
+            || !is_x86_feature_detected!("rdseed")
+            || !is_x86_feature_detected!("sse2")
+        {
+            return None;
+        }
+        Some(RandomGenerator::Hardware(HardAesCtrGenerator::new(
+            Some(AesKey(state.key)),
+            Some(state.state.clone()),
+            state.bound.clone(),
+        )))
+    }
+
+    /// Exports the state of the generator, from which it can be restored with
+    /// `RandomGenerator::from_state`.
+    pub fn export_state(&self) -> RandomGeneratorState {
+        let (cipher, key, state, bound) = match self {
+            Self::Hardware(rand) => (
+                Cipher::Aes,
+                rand.get_key(),
+                rand.get_state(),
+                rand.get_bound(),
+            ),
+            Self::Software(rand) => (
+                Cipher::Aes,
+                rand.get_key(),
+                rand.get_state(),
+                rand.get_bound(),
+            ),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(rand) => (
+                Cipher::ChaCha20,
+                rand.get_key(),
+                rand.get_state(),
+                rand.get_bound(),
+            ),
+        };
+        RandomGeneratorState {
+            cipher,
+            key: key.0,
+            state: state.clone(),
+            bound: bound.cloned(),
+        }
+    }
+
     /// Yields the next byte from the generator.
     pub fn generate_next(&mut self) -> u8 {
         match self {
             Self::Hardware(ref mut rand) => rand.generate_next(),
             Self::Software(ref mut rand) => rand.generate_next(),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(ref mut rand) => rand.generate_next(),
+        }
+    }
+
+    /// Fills a slice with the next bytes from the generator.
+    ///
+    /// This yields the same bytes as successive calls to `generate_next`, but much faster, since
+    /// whole batches of bytes are copied at a time.
+    ///
+    /// # Panics
+    ///
+    /// If the generator is bounded, and the slice is longer than the number of remaining bytes.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Self::Hardware(ref mut rand) => rand.fill_bytes(dest),
+            Self::Software(ref mut rand) => rand.fill_bytes(dest),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(ref mut rand) => rand.fill_bytes(dest),
         }
     }
 
@@ -77,6 +259,8 @@ impl RandomGenerator {
         match self {
             Self::Hardware(rand) => rand.is_bounded(),
             Self::Software(rand) => rand.is_bounded(),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(rand) => rand.is_bounded(),
         }
     }
 
@@ -85,6 +269,49 @@ impl RandomGenerator {
         match self {
             Self::Hardware(rand) => rand.remaining_bytes(),
             Self::Software(rand) => rand.remaining_bytes(),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(rand) => rand.remaining_bytes(),
+        }
+    }
+
+    /// Moves the generator forward to the byte at the given offset from the start of its
+    /// keystream, in constant time.
+    ///
+    /// The generators built with `RandomGenerator::new` start at the beginning of their keystream.
+    /// The skipped bytes are never yielded.
+    ///
+    /// # Panics
+    ///
+    /// If the generator is bounded, since it could then yield bytes reserved to other generators,
+    /// or if the offset is behind the bytes already yielded or forked.
+    pub fn seek(&mut self, offset: usize) {
+        match self {
+            Self::Hardware(ref mut rand) => rand.seek(offset),
+            Self::Software(ref mut rand) => rand.seek(offset),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(ref mut rand) => rand.seek(offset),
+        }
+    }
+
+    /// Returns a generator bounded to the `length` bytes following the byte at the given offset
+    /// from the start of the keystream, in constant time.
+    ///
+    /// Contrarily to `try_fork`, the window does not have to start at the current state: the
+    /// current generator is moved past the window, and the bytes in between are never yielded. If
+    /// the current generator is bounded, or if the window starts behind the bytes already yielded
+    /// or forked, `None` is returned.
+    pub fn generator_at(&mut self, offset: usize, length: usize) -> Option<RandomGenerator> {
+        match self {
+            Self::Hardware(rand) => rand
+                .generator_at(offset, length)
+                .map(RandomGenerator::Hardware),
+            Self::Software(rand) => rand
+                .generator_at(offset, length)
+                .map(RandomGenerator::Software),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(rand) => rand
+                .generator_at(offset, length)
+                .map(RandomGenerator::ChaCha),
         }
     }
 
@@ -98,41 +325,8 @@ impl RandomGenerator {
         n_child: usize,
         child_bytes: usize,
     ) -> Option<impl Iterator<Item = RandomGenerator>> {
-        enum GeneratorChildIter<HardIter, SoftIter>
-        where
-            HardIter: Iterator<Item = HardAesCtrGenerator>,
-            SoftIter: Iterator<Item = SoftAesCtrGenerator>,
-        {
-            Hardware(HardIter),
-            Software(SoftIter),
-        }
-
-        impl<HardIter, SoftIter> Iterator for GeneratorChildIter<HardIter, SoftIter>
-        where
-            HardIter: Iterator<Item = HardAesCtrGenerator>,
-            SoftIter: Iterator<Item = SoftAesCtrGenerator>,
-        {
-            type Item = RandomGenerator;
-
-            fn next(&mut self) -> Option<Self::Item> {
-                match self {
-                    GeneratorChildIter::Hardware(ref mut iter) => {
-                        iter.next().map(RandomGenerator::Hardware)
-                    }
-                    GeneratorChildIter::Software(ref mut iter) => {
-                        iter.next().map(RandomGenerator::Software)
-                    }
-                }
-            }
-        }
-        match self {
-            Self::Hardware(ref mut rand) => rand
-                .try_fork(ChildCount(n_child), BytesPerChild(child_bytes))
-                .map(GeneratorChildIter::Hardware),
-            Self::Software(ref mut rand) => rand
-                .try_fork(ChildCount(n_child), BytesPerChild(child_bytes))
-                .map(GeneratorChildIter::Software),
-        }
+        let factory = self.try_fork_factory(n_child, child_bytes)?;
+        Some((0..n_child).map(move |i| factory.create_child(i)))
     }
 
     /// Tries to fork the current generator into `n_child` generators each able to yield
@@ -150,81 +344,92 @@ impl RandomGenerator {
         n_child: usize,
         child_bytes: usize,
     ) -> Option<impl IndexedParallelIterator<Item = RandomGenerator>> {
-        use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
-        enum GeneratorChildIter<HardIter, SoftIter>
-        where
-            HardIter: IndexedParallelIterator<Item = HardAesCtrGenerator> + Send + Sync,
-            SoftIter: IndexedParallelIterator<Item = SoftAesCtrGenerator> + Send + Sync,
-        {
-            Hardware(HardIter),
-            Software(SoftIter),
-        }
-        impl<HardIter, SoftIter> ParallelIterator for GeneratorChildIter<HardIter, SoftIter>
-        where
-            HardIter: IndexedParallelIterator<Item = HardAesCtrGenerator> + Send + Sync,
-            SoftIter: IndexedParallelIterator<Item = SoftAesCtrGenerator> + Send + Sync,
-        {
-            type Item = RandomGenerator;
-            fn drive_unindexed<C>(self, consumer: C) -> <C as Consumer<Self::Item>>::Result
-            where
-                C: UnindexedConsumer<Self::Item>,
-            {
-                match self {
-                    Self::Hardware(iter) => iter
-                        .map(RandomGenerator::Hardware)
-                        .drive_unindexed(consumer),
-                    Self::Software(iter) => iter
-                        .map(RandomGenerator::Software)
-                        .drive_unindexed(consumer),
-                }
-            }
-        }
-        impl<HardIter, SoftIter> IndexedParallelIterator for GeneratorChildIter<HardIter, SoftIter>
-        where
-            HardIter: IndexedParallelIterator<Item = HardAesCtrGenerator> + Send + Sync,
-            SoftIter: IndexedParallelIterator<Item = SoftAesCtrGenerator> + Send + Sync,
-        {
-            fn len(&self) -> usize {
-                match self {
-                    Self::Software(iter) => iter.len(),
-                    Self::Hardware(iter) => iter.len(),
-                }
+        let factory = self.try_fork_factory(n_child, child_bytes)?;
+        Some(
+            (0..n_child)
+                .into_par_iter()
+                .map(move |i| factory.create_child(i)),
+        )
+    }
+
+    fn try_fork_factory(&mut self, n_child: usize, child_bytes: usize) -> Option<GeneratorFactory> {
+        let (n_child, child_bytes) = (ChildCount(n_child), BytesPerChild(child_bytes));
+        Some(match self {
+            Self::Hardware(ref mut rand) => {
+                GeneratorFactory::Hardware(rand.try_fork_factory(n_child, child_bytes)?)
             }
-            fn drive<C: Consumer<Self::Item>>(
-                self,
-                consumer: C,
-            ) -> <C as Consumer<Self::Item>>::Result {
-                match self {
-                    Self::Software(iter) => iter.map(RandomGenerator::Software).drive(consumer),
-                    Self::Hardware(iter) => iter.map(RandomGenerator::Hardware).drive(consumer),
-                }
+            Self::Software(ref mut rand) => {
+                GeneratorFactory::Software(rand.try_fork_factory(n_child, child_bytes)?)
             }
-            fn with_producer<CB: ProducerCallback<Self::Item>>(
-                self,
-                callback: CB,
-            ) -> <CB as ProducerCallback<Self::Item>>::Output {
-                match self {
-                    Self::Software(iter) => {
-                        iter.map(RandomGenerator::Software).with_producer(callback)
-                    }
-                    Self::Hardware(iter) => {
-                        iter.map(RandomGenerator::Hardware).with_producer(callback)
-                    }
-                }
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(ref mut rand) => {
+                GeneratorFactory::ChaCha(rand.try_fork_factory(n_child, child_bytes)?)
             }
-        }
+        })
+    }
+}
 
+// Creates the children of a fork, with the same implementation as the parent.
+#[allow(clippy::large_enum_variant)]
+enum GeneratorFactory {
+    Software(ChildrenFactory<software::Generator>),
+    Hardware(ChildrenFactory<aesni::Generator>),
+    #[cfg(feature = "chacha")]
+    ChaCha(ChildrenFactory<chacha::Generator>),
+}
+
+impl GeneratorFactory {
+    fn create_child(&self, i: usize) -> RandomGenerator {
         match self {
-            Self::Hardware(ref mut rand) => rand
-                .par_try_fork(ChildCount(n_child), BytesPerChild(child_bytes))
-                .map(GeneratorChildIter::Hardware),
-            Self::Software(ref mut rand) => rand
-                .par_try_fork(ChildCount(n_child), BytesPerChild(child_bytes))
-                .map(GeneratorChildIter::Software),
+            Self::Software(factory) => RandomGenerator::Software(factory.create_child(i)),
+            Self::Hardware(factory) => RandomGenerator::Hardware(factory.create_child(i)),
+            #[cfg(feature = "chacha")]
+            Self::ChaCha(factory) => RandomGenerator::ChaCha(factory.create_child(i)),
+        }
+    }
+}
+
+impl RngCore for RandomGenerator {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        RandomGenerator::fill_bytes(self, &mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        RandomGenerator::fill_bytes(self, &mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        RandomGenerator::fill_bytes(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand_core::Error> {
+        match self.remaining_bytes() {
+            Some(remaining) if dest.len() > remaining => Err(rand_core::Error::new(
+                "Tried to generate a byte outside the generator bound.",
+            )),
+            _ => {
+                RandomGenerator::fill_bytes(self, dest);
+                Ok(())
+            }
         }
     }
 }
 
+impl CryptoRng for RandomGenerator {}
+
+/// The seed is the little-endian representation of the secret key of the generator.
+impl SeedableRng for RandomGenerator {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        RandomGenerator::new(Some(u128::from_le_bytes(seed)))
+    }
+}
+
 impl Debug for RandomGenerator {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, "RandomGenerator")
@@ -274,6 +479,156 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_export_import_state() {
+        // Checks that a generator restored from an exported state yields the same bytes.
+        let mut generator = RandomGenerator::new(None);
+        for _ in 0..1000 {
+            generator.generate_next();
+        }
+        let state = generator.export_state();
+        let mut software = RandomGenerator::software_from_state(&state).unwrap();
+        let mut restored = RandomGenerator::from_state(&state);
+        for _ in 0..1000 {
+            let byte = generator.generate_next();
+            assert_eq!(byte, software.generate_next());
+            assert_eq!(byte, restored.generate_next());
+        }
+
+        // the bound of a child is restored as well
+        let child = generator.try_fork(1, 10).unwrap().next().unwrap();
+        let restored = RandomGenerator::from_state(&child.export_state());
+        assert_eq!(restored.remaining_bytes(), Some(10));
+    }
+
+    #[test]
+    fn test_serialize_state() {
+        // Checks that the state survives a serialization round trip.
+        let mut generator = RandomGenerator::new(None);
+        for _ in 0..300 {
+            generator.generate_next();
+        }
+        let state = generator.export_state();
+        let serialized = bincode::serialize(&state).unwrap();
+        let deserialized: RandomGeneratorState = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(state, deserialized);
+        let mut restored = RandomGenerator::from_state(&deserialized);
+        for _ in 0..300 {
+            assert_eq!(generator.generate_next(), restored.generate_next());
+        }
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        // Checks that filling a slice yields the same bytes as generating them one at a time.
+        let key = software::dev_random();
+        let mut first_generator = RandomGenerator::new(Some(key));
+        let mut second_generator = RandomGenerator::new(Some(key));
+        let mut bytes = [0u8; 1000];
+        first_generator.fill_bytes(&mut bytes);
+        for byte in bytes.iter() {
+            assert_eq!(*byte, second_generator.generate_next());
+        }
+        assert_eq!(
+            first_generator.generate_next(),
+            second_generator.generate_next()
+        );
+    }
+
+    #[test]
+    fn test_rand_core() {
+        // Checks the rand_core traits implementations.
+        let mut first_generator = RandomGenerator::from_seed([1; 16]);
+        let mut second_generator = RandomGenerator::new(Some(u128::from_le_bytes([1; 16])));
+        let mut bytes = [0u8; 12];
+        second_generator.fill_bytes(&mut bytes);
+        assert_eq!(
+            first_generator.next_u32(),
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        );
+        let mut expected = [0u8; 8];
+        expected.copy_from_slice(&bytes[4..]);
+        assert_eq!(first_generator.next_u64(), u64::from_le_bytes(expected));
+
+        // the generator can be used with the rand distributions
+        use rand::Rng;
+        let value: f64 = first_generator.gen_range(0.0..1.0);
+        assert!((0.0..1.0).contains(&value));
+
+        // filling a bounded generator beyond its bound is an error
+        let mut bounded = first_generator.try_fork(1, 10).unwrap().next().unwrap();
+        assert!(bounded.try_fill_bytes(&mut [0u8; 11]).is_err());
+        assert!(bounded.try_fill_bytes(&mut [0u8; 10]).is_ok());
+    }
+
+    #[cfg(feature = "chacha")]
+    #[test]
+    fn test_chacha() {
+        // Checks that the chacha20 generator is deterministic, and can be forked and restored.
+        let key = software::dev_random();
+        let mut generator = RandomGenerator::new_chacha(Some(key));
+        let mut other_generator = RandomGenerator::new_chacha(Some(key));
+        let mut aes_generator = RandomGenerator::new_software(Some(key));
+        let mut bytes = [0u8; 1000];
+        generator.fill_bytes(&mut bytes);
+        let mut aes_bytes = [0u8; 1000];
+        aes_generator.fill_bytes(&mut aes_bytes);
+        assert_ne!(bytes[..], aes_bytes[..]);
+        for byte in bytes.iter() {
+            assert_eq!(*byte, other_generator.generate_next());
+        }
+
+        // the children yield the bytes of the parent
+        let mut children_bytes = Vec::new();
+        for mut child in generator.try_fork(5, 30).unwrap() {
+            assert_eq!(child.remaining_bytes(), Some(30));
+            children_bytes.extend((0..30).map(|_| child.generate_next()));
+        }
+        let parent_bytes: Vec<u8> = (0..150).map(|_| other_generator.generate_next()).collect();
+        assert_eq!(children_bytes, parent_bytes);
+
+        // a restored generator is still a chacha20 generator
+        let state = generator.export_state();
+        assert_eq!(state.cipher, Cipher::ChaCha20);
+        let mut restored = RandomGenerator::from_state(&state);
+        for _ in 0..1000 {
+            assert_eq!(generator.generate_next(), restored.generate_next());
+        }
+
+        // the aes constructors refuse the state of a chacha20 generator
+        assert!(RandomGenerator::software_from_state(&state).is_none());
+        assert!(RandomGenerator::hardware_from_state(&state).is_none());
+    }
+
+    #[test]
+    fn test_seek_and_generator_at() {
+        // Checks that seeking and window generators give the bytes at the right offsets.
+        let key = software::dev_random();
+        let mut reference = RandomGenerator::new(Some(key));
+        let mut bytes = [0u8; 2000];
+        reference.fill_bytes(&mut bytes);
+
+        let mut generator = RandomGenerator::new(Some(key));
+        generator.seek(100);
+        for byte in bytes[100..300].iter() {
+            assert_eq!(*byte, generator.generate_next());
+        }
+
+        let mut window = generator.generator_at(500, 500).unwrap();
+        assert_eq!(window.remaining_bytes(), Some(500));
+        for byte in bytes[500..1000].iter() {
+            assert_eq!(*byte, window.generate_next());
+        }
+        assert!(window.generator_at(1000, 10).is_none());
+
+        // the bytes already yielded or given to a window can not be reached again
+        assert!(generator.generator_at(200, 10).is_none());
+        assert!(generator.generator_at(900, 10).is_none());
+        for byte in bytes[1000..].iter() {
+            assert_eq!(*byte, generator.generate_next());
+        }
+    }
+
     #[test]
     fn test_fork() {
         // Checks that forks returns a bounded child, and that the proper number of bytes can be
@@ -35,7 +35,7 @@ pub fn set_soft_rdseed_secret(secret: u128) {
     })
 }
 
-fn rdseed() -> u128 {
+pub(crate) fn rdseed() -> u128 {
     RDSEED_SEEDED.with(|f| {
         let is_seeded = unsafe { &*{ f.get() } };
         if !*is_seeded {
@@ -63,14 +63,19 @@ fn rdseed() -> u128 {
 pub struct Generator {
     // Aes structure
     aes: Aes128,
+    // The secret key, kept to export the state of the generator
+    key: AesKey,
 }
 
 impl AesBatchedGenerator for Generator {
     fn new(key: Option<AesKey>) -> Generator {
-        let key: [u8; 16] = key.map(|AesKey(k)| k).unwrap_or_else(rdseed).to_ne_bytes();
-        let key = GenericArray::clone_from_slice(&key[..]);
-        let aes = Aes128::new(&key);
-        Generator { aes }
+        let key = key.unwrap_or_else(|| AesKey(rdseed()));
+        let aes = Aes128::new(&key.0.to_ne_bytes().into());
+        Generator { aes, key }
+    }
+
+    fn get_key(&self) -> AesKey {
+        self.key
     }
 
     fn generate_batch(&mut self, AesCtr(aes_ctr): AesCtr) -> [u8; 128] {
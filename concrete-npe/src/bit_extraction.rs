@@ -0,0 +1,126 @@
+//! Noise formulas for the extraction of the bits of an LWE ciphertext
+//! The bits are extracted from the least significant one: at the step i, the ciphertext is
+//! multiplied by 2^(nb_bit_message - 1 - i) so the bit i lands on the most significant bit of the
+//! torus, then a bootstrap followed by a key switch computes the bit, which is subtracted from the
+//! ciphertext before the next step. The extracted bits have the variance of the output of a
+//! bootstrap followed by a key switch.
+
+use super::cross::drift_index_lut;
+use super::erfc;
+
+/// Computes the variance of the input of the bootstrap of the step `index` of a bit extraction
+/// Arguments
+/// * `var_in` - noise variance of the input ciphertext
+/// * `var_bootstrap_key_switch` - noise variance of the output of a bootstrap followed by a key
+///   switch
+/// * `nb_bit_message` - the number of bits of precision and padding of the input ciphertext
+/// * `index` - the index of the extracted bit, starting from the least significant one
+///
+/// Output
+/// * the variance of the input ciphertext after the subtraction of the `index` first bits,
+///   multiplied by 2^(nb_bit_message - 1 - index)
+pub fn variance_shifted_input(
+    var_in: f64,
+    var_bootstrap_key_switch: f64,
+    nb_bit_message: usize,
+    index: usize,
+) -> f64 {
+    let var = var_in + index as f64 * var_bootstrap_key_switch;
+    var * f64::powi(4., (nb_bit_message - 1 - index) as i32)
+}
+
+/// Computes the probability that a bootstrap extracting the most significant bit of a ciphertext
+/// (encrypting 1/4 or 3/4 on the torus) outputs the wrong bit, the noise and the drift of the
+/// rounding of the mask being normally distributed
+/// Arguments
+/// * `var_in` - noise variance of the input of the bootstrap
+/// * `lwe_dimension` - size of the LWE mask of the input of the bootstrap
+/// * `polynomial_size` - number of coefficients of the polynomials of the bootstrapping key
+///
+/// Output
+/// * the failure probability
+pub fn failure_probability_msb(var_in: f64, lwe_dimension: usize, polynomial_size: usize) -> f64 {
+    let two_n = (2 * polynomial_size) as f64;
+    let var_drift = drift_index_lut(lwe_dimension) / (two_n * two_n);
+    erfc(0.25 / f64::sqrt(2. * (var_in + var_drift)))
+}
+
+/// Computes an upper bound of the probability that at least one of the `nb_bits` extracted bits
+/// is wrong, with a union bound over the steps of the extraction
+/// Arguments
+/// * `var_in` - noise variance of the input ciphertext
+/// * `var_bootstrap_key_switch` - noise variance of the output of a bootstrap followed by a key
+///   switch
+/// * `lwe_dimension` - size of the LWE mask of the input ciphertext
+/// * `polynomial_size` - number of coefficients of the polynomials of the bootstrapping key
+/// * `nb_bit_message` - the number of bits of precision and padding of the input ciphertext
+/// * `nb_bits` - the number of extracted bits
+///
+/// Output
+/// * the upper bound of the failure probability
+/// # Example
+/// ```rust
+/// use concrete_npe::bit_extraction::failure_probability;
+///
+/// // 4 bits out of 5 bits of message
+/// let p = failure_probability(f64::powi(2., -50), f64::powi(2., -36), 630, 1024, 5, 4);
+/// assert!(p < f64::powi(2., -40));
+/// ```
+pub fn failure_probability(
+    var_in: f64,
+    var_bootstrap_key_switch: f64,
+    lwe_dimension: usize,
+    polynomial_size: usize,
+    nb_bit_message: usize,
+    nb_bits: usize,
+) -> f64 {
+    let res: f64 = (0..nb_bits)
+        .map(|index| {
+            failure_probability_msb(
+                variance_shifted_input(var_in, var_bootstrap_key_switch, nb_bit_message, index),
+                lwe_dimension,
+                polynomial_size,
+            )
+        })
+        .sum();
+    f64::min(res, 1.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erfc() {
+        // known values
+        assert!((erfc(0.) - 1.).abs() < 1e-7);
+        assert!((erfc(1.) - 0.157_299_207_050_285_1).abs() < 1e-7);
+        assert!((erfc(-1.) - 1.842_700_792_949_715).abs() < 1e-7);
+        assert!((erfc(5.) / 1.537_459_794_428_035e-12 - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_failure_probability() {
+        let (var_in, var_bks) = (f64::powi(2., -50), f64::powi(2., -36));
+
+        // extracting more bits can only increase the failure probability
+        let mut previous = 0.;
+        for nb_bits in 1..=6 {
+            let p = failure_probability(var_in, var_bks, 630, 1024, 6, nb_bits);
+            assert!(p >= previous);
+            previous = p;
+        }
+
+        // once the shift of each step is taken into account, the variance grows with the number
+        // of extracted bits since each extracted bit adds the noise of a bootstrap
+        for index in 0..5 {
+            assert!(
+                4. * variance_shifted_input(var_in, var_bks, 6, index + 1)
+                    > variance_shifted_input(var_in, var_bks, 6, index)
+            );
+        }
+
+        // a huge noise always fails
+        assert_eq!(failure_probability(1., 1., 630, 1024, 6, 6), 1.);
+    }
+}
@@ -2,6 +2,9 @@
 //! Those functions will be used in the cross-sample tests to check that
 //! the noise behavior is consistent with the theory.
 
+use crate::key_dispersion::KeyDispersion;
+use concrete_commons::key_kinds::BinaryKeyKind;
+
 pub trait Cross: Sized {
     fn external_product(
         dimension: usize,
@@ -28,6 +31,31 @@ pub trait Cross: Sized {
         base_log: usize,
         l_gadget: usize,
     ) -> f64;
+    fn external_product_with_key_kind<K: KeyDispersion>(
+        dimension: usize,
+        l_gadget: usize,
+        base_log: usize,
+        polynomial_size: usize,
+        var_trgsw: f64,
+        var_trlwe: f64,
+    ) -> f64;
+    fn bootstrap_with_key_kind<K: KeyDispersion>(
+        lwe_dimension: usize,
+        rlwe_dimension: usize,
+        l_gadget: usize,
+        base_log: usize,
+        polynomial_size: usize,
+        var_bsk: f64,
+    ) -> f64;
+    fn cmux_with_key_kind<K: KeyDispersion>(
+        var_rlwe_0: f64,
+        var_rlwe_1: f64,
+        var_trgsw: f64,
+        dimension: usize,
+        polynomial_size: usize,
+        base_log: usize,
+        l_gadget: usize,
+    ) -> f64;
 }
 
 macro_rules! impl_trait_npe_cross {
@@ -68,21 +96,71 @@ macro_rules! impl_trait_npe_cross {
                 polynomial_size: usize,
                 var_trgsw: f64,
                 var_trlwe: f64,
+            ) -> f64 {
+                Self::external_product_with_key_kind::<BinaryKeyKind>(
+                    dimension,
+                    l_gadget,
+                    base_log,
+                    polynomial_size,
+                    var_trgsw,
+                    var_trlwe,
+                )
+            }
+
+            /// Return the variance of the external product given a set of parameters, the
+            /// secret key of the TRLWE being of kind K.
+            /// Arguments
+            /// * `dimension` - the size of the RLWE mask
+            /// * `l_gadget` - number of elements for the Torus decomposition
+            /// * `base_log` - decomposition base of the gadget matrix
+            /// * `polynomial_size` - number of coefficients of the polynomial e.g. degree + 1
+            /// * `var_trgsw` - noise variance of the TRGSW
+            /// * `var_trlwe` - noise variance of the TRLWE
+            /// # Output
+            /// * Returns the variance of the output RLWE
+            /// # Warning
+            /// * only correct for the external product inside a bootstrap
+            /// # Example
+            /// ```rust
+            /// use concrete_commons::key_kinds::GaussianKeyKind;
+            /// use concrete_npe::Cross ;
+            #[doc = $DOC]
+            /// // settings
+            /// let dimension: usize = 3 ;
+            /// let l_gadget: usize = 4 ;
+            /// let base_log: usize = 7 ;
+            /// let polynomial_size: usize = 1024 ;
+            /// let var_trgsw: f64 = f64::powi(2., -38) ;
+            /// let var_trlwe: f64 = f64::powi(2., -40) ;
+            /// // Computing the noise
+            /// let var_external_product =
+            ///     <Torus as Cross>::external_product_with_key_kind::<GaussianKeyKind>(dimension,
+            ///     l_gadget, base_log, polynomial_size, var_trgsw, var_trlwe) ;
+            /// ```
+            fn external_product_with_key_kind<K: KeyDispersion>(
+                dimension: usize,
+                l_gadget: usize,
+                base_log: usize,
+                polynomial_size: usize,
+                var_trgsw: f64,
+                var_trlwe: f64,
             ) -> f64 {
                 // norm 2 of the integer polynomial hidden in the TRGSW
                 // for an external product inside a bootstrap, the integer polynomial is in fact
                 // a constant polynomial equal to 0 or 1
                 let norm_2_msg_trgsw = 1.;
                 let b_g = 1 << base_log;
-                let q_square = f64::powi(2., (2 * std::mem::size_of::<$T>() * 8) as i32);
+                let log2_modulus = std::mem::size_of::<$T>() * 8;
                 let res_1: f64 =
                     ((dimension + 1) * l_gadget * polynomial_size * (b_g * b_g + 2)) as f64 / 12.
                         * var_trgsw;
 
                 let res_2: f64 = norm_2_msg_trgsw
-                    * ((dimension * polynomial_size + 2) as f64
-                        / (24. * f64::powi(b_g as f64, 2 * l_gadget as i32)) as f64
-                        + (dimension * polynomial_size / 48 - 1 / 12) as f64 / q_square);
+                    * rounding_variance::<K>(
+                        dimension * polynomial_size,
+                        base_log * l_gadget,
+                        log2_modulus,
+                    );
 
                 let res_3: f64 = norm_2_msg_trgsw * var_trlwe;
                 let res: f64 = res_1 + res_2 + res_3;
@@ -128,7 +206,41 @@ macro_rules! impl_trait_npe_cross {
                 base_log: usize,
                 l_gadget: usize,
             ) -> f64 {
-                let var_external_product = Self::external_product(
+                Self::cmux_with_key_kind::<BinaryKeyKind>(
+                    var_rlwe_0,
+                    var_rlwe_1,
+                    var_trgsw,
+                    dimension,
+                    polynomial_size,
+                    base_log,
+                    l_gadget,
+                )
+            }
+
+            /// Return the variance of the cmux given a set of parameters, the secret key of the
+            /// TRLWE being of kind K.
+            /// Arguments
+            /// * `var_rlwe_0` - noise variance of the first TRLWE
+            /// * `var_rlwe_1` - noise variance of the second TRLWE
+            /// * `var_trgsw` - noise variance of the TRGSW
+            /// * `dimension` - the size of the RLWE mask
+            /// * `polynomial_size` - number of coefficients of the polynomial e.g. degree + 1
+            /// * `base_log` - decomposition base of the gadget matrix
+            /// * `l_gadget` - number of elements for the Torus decomposition
+            /// # Output
+            /// * Returns the variance of the output RLWE
+            /// # Warning
+            /// * only correct for the cmux inside a bootstrap
+            fn cmux_with_key_kind<K: KeyDispersion>(
+                var_rlwe_0: f64,
+                var_rlwe_1: f64,
+                var_trgsw: f64,
+                dimension: usize,
+                polynomial_size: usize,
+                base_log: usize,
+                l_gadget: usize,
+            ) -> f64 {
+                let var_external_product = Self::external_product_with_key_kind::<K>(
                     dimension,
                     l_gadget,
                     base_log,
@@ -174,9 +286,55 @@ macro_rules! impl_trait_npe_cross {
                 base_log: usize,
                 polynomial_size: usize,
                 var_bsk: f64,
+            ) -> f64 {
+                Self::bootstrap_with_key_kind::<BinaryKeyKind>(
+                    lwe_dimension,
+                    rlwe_dimension,
+                    l_gadget,
+                    base_log,
+                    polynomial_size,
+                    var_bsk,
+                )
+            }
+
+            /// Return the variance of output of a bootstrap given a set of parameters, the
+            /// secret key of the RLWE encrypted in the bootstrapping key being of kind K.
+            /// The LWE secret key of the input remains binary.
+            /// Arguments
+            /// * `lwe_dimension` - size of the LWE mask
+            /// * `rlwe_dimension` - size of the RLWE mask
+            /// * `l_gadget` - number of elements for the Torus decomposition
+            /// * `base_log` - decomposition base of the gadget matrix
+            /// * `polynomial_size` - number of coefficients of the polynomial e.g. degree + 1
+            /// * `var_bsk` - variance of the bootstrapping key
+            /// # Output
+            /// * Returns the variance of the output RLWE
+            /// # Example
+            /// ```rust
+            /// use concrete_commons::key_kinds::TernaryKeyKind;
+            /// use concrete_npe::Cross ;
+            #[doc = $DOC]
+            /// // settings
+            /// let rlwe_dimension: usize = 3 ;
+            /// let lwe_dimension: usize = 630 ;
+            /// let l_gadget: usize = 4 ;
+            /// let base_log: usize = 7 ;
+            /// let polynomial_size: usize = 1024 ;
+            /// let var_bsk: f64 = f64::powi(2., -38) ;
+            /// // Computing the noise
+            /// let var_bootstrap = <Torus as Cross>::bootstrap_with_key_kind::<TernaryKeyKind>(
+            /// lwe_dimension, rlwe_dimension, l_gadget, base_log, polynomial_size, var_bsk) ;
+            /// ```
+            fn bootstrap_with_key_kind<K: KeyDispersion>(
+                lwe_dimension: usize,
+                rlwe_dimension: usize,
+                l_gadget: usize,
+                base_log: usize,
+                polynomial_size: usize,
+                var_bsk: f64,
             ) -> f64 {
                 let b_g = 1 << base_log;
-                let q_square = f64::powi(2., (2 * std::mem::size_of::<$T>() * 8) as i32);
+                let log2_modulus = std::mem::size_of::<$T>() * 8;
                 let res_1: f64 = (lwe_dimension
                     * (rlwe_dimension + 1)
                     * l_gadget
@@ -186,11 +344,11 @@ macro_rules! impl_trait_npe_cross {
                     * var_bsk;
 
                 let res_2: f64 = lwe_dimension as f64
-                    * ((rlwe_dimension * polynomial_size + 2) as f64
-                        / (24. * f64::powi(b_g as f64, 2 * l_gadget as i32)) as f64
-                        + lwe_dimension as f64
-                            * (rlwe_dimension * polynomial_size / 48 - 1 / 12) as f64
-                            / q_square);
+                    * rounding_variance::<K>(
+                        rlwe_dimension * polynomial_size,
+                        base_log * l_gadget,
+                        log2_modulus,
+                    );
 
                 let res: f64 = res_1 + res_2;
                 return res;
@@ -202,6 +360,37 @@ macro_rules! impl_trait_npe_cross {
 impl_trait_npe_cross!(u32, "type Torus = u32;");
 impl_trait_npe_cross!(u64, "type Torus = u64;");
 
+/// Computes the variance of the error due to the rounding of a TRLWE in the decomposition of an
+/// external product, the TRLWE secret key being of kind K
+/// # Arguments
+/// * `key_size` - the number of coefficients of the TRLWE secret key i.e. dimension * polynomial
+///   size
+/// * `decomposition_precision` - the number of bits kept by the decomposition i.e. base_log *
+///   l_gadget
+/// * `log2_modulus` - the number of bits of the torus
+/// # Output
+/// * Return the variance of the error
+pub fn rounding_variance<K: KeyDispersion>(
+    key_size: usize,
+    decomposition_precision: usize,
+    log2_modulus: usize,
+) -> f64 {
+    if decomposition_precision >= log2_modulus {
+        // the decomposition is exact
+        return 0.;
+    }
+    // rounding of the body
+    let q_square = f64::powi(2., 2 * log2_modulus as i32);
+    let res_body: f64 = (f64::powi(2., -2 * decomposition_precision as i32) - 1. / q_square) / 12.;
+    // rounding of the mask multiplied by the key
+    let res_mask: f64 = key_size as f64
+        * crate::key_dispersion::rounding_times_key_variance::<K>(
+            decomposition_precision,
+            log2_modulus,
+        );
+    res_body + res_mask
+}
+
 /// Computes tho variance of the error during a bootstrap due to the round on the LWE mask
 /// # Argument
 /// * `lwe_dimension` - size of the LWE mask
@@ -260,4 +449,41 @@ mod tests {
             stdev_ks,
         );
     }
+
+    #[test]
+    fn noise_test_bootstrap_key_kinds() {
+        use concrete_commons::key_kinds::{GaussianKeyKind, TernaryKeyKind};
+
+        let (n, k, l, base_log, polynomial_size) = (630, 1, 3, 7, 1024);
+        let var_bsk = f64::powi(2., -50);
+
+        // binary keys: (kN+2)/(24 B^2l) per coefficient of the input mask
+        let expected = (n * (k + 1) * l * polynomial_size * ((1 << (2 * base_log)) + 2)) as f64
+            / 12.
+            * var_bsk
+            + n as f64 * (k * polynomial_size + 2) as f64
+                / (24. * f64::powi(2., 2 * (base_log * l) as i32));
+        let binary = <u64 as Cross>::bootstrap(n, k, l, base_log, polynomial_size, var_bsk);
+        assert!(f64::abs(binary - expected) < 1e-10 * expected);
+
+        // the rounding term grows with the second moment of the key coefficients
+        let ternary = <u64 as Cross>::bootstrap_with_key_kind::<TernaryKeyKind>(
+            n,
+            k,
+            l,
+            base_log,
+            polynomial_size,
+            var_bsk,
+        );
+        let gaussian = <u64 as Cross>::bootstrap_with_key_kind::<GaussianKeyKind>(
+            n,
+            k,
+            l,
+            base_log,
+            polynomial_size,
+            var_bsk,
+        );
+        assert!(binary < ternary);
+        assert!(ternary < gaussian);
+    }
 }
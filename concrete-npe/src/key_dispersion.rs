@@ -0,0 +1,146 @@
+//! Moments of the coefficients of the secret keys
+//! The noise formulas of the key switch, the external product and the bootstrap depend on the
+//! distribution of the coefficients of the secret key, through their mean and their variance.
+//! Those moments are given here for each kind of key generated by concrete-core.
+
+use concrete_commons::key_kinds::{
+    BinaryKeyKind, GaussianKeyKind, KeyKind, TernaryKeyKind, UniformKeyKind,
+};
+
+/// The standard deviation of the integer coefficients of a Gaussian secret key, i.e. 2^bits
+/// times the `GAUSSIAN_KEY_LOG_STD` of concrete-core, which is the same for 32 and 64 bit torus
+pub const GAUSSIAN_KEY_STD_DEV: f64 = 3.2;
+
+/// The moments of the integer coefficients of a secret key, the coefficients being seen as
+/// signed integers in [-2^(log2_modulus-1), 2^(log2_modulus-1)[
+pub trait KeyDispersion: KeyKind {
+    /// Returns the mean of a coefficient of the secret key
+    fn expectation(log2_modulus: usize) -> f64;
+
+    /// Returns the variance of a coefficient of the secret key
+    fn variance(log2_modulus: usize) -> f64;
+
+    /// Returns the variance of the square of a coefficient of the secret key
+    fn variance_of_square(log2_modulus: usize) -> f64;
+
+    /// Returns the mean of the square of a coefficient of the secret key
+    fn expectation_of_square(log2_modulus: usize) -> f64 {
+        Self::variance(log2_modulus) + f64::powi(Self::expectation(log2_modulus), 2)
+    }
+}
+
+/// Coefficients uniform in {0,1}
+impl KeyDispersion for BinaryKeyKind {
+    fn expectation(_log2_modulus: usize) -> f64 {
+        1. / 2.
+    }
+
+    fn variance(_log2_modulus: usize) -> f64 {
+        1. / 4.
+    }
+
+    fn variance_of_square(_log2_modulus: usize) -> f64 {
+        1. / 4.
+    }
+}
+
+/// Coefficients uniform in {-1,0,1}
+impl KeyDispersion for TernaryKeyKind {
+    fn expectation(_log2_modulus: usize) -> f64 {
+        0.
+    }
+
+    fn variance(_log2_modulus: usize) -> f64 {
+        2. / 3.
+    }
+
+    fn variance_of_square(_log2_modulus: usize) -> f64 {
+        2. / 9.
+    }
+}
+
+/// Coefficients following a centered normal distribution of standard deviation
+/// `GAUSSIAN_KEY_STD_DEV`, rounded to the closest integer
+impl KeyDispersion for GaussianKeyKind {
+    fn expectation(_log2_modulus: usize) -> f64 {
+        0.
+    }
+
+    fn variance(_log2_modulus: usize) -> f64 {
+        f64::powi(GAUSSIAN_KEY_STD_DEV, 2) + 1. / 12.
+    }
+
+    fn variance_of_square(_log2_modulus: usize) -> f64 {
+        2. * f64::powi(GAUSSIAN_KEY_STD_DEV, 4)
+    }
+}
+
+/// Coefficients uniform in [-2^(log2_modulus-1), 2^(log2_modulus-1)[
+impl KeyDispersion for UniformKeyKind {
+    fn expectation(_log2_modulus: usize) -> f64 {
+        -1. / 2.
+    }
+
+    fn variance(log2_modulus: usize) -> f64 {
+        (f64::powi(2., 2 * log2_modulus as i32) - 1.) / 12.
+    }
+
+    fn variance_of_square(log2_modulus: usize) -> f64 {
+        f64::powi(2., 4 * log2_modulus as i32) / 180.
+    }
+}
+
+/// Computes the variance of the error added by the rounding of a torus element to its closest
+/// multiple of 2^-decomposition_precision, multiplied by a coefficient of the secret key
+/// Arguments
+/// * `decomposition_precision` - the number of bits kept by the decomposition i.e. base_log * level
+/// * `log2_modulus` - the number of bits of the torus
+///
+/// Output
+/// * the variance of the product of the rounding error and the key coefficient
+pub fn rounding_times_key_variance<K: KeyDispersion>(
+    decomposition_precision: usize,
+    log2_modulus: usize,
+) -> f64 {
+    if decomposition_precision >= log2_modulus {
+        // the decomposition is exact
+        return 0.;
+    }
+    // the rounding error has a mean of 1/(2q) and a second moment of 2^(-2 precision)/12 +
+    // 1/(6q^2)
+    let q_square = f64::powi(2., 2 * log2_modulus as i32);
+    (f64::powi(2., -2 * decomposition_precision as i32) / 12. + 1. / (6. * q_square))
+        * K::expectation_of_square(log2_modulus)
+        - f64::powi(K::expectation(log2_modulus), 2) / (4. * q_square)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moments() {
+        // binary keys: s^2 = s
+        assert_eq!(BinaryKeyKind::expectation_of_square(64), 1. / 2.);
+        assert_eq!(TernaryKeyKind::expectation_of_square(64), 2. / 3.);
+        assert!(GaussianKeyKind::expectation_of_square(32) > 10.);
+
+        // uniform keys grow with the modulus
+        assert!(
+            UniformKeyKind::expectation_of_square(64)
+                > f64::powi(2., 32) * UniformKeyKind::expectation_of_square(32)
+        );
+    }
+
+    #[test]
+    fn test_rounding_times_key_variance() {
+        // binary keys give the 1/24 * 2^(-2 precision) + 1/(48 q^2) term
+        let q_square = f64::powi(2., 64);
+        let expected = f64::powi(2., -48) / 24. + 1. / (48. * q_square);
+        let res = rounding_times_key_variance::<BinaryKeyKind>(24, 32);
+        assert!(f64::abs(res - expected) < 1e-10 * expected);
+
+        // exact decompositions do not add noise
+        assert_eq!(rounding_times_key_variance::<UniformKeyKind>(32, 32), 0.);
+    }
+}
@@ -3,13 +3,18 @@
 //! * Contains material needed to estimate the growth of the noise when performing homomophic
 //!   computation
 
+pub mod bit_extraction;
 pub mod cross;
 pub mod gsw;
+pub mod key_dispersion;
 pub mod lwe;
+pub mod optimizer;
 pub mod rlwe;
+pub mod security;
 
 pub use cross::Cross;
 pub use gsw::GSW;
+pub use key_dispersion::KeyDispersion;
 pub use lwe::LWE;
 pub use rlwe::RLWE;
 
@@ -53,3 +58,230 @@ pub fn nb_bit_from_variance_99(var: f64, torus_bit: usize) -> usize {
         tmp.ceil() as usize
     }
 }
+
+/// Computes the complementary error function erfc(x) = 1 - erf(x) with a relative error smaller
+/// than 1.2e-7, using the Chebyshev approximation of Numerical Recipes
+/// Argument
+/// * `x` - a real number
+///
+/// Output
+/// * erfc(x)
+pub fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1. / (1. + 0.5 * z);
+    let res = t * f64::exp(
+        -z * z - 1.265_512_23
+            + t * (1.000_023_68
+                + t * (0.374_091_96
+                    + t * (0.096_784_18
+                        + t * (-0.186_288_06
+                            + t * (0.278_868_07
+                                + t * (-1.135_203_98
+                                    + t * (1.488_515_87
+                                        + t * (-0.822_152_23 + t * 0.170_872_77)))))))),
+    );
+    if x >= 0. {
+        res
+    } else {
+        2. - res
+    }
+}
+
+/// Computes the inverse of the complementary error function, i.e. x such that erfc(x) = p,
+/// with Newton iterations on log(erfc) so that the relative error on p stays the one of `erfc`
+/// even for very small probabilities
+/// Argument
+/// * `p` - a real number in ]0, 2[
+///
+/// Output
+/// * erfc^-1(p), infinite for 0 and 2
+/// # Example
+/// ```rust
+/// use concrete_npe::{erfc, erfc_inv};
+/// let x = erfc_inv(f64::powi(2., -40));
+/// assert!((erfc(x) / f64::powi(2., -40) - 1.).abs() < 1e-6);
+/// ```
+pub fn erfc_inv(p: f64) -> f64 {
+    if p <= 0. {
+        return f64::INFINITY;
+    } else if p >= 2. {
+        return f64::NEG_INFINITY;
+    } else if p > 1. {
+        // erfc(-x) = 2 - erfc(x)
+        return -erfc_inv(2. - p);
+    }
+
+    // first approximation from erfc(x) ~ exp(-x^2) / (x sqrt(pi))
+    let mut x: f64 = f64::sqrt(f64::max(-f64::ln(p), 0.));
+    let log_p = f64::ln(p);
+    for _ in 0..100 {
+        // f(x) = log(erfc(x)) - log(p), f'(x) = -2 exp(-x^2) / (sqrt(pi) erfc(x))
+        let e = erfc(x);
+        let derivative = -2. * f64::exp(-x * x) / (std::f64::consts::PI.sqrt() * e);
+        let step = (f64::ln(e) - log_p) / derivative;
+        x -= step;
+        if step.abs() <= 1e-15 * f64::max(1., x.abs()) {
+            break;
+        }
+    }
+    x
+}
+
+/// Computes the number of standard deviations z such that a centered normal distribution
+/// exceeds z standard deviations in absolute value with a given probability
+/// Argument
+/// * `failure_probability` - the probability P(|X| > z sigma)
+///
+/// Output
+/// * z
+pub fn nb_std_dev_from_failure_probability(failure_probability: f64) -> f64 {
+    std::f64::consts::SQRT_2 * erfc_inv(failure_probability)
+}
+
+/// Computes the number of bits affected by the noise with a variance var describing a normal
+/// distribution, the noise exceeding those bits with a given probability
+/// Arguments
+/// * `var` - the variance of the noise
+/// * `torus_bit` - the number of bits of the integers
+/// * `failure_probability` - the probability that the noise exceeds the returned number of bits
+///
+/// Output
+/// * the number of bits affected by the noise
+pub fn nb_bit_from_variance(var: f64, torus_bit: usize, failure_probability: f64) -> usize {
+    let sigma: f64 = f64::sqrt(var);
+    let z: f64 = nb_std_dev_from_failure_probability(failure_probability);
+    let tmp = torus_bit as f64 + f64::log2(sigma * z);
+    if tmp < 0. {
+        // means no bits are affected by the noise in the integer representation (discrete space)
+        0usize
+    } else {
+        tmp.ceil() as usize
+    }
+}
+
+/// Computes the largest variance such that a message with a given precision and padding is
+/// decrypted correctly, i.e. that the noise stays below half of the granularity, with a given
+/// probability
+/// Arguments
+/// * `nb_bit_precision` - the number of bits of precision of the message
+/// * `nb_bit_padding` - the number of bits of padding of the message
+/// * `failure_probability` - the largest probability of a wrong decryption
+///
+/// Output
+/// * the largest variance
+pub fn max_variance(
+    nb_bit_precision: usize,
+    nb_bit_padding: usize,
+    failure_probability: f64,
+) -> f64 {
+    let half_granularity = f64::powi(2., -((nb_bit_precision + nb_bit_padding) as i32 + 1));
+    let z = nb_std_dev_from_failure_probability(failure_probability);
+    f64::powi(half_granularity / z, 2)
+}
+
+/// Computes an upper bound of the probability that at least one operation of a circuit fails,
+/// with the union bound
+/// Argument
+/// * `failure_probabilities` - the failure probability of each operation
+///
+/// Output
+/// * the failure probability of the circuit
+pub fn union_bound(failure_probabilities: &[f64]) -> f64 {
+    f64::min(failure_probabilities.iter().sum(), 1.)
+}
+
+/// Computes the failure probability of each of nb_operations operations needed so that the whole
+/// circuit fails with a probability smaller than a target, with the union bound
+/// Arguments
+/// * `failure_probability` - the largest failure probability of the circuit
+/// * `nb_operations` - the number of operations which can fail
+///
+/// Output
+/// * the failure probability of one operation
+pub fn failure_probability_per_operation(failure_probability: f64, nb_operations: usize) -> f64 {
+    failure_probability / usize::max(nb_operations, 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erfc_inv() {
+        assert!(erfc_inv(1.).abs() < 1e-6);
+        for log2_p in 1..=60 {
+            let p = f64::powi(2., -log2_p);
+            let x = erfc_inv(p);
+            assert!((erfc(x) / p - 1.).abs() < 1e-6, "p = 2^-{}", log2_p);
+            if log2_p <= 40 {
+                assert!((erfc_inv(2. - p) + x).abs() < 1e-9);
+            }
+        }
+
+        // reference values computed with 30 significant digits (mpmath), the relative error of
+        // erfc moves the result by less than 1e-7
+        for (p, reference) in [
+            (f64::powi(2., -60), 6.258_604_289_958_783_9),
+            (f64::powi(2., -40), 5.051_254_085_249_389_9),
+            (1e-10, 4.572_824_967_389_485_3),
+            (1e-3, 2.326_753_765_513_524_7),
+            (0.5, 0.476_936_276_204_469_87),
+            (1.5, -0.476_936_276_204_469_87),
+        ]
+        .iter()
+        {
+            assert!(
+                (erfc_inv(*p) - reference).abs() < 1e-7,
+                "erfc_inv({:e}) = {}, expected {}",
+                p,
+                erfc_inv(*p),
+                reference
+            );
+        }
+
+        // the 99% rule of nb_bit_from_variance_99
+        let z = nb_std_dev_from_failure_probability(erfc(3. / std::f64::consts::SQRT_2));
+        assert!((z - 3.).abs() < 1e-6);
+        assert_eq!(erfc_inv(0.), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_nb_bit_from_variance() {
+        // same as the 99% rule with its failure probability
+        let p_99 = erfc(3. / std::f64::consts::SQRT_2);
+        for log2_var in -120..-10 {
+            let var = f64::powf(2., log2_var as f64 + 0.3);
+            assert_eq!(
+                nb_bit_from_variance(var, 64, p_99),
+                nb_bit_from_variance_99(var, 64)
+            );
+        }
+
+        assert!(
+            (lwe::log2_rounding_noise_from_failure_probability(630, p_99)
+                - lwe::log2_rounding_noise(630))
+            .abs()
+                < 1e-6
+        );
+
+        // smaller failure probabilities need more bits
+        let var = f64::powi(2., -50);
+        assert!(
+            nb_bit_from_variance(var, 64, f64::powi(2., -40)) > nb_bit_from_variance_99(var, 64)
+        );
+    }
+
+    #[test]
+    fn test_max_variance_x_union_bound() {
+        let p = f64::powi(2., -40);
+        let var = max_variance(4, 1, p);
+        let half_granularity = f64::powi(2., -6);
+        assert!((erfc(half_granularity / f64::sqrt(2. * var)) / p - 1.).abs() < 1e-6);
+
+        // 2^20 bootstraps with a global failure probability of 2^-20
+        let p_op = failure_probability_per_operation(f64::powi(2., -20), 1 << 20);
+        assert_eq!(p_op, f64::powi(2., -40));
+        assert_eq!(union_bound(&vec![p_op; 1 << 20]), f64::powi(2., -20));
+        assert_eq!(union_bound(&[0.6, 0.7]), 1.);
+    }
+}
@@ -2,6 +2,8 @@
 //! Those functions will be used in the lwe tests to check that
 //! the noise behavior is consistent with the theory.
 
+use crate::key_dispersion::KeyDispersion;
+use concrete_commons::key_kinds::BinaryKeyKind;
 use itertools::izip;
 
 pub trait LWE: Sized {
@@ -17,6 +19,13 @@ pub trait LWE: Sized {
         var_ks: f64,
         var_input_lwe: f64,
     ) -> f64;
+    fn key_switch_with_key_kind<K: KeyDispersion>(
+        dimension_before: usize,
+        l_ks: usize,
+        base_log: usize,
+        var_ks: f64,
+        var_input_lwe: f64,
+    ) -> f64;
 }
 
 macro_rules! impl_trait_npe_lwe {
@@ -54,10 +63,53 @@ macro_rules! impl_trait_npe_lwe {
                 var_ks: f64,
                 var_input: f64,
             ) -> f64 {
-                let q_square = f64::powi(2., (2 * std::mem::size_of::<$T>() * 8) as i32);
+                Self::key_switch_with_key_kind::<BinaryKeyKind>(
+                    dimension_before,
+                    l_ks,
+                    base_log,
+                    var_ks,
+                    var_input,
+                )
+            }
+
+            /// Return the variance of the keyswitch on a LWE sample given a set of parameters,
+            /// the input secret key being of kind K.
+            /// # Warning
+            /// * This function compute the noise of the keyswitch without functional evaluation
+            /// # Arguments
+            /// `dimension_before` - size of the input LWE mask
+            /// `l_ks` - number of level max for the torus decomposition
+            /// `base_log` - number of bits for the base B (B=2^base_log)
+            /// `var_ks` - variance of the keyswitching key
+            /// `var_input` - variance of the input LWE
+            /// # Example
+            /// ```rust
+            /// use concrete_commons::key_kinds::TernaryKeyKind;
+            /// use concrete_npe::LWE ;
+            #[doc = $DOC]
+            /// // settings
+            /// let dimension_before: usize = 630 ;
+            /// let l_ks: usize = 4 ;
+            /// let base_log: usize = 7 ;
+            /// let var_ks: f64 = f64::powi(2., -38) ;
+            /// let var_input: f64 = f64::powi(2., -40) ;
+            /// // Computing the noise
+            /// let var_ks = <Torus as LWE>::key_switch_with_key_kind::<TernaryKeyKind>(
+            /// dimension_before, l_ks, base_log, var_ks, var_input) ;
+            /// ```
+            fn key_switch_with_key_kind<K: KeyDispersion>(
+                dimension_before: usize,
+                l_ks: usize,
+                base_log: usize,
+                var_ks: f64,
+                var_input: f64,
+            ) -> f64 {
+                let log2_modulus = std::mem::size_of::<$T>() * 8;
                 let res_1: f64 = dimension_before as f64
-                    * (1. / 24. * f64::powi(2.0, -2 * (base_log * l_ks) as i32)
-                        + 1. / (48. * q_square));
+                    * crate::key_dispersion::rounding_times_key_variance::<K>(
+                        base_log * l_ks,
+                        log2_modulus,
+                    );
                 let res_2: f64 = dimension_before as f64
                     * l_ks as f64
                     * (f64::powi(2., 2 * base_log as i32) / 12. + 1. / 6.)
@@ -194,11 +246,24 @@ pub fn add_n_uncorrelated(variances: &[f64]) -> f64 {
 /// Computes an upper bound for the number of 1 in a secret key
 /// z*sigma + mean
 pub fn upper_bound_hw_secret_key(n: usize) -> usize {
+    upper_bound_square_norm_secret_key::<BinaryKeyKind>(n, 64).round() as usize
+}
+
+/// Computes an upper bound for the sum of the squares of the coefficients of a secret key of
+/// kind K, which is its hamming weight for a binary key
+/// z*sigma + mean
+/// Arguments
+/// * `n` - the size of the secret key
+/// * `log2_modulus` - the number of bits of the torus
+///
+/// Output
+/// * the upper bound
+pub fn upper_bound_square_norm_secret_key<K: KeyDispersion>(n: usize, log2_modulus: usize) -> f64 {
     let n_f: f64 = n as f64;
-    let sigma: f64 = f64::sqrt(n_f) / 2.;
-    let mean: f64 = n_f / 2.;
+    let sigma: f64 = f64::sqrt(n_f * K::variance_of_square(log2_modulus));
+    let mean: f64 = n_f * K::expectation_of_square(log2_modulus);
     let z: f64 = 3.;
-    (mean + z * sigma).round() as usize
+    mean + z * sigma
 }
 
 /// Computes an upper bound for the log2 of the rounding noise
@@ -213,3 +278,13 @@ pub fn log2_rounding_noise(n: usize) -> f64 {
     let z: f64 = 3.;
     f64::log2(sigma * z)
 }
+
+/// Computes an upper bound for the log2 of the rounding noise, exceeded with a probability
+/// smaller than failure_probability
+/// z*sigma (mean is 0.), z being given by the failure probability
+pub fn log2_rounding_noise_from_failure_probability(n: usize, failure_probability: f64) -> f64 {
+    let bound_sup: f64 = upper_bound_hw_secret_key(n) as f64;
+    let sigma: f64 = f64::sqrt(bound_sup / 12.);
+    let z: f64 = crate::nb_std_dev_from_failure_probability(failure_probability);
+    f64::log2(sigma * z)
+}
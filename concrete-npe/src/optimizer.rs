@@ -0,0 +1,398 @@
+//! Search of secure parameters for a bootstrap followed by a key switch
+//! The noise is estimated with the formulas of this crate and the security with the LWE estimator
+//! points of the parameter sets of concrete, the search minimizing the cost of a bootstrap and a key
+//! switch for a target failure probability.
+
+use crate::cross::{drift_index_lut, Cross};
+use crate::lwe::LWE;
+
+/// (dimension, log2 of the standard deviation) giving 128 bits of security
+/// (LWE estimator, September 15th 2020)
+const SECURITY_128: [(usize, i32); 12] = [
+    (256, -5),
+    (512, -11),
+    (630, -14),
+    (650, -15),
+    (688, -16),
+    (710, -17),
+    (750, -18),
+    (800, -19),
+    (830, -20),
+    (1024, -25),
+    (2048, -52),
+    (4096, -105),
+];
+
+/// (dimension, log2 of the standard deviation) giving 80 bits of security
+/// (LWE estimator, September 15th 2020)
+const SECURITY_80: [(usize, i32); 11] = [
+    (256, -9),
+    (512, -19),
+    (630, -24),
+    (650, -25),
+    (688, -26),
+    (710, -27),
+    (750, -29),
+    (800, -31),
+    (830, -32),
+    (1024, -40),
+    (2048, -82),
+];
+
+/// Smallest log2 of the standard deviation of the noise on the 64-bit torus
+const MIN_LOG2_STD_DEV: i32 = -62;
+
+/// Structure describing a set of parameters found by the optimizer
+/// # Attributes
+/// * `lwe_dimension` - the size of the LWE mask, i.e. the input of the bootstrap
+/// * `lwe_log2_std_dev` - the log2 of the standard deviation of the LWE secret key
+/// * `rlwe_dimension` - the size of the RLWE mask
+/// * `polynomial_size` - the number of coefficients of the polynomials
+/// * `rlwe_log2_std_dev` - the log2 of the standard deviation of the RLWE secret key
+/// * `bsk_base_log` - the log2 of the decomposition base of the bootstrapping key
+/// * `bsk_level` - the number of levels of the decomposition of the bootstrapping key
+/// * `ksk_base_log` - the log2 of the decomposition base of the key switching key
+/// * `ksk_level` - the number of levels of the decomposition of the key switching key
+/// * `cost` - the estimated cost of a bootstrap followed by a key switch
+/// * `failure_probability` - the probability that a bootstrap outputs a wrong value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptimizedParameters {
+    pub lwe_dimension: usize,
+    pub lwe_log2_std_dev: i32,
+    pub rlwe_dimension: usize,
+    pub polynomial_size: usize,
+    pub rlwe_log2_std_dev: i32,
+    pub bsk_base_log: usize,
+    pub bsk_level: usize,
+    pub ksk_base_log: usize,
+    pub ksk_level: usize,
+    pub cost: f64,
+    pub failure_probability: f64,
+}
+
+/// Computes the smallest log2 of the standard deviation of an LWE secret key with a given
+/// dimension reaching a security level, by interpolating the LWE estimator points
+/// Arguments
+/// * `dimension` - the size of the LWE mask
+/// * `security_level` - the number of bits of security, 80 or 128
+///
+/// Output
+/// * None if the security level is not supported or if the dimension is outside of the points
+/// # Example
+/// ```rust
+/// use concrete_npe::optimizer::minimal_log2_std_dev;
+/// assert_eq!(minimal_log2_std_dev(630, 128), Some(-14));
+/// assert_eq!(minimal_log2_std_dev(640, 128), Some(-14));
+/// ```
+pub fn minimal_log2_std_dev(dimension: usize, security_level: usize) -> Option<i32> {
+    let points: &[(usize, i32)] = match security_level {
+        128 => &SECURITY_128,
+        80 => &SECURITY_80,
+        _ => return None,
+    };
+    for w in points.windows(2) {
+        let ((d_0, s_0), (d_1, s_1)) = (w[0], w[1]);
+        if dimension >= d_0 && dimension <= d_1 {
+            // round toward the larger noise to stay on the secure side
+            let s = s_0 as f64 + (s_1 - s_0) as f64 * (dimension - d_0) as f64 / (d_1 - d_0) as f64;
+            return Some(i32::max(s.ceil() as i32, MIN_LOG2_STD_DEV));
+        }
+    }
+    None
+}
+
+/// Computes the cost of a bootstrap followed by a key switch, counted in multiplications: the
+/// external products are dominated by the FFTs of the decomposed polynomials
+/// Arguments
+/// * `lwe_dimension` - the size of the LWE mask
+/// * `rlwe_dimension` - the size of the RLWE mask
+/// * `polynomial_size` - the number of coefficients of the polynomials
+/// * `bsk_level` - the number of levels of the bootstrapping key
+/// * `ksk_level` - the number of levels of the key switching key
+///
+/// Output
+/// * the estimated cost
+pub fn bootstrap_then_key_switch_cost(
+    lwe_dimension: usize,
+    rlwe_dimension: usize,
+    polynomial_size: usize,
+    bsk_level: usize,
+    ksk_level: usize,
+) -> f64 {
+    let k_1 = (rlwe_dimension + 1) as f64;
+    let n = polynomial_size as f64;
+    let external_product = k_1 * bsk_level as f64 * n * f64::log2(n) + k_1 * k_1 * n;
+    let key_switch = (rlwe_dimension * polynomial_size * ksk_level * (lwe_dimension + 1)) as f64;
+    lwe_dimension as f64 * external_product + key_switch
+}
+
+/// Computes the probability that a bootstrap outputs a wrong value when its input is the key
+/// switch of a sum of bootstrapped ciphertexts
+/// Arguments
+/// * `params` - the parameters, the cost and the failure probability being ignored
+/// * `nb_bit_precision` - the number of bits of precision of the messages
+/// * `nb_bit_padding` - the number of bits of padding of the messages
+/// * `nb_additions` - the number of additions between two bootstraps
+///
+/// Output
+/// * the failure probability
+pub fn failure_probability(
+    params: &OptimizedParameters,
+    nb_bit_precision: usize,
+    nb_bit_padding: usize,
+    nb_additions: usize,
+) -> f64 {
+    let var_bsk = f64::powi(2., 2 * params.rlwe_log2_std_dev);
+    let var_ksk = f64::powi(2., 2 * params.lwe_log2_std_dev);
+    let var_bootstrap = <u64 as Cross>::bootstrap(
+        params.lwe_dimension,
+        params.rlwe_dimension,
+        params.bsk_level,
+        params.bsk_base_log,
+        params.polynomial_size,
+        var_bsk,
+    );
+    let var_key_switch = <u64 as LWE>::key_switch(
+        params.rlwe_dimension * params.polynomial_size,
+        params.ksk_level,
+        params.ksk_base_log,
+        var_ksk,
+        0.,
+    );
+    let variance = input_variance(
+        var_bootstrap,
+        var_key_switch,
+        params.lwe_dimension,
+        params.polynomial_size,
+        nb_additions,
+    );
+    failure_probability_from_variance(variance, nb_bit_precision, nb_bit_padding)
+}
+
+/// Searches the parameters minimizing the cost of a bootstrap followed by a key switch, such that
+/// the sum of `nb_additions + 1` bootstrapped ciphertexts can be key switched and bootstrapped with
+/// a failure probability smaller than the target
+/// Arguments
+/// * `nb_bit_precision` - the number of bits of precision of the messages
+/// * `nb_bit_padding` - the number of bits of padding of the messages
+/// * `nb_additions` - the number of additions between two bootstraps
+/// * `target_failure_probability` - the largest failure probability of a bootstrap
+/// * `security_level` - the number of bits of security, 80 or 128
+///
+/// Output
+/// * None if no parameters reach the target
+/// # Example
+/// ```rust
+/// use concrete_npe::optimizer::optimize;
+/// let params = optimize(3, 1, 2, f64::powi(2., -20), 128).unwrap();
+/// assert!(params.failure_probability <= f64::powi(2., -20));
+/// ```
+pub fn optimize(
+    nb_bit_precision: usize,
+    nb_bit_padding: usize,
+    nb_additions: usize,
+    target_failure_probability: f64,
+    security_level: usize,
+) -> Option<OptimizedParameters> {
+    let mut best: Option<OptimizedParameters> = None;
+    for log2_polynomial_size in 8..=12 {
+        let polynomial_size: usize = 1 << log2_polynomial_size;
+        for rlwe_dimension in 1..=4 {
+            // security of the RLWE secret key, seen as an LWE secret key
+            let rlwe_log2_std_dev =
+                match minimal_log2_std_dev(rlwe_dimension * polynomial_size, security_level) {
+                    Some(s) => s,
+                    None => continue,
+                };
+            let var_bsk = f64::powi(2., 2 * rlwe_log2_std_dev);
+
+            for lwe_dimension in (256..=2048).step_by(8) {
+                let lwe_log2_std_dev = match minimal_log2_std_dev(lwe_dimension, security_level) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                let var_ksk = f64::powi(2., 2 * lwe_log2_std_dev);
+
+                // best decomposition for each number of levels
+                let bsk_decompositions = best_decompositions(|base_log, level| {
+                    // the formula is computed with integers
+                    let log2_size = f64::log2(
+                        (lwe_dimension * (rlwe_dimension + 1) * level * polynomial_size) as f64,
+                    );
+                    if (2 * base_log) as f64 + log2_size >= 62. {
+                        return f64::INFINITY;
+                    }
+                    <u64 as Cross>::bootstrap(
+                        lwe_dimension,
+                        rlwe_dimension,
+                        level,
+                        base_log,
+                        polynomial_size,
+                        var_bsk,
+                    )
+                });
+                let ksk_decompositions = best_decompositions(|base_log, level| {
+                    <u64 as LWE>::key_switch(
+                        rlwe_dimension * polynomial_size,
+                        level,
+                        base_log,
+                        var_ksk,
+                        0.,
+                    )
+                });
+
+                for (bsk_level, (bsk_base_log, var_bootstrap)) in bsk_decompositions.iter() {
+                    for (ksk_level, (ksk_base_log, var_key_switch)) in ksk_decompositions.iter() {
+                        let cost = bootstrap_then_key_switch_cost(
+                            lwe_dimension,
+                            rlwe_dimension,
+                            polynomial_size,
+                            *bsk_level,
+                            *ksk_level,
+                        );
+                        if matches!(best, Some(b) if b.cost <= cost) {
+                            continue;
+                        }
+                        let variance = input_variance(
+                            *var_bootstrap,
+                            *var_key_switch,
+                            lwe_dimension,
+                            polynomial_size,
+                            nb_additions,
+                        );
+                        let p = failure_probability_from_variance(
+                            variance,
+                            nb_bit_precision,
+                            nb_bit_padding,
+                        );
+                        if p <= target_failure_probability {
+                            best = Some(OptimizedParameters {
+                                lwe_dimension,
+                                lwe_log2_std_dev,
+                                rlwe_dimension,
+                                polynomial_size,
+                                rlwe_log2_std_dev,
+                                bsk_base_log: *bsk_base_log,
+                                bsk_level: *bsk_level,
+                                ksk_base_log: *ksk_base_log,
+                                ksk_level: *ksk_level,
+                                cost,
+                                failure_probability: p,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Computes the variance of the input of a bootstrap, i.e. the key switch of a sum of bootstrapped
+/// ciphertexts, plus the drift due to the rounding of the mask
+fn input_variance(
+    var_bootstrap: f64,
+    var_key_switch: f64,
+    lwe_dimension: usize,
+    polynomial_size: usize,
+    nb_additions: usize,
+) -> f64 {
+    let two_n = (2 * polynomial_size) as f64;
+    (nb_additions + 1) as f64 * var_bootstrap
+        + var_key_switch
+        + drift_index_lut(lwe_dimension) / (two_n * two_n)
+}
+
+/// Computes the probability that the noise exceeds half of the granularity
+fn failure_probability_from_variance(
+    variance: f64,
+    nb_bit_precision: usize,
+    nb_bit_padding: usize,
+) -> f64 {
+    let half_granularity = f64::powi(2., -((nb_bit_precision + nb_bit_padding) as i32 + 1));
+    crate::erfc(half_granularity / f64::sqrt(2. * variance))
+}
+
+/// Returns, for each number of levels, the (level, (base_log, variance)) with the smallest
+/// variance, the decomposition fitting in 64 bits
+fn best_decompositions<F: Fn(usize, usize) -> f64>(variance: F) -> Vec<(usize, (usize, f64))> {
+    let mut res = Vec::new();
+    for level in 1..=16 {
+        let mut best: Option<(usize, f64)> = None;
+        for base_log in 1..=(64 / level) {
+            let var = variance(base_log, level);
+            if !matches!(best, Some((_, v)) if var >= v) {
+                best = Some((base_log, var));
+            }
+        }
+        if let Some(b) = best {
+            res.push((level, b));
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_log2_std_dev() {
+        // the points of the estimator, within the 64-bit torus
+        for (dimension, log2_std_dev) in SECURITY_128.iter() {
+            assert_eq!(
+                minimal_log2_std_dev(*dimension, 128),
+                Some(i32::max(*log2_std_dev, MIN_LOG2_STD_DEV))
+            );
+        }
+        for (dimension, log2_std_dev) in SECURITY_80.iter() {
+            assert_eq!(
+                minimal_log2_std_dev(*dimension, 80),
+                Some(i32::max(*log2_std_dev, MIN_LOG2_STD_DEV))
+            );
+        }
+
+        // the noise decreases with the dimension and increases with the security
+        for dimension in 256..2048 {
+            let s = minimal_log2_std_dev(dimension, 128).unwrap();
+            assert!(s >= minimal_log2_std_dev(dimension + 1, 128).unwrap());
+            assert!(s >= minimal_log2_std_dev(dimension, 80).unwrap());
+        }
+
+        // unsupported
+        assert_eq!(minimal_log2_std_dev(100, 128), None);
+        assert_eq!(minimal_log2_std_dev(8192, 128), None);
+        assert_eq!(minimal_log2_std_dev(630, 256), None);
+    }
+
+    #[test]
+    fn test_optimize() {
+        let target = f64::powi(2., -30);
+        let params_2 = optimize(2, 1, 3, target, 128).unwrap();
+        let params_4 = optimize(4, 1, 3, target, 128).unwrap();
+
+        // the failure probability is recomputed from the parameters
+        assert!(params_2.failure_probability <= target);
+        assert_eq!(
+            failure_probability(&params_2, 2, 1, 3),
+            params_2.failure_probability
+        );
+
+        // secure parameters
+        assert_eq!(
+            Some(params_2.lwe_log2_std_dev),
+            minimal_log2_std_dev(params_2.lwe_dimension, 128)
+        );
+        assert_eq!(
+            Some(params_2.rlwe_log2_std_dev),
+            minimal_log2_std_dev(params_2.rlwe_dimension * params_2.polynomial_size, 128)
+        );
+
+        // more precision costs more, less security costs less
+        assert!(params_4.cost >= params_2.cost);
+        assert!(optimize(2, 1, 3, target, 80).unwrap().cost <= params_2.cost);
+
+        // out of reach
+        assert_eq!(optimize(20, 1, 3, target, 128), None);
+    }
+}
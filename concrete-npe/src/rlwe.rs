@@ -1,10 +1,15 @@
-//! Noise formulas for the RLWE operations considering that all slot have the same error variance
+//! Noise formulas for the RLWE operations
+//! The variances are given slot by slot, i.e. one variance for each coefficient of the polynomial
+//! encrypted in the RLWE ciphertext, except for the functions considering that all slots have the
+//! same error variance.
 
 use crate::LWE;
 
 pub trait RLWE: Sized {
     type STorus;
     fn scalar_polynomial_mult(variance: f64, scalar_polynomial: &[Self]) -> f64;
+    fn polynomial_mult(variances: &[f64], scalar_polynomial: &[Self]) -> Vec<f64>;
+    fn constant_mult(variances: &[f64], constant: Self) -> Vec<f64>;
 }
 
 macro_rules! impl_trait_npe_rlwe {
@@ -25,9 +30,163 @@ macro_rules! impl_trait_npe_rlwe {
                     scalar_polynomial,
                 );
             }
+
+            /// Computes the variances of the error distribution after a multiplication modulo
+            /// X^N+1 between a RLWE sample and a scalar polynomial with signed coefficients, each
+            /// slot having its own variance i.e.
+            /// sigma_out_j^2 <- \Sum_i weight_i^2 * sigma_{j-i mod N}^2
+            /// Arguments
+            /// * `variances` - the error variances of the slots of the input ciphertext
+            /// * `scalar_polynomial` - a slice of Torus with the coefficients of the polynomial
+            /// Output
+            /// * the error variances of the slots of the output ciphertext
+            /// # Example
+            /// ```rust
+            /// use concrete_npe::RLWE ;
+            #[doc = $DOC]
+            /// // parameters
+            /// let variances: Vec<f64> = vec![f64::powi(2., -30), f64::powi(2., -32)] ;
+            /// let polynomial: Vec<Torus> = vec![(-3 as i64) as Torus, 2 as Torus] ;
+            /// // noise computation
+            /// let var_out: Vec<f64> = <Torus as RLWE>::polynomial_mult(&variances, &polynomial) ;
+            /// ```
+            fn polynomial_mult(variances: &[f64], scalar_polynomial: &[Self]) -> Vec<f64> {
+                let polynomial_size = variances.len();
+                let mut res: Vec<f64> = vec![0.; polynomial_size];
+                for (i, &weight) in scalar_polynomial.iter().enumerate() {
+                    let square_weight = <$T as LWE>::single_scalar_mul(1., weight);
+                    for (j, var_out) in res.iter_mut().enumerate() {
+                        *var_out += square_weight
+                            * variances[(j + polynomial_size - i) % polynomial_size];
+                    }
+                }
+                return res;
+            }
+
+            /// Computes the variances of the error distribution after a multiplication between a
+            /// RLWE sample and a signed constant i.e. sigma_out_j^2 <- constant^2 * sigma_j^2
+            /// Arguments
+            /// * `variances` - the error variances of the slots of the input ciphertext
+            /// * `constant` - the constant as a Torus element
+            /// Output
+            /// * the error variances of the slots of the output ciphertext
+            /// # Example
+            /// ```rust
+            /// use concrete_npe::RLWE ;
+            #[doc = $DOC]
+            /// // parameters
+            /// let variances: Vec<f64> = vec![f64::powi(2., -30), f64::powi(2., -32)] ;
+            /// let constant: Torus = (-3 as i64) as Torus ;
+            /// // noise computation
+            /// let var_out: Vec<f64> = <Torus as RLWE>::constant_mult(&variances, constant) ;
+            /// ```
+            fn constant_mult(variances: &[f64], constant: Self) -> Vec<f64> {
+                return variances
+                    .iter()
+                    .map(|var| <$T as LWE>::single_scalar_mul(*var, constant))
+                    .collect();
+            }
         }
     };
 }
 
 impl_trait_npe_rlwe!(u32, i32, "type Torus = u32;");
 impl_trait_npe_rlwe!(u64, i64, "type Torus = u64;");
+
+/// Computes the variances of the error distribution after the addition of two uncorrelated RLWE
+/// ciphertexts sigma_out_j^2 <- sigma0_j^2 + sigma1_j^2
+/// Arguments
+/// * `variances_0` - the error variances of the slots of the first input ciphertext
+/// * `variances_1` - the error variances of the slots of the second input ciphertext
+///
+/// Output
+/// * the error variances of the slots of the output ciphertext
+pub fn add_uncorrelated(variances_0: &[f64], variances_1: &[f64]) -> Vec<f64> {
+    variances_0
+        .iter()
+        .zip(variances_1.iter())
+        .map(|(var_0, var_1)| crate::add_ciphertexts(*var_0, *var_1))
+        .collect()
+}
+
+/// Computes the variances of the error distribution after the addition of two RLWE ciphertexts
+/// encrypted under different keys. The output is encrypted under the concatenation of the two
+/// keys, its dimension being the sum of the input dimensions, and the errors are simply added.
+/// Arguments
+/// * `variances_0` - the error variances of the slots of the first input ciphertext
+/// * `variances_1` - the error variances of the slots of the second input ciphertext
+///
+/// Output
+/// * the error variances of the slots of the output ciphertext
+pub fn add_different_keys(variances_0: &[f64], variances_1: &[f64]) -> Vec<f64> {
+    add_uncorrelated(variances_0, variances_1)
+}
+
+/// Computes the variances of the error distribution after a multiplication by the monomial
+/// X^degree modulo X^N+1, which only rotates the slots (and negates some of them)
+/// Arguments
+/// * `variances` - the error variances of the slots of the input ciphertext
+/// * `degree` - the degree of the monomial, in [0,2N[
+///
+/// Output
+/// * the error variances of the slots of the output ciphertext
+pub fn monomial_mult(variances: &[f64], degree: usize) -> Vec<f64> {
+    let polynomial_size = variances.len();
+    let shift = degree % polynomial_size;
+    (0..polynomial_size)
+        .map(|j| variances[(j + polynomial_size - shift) % polynomial_size])
+        .collect()
+}
+
+/// Computes the variance of the error of the LWE ciphertext obtained with the sample extraction
+/// of a slot of a RLWE ciphertext, which does not add any noise
+/// Arguments
+/// * `variances` - the error variances of the slots of the input ciphertext
+/// * `n_coeff` - the index of the extracted slot
+///
+/// Output
+/// * the error variance of the output LWE ciphertext
+pub fn sample_extract(variances: &[f64], n_coeff: usize) -> f64 {
+    variances[n_coeff]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polynomial_mult() {
+        let variances: Vec<f64> = vec![1., 2., 3., 4.];
+
+        // a constant polynomial equals the constant multiplication
+        let constant: u64 = (-3_i64) as u64;
+        assert_eq!(
+            <u64 as RLWE>::polynomial_mult(&variances, &[constant, 0, 0, 0]),
+            <u64 as RLWE>::constant_mult(&variances, constant)
+        );
+
+        // a monomial polynomial rotates the variances
+        assert_eq!(
+            <u64 as RLWE>::polynomial_mult(&variances, &[0, 0, 1, 0]),
+            monomial_mult(&variances, 2)
+        );
+        assert_eq!(monomial_mult(&variances, 6), vec![3., 4., 1., 2.]);
+
+        // uniform variances give back the scalar polynomial formula
+        let polynomial: Vec<u32> = vec![1, (-2_i32) as u32, 3, 0];
+        let res = <u32 as RLWE>::polynomial_mult(&[1.; 4], &polynomial);
+        for var in res.iter() {
+            assert_eq!(*var, <u32 as RLWE>::scalar_polynomial_mult(1., &polynomial));
+        }
+    }
+
+    #[test]
+    fn test_add_x_sample_extract() {
+        let variances_0: Vec<f64> = vec![1., 2.];
+        let variances_1: Vec<f64> = vec![3., 5.];
+        let res = add_uncorrelated(&variances_0, &variances_1);
+        assert_eq!(res, vec![4., 7.]);
+        assert_eq!(add_different_keys(&variances_0, &variances_1), res);
+        assert_eq!(sample_extract(&res, 1), 7.);
+    }
+}
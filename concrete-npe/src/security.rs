@@ -0,0 +1,413 @@
+//! Estimation of the security of LWE on the 64-bit torus against lattice attacks
+//! The primal attack (uSVP), the dual attack and the hybrid dual attack guessing some coordinates
+//! of a small secret are estimated with the 2016 estimate of the BKZ block size and a choice of
+//! cost models for BKZ. The `Sieve` model is the one of the LWE estimator used for the parameter
+//! sets of concrete.
+
+/// log2 of the modulus of the 64-bit torus
+const LOG2_MODULUS: f64 = 64.;
+
+/// Smallest BKZ block size considered by the attacks
+const MIN_BLOCK_SIZE: usize = 40;
+
+/// Distributions of the coefficients of the secret key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SecretDistribution {
+    /// uniform in {0, 1}
+    Binary,
+    /// uniform in {-1, 0, 1}
+    Ternary,
+    /// discrete Gaussian over the integers with a given standard deviation
+    Gaussian(f64),
+}
+
+impl SecretDistribution {
+    /// Returns the standard deviation of a coefficient of the secret (centered)
+    pub fn std_dev(&self) -> f64 {
+        match self {
+            SecretDistribution::Binary => 0.5,
+            SecretDistribution::Ternary => f64::sqrt(2. / 3.),
+            SecretDistribution::Gaussian(std_dev) => *std_dev,
+        }
+    }
+
+    /// Returns the log2 of the number of guesses needed per coefficient of the secret
+    pub fn log2_guesses(&self) -> f64 {
+        match self {
+            SecretDistribution::Binary => 1.,
+            SecretDistribution::Ternary => f64::log2(3.),
+            // entropy of the Gaussian, at least one bit
+            SecretDistribution::Gaussian(std_dev) => f64::max(
+                1.,
+                f64::log2(std_dev * f64::sqrt(2. * std::f64::consts::PI * std::f64::consts::E)),
+            ),
+        }
+    }
+}
+
+/// Cost models of BKZ with a block size beta in a lattice of dimension d
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CostModel {
+    /// one call to a classical sieve: 0.292 beta
+    CoreSVP,
+    /// one call to a quantum sieve: 0.265 beta
+    QuantumCoreSVP,
+    /// 8 d calls to a classical sieve: 0.292 beta + 16.4 + log2(8 d)
+    Sieve,
+    /// 8 d calls to a quantum sieve: 0.265 beta + 16.4 + log2(8 d)
+    QuantumSieve,
+}
+
+impl CostModel {
+    /// Returns the log2 of the cost of BKZ
+    /// Arguments
+    /// * `block_size` - the block size beta
+    /// * `lattice_dimension` - the dimension d of the lattice
+    pub fn log2_bkz_cost(&self, block_size: usize, lattice_dimension: usize) -> f64 {
+        let beta = block_size as f64;
+        let nb_tours = f64::log2(8. * lattice_dimension as f64);
+        match self {
+            CostModel::CoreSVP => 0.292 * beta,
+            CostModel::QuantumCoreSVP => 0.265 * beta,
+            CostModel::Sieve => 0.292 * beta + 16.4 + nb_tours,
+            CostModel::QuantumSieve => 0.265 * beta + 16.4 + nb_tours,
+        }
+    }
+}
+
+/// Lattice attacks against LWE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Attack {
+    /// embedding of the LWE instance in a uSVP instance, solved with BKZ
+    PrimalUSVP,
+    /// distinguishing with short vectors of the dual lattice
+    Dual,
+    /// dual attack after guessing some coordinates of the secret
+    Hybrid,
+}
+
+/// Structure describing the cheapest instance of an attack
+/// # Attributes
+/// * `attack` - the attack
+/// * `log2_cost` - the log2 of the number of operations, i.e. the number of bits of security
+/// * `block_size` - the block size of BKZ
+/// * `nb_samples` - the number of LWE samples used
+/// * `nb_guessed` - the number of guessed coordinates of the secret
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecurityEstimate {
+    pub attack: Attack,
+    pub log2_cost: f64,
+    pub block_size: usize,
+    pub nb_samples: usize,
+    pub nb_guessed: usize,
+}
+
+/// Computes the root Hermite factor reached by BKZ with a given block size
+pub fn root_hermite_factor(block_size: usize) -> f64 {
+    let beta = block_size as f64;
+    let pi = std::f64::consts::PI;
+    f64::powf(
+        f64::powf(pi * beta, 1. / beta) * beta / (2. * pi * std::f64::consts::E),
+        1. / (2. * (beta - 1.)),
+    )
+}
+
+/// Estimates the cost of the primal attack: the secret and the error are found as the unique
+/// shortest vector of a q-ary lattice, the secret being rescaled to the size of the error, with
+/// the smallest block size such that sqrt(beta) sigma <= delta^(2 beta - d) Vol^(1/d)
+/// Arguments
+/// * `dimension` - the size of the LWE mask
+/// * `log2_std_dev` - the log2 of the standard deviation of the error on the torus
+/// * `secret` - the distribution of the secret
+/// * `cost_model` - the cost model of BKZ
+///
+/// Output
+/// * the estimate, with an infinite cost if no block size succeeds
+pub fn primal_usvp(
+    dimension: usize,
+    log2_std_dev: f64,
+    secret: SecretDistribution,
+    cost_model: CostModel,
+) -> SecurityEstimate {
+    let n = dimension as f64;
+    let log2_error = log2_std_dev + LOG2_MODULUS;
+    let log2_scaling = log2_error - f64::log2(secret.std_dev());
+    let mut res = infinite_estimate(Attack::PrimalUSVP);
+    for block_size in MIN_BLOCK_SIZE..=(3 * dimension) {
+        let log2_delta = f64::log2(root_hermite_factor(block_size));
+
+        // lattice dimension maximizing the right hand side
+        let c = n * (LOG2_MODULUS - log2_scaling) + LOG2_MODULUS;
+        let d = f64::max(n + 1., f64::sqrt(c / log2_delta).round());
+        let m = d - n - 1.;
+
+        let lhs = log2_error + 0.5 * f64::log2(block_size as f64);
+        let rhs =
+            (2. * block_size as f64 - d) * log2_delta + (m * LOG2_MODULUS + n * log2_scaling) / d;
+        if lhs <= rhs {
+            res.log2_cost = cost_model.log2_bkz_cost(block_size, d as usize);
+            res.block_size = block_size;
+            res.nb_samples = m as usize;
+            break;
+        }
+    }
+    res
+}
+
+/// Estimates the cost of the dual attack: short vectors of the (rescaled) dual lattice are used to
+/// distinguish the LWE samples from uniform ones, with an advantage
+/// exp(-2 pi^2 (sigma l)^2) for a vector of length l, each of the 1/advantage^2 vectors costing one BKZ
+/// Arguments
+/// * `dimension` - the size of the LWE mask
+/// * `log2_std_dev` - the log2 of the standard deviation of the error on the torus
+/// * `secret` - the distribution of the secret
+/// * `cost_model` - the cost model of BKZ
+///
+/// Output
+/// * the estimate
+pub fn dual(
+    dimension: usize,
+    log2_std_dev: f64,
+    secret: SecretDistribution,
+    cost_model: CostModel,
+) -> SecurityEstimate {
+    dual_with_guesses(dimension, log2_std_dev, secret, cost_model, 0)
+}
+
+/// Estimates the cost of the hybrid dual attack: k coordinates of the secret are guessed and the
+/// dual attack is run on the remaining ones, the short vectors being shared between the guesses
+/// Arguments
+/// * `dimension` - the size of the LWE mask
+/// * `log2_std_dev` - the log2 of the standard deviation of the error on the torus
+/// * `secret` - the distribution of the secret
+/// * `cost_model` - the cost model of BKZ
+///
+/// Output
+/// * the estimate for the best number of guessed coordinates
+pub fn hybrid(
+    dimension: usize,
+    log2_std_dev: f64,
+    secret: SecretDistribution,
+    cost_model: CostModel,
+) -> SecurityEstimate {
+    let mut res = infinite_estimate(Attack::Hybrid);
+    let step = usize::max(1, dimension / 256);
+    for nb_guessed in (step..dimension / 2).step_by(step) {
+        let estimate = dual_with_guesses(dimension, log2_std_dev, secret, cost_model, nb_guessed);
+        if estimate.log2_cost < res.log2_cost {
+            res = estimate;
+        }
+    }
+    res
+}
+
+/// Estimates the security of LWE as the cost of the cheapest attack
+/// Arguments
+/// * `dimension` - the size of the LWE mask
+/// * `log2_std_dev` - the log2 of the standard deviation of the error on the torus
+/// * `secret` - the distribution of the secret
+/// * `cost_model` - the cost model of BKZ
+///
+/// Output
+/// * the estimate of the cheapest attack
+/// # Example
+/// ```rust
+/// use concrete_npe::security::{estimate_security, CostModel, SecretDistribution};
+/// // LWE128_630
+/// let estimate = estimate_security(630, -14., SecretDistribution::Binary, CostModel::Sieve);
+/// assert!(estimate.log2_cost >= 128.);
+/// ```
+pub fn estimate_security(
+    dimension: usize,
+    log2_std_dev: f64,
+    secret: SecretDistribution,
+    cost_model: CostModel,
+) -> SecurityEstimate {
+    let mut res = primal_usvp(dimension, log2_std_dev, secret, cost_model);
+    for estimate in [
+        dual(dimension, log2_std_dev, secret, cost_model),
+        hybrid(dimension, log2_std_dev, secret, cost_model),
+    ]
+    .iter()
+    {
+        if estimate.log2_cost < res.log2_cost {
+            res = *estimate;
+        }
+    }
+    res
+}
+
+/// Dual attack on the last dimension - nb_guessed coordinates of the secret, the first ones being
+/// guessed, the cost of the guesses being added to the one of the lattice reduction
+fn dual_with_guesses(
+    dimension: usize,
+    log2_std_dev: f64,
+    secret: SecretDistribution,
+    cost_model: CostModel,
+    nb_guessed: usize,
+) -> SecurityEstimate {
+    let n = (dimension - nb_guessed) as f64;
+    let log2_scaling = f64::log2(secret.std_dev()) - log2_std_dev - LOG2_MODULUS;
+    let attack = if nb_guessed == 0 {
+        Attack::Dual
+    } else {
+        Attack::Hybrid
+    };
+    let mut res = infinite_estimate(attack);
+    for block_size in MIN_BLOCK_SIZE..=(2 * dimension) {
+        let log2_delta = f64::log2(root_hermite_factor(block_size));
+
+        // lattice dimension minimizing the length of the short vectors
+        let d = f64::max(
+            n,
+            f64::sqrt(n * (LOG2_MODULUS + log2_scaling) / log2_delta).round(),
+        );
+        let log2_length = d * log2_delta + n * (LOG2_MODULUS + log2_scaling) / d;
+
+        // log2 of the number of short vectors, i.e. 1 / advantage^2
+        let tau = f64::powf(2., log2_std_dev + log2_length);
+        let log2_nb_vectors = f64::max(
+            0.,
+            4. * std::f64::consts::PI.powi(2) * tau * tau / std::f64::consts::LN_2,
+        );
+        let mut log2_cost = cost_model.log2_bkz_cost(block_size, d as usize) + log2_nb_vectors;
+        if nb_guessed > 0 {
+            let log2_guesses =
+                nb_guessed as f64 * secret.log2_guesses() + log2_nb_vectors + f64::log2(d);
+            log2_cost = log2_add(log2_cost, log2_guesses);
+        }
+        if log2_cost < res.log2_cost {
+            res.log2_cost = log2_cost;
+            res.block_size = block_size;
+            res.nb_samples = (d - n) as usize;
+            res.nb_guessed = nb_guessed;
+        }
+    }
+    res
+}
+
+/// Computes log2(2^a + 2^b)
+fn log2_add(a: f64, b: f64) -> f64 {
+    f64::max(a, b) + f64::log2(1. + f64::powf(2., -(a - b).abs()))
+}
+
+fn infinite_estimate(attack: Attack) -> SecurityEstimate {
+    SecurityEstimate {
+        attack,
+        log2_cost: f64::INFINITY,
+        block_size: 0,
+        nb_samples: 0,
+        nb_guessed: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (dimension, log2_std_dev) of LWE128_* and LWE80_* (LWE estimator, September 15th 2020),
+    /// with the log2 of the cost of the cheapest attack found by this estimator
+    const LWE128: [(usize, i32, f64); 11] = [
+        (256, -5, 130.296),
+        (512, -11, 137.295),
+        (630, -14, 136.164),
+        (650, -15, 132.132),
+        (688, -16, 131.929),
+        (710, -17, 128.476),
+        (750, -18, 128.853),
+        (800, -19, 130.700),
+        (830, -20, 129.298),
+        (1024, -25, 129.617),
+        (2048, -52, 128.605),
+    ];
+    const LWE80: [(usize, i32, f64); 11] = [
+        (256, -9, 85.834),
+        (512, -19, 83.665),
+        (630, -24, 82.223),
+        (650, -25, 81.393),
+        (688, -26, 82.935),
+        (710, -27, 82.398),
+        (750, -29, 81.313),
+        (800, -31, 81.409),
+        (830, -32, 81.753),
+        (1024, -40, 81.186),
+        (2048, -82, 80.445),
+    ];
+
+    /// Tolerance on the recorded estimates, in bits
+    const ESTIMATE_TOLERANCE: f64 = 0.01;
+
+    /// Tolerance on the security level when checking that a table entry is the smallest noise
+    /// reaching it, in bits
+    const LEVEL_TOLERANCE: f64 = 0.5;
+
+    #[test]
+    fn test_reproduce_parameter_tables() {
+        for (table, level) in [(LWE128, 128.), (LWE80, 80.)].iter() {
+            for (dimension, log2_std_dev, log2_cost) in table.iter() {
+                let estimate = estimate_security(
+                    *dimension,
+                    *log2_std_dev as f64,
+                    SecretDistribution::Binary,
+                    CostModel::Sieve,
+                );
+                assert!(
+                    (estimate.log2_cost - log2_cost).abs() < ESTIMATE_TOLERANCE,
+                    "({}, {}): {:?}",
+                    dimension,
+                    log2_std_dev,
+                    estimate
+                );
+
+                // the entry reaches the level and one bit of noise less does not
+                let weaker = estimate_security(
+                    *dimension,
+                    (*log2_std_dev - 1) as f64,
+                    SecretDistribution::Binary,
+                    CostModel::Sieve,
+                );
+                assert!(
+                    estimate.log2_cost > level - LEVEL_TOLERANCE,
+                    "({}, {}): {:?}",
+                    dimension,
+                    log2_std_dev,
+                    estimate
+                );
+                assert!(
+                    weaker.log2_cost < level + LEVEL_TOLERANCE,
+                    "({}, {}): {:?}",
+                    dimension,
+                    log2_std_dev - 1,
+                    weaker
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_attacks() {
+        // LWE128_630
+        let (n, s) = (630, -14.);
+        let usvp = primal_usvp(n, s, SecretDistribution::Binary, CostModel::Sieve);
+        let dual_estimate = dual(n, s, SecretDistribution::Binary, CostModel::Sieve);
+        let hybrid_estimate = hybrid(n, s, SecretDistribution::Binary, CostModel::Sieve);
+        assert_eq!(usvp.attack, Attack::PrimalUSVP);
+        assert_eq!(dual_estimate.attack, Attack::Dual);
+        assert_eq!(hybrid_estimate.attack, Attack::Hybrid);
+        assert!(hybrid_estimate.nb_guessed > 0);
+        assert!(usvp.log2_cost < dual_estimate.log2_cost);
+
+        // larger secrets are harder to find
+        let binary = estimate_security(n, s, SecretDistribution::Binary, CostModel::Sieve);
+        let ternary = estimate_security(n, s, SecretDistribution::Ternary, CostModel::Sieve);
+        let gaussian = estimate_security(n, s, SecretDistribution::Gaussian(3.2), CostModel::Sieve);
+        assert!(binary.log2_cost < ternary.log2_cost);
+        assert!(ternary.log2_cost < gaussian.log2_cost);
+
+        // quantum and core-SVP models are cheaper
+        let core = estimate_security(n, s, SecretDistribution::Binary, CostModel::CoreSVP);
+        let quantum = estimate_security(n, s, SecretDistribution::Binary, CostModel::QuantumSieve);
+        assert!(core.log2_cost < binary.log2_cost);
+        assert!(quantum.log2_cost < binary.log2_cost);
+    }
+}
@@ -80,6 +80,10 @@ fn main() -> Result<(), std::io::Error> {
         )
         .subcommand(App::new("test_npe").about("Tests the `concrete-npe` crate in native mode"))
         .subcommand(App::new("test_crates").about("Tests all the crates in native mode"))
+        .subcommand(
+            App::new("calibrate_npe")
+                .about("Compares the `concrete-npe` formulas with the noise of `concrete-core`"),
+        )
         .subcommand(
             App::new("test_and_cov_crates")
                 .about("Compute tests coverage of all crates in native mode"),
@@ -158,6 +162,9 @@ fn main() -> Result<(), std::io::Error> {
     if matches.subcommand_matches("test_crates").is_some() {
         test::crates()?;
     }
+    if matches.subcommand_matches("calibrate_npe").is_some() {
+        test::npe_calibration()?;
+    }
     if matches.subcommand_matches("test_and_cov_crates").is_some() {
         test::cov_crates()?;
     }
@@ -33,6 +33,10 @@ pub fn npe() -> Result<(), Error> {
     cmd!(<ENV_TARGET_NATIVE> "cargo test --release --no-fail-fast --all-features -p concrete-npe")
 }
 
+pub fn npe_calibration() -> Result<(), Error> {
+    cmd!(<ENV_TARGET_NATIVE> "cargo test --release -p concrete-core noise_calibration -- --ignored --nocapture")
+}
+
 pub fn crates() -> Result<(), Error> {
     cmd!(<ENV_TARGET_NATIVE> "cargo test --release --no-fail-fast --all-features")
 }
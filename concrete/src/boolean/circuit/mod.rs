@@ -0,0 +1,522 @@
+//! circuit module: parsing and evaluation of Bristol Fashion netlists on encrypted booleans
+//!
+//! A Bristol Fashion file starts with a header describing the number of gates, the number of
+//! wires and the sizes of the input and output values, followed by one gate per line:
+//! `<nb inputs> <nb outputs> <input wires> <output wires> <gate type>`.
+//! The input values are mapped to the first wires and the output values to the last ones,
+//! with the least significant bit first.
+
+#[cfg(test)]
+mod tests;
+
+use super::{BoolCiphertext, BoolServerKey};
+use crate::error::CryptoAPIError;
+use backtrace::Backtrace;
+use colored::Colorize;
+#[cfg(feature = "multithread")]
+use rayon::prelude::*;
+use std::error::Error;
+use std::fmt;
+
+/// The gate types of a Bristol Fashion netlist
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateKind {
+    /// exclusive or of two wires
+    Xor,
+    /// and of two wires
+    And,
+    /// or of two wires
+    Or,
+    /// negation of a wire
+    Inv,
+    /// assignment of a constant to a wire
+    Eq(bool),
+    /// copy of a wire
+    Eqw,
+    /// several and gates packed together: the i-th output is the and of the i-th and (k+i)-th inputs
+    Mand,
+}
+
+impl GateKind {
+    /// Return the number of bootstraps needed to evaluate a gate with nb_outputs output wires
+    pub fn get_nb_bootstraps(&self, nb_outputs: usize) -> usize {
+        match self {
+            GateKind::Xor | GateKind::And | GateKind::Or => 1,
+            GateKind::Mand => nb_outputs,
+            GateKind::Inv | GateKind::Eq(_) | GateKind::Eqw => 0,
+        }
+    }
+
+    /// Return the name of the gate type in the Bristol Fashion format
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GateKind::Xor => "XOR",
+            GateKind::And => "AND",
+            GateKind::Or => "OR",
+            GateKind::Inv => "INV",
+            GateKind::Eq(_) => "EQ",
+            GateKind::Eqw => "EQW",
+            GateKind::Mand => "MAND",
+        }
+    }
+}
+
+/// Structure describing a single gate of a netlist
+/// # Attributes
+/// * `kind` - the type of the gate
+/// * `inputs` - the indexes of the input wires
+/// * `outputs` - the indexes of the output wires
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub inputs: Vec<usize>,
+    pub outputs: Vec<usize>,
+}
+
+impl Gate {
+    /// Evaluate the gate on cleartext wires
+    ///
+    /// # Argument
+    /// * `wires` - the values of the wires
+    ///
+    /// # Output
+    /// * the values of the output wires
+    pub fn evaluate_clear(&self, wires: &[Option<bool>]) -> Vec<bool> {
+        let input = |i: usize| wires[self.inputs[i]].unwrap();
+        match self.kind {
+            GateKind::Xor => vec![input(0) ^ input(1)],
+            GateKind::And => vec![input(0) & input(1)],
+            GateKind::Or => vec![input(0) | input(1)],
+            GateKind::Inv => vec![!input(0)],
+            GateKind::Eq(value) => vec![value],
+            GateKind::Eqw => vec![input(0)],
+            GateKind::Mand => {
+                let k = self.outputs.len();
+                (0..k).map(|i| input(i) & input(k + i)).collect()
+            }
+        }
+    }
+
+    /// Evaluate the gate on encrypted wires with gate bootstrapping
+    ///
+    /// # Arguments
+    /// * `server_key` - the server key used to evaluate the gates
+    /// * `wires` - the encrypted values of the wires
+    ///
+    /// # Output
+    /// * the encrypted values of the output wires
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    pub fn evaluate(
+        &self,
+        server_key: &BoolServerKey,
+        wires: &[Option<BoolCiphertext>],
+    ) -> Result<Vec<BoolCiphertext>, CryptoAPIError> {
+        let input = |i: usize| wires[self.inputs[i]].as_ref().unwrap();
+        Ok(match self.kind {
+            GateKind::Xor => vec![server_key.xor(input(0), input(1))?],
+            GateKind::And => vec![server_key.and(input(0), input(1))?],
+            GateKind::Or => vec![server_key.or(input(0), input(1))?],
+            GateKind::Inv => vec![server_key.not(input(0))],
+            GateKind::Eq(value) => vec![BoolCiphertext::trivial(
+                value,
+                server_key.bsk.get_lwe_dimension(),
+            )],
+            GateKind::Eqw => vec![input(0).clone()],
+            GateKind::Mand => {
+                let k = self.outputs.len();
+                (0..k)
+                    .map(|i| server_key.and(input(i), input(k + i)))
+                    .collect::<Result<Vec<BoolCiphertext>, CryptoAPIError>>()?
+            }
+        })
+    }
+}
+
+/// Structure containing a Bristol Fashion netlist, with its gates sorted in layers
+/// of independent gates
+/// # Attributes
+/// * `nb_wires` - the number of wires
+/// * `input_sizes` - the number of bits of each input value
+/// * `output_sizes` - the number of bits of each output value
+/// * `gates` - the gates in the order of the file
+/// * `layers` - the indexes of the gates of each layer, a gate only depends on the previous layers
+/// * `depth` - the maximum number of bootstraps on a path from an input to an output
+#[derive(Debug, Clone, PartialEq)]
+pub struct BristolCircuit {
+    pub nb_wires: usize,
+    pub input_sizes: Vec<usize>,
+    pub output_sizes: Vec<usize>,
+    pub gates: Vec<Gate>,
+    pub layers: Vec<Vec<usize>>,
+    pub depth: usize,
+}
+
+/// Structure describing the cost of a netlist
+/// # Attributes
+/// * `nb_gates` - the number of gates
+/// * `gate_counts` - the number of gates of each type
+/// * `nb_bootstraps` - the number of bootstraps needed to evaluate the circuit
+/// * `nb_layers` - the number of layers of independent gates
+/// * `depth` - the maximum number of bootstraps on a path from an input to an output
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitReport {
+    pub nb_gates: usize,
+    pub gate_counts: Vec<(&'static str, usize)>,
+    pub nb_bootstraps: usize,
+    pub nb_layers: usize,
+    pub depth: usize,
+}
+
+impl fmt::Display for CircuitReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = self
+            .gate_counts
+            .iter()
+            .map(|(name, count)| format!("{}: {}", name, count))
+            .collect::<Vec<String>>()
+            .join(", ");
+        writeln!(
+            f,
+            "CircuitReport {{\n         -> nb_gates = {} ({})\n         -> nb_bootstraps = {}\n         -> nb_layers = {}\n         -> depth = {}\n}}",
+            self.nb_gates, counts, self.nb_bootstraps, self.nb_layers, self.depth
+        )
+    }
+}
+
+/// Parse a list of integers from a line of a netlist
+fn parse_integers(tokens: &[&str], line: usize) -> Result<Vec<usize>, CryptoAPIError> {
+    tokens
+        .iter()
+        .map(|token| {
+            token
+                .parse::<usize>()
+                .map_err(|_| CircuitParseError!(line, format!("'{}' is not an integer", token)))
+        })
+        .collect()
+}
+
+/// Parse a header line of the form `<n> <size_1> ... <size_n>`
+fn parse_sizes(line_content: &str, line: usize) -> Result<Vec<usize>, CryptoAPIError> {
+    let tokens: Vec<&str> = line_content.split_whitespace().collect();
+    let integers = parse_integers(&tokens, line)?;
+    if integers.is_empty() || integers[0] + 1 != integers.len() {
+        return Err(CircuitParseError!(
+            line,
+            "the number of values does not match the number of sizes"
+        ));
+    }
+    Ok(integers[1..].to_vec())
+}
+
+impl BristolCircuit {
+    /// Parse a netlist in the Bristol Fashion format
+    ///
+    /// # Argument
+    /// * `netlist` - the content of a Bristol Fashion file
+    ///
+    /// # Output
+    /// * a BristolCircuit
+    /// * CircuitParseError - if the netlist is malformed
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // a one bit full adder: inputs a, b, carry and outputs sum, carry
+    /// let netlist = "5 8\n3 1 1 1\n2 1 1\n\n\
+    ///                2 1 0 1 3 XOR\n2 1 3 2 6 XOR\n\
+    ///                2 1 0 1 4 AND\n2 1 3 2 5 AND\n2 1 4 5 7 XOR\n";
+    /// let circuit = BristolCircuit::parse(netlist).unwrap();
+    /// assert_eq!(circuit.get_report().depth, 3);
+    /// ```
+    pub fn parse(netlist: &str) -> Result<BristolCircuit, CryptoAPIError> {
+        // non empty lines with their numbers
+        let mut lines = netlist
+            .lines()
+            .enumerate()
+            .map(|(i, content)| (i + 1, content.trim()))
+            .filter(|(_, content)| !content.is_empty());
+
+        // header
+        let (line, content) = lines
+            .next()
+            .ok_or_else(|| CircuitParseError!(1, "missing header"))?;
+        let tokens: Vec<&str> = content.split_whitespace().collect();
+        let header = parse_integers(&tokens, line)?;
+        if header.len() != 2 {
+            return Err(CircuitParseError!(
+                line,
+                "expected the number of gates and of wires"
+            ));
+        }
+        let (nb_gates, nb_wires) = (header[0], header[1]);
+        let (line, content) = lines
+            .next()
+            .ok_or_else(|| CircuitParseError!(line + 1, "missing input sizes"))?;
+        let input_sizes = parse_sizes(content, line)?;
+        let (line, content) = lines
+            .next()
+            .ok_or_else(|| CircuitParseError!(line + 1, "missing output sizes"))?;
+        let output_sizes = parse_sizes(content, line)?;
+
+        let nb_inputs: usize = input_sizes.iter().sum();
+        let nb_outputs: usize = output_sizes.iter().sum();
+        if nb_inputs > nb_wires || nb_outputs > nb_wires {
+            return Err(CircuitParseError!(
+                line,
+                "more input or output wires than wires"
+            ));
+        }
+
+        // layer and depth of each wire, None if the wire is not yet assigned
+        let mut wire_layers: Vec<Option<(usize, usize)>> = vec![None; nb_wires];
+        for wire in wire_layers.iter_mut().take(nb_inputs) {
+            *wire = Some((0, 0));
+        }
+
+        let mut gates: Vec<Gate> = Vec::with_capacity(nb_gates);
+        let mut layers: Vec<Vec<usize>> = Vec::new();
+        for (line, content) in lines {
+            let tokens: Vec<&str> = content.split_whitespace().collect();
+            if tokens.len() < 3 {
+                return Err(CircuitParseError!(line, "a gate needs at least 3 fields"));
+            }
+            let integers = parse_integers(&tokens[..tokens.len() - 1], line)?;
+            let (nb_in, nb_out) = (integers[0], integers[1]);
+            if integers.len() != 2 + nb_in + nb_out {
+                return Err(CircuitParseError!(
+                    line,
+                    "the number of wires does not match the gate arity"
+                ));
+            }
+            let mut inputs = integers[2..2 + nb_in].to_vec();
+            let outputs = integers[2 + nb_in..].to_vec();
+
+            // gate type and arity
+            let kind = match (tokens[tokens.len() - 1], nb_in, nb_out) {
+                ("XOR", 2, 1) => GateKind::Xor,
+                ("AND", 2, 1) => GateKind::And,
+                ("OR", 2, 1) => GateKind::Or,
+                ("INV", 1, 1) | ("NOT", 1, 1) => GateKind::Inv,
+                ("EQW", 1, 1) => GateKind::Eqw,
+                ("EQ", 1, 1) => match inputs.pop() {
+                    Some(0) => GateKind::Eq(false),
+                    Some(1) => GateKind::Eq(true),
+                    _ => return Err(CircuitParseError!(line, "EQ expects a constant 0 or 1")),
+                },
+                ("MAND", _, _) if nb_in == 2 * nb_out && nb_out > 0 => GateKind::Mand,
+                (name, _, _) => {
+                    return Err(CircuitParseError!(
+                        line,
+                        format!(
+                            "unknown gate {} with {} inputs and {} outputs",
+                            name, nb_in, nb_out
+                        )
+                    ))
+                }
+            };
+
+            // the inputs must already be assigned
+            let mut layer = 0;
+            let mut depth = 0;
+            for &wire in inputs.iter() {
+                match wire_layers.get(wire).copied() {
+                    Some(Some((wire_layer, wire_depth))) => {
+                        layer = usize::max(layer, wire_layer);
+                        depth = usize::max(depth, wire_depth);
+                    }
+                    _ => {
+                        return Err(CircuitParseError!(
+                            line,
+                            format!("the wire {} is used before being assigned", wire)
+                        ))
+                    }
+                }
+            }
+            if kind.get_nb_bootstraps(nb_out) > 0 {
+                depth += 1;
+            }
+
+            // the outputs are assigned once
+            for &wire in outputs.iter() {
+                match wire_layers.get(wire).copied() {
+                    Some(None) => wire_layers[wire] = Some((layer + 1, depth)),
+                    _ => {
+                        return Err(CircuitParseError!(
+                            line,
+                            format!("the wire {} is invalid or assigned twice", wire)
+                        ))
+                    }
+                }
+            }
+
+            if layers.len() <= layer {
+                layers.resize(layer + 1, Vec::new());
+            }
+            layers[layer].push(gates.len());
+            gates.push(Gate {
+                kind,
+                inputs,
+                outputs,
+            });
+        }
+
+        if gates.len() != nb_gates {
+            return Err(CircuitParseError!(
+                netlist.lines().count(),
+                format!("expected {} gates, found {}", nb_gates, gates.len())
+            ));
+        }
+
+        // the outputs are the last wires
+        if wire_layers[nb_wires - nb_outputs..]
+            .iter()
+            .any(|wire| wire.is_none())
+        {
+            return Err(CircuitParseError!(
+                netlist.lines().count(),
+                "some output wires are never assigned"
+            ));
+        }
+
+        let depth = wire_layers
+            .iter()
+            .filter_map(|wire| wire.map(|(_, depth)| depth))
+            .max()
+            .unwrap_or(0);
+
+        Ok(BristolCircuit {
+            nb_wires,
+            input_sizes,
+            output_sizes,
+            gates,
+            layers,
+            depth,
+        })
+    }
+
+    /// Load and parse a netlist from a Bristol Fashion file
+    ///
+    /// # Argument
+    /// * `path` - the path of the file
+    ///
+    /// # Output
+    /// * a BristolCircuit
+    pub fn load(path: &str) -> Result<BristolCircuit, Box<dyn Error>> {
+        let netlist = std::fs::read_to_string(path)?;
+        Ok(BristolCircuit::parse(&netlist)?)
+    }
+
+    /// Return the depth and the gate counts of the circuit
+    ///
+    /// # Output
+    /// * a CircuitReport
+    pub fn get_report(&self) -> CircuitReport {
+        let mut gate_counts: Vec<(&'static str, usize)> = Vec::new();
+        for gate in self.gates.iter() {
+            let name = gate.kind.get_name();
+            match gate_counts.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, count)) => *count += 1,
+                None => gate_counts.push((name, 1)),
+            }
+        }
+
+        CircuitReport {
+            nb_gates: self.gates.len(),
+            gate_counts,
+            nb_bootstraps: self
+                .gates
+                .iter()
+                .map(|gate| gate.kind.get_nb_bootstraps(gate.outputs.len()))
+                .sum(),
+            nb_layers: self.layers.len(),
+            depth: self.depth,
+        }
+    }
+
+    /// Check the number of input values and their sizes, and return the input wires
+    fn check_inputs<T: Clone>(&self, inputs: &[Vec<T>]) -> Result<Vec<Option<T>>, CryptoAPIError> {
+        if inputs.len() != self.input_sizes.len() {
+            return Err(NbCTError!(inputs.len(), self.input_sizes.len()));
+        }
+        let mut wires: Vec<Option<T>> = Vec::with_capacity(self.nb_wires);
+        for (value, size) in inputs.iter().zip(self.input_sizes.iter()) {
+            if value.len() != *size {
+                return Err(NbCTError!(value.len(), *size));
+            }
+            wires.extend(value.iter().cloned().map(Some));
+        }
+        wires.resize(self.nb_wires, None);
+        Ok(wires)
+    }
+
+    /// Split the last wires into the output values
+    fn collect_outputs<T>(&self, mut wires: Vec<Option<T>>) -> Vec<Vec<T>> {
+        let nb_outputs: usize = self.output_sizes.iter().sum();
+        let mut outputs = wires
+            .drain(self.nb_wires - nb_outputs..)
+            .map(|wire| wire.unwrap());
+        self.output_sizes
+            .iter()
+            .map(|size| outputs.by_ref().take(*size).collect())
+            .collect()
+    }
+
+    /// Evaluate the circuit on cleartext inputs
+    ///
+    /// # Argument
+    /// * `inputs` - the bits of each input value, least significant bit first
+    ///
+    /// # Output
+    /// * the bits of each output value
+    /// * NbCTError - if the number or the sizes of the inputs are wrong
+    pub fn evaluate_clear(&self, inputs: &[Vec<bool>]) -> Result<Vec<Vec<bool>>, CryptoAPIError> {
+        let mut wires = self.check_inputs(inputs)?;
+        for gate in self.gates.iter() {
+            for (wire, value) in gate.outputs.iter().zip(gate.evaluate_clear(&wires)) {
+                wires[*wire] = Some(value);
+            }
+        }
+        Ok(self.collect_outputs(wires))
+    }
+
+    /// Evaluate the circuit on encrypted inputs, the gates of a layer being evaluated in parallel
+    /// with the multithread feature
+    ///
+    /// # Arguments
+    /// * `server_key` - the server key used to evaluate the gates
+    /// * `inputs` - the encrypted bits of each input value, least significant bit first
+    ///
+    /// # Output
+    /// * the encrypted bits of each output value
+    /// * NbCTError - if the number or the sizes of the inputs are wrong
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    pub fn evaluate(
+        &self,
+        server_key: &BoolServerKey,
+        inputs: &[Vec<BoolCiphertext>],
+    ) -> Result<Vec<Vec<BoolCiphertext>>, CryptoAPIError> {
+        let mut wires = self.check_inputs(inputs)?;
+        for layer in self.layers.iter() {
+            // the gates of a layer only read wires of the previous layers
+            let evaluate_gate = |index: &usize| self.gates[*index].evaluate(server_key, &wires);
+            #[cfg(feature = "multithread")]
+            let results = layer
+                .par_iter()
+                .map(evaluate_gate)
+                .collect::<Result<Vec<Vec<BoolCiphertext>>, CryptoAPIError>>()?;
+            #[cfg(not(feature = "multithread"))]
+            let results = layer
+                .iter()
+                .map(evaluate_gate)
+                .collect::<Result<Vec<Vec<BoolCiphertext>>, CryptoAPIError>>()?;
+
+            for (index, values) in layer.iter().zip(results) {
+                for (wire, value) in self.gates[*index].outputs.iter().zip(values) {
+                    wires[*wire] = Some(value);
+                }
+            }
+        }
+        Ok(self.collect_outputs(wires))
+    }
+}
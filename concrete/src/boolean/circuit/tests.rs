@@ -0,0 +1,115 @@
+use super::*;
+use crate::{BoolClientKey, BOOL128_DEFAULT};
+
+/// one bit full adder: inputs a, b, carry and outputs sum, carry
+const FULL_ADDER: &str = "5 8
+3 1 1 1
+2 1 1
+
+2 1 0 1 3 XOR
+2 1 3 2 6 XOR
+2 1 0 1 4 AND
+2 1 3 2 5 AND
+2 1 4 5 7 XOR
+";
+
+/// exercises the free gates and MAND: outputs (!a, a & b, c & d, 1, b)
+const OTHER_GATES: &str = "4 11
+4 1 1 1 1
+5 1 1 1 1 1
+
+1 1 0 6 INV
+4 2 0 2 1 3 7 8 MAND
+1 1 1 9 EQ
+1 1 1 10 EQW
+";
+
+#[test]
+fn test_parse_x_report() {
+    let circuit = BristolCircuit::parse(FULL_ADDER).unwrap();
+    let report = circuit.get_report();
+    assert_eq!(report.nb_gates, 5);
+    assert_eq!(report.gate_counts, vec![("XOR", 3), ("AND", 2)]);
+    assert_eq!(report.nb_bootstraps, 5);
+    assert_eq!(report.nb_layers, 3);
+    assert_eq!(report.depth, 3);
+
+    let circuit = BristolCircuit::parse(OTHER_GATES).unwrap();
+    let report = circuit.get_report();
+    assert_eq!(report.nb_bootstraps, 2);
+    assert_eq!(report.nb_layers, 1);
+    assert_eq!(report.depth, 1);
+}
+
+#[test]
+fn test_parse_errors() {
+    // wrong number of gates
+    assert!(BristolCircuit::parse("2 4\n2 1 1\n1 1\n\n2 1 0 1 3 XOR\n").is_err());
+    // wire used before being assigned
+    assert!(BristolCircuit::parse("1 4\n2 1 1\n1 1\n\n2 1 0 2 3 XOR\n").is_err());
+    // wire assigned twice
+    assert!(BristolCircuit::parse("2 4\n2 1 1\n1 1\n\n2 1 0 1 3 XOR\n2 1 0 1 3 AND\n").is_err());
+    // unknown gate
+    assert!(BristolCircuit::parse("1 4\n2 1 1\n1 1\n\n2 1 0 1 3 FOO\n").is_err());
+    // wrong arity
+    assert!(BristolCircuit::parse("1 4\n2 1 1\n1 1\n\n2 1 0 1 XOR\n").is_err());
+    // output never assigned
+    assert!(BristolCircuit::parse("1 5\n2 1 1\n1 1\n\n2 1 0 1 3 XOR\n").is_err());
+}
+
+#[test]
+fn test_evaluate_clear() {
+    let circuit = BristolCircuit::parse(FULL_ADDER).unwrap();
+    for i in 0..8 {
+        let (a, b, c) = (i & 1 == 1, i & 2 == 2, i & 4 == 4);
+        let outputs = circuit
+            .evaluate_clear(&[vec![a], vec![b], vec![c]])
+            .unwrap();
+        let sum = a as usize + b as usize + c as usize;
+        assert_eq!(outputs, vec![vec![sum & 1 == 1], vec![sum >> 1 == 1]]);
+    }
+
+    let circuit = BristolCircuit::parse(OTHER_GATES).unwrap();
+    let outputs = circuit
+        .evaluate_clear(&[vec![true], vec![true], vec![true], vec![false]])
+        .unwrap();
+    assert_eq!(
+        outputs,
+        vec![vec![false], vec![true], vec![false], vec![true], vec![true]]
+    );
+
+    // wrong number of inputs
+    assert!(circuit.evaluate_clear(&[vec![true]]).is_err());
+}
+
+#[test]
+fn test_evaluate_encrypted() {
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    let server_key = client_key.get_server_key();
+
+    let circuit = BristolCircuit::parse(FULL_ADDER).unwrap();
+    for i in 0..8 {
+        let clear_inputs = vec![vec![i & 1 == 1], vec![i & 2 == 2], vec![i & 4 == 4]];
+        let inputs: Vec<Vec<BoolCiphertext>> = clear_inputs
+            .iter()
+            .map(|value| {
+                value
+                    .iter()
+                    .map(|bit| client_key.encrypt(*bit).unwrap())
+                    .collect()
+            })
+            .collect();
+
+        let outputs = circuit.evaluate(&server_key, &inputs).unwrap();
+        let decryptions: Vec<Vec<bool>> = outputs
+            .iter()
+            .map(|value| {
+                value
+                    .iter()
+                    .map(|ct| client_key.decrypt(ct).unwrap())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(circuit.evaluate_clear(&clear_inputs).unwrap(), decryptions);
+    }
+}
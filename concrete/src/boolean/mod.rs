@@ -0,0 +1,754 @@
+//! boolean module: TFHE-style gate bootstrapping on encrypted booleans
+//!
+//! A boolean is encrypted as an LWE ciphertext of 1/8 (true) or -1/8 (false).
+//! A binary gate computes a linear combination of its inputs followed by a sign bootstrap
+//! and a key switch, so its output can be used as the input of any other gate.
+
+#[cfg(test)]
+mod tests;
+
+pub mod circuit;
+pub use circuit::*;
+
+use crate::error::CryptoAPIError;
+use crate::{read_from_file, write_to_file, Torus};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::LweSize;
+use concrete_core::{
+    crypto::{
+        encoding::{Cleartext, Plaintext},
+        lwe::LweCiphertext,
+        secret::generators::{EncryptionRandomGenerator, SecretRandomGenerator},
+    },
+    math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor},
+};
+use concrete_npe as npe;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure describing the parameters used for gate bootstrapping
+/// # Attributes
+/// * `lwe_params` - the parameters of the LWE secret key encrypting the booleans
+/// * `rlwe_params` - the parameters of the RLWE secret key used in the bootstrapping key
+/// * `bsk_base_log` - the log2 of the decomposition base of the bootstrapping key
+/// * `bsk_level` - the number of levels of the decomposition of the bootstrapping key
+/// * `ksk_base_log` - the log2 of the decomposition base of the key switching key
+/// * `ksk_level` - the number of levels of the decomposition of the key switching key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoolParams {
+    pub lwe_params: crate::LWEParams,
+    pub rlwe_params: crate::RLWEParams,
+    pub bsk_base_log: usize,
+    pub bsk_level: usize,
+    pub ksk_base_log: usize,
+    pub ksk_level: usize,
+}
+
+/// 128 bits of security, the xor gate input is 6.7 standard deviations away from the threshold so
+/// it fails with a probability around 2^-36 (concrete-npe)
+pub const BOOL128_DEFAULT: BoolParams = BoolParams {
+    lwe_params: crate::LWE128_630,
+    rlwe_params: crate::RLWE128_1024_1,
+    bsk_base_log: 7,
+    bsk_level: 3,
+    ksk_base_log: 2,
+    ksk_level: 6,
+};
+
+/// 128 bits of security, the xor gate input is 8.4 standard deviations away from the threshold so
+/// it fails with a probability around 2^-55 (concrete-npe)
+pub const BOOL128_LOW_FAILURE: BoolParams = BoolParams {
+    lwe_params: crate::LWE128_630,
+    rlwe_params: crate::RLWE128_1024_1,
+    bsk_base_log: 5,
+    bsk_level: 5,
+    ksk_base_log: 1,
+    ksk_level: 12,
+};
+
+impl BoolParams {
+    /// Return the variance of the noise of the output of a gate, computed with concrete-npe
+    ///
+    /// # Output
+    /// * the variance after a bootstrap followed by a key switch
+    pub fn get_gate_output_variance(&self) -> f64 {
+        crate::bootstrap_key_switch_variance(
+            &self.lwe_params,
+            &self.rlwe_params,
+            self.bsk_base_log,
+            self.bsk_level,
+            self.ksk_base_log,
+            self.ksk_level,
+        )
+    }
+
+    /// Return the variance of the noise of the input of the bootstrap of the worst gate (xor),
+    /// including the drift due to the rounding of the mask
+    ///
+    /// # Output
+    /// * the variance of the phase before the sign extraction
+    pub fn get_gate_input_variance(&self) -> f64 {
+        // the inputs are either fresh or the output of a gate
+        let var_fresh = f64::powi(f64::powi(2., self.lwe_params.log2_std_dev), 2);
+        let var_in = f64::max(var_fresh, self.get_gate_output_variance());
+
+        // xor computes 2 * (ct_1 + ct_2)
+        let var_sum = npe::add_ciphertexts(var_in, var_in);
+        let var_xor = <Torus as npe::LWE>::single_scalar_mul(var_sum, 2);
+
+        // drift of the rounding of the mask in [0, 2N[
+        let var_drift = crate::bootstrap_drift_variance(
+            self.lwe_params.dimension,
+            self.rlwe_params.polynomial_size,
+        );
+
+        var_xor + var_drift
+    }
+
+    /// Return the distance between a gate input and the decision threshold (1/8),
+    /// counted in standard deviations of the noise: the bigger, the smaller the failure probability
+    ///
+    /// # Output
+    /// * the number of standard deviations
+    pub fn get_nb_std_dev_margin(&self) -> f64 {
+        0.125 / f64::sqrt(self.get_gate_input_variance())
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<BoolParams, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Encoding of true: 1/8
+const TRUE_ENCODING: Torus = 1 << (<Torus as Numeric>::BITS - 3);
+
+/// Return the encoding of a boolean on the torus
+fn encode_bool(message: bool) -> Torus {
+    if message {
+        TRUE_ENCODING
+    } else {
+        TRUE_ENCODING.wrapping_neg()
+    }
+}
+
+/// Structure containing an encrypted boolean.
+///
+/// # Attributes
+/// * `ciphertext` - the LWE ciphertext of 1/8 (true) or -1/8 (false)
+/// * `variance` - the variance of the noise of the LWE ciphertext
+/// * `dimension` - the length the LWE mask
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BoolCiphertext {
+    pub ciphertext: LweCiphertext<Vec<Torus>>,
+    pub variance: f64,
+    pub dimension: usize,
+}
+
+impl BoolCiphertext {
+    /// Create a noiseless encryption of a boolean, which can be decrypted with any key of the right dimension
+    ///
+    /// # Arguments
+    /// * `message` - a boolean
+    /// * `dimension` - the length the LWE mask
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let ct_true = BoolCiphertext::trivial(true, 630);
+    /// ```
+    pub fn trivial(message: bool, dimension: usize) -> BoolCiphertext {
+        let mut ciphertext = LweCiphertext::allocate(0, LweSize(dimension + 1));
+        ciphertext.as_mut_tensor().as_mut_slice()[dimension] = encode_bool(message);
+        BoolCiphertext {
+            ciphertext,
+            variance: 0.,
+            dimension,
+        }
+    }
+
+    /// Return the size of one LWE ciphertext with the parameters of self
+    ///
+    /// # Output
+    /// * a usize with the size of a single LWE ciphertext
+    pub fn get_ciphertext_size(&self) -> usize {
+        self.dimension + 1
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<BoolCiphertext, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+impl fmt::Display for BoolCiphertext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "BoolCiphertext {{\n         -> dimension = {}\n         -> variance = {}\n}}",
+            self.dimension, self.variance
+        )
+    }
+}
+
+/// Structure containing the secret keys needed to encrypt and decrypt booleans.
+///
+/// # Attributes
+/// * `params` - the parameters used to generate the keys
+/// * `lwe_secret_key` - the LWE secret key encrypting the booleans
+/// * `rlwe_secret_key` - the RLWE secret key used in the bootstrap
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct BoolClientKey {
+    pub params: BoolParams,
+    pub lwe_secret_key: crate::LWESecretKey,
+    pub rlwe_secret_key: crate::RLWESecretKey,
+}
+
+impl BoolClientKey {
+    /// Generate a new client key from a BoolParams
+    ///
+    /// # Argument
+    /// * `params` - a BoolParams instance
+    ///
+    /// # Output
+    /// * a new BoolClientKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    /// ```
+    pub fn new(params: &BoolParams) -> BoolClientKey {
+        BoolClientKey::new_with_generator(params, &mut SecretRandomGenerator::new(None))
+    }
+
+    /// Generate a new client key from a BoolParams with a provided random generator
+    ///
+    /// # Arguments
+    /// * `params` - a BoolParams instance
+    /// * `generator` - the random generator sampling the LWE and the RLWE secret keys
+    ///
+    /// # Output
+    /// * a new BoolClientKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::SecretRandomGenerator;
+    ///
+    /// let client_key_1 = BoolClientKey::new_with_generator(
+    ///     &BOOL128_DEFAULT,
+    ///     &mut SecretRandomGenerator::new(Some(42)),
+    /// );
+    /// let client_key_2 = BoolClientKey::new_with_generator(
+    ///     &BOOL128_DEFAULT,
+    ///     &mut SecretRandomGenerator::new(Some(42)),
+    /// );
+    /// assert_eq!(client_key_1, client_key_2);
+    /// ```
+    pub fn new_with_generator(
+        params: &BoolParams,
+        generator: &mut SecretRandomGenerator,
+    ) -> BoolClientKey {
+        BoolClientKey {
+            params: params.clone(),
+            lwe_secret_key: crate::LWESecretKey::new_with_generator(&params.lwe_params, generator),
+            rlwe_secret_key: crate::RLWESecretKey::new_with_generator(
+                &params.rlwe_params,
+                generator,
+            ),
+        }
+    }
+
+    /// Generate the server key (bootstrapping and key switching keys) matching the client key
+    ///
+    /// # Output
+    /// * a new BoolServerKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    /// let server_key = client_key.get_server_key();
+    /// ```
+    pub fn get_server_key(&self) -> BoolServerKey {
+        self.get_server_key_with_generator(&mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Generate the server key matching the client key with a provided encryption generator
+    ///
+    /// # Argument
+    /// * `generator` - the encryption generator sampling the masks and the noises of the keys
+    ///
+    /// # Output
+    /// * a new BoolServerKey
+    pub fn get_server_key_with_generator(
+        &self,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> BoolServerKey {
+        let bsk = crate::LWEBSK::new_with_generator(
+            &self.lwe_secret_key,
+            &self.rlwe_secret_key,
+            self.params.bsk_base_log,
+            self.params.bsk_level,
+            generator,
+        );
+        let ksk = crate::LWEKSK::new_with_generator(
+            &self.rlwe_secret_key.to_lwe_secret_key(),
+            &self.lwe_secret_key,
+            self.params.ksk_base_log,
+            self.params.ksk_level,
+            generator,
+        );
+        BoolServerKey { bsk, ksk }
+    }
+
+    /// Encrypt a boolean
+    ///
+    /// # Argument
+    /// * `message` - a boolean
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * NoNoiseInCiphertext - if the secret key has not enough noise
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    /// let ct = client_key.encrypt(true).unwrap();
+    /// ```
+    pub fn encrypt(&self, message: bool) -> Result<BoolCiphertext, CryptoAPIError> {
+        self.encrypt_with_generator(message, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt a boolean with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `message` - a boolean
+    /// * `generator` - the encryption generator sampling the mask and the noise
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * NoNoiseInCiphertext - if the secret key has not enough noise
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    ///
+    /// // two generators with the same seeds produce the same ciphertexts
+    /// let ct_1 = client_key
+    ///     .encrypt_with_generator(true, &mut EncryptionRandomGenerator::new_deterministic(1, 2))
+    ///     .unwrap();
+    /// let ct_2 = client_key
+    ///     .encrypt_with_generator(true, &mut EncryptionRandomGenerator::new_deterministic(1, 2))
+    ///     .unwrap();
+    /// assert_eq!(ct_1, ct_2);
+    /// ```
+    pub fn encrypt_with_generator(
+        &self,
+        message: bool,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        let mut lwe = crate::LWE::zero(self.lwe_secret_key.dimension)?;
+        lwe.encrypt_raw_with_generator(&self.lwe_secret_key, encode_bool(message), generator)?;
+        Ok(BoolCiphertext {
+            ciphertext: lwe.ciphertext,
+            variance: lwe.variance,
+            dimension: lwe.dimension,
+        })
+    }
+
+    /// Decrypt an encrypted boolean
+    ///
+    /// # Argument
+    /// * `ct` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a boolean
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    /// let ct = client_key.encrypt(true).unwrap();
+    /// assert!(client_key.decrypt(&ct).unwrap());
+    /// ```
+    pub fn decrypt(&self, ct: &BoolCiphertext) -> Result<bool, CryptoAPIError> {
+        // check dimensions
+        if ct.dimension != self.lwe_secret_key.dimension {
+            return Err(DimensionError!(ct.dimension, self.lwe_secret_key.dimension));
+        }
+
+        // compute the phase
+        let mut plaintext = Plaintext(0 as Torus);
+        self.lwe_secret_key
+            .val
+            .decrypt_lwe(&mut plaintext, &ct.ciphertext);
+
+        // true iff the phase is in [0, 1/2[
+        Ok(plaintext.0 < (1 << (<Torus as Numeric>::BITS - 1)))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<BoolClientKey, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Structure containing the public keys needed to evaluate gates on encrypted booleans.
+///
+/// # Attributes
+/// * `bsk` - the bootstrapping key
+/// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+#[derive(Debug, PartialEq)]
+pub struct BoolServerKey {
+    pub bsk: crate::LWEBSK,
+    pub ksk: crate::LWEKSK,
+}
+
+impl BoolServerKey {
+    /// Check that a ciphertext can be used as the input of a gate
+    fn check_dimension(&self, ct: &BoolCiphertext) -> Result<(), CryptoAPIError> {
+        if ct.dimension != self.bsk.get_lwe_dimension() {
+            return Err(DimensionError!(ct.dimension, self.bsk.get_lwe_dimension()));
+        }
+        Ok(())
+    }
+
+    /// Compute scalar * (ct_1 + ct_2) + constant
+    fn linear_combination(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+        scalar: i64,
+        constant: Torus,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        self.check_dimension(ct_1)?;
+        self.check_dimension(ct_2)?;
+
+        // sum
+        let mut res = ct_1.clone();
+        res.ciphertext.update_with_add(&ct_2.ciphertext);
+        res.variance = npe::add_ciphertexts(ct_1.variance, ct_2.variance);
+
+        // multiplication by the scalar
+        res.ciphertext
+            .update_with_scalar_mul(Cleartext(scalar as Torus));
+        res.variance = <Torus as npe::LWE>::single_scalar_mul(res.variance, scalar as Torus);
+
+        // add the constant
+        let update = res.ciphertext.as_tensor().as_slice()[res.dimension].wrapping_add(constant);
+        res.ciphertext.as_mut_tensor().as_mut_slice()[res.dimension] = update;
+
+        Ok(res)
+    }
+
+    /// Bootstrap the sign of the input ciphertext (1/8 if the phase is in [0, 1/2[, -1/8 otherwise),
+    /// without key switching the output
+    fn sign_bootstrap(&self, ct: &BoolCiphertext) -> BoolCiphertext {
+        let lut = vec![TRUE_ENCODING; self.bsk.polynomial_size];
+        let ciphertext = self.bsk.bootstrap_raw(&ct.ciphertext, &lut);
+
+        BoolCiphertext {
+            ciphertext,
+            variance: <Torus as npe::Cross>::bootstrap(
+                ct.dimension,
+                self.bsk.dimension,
+                self.bsk.level,
+                self.bsk.base_log,
+                self.bsk.polynomial_size,
+                self.bsk.variance,
+            ),
+            dimension: self.bsk.dimension * self.bsk.polynomial_size,
+        }
+    }
+
+    /// Key switch the output of a bootstrap back to the input key
+    fn keyswitch(&self, ct: &BoolCiphertext) -> BoolCiphertext {
+        let mut ciphertext = LweCiphertext::allocate(0, LweSize(self.ksk.dimension_after + 1));
+        self.ksk
+            .ciphertexts
+            .keyswitch_ciphertext(&mut ciphertext, &ct.ciphertext);
+
+        BoolCiphertext {
+            ciphertext,
+            variance: <Torus as npe::LWE>::key_switch(
+                ct.dimension,
+                self.ksk.level,
+                self.ksk.base_log,
+                self.ksk.variance,
+                ct.variance,
+            ),
+            dimension: self.ksk.dimension_after,
+        }
+    }
+
+    /// Compute the sign bootstrap of the linear combination scalar * (ct_1 + ct_2) + constant,
+    /// followed by a key switch
+    fn binary_gate(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+        scalar: i64,
+        constant: Torus,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        let combination = self.linear_combination(ct_1, ct_2, scalar, constant)?;
+        Ok(self.keyswitch(&self.sign_bootstrap(&combination)))
+    }
+
+    /// Homomorphically compute ct_1 AND ct_2
+    ///
+    /// # Arguments
+    /// * `ct_1` - a BoolCiphertext
+    /// * `ct_2` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(true).unwrap();
+    /// let ct_2 = client_key.encrypt(false).unwrap();
+    ///
+    /// let ct_res = server_key.and(&ct_1, &ct_2).unwrap();
+    /// assert!(!client_key.decrypt(&ct_res).unwrap());
+    /// ```
+    pub fn and(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        // ct_1 + ct_2 - 1/8
+        self.binary_gate(ct_1, ct_2, 1, TRUE_ENCODING.wrapping_neg())
+    }
+
+    /// Homomorphically compute ct_1 OR ct_2
+    ///
+    /// # Arguments
+    /// * `ct_1` - a BoolCiphertext
+    /// * `ct_2` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    pub fn or(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        // ct_1 + ct_2 + 1/8
+        self.binary_gate(ct_1, ct_2, 1, TRUE_ENCODING)
+    }
+
+    /// Homomorphically compute NOT (ct_1 AND ct_2)
+    ///
+    /// # Arguments
+    /// * `ct_1` - a BoolCiphertext
+    /// * `ct_2` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    pub fn nand(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        // - ct_1 - ct_2 + 1/8
+        self.binary_gate(ct_1, ct_2, -1, TRUE_ENCODING)
+    }
+
+    /// Homomorphically compute NOT (ct_1 OR ct_2)
+    ///
+    /// # Arguments
+    /// * `ct_1` - a BoolCiphertext
+    /// * `ct_2` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    pub fn nor(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        // - ct_1 - ct_2 - 1/8
+        self.binary_gate(ct_1, ct_2, -1, TRUE_ENCODING.wrapping_neg())
+    }
+
+    /// Homomorphically compute ct_1 XOR ct_2
+    ///
+    /// # Arguments
+    /// * `ct_1` - a BoolCiphertext
+    /// * `ct_2` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(true).unwrap();
+    /// let ct_2 = client_key.encrypt(false).unwrap();
+    ///
+    /// let ct_res = server_key.xor(&ct_1, &ct_2).unwrap();
+    /// assert!(client_key.decrypt(&ct_res).unwrap());
+    /// ```
+    pub fn xor(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        // 2 * (ct_1 + ct_2) + 1/4
+        self.binary_gate(ct_1, ct_2, 2, TRUE_ENCODING << 1)
+    }
+
+    /// Homomorphically compute NOT (ct_1 XOR ct_2)
+    ///
+    /// # Arguments
+    /// * `ct_1` - a BoolCiphertext
+    /// * `ct_2` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    pub fn xnor(
+        &self,
+        ct_1: &BoolCiphertext,
+        ct_2: &BoolCiphertext,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        // - 2 * (ct_1 + ct_2) - 1/4
+        self.binary_gate(ct_1, ct_2, -2, (TRUE_ENCODING << 1).wrapping_neg())
+    }
+
+    /// Homomorphically compute NOT ct
+    /// This gate is a simple negation of the ciphertext and needs neither bootstrap nor key switch
+    ///
+    /// # Argument
+    /// * `ct` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    pub fn not(&self, ct: &BoolCiphertext) -> BoolCiphertext {
+        let mut res = ct.clone();
+        res.ciphertext.update_with_neg();
+        res
+    }
+
+    /// Homomorphically compute ct_condition ? ct_then : ct_else
+    /// The two products are bootstrapped separately and their sum is key switched once
+    ///
+    /// # Arguments
+    /// * `ct_condition` - a BoolCiphertext
+    /// * `ct_then` - a BoolCiphertext
+    /// * `ct_else` - a BoolCiphertext
+    ///
+    /// # Output
+    /// * a new BoolCiphertext
+    /// * DimensionError - if the ciphertexts and the server key have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_condition = client_key.encrypt(true).unwrap();
+    /// let ct_then = client_key.encrypt(false).unwrap();
+    /// let ct_else = client_key.encrypt(true).unwrap();
+    ///
+    /// let ct_res = server_key.mux(&ct_condition, &ct_then, &ct_else).unwrap();
+    /// assert!(!client_key.decrypt(&ct_res).unwrap());
+    /// ```
+    pub fn mux(
+        &self,
+        ct_condition: &BoolCiphertext,
+        ct_then: &BoolCiphertext,
+        ct_else: &BoolCiphertext,
+    ) -> Result<BoolCiphertext, CryptoAPIError> {
+        // condition AND then, without key switch
+        let combination =
+            self.linear_combination(ct_condition, ct_then, 1, TRUE_ENCODING.wrapping_neg())?;
+        let mut res = self.sign_bootstrap(&combination);
+
+        // (NOT condition) AND else, without key switch
+        let combination = self.linear_combination(
+            &self.not(ct_condition),
+            ct_else,
+            1,
+            TRUE_ENCODING.wrapping_neg(),
+        )?;
+        let ct_else_out = self.sign_bootstrap(&combination);
+
+        // OR of the two exclusive products: sum + 1/8
+        res.ciphertext.update_with_add(&ct_else_out.ciphertext);
+        res.variance = npe::add_ciphertexts(res.variance, ct_else_out.variance);
+        let update =
+            res.ciphertext.as_tensor().as_slice()[res.dimension].wrapping_add(TRUE_ENCODING);
+        res.ciphertext.as_mut_tensor().as_mut_slice()[res.dimension] = update;
+
+        Ok(self.keyswitch(&res))
+    }
+
+    /// Save the bootstrapping key and the key switching key in two files
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    pub fn save(&self, path_bsk: &str, path_ksk: &str) {
+        self.bsk.save(path_bsk);
+        self.ksk.save(path_ksk);
+    }
+
+    /// Load a server key saved with `BoolServerKey::save`
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    ///
+    /// # Output
+    /// * a BoolServerKey
+    pub fn load(path_bsk: &str, path_ksk: &str) -> BoolServerKey {
+        BoolServerKey {
+            bsk: crate::LWEBSK::load(path_bsk),
+            ksk: crate::LWEKSK::load(path_ksk),
+        }
+    }
+}
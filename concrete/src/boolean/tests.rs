@@ -0,0 +1,109 @@
+use super::*;
+
+/// Check a binary gate against its truth table
+macro_rules! assert_binary_gate {
+    ($client_key: expr, $server_key: expr, $gate: ident, $clear: expr) => {
+        for &m_1 in [false, true].iter() {
+            for &m_2 in [false, true].iter() {
+                let ct_1 = $client_key.encrypt(m_1).unwrap();
+                let ct_2 = $client_key.encrypt(m_2).unwrap();
+                let ct_res = $server_key.$gate(&ct_1, &ct_2).unwrap();
+                let decryption = $client_key.decrypt(&ct_res).unwrap();
+                assert_eq!(
+                    $clear(m_1, m_2),
+                    decryption,
+                    "{}({}, {})",
+                    stringify!($gate),
+                    m_1,
+                    m_2
+                );
+            }
+        }
+    };
+}
+
+#[test]
+fn test_default_params_npe_margin() {
+    // a normal noise crosses the threshold with a probability erfc(margin / sqrt(2)):
+    // 6.7 standard deviations give 2^-35.5 and 8.4 give 2^-54.3
+    assert!(BOOL128_DEFAULT.get_nb_std_dev_margin() > 6.7);
+    assert!(BOOL128_LOW_FAILURE.get_nb_std_dev_margin() > 8.4);
+}
+
+#[test]
+fn test_encrypt_x_decrypt() {
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+
+    for &m in [false, true].iter() {
+        let ct = client_key.encrypt(m).unwrap();
+        assert_eq!(m, client_key.decrypt(&ct).unwrap());
+
+        let ct_trivial = BoolCiphertext::trivial(m, client_key.lwe_secret_key.dimension);
+        assert_eq!(m, client_key.decrypt(&ct_trivial).unwrap());
+    }
+}
+
+#[test]
+fn test_binary_gates_truth_tables() {
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    let server_key = client_key.get_server_key();
+
+    assert_binary_gate!(client_key, server_key, and, |a: bool, b: bool| a & b);
+    assert_binary_gate!(client_key, server_key, or, |a: bool, b: bool| a | b);
+    assert_binary_gate!(client_key, server_key, nand, |a: bool, b: bool| !(a & b));
+    assert_binary_gate!(client_key, server_key, nor, |a: bool, b: bool| !(a | b));
+    assert_binary_gate!(client_key, server_key, xor, |a: bool, b: bool| a ^ b);
+    assert_binary_gate!(client_key, server_key, xnor, |a: bool, b: bool| !(a ^ b));
+}
+
+#[test]
+fn test_not_x_mux_truth_tables() {
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    let server_key = client_key.get_server_key();
+
+    for &m in [false, true].iter() {
+        let ct = client_key.encrypt(m).unwrap();
+        let ct_res = server_key.not(&ct);
+        assert_eq!(!m, client_key.decrypt(&ct_res).unwrap());
+    }
+
+    for &m_condition in [false, true].iter() {
+        for &m_then in [false, true].iter() {
+            for &m_else in [false, true].iter() {
+                let ct_condition = client_key.encrypt(m_condition).unwrap();
+                let ct_then = client_key.encrypt(m_then).unwrap();
+                let ct_else = client_key.encrypt(m_else).unwrap();
+                let ct_res = server_key.mux(&ct_condition, &ct_then, &ct_else).unwrap();
+                let expected = if m_condition { m_then } else { m_else };
+                assert_eq!(expected, client_key.decrypt(&ct_res).unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_chained_gates() {
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    let server_key = client_key.get_server_key();
+
+    // the output of a gate is a valid input of another gate
+    let mut clear = false;
+    let mut ct = client_key.encrypt(clear).unwrap();
+    for i in 0..20 {
+        let m = i % 3 == 0;
+        let ct_m = client_key.encrypt(m).unwrap();
+        ct = server_key.xor(&ct, &ct_m).unwrap();
+        clear ^= m;
+        assert_eq!(clear, client_key.decrypt(&ct).unwrap());
+    }
+}
+
+#[test]
+fn test_gate_dimension_error() {
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    let server_key = client_key.get_server_key();
+
+    let ct_1 = client_key.encrypt(true).unwrap();
+    let ct_2 = BoolCiphertext::trivial(true, 10);
+    assert!(server_key.and(&ct_1, &ct_2).is_err());
+}
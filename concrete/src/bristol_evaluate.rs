@@ -0,0 +1,132 @@
+//! This program evaluates a Bristol Fashion netlist on encrypted inputs with gate bootstrapping.
+//!
+//! Usage:
+//! * `bristol_evaluate keygen <client_key> <bsk> <ksk>` generates and saves the keys
+//! * `bristol_evaluate run <circuit> <client_key> <bsk> <ksk> <input_1> ... <input_n>` encrypts
+//!   the inputs (decimal or hexadecimal with a 0x prefix), evaluates the circuit and prints the
+//!   decrypted outputs in hexadecimal
+use std::env;
+use std::error::Error;
+use std::process;
+use std::time::Instant;
+
+use concrete::{BoolCiphertext, BoolClientKey, BoolServerKey, BristolCircuit, BOOL128_DEFAULT};
+
+const USAGE: &str = "usage:
+    bristol_evaluate keygen <client_key> <bsk> <ksk>
+    bristol_evaluate run <circuit> <client_key> <bsk> <ksk> <input_1> ... <input_n>";
+
+/// Convert a decimal or hexadecimal (0x prefix) value into bits, least significant bit first
+fn parse_value(value: &str, size: usize) -> Result<Vec<bool>, Box<dyn Error>> {
+    let digits: Vec<u32> = if let Some(hex) = value.strip_prefix("0x") {
+        hex.chars()
+            .rev()
+            .map(|c| c.to_digit(16).ok_or("invalid hexadecimal digit"))
+            .collect::<Result<Vec<u32>, &str>>()?
+    } else {
+        // split the decimal value into hexadecimal digits
+        let mut decimal: u128 = value.parse()?;
+        let mut digits = Vec::new();
+        while decimal > 0 {
+            digits.push((decimal & 0xf) as u32);
+            decimal >>= 4;
+        }
+        digits
+    };
+
+    let bits: Vec<bool> = digits
+        .iter()
+        .flat_map(|digit| (0..4).map(move |i| (digit >> i) & 1 == 1))
+        .collect();
+    if bits.iter().skip(size).any(|bit| *bit) {
+        return Err(format!("the value {} does not fit in {} bits", value, size).into());
+    }
+    Ok((0..size).map(|i| *bits.get(i).unwrap_or(&false)).collect())
+}
+
+/// Convert bits, least significant bit first, into a hexadecimal value
+fn format_value(bits: &[bool]) -> String {
+    let digits: String = bits
+        .chunks(4)
+        .rev()
+        .map(|chunk| {
+            let digit = chunk
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, bit)| acc | ((*bit as u32) << i));
+            std::char::from_digit(digit, 16).unwrap()
+        })
+        .collect();
+    format!("0x{}", digits)
+}
+
+fn keygen(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.len() != 3 {
+        return Err(USAGE.into());
+    }
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    let server_key = client_key.get_server_key();
+    client_key.save(&args[0])?;
+    server_key.save(&args[1], &args[2]);
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.len() < 4 {
+        return Err(USAGE.into());
+    }
+
+    // load the circuit and the keys
+    let circuit = BristolCircuit::load(&args[0])?;
+    let client_key = BoolClientKey::load(&args[1])?;
+    let server_key = BoolServerKey::load(&args[2], &args[3]);
+    println!("{}", circuit.get_report());
+
+    // encrypt the inputs
+    let values = &args[4..];
+    if values.len() != circuit.input_sizes.len() {
+        return Err(format!(
+            "the circuit expects {} inputs, {} provided",
+            circuit.input_sizes.len(),
+            values.len()
+        )
+        .into());
+    }
+    let mut inputs: Vec<Vec<BoolCiphertext>> = Vec::with_capacity(values.len());
+    for (value, size) in values.iter().zip(circuit.input_sizes.iter()) {
+        let bits = parse_value(value, *size)?;
+        inputs.push(
+            bits.iter()
+                .map(|bit| client_key.encrypt(*bit))
+                .collect::<Result<Vec<BoolCiphertext>, _>>()?,
+        );
+    }
+
+    // evaluate
+    let start = Instant::now();
+    let outputs = circuit.evaluate(&server_key, &inputs)?;
+    println!("evaluation time: {:?}", start.elapsed());
+
+    // decrypt the outputs
+    for (i, output) in outputs.iter().enumerate() {
+        let bits = output
+            .iter()
+            .map(|ct| client_key.decrypt(ct))
+            .collect::<Result<Vec<bool>, _>>()?;
+        println!("output {}: {}", i, format_value(&bits));
+    }
+    Ok(())
+}
+
+pub fn main() {
+    let args: Vec<String> = env::args().collect();
+    let result = match args.get(1).map(|command| command.as_str()) {
+        Some("keygen") => keygen(&args[2..]),
+        Some("run") => run(&args[2..]),
+        _ => Err(USAGE.into()),
+    };
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,854 @@
+//! debug module: shadow execution of the homomorphic operations
+//!
+//! A `DebugLWE` (resp. `DebugVectorLWE`) carries, next to an `LWE` (resp. a `VectorLWE`), the cleartext
+//! it is expected to encrypt, called its shadow, which is updated alongside every homomorphic operation.
+//! After each operation, the shadow is checked against the encoder of the output and, if the ciphertext
+//! carries its secret key, against the decryption of the output. The key is attached by `encode_encrypt`
+//! and kept by the operations that do not change it, the output of a key switch or a bootstrap being
+//! under another key, that can be attached with `with_secret_key`.
+//! The first operation whose message leaves the interval of its encoder or whose decryption is further
+//! than the granularity from the shadow returns a `ShadowIntervalError` or a `ShadowPrecisionError`,
+//! with the backtrace of that operation.
+//!
+//! Both types implement `LWEOperations` and `VectorLWEOperations`, so that generic code runs in debug mode
+//! by only changing its type parameter.
+
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+
+use backtrace::Backtrace;
+use colored::Colorize;
+
+use crate::error::CryptoAPIError;
+use crate::traits::{
+    GenericAdd, GenericMul, GenericNeg, GenericPolicyOps, GenericSub, LWEOperations,
+    OperatorPolicy, VectorLWEOperations,
+};
+
+/// Structure containing an LWE ciphertext and its shadow cleartext
+///
+/// # Attributes
+/// * `ciphertext` - the LWE ciphertext
+/// * `shadow` - the message the ciphertext is expected to encrypt
+/// * `secret_key` - the secret key of the ciphertext if it is known, used to check the decryptions
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugLWE {
+    pub ciphertext: crate::LWE,
+    pub shadow: f64,
+    pub secret_key: Option<Arc<crate::LWESecretKey>>,
+}
+
+impl GenericAdd<f64, CryptoAPIError> for DebugLWE {
+    fn add(&self, right: f64) -> Result<DebugLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(right)
+    }
+    fn add_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        *self = self.add_constant_dynamic_encoder(right)?;
+        Ok(())
+    }
+}
+
+impl GenericAdd<&DebugLWE, CryptoAPIError> for DebugLWE {
+    fn add(&self, right: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        self.add_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn add_inplace(&mut self, right: &DebugLWE) -> Result<(), CryptoAPIError> {
+        *self = GenericAdd::add(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericSub<f64, CryptoAPIError> for DebugLWE {
+    fn sub(&self, right: f64) -> Result<DebugLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(-right)
+    }
+    fn sub_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        *self = self.add_constant_dynamic_encoder(-right)?;
+        Ok(())
+    }
+}
+
+impl GenericSub<&DebugLWE, CryptoAPIError> for DebugLWE {
+    fn sub(&self, right: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        self.sub_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn sub_inplace(&mut self, right: &DebugLWE) -> Result<(), CryptoAPIError> {
+        *self = GenericSub::sub(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericPolicyOps<CryptoAPIError> for DebugLWE {
+    fn add_with_policy_inplace(
+        &mut self,
+        right: &DebugLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        *self = match policy {
+            OperatorPolicy::Padding => self.add_with_padding(right)?,
+            OperatorPolicy::Centered => self.add_centered(right)?,
+            OperatorPolicy::Exact => self.add_with_padding_exact(right)?,
+        };
+        Ok(())
+    }
+
+    fn sub_with_policy_inplace(
+        &mut self,
+        right: &DebugLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        *self = match policy {
+            OperatorPolicy::Padding => self.sub_with_padding(right)?,
+            OperatorPolicy::Centered => self.add_centered(&right.opposite()?)?,
+            OperatorPolicy::Exact => self.sub_with_padding_exact(right)?,
+        };
+        Ok(())
+    }
+}
+
+impl GenericMul<i32, CryptoAPIError> for DebugLWE {
+    fn mul(&self, right: i32) -> Result<DebugLWE, CryptoAPIError> {
+        self.mul_constant_static_encoder(right)
+    }
+    fn mul_inplace(&mut self, right: i32) -> Result<(), CryptoAPIError> {
+        *self = self.mul_constant_static_encoder(right)?;
+        Ok(())
+    }
+}
+
+impl GenericNeg<CryptoAPIError> for DebugLWE {
+    fn neg(&self) -> Result<DebugLWE, CryptoAPIError> {
+        self.opposite()
+    }
+    fn neg_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        *self = self.opposite()?;
+        Ok(())
+    }
+}
+
+impl_operators!(DebugLWE);
+
+impl DebugLWE {
+    /// Attach a shadow cleartext to an LWE ciphertext and check it
+    ///
+    /// # Arguments
+    /// * `ciphertext` - an LWE ciphertext
+    /// * `shadow` - the message the ciphertext is expected to encrypt
+    /// * `secret_key` - the secret key of the ciphertext, None to only check the interval
+    ///
+    /// # Output
+    /// * a DebugLWE
+    /// * ShadowIntervalError - if the shadow is not in the interval of the encoder
+    /// * ShadowPrecisionError - if the decryption of the ciphertext is too far from the shadow
+    pub fn new(
+        ciphertext: crate::LWE,
+        shadow: f64,
+        secret_key: Option<Arc<crate::LWESecretKey>>,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(ciphertext, shadow, secret_key, "new")
+    }
+
+    /// Encode a message, encrypt it and use it as the shadow, the ciphertext carrying a copy of the key
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `message` - a message as f64
+    /// * `encoder` - an Encoder
+    ///
+    /// # Output
+    /// * a DebugLWE
+    pub fn encode_encrypt(
+        sk: &crate::LWESecretKey,
+        message: f64,
+        encoder: &crate::Encoder,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        let ciphertext = crate::LWE::encode_encrypt(sk, message, encoder)?;
+        DebugLWE::checked(
+            ciphertext,
+            message,
+            Some(Arc::new(sk.clone())),
+            "encode_encrypt",
+        )
+    }
+
+    /// Attach the secret key of the ciphertext, e.g. the output key of a key switch or a bootstrap,
+    /// and check the decryption against the shadow
+    ///
+    /// # Argument
+    /// * `secret_key` - the secret key of the ciphertext
+    ///
+    /// # Output
+    /// * a DebugLWE
+    /// * ShadowPrecisionError - if the decryption of the ciphertext is too far from the shadow
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use std::sync::Arc;
+    ///
+    /// let secret_key = Arc::new(LWESecretKey::new(&LWE128_630));
+    /// let encoder = Encoder::new(0., 10., 4, 2).unwrap();
+    /// let ct = LWE::encode_encrypt(&secret_key, 3., &encoder).unwrap();
+    ///
+    /// // the wrong shadow is only detected with the key
+    /// let debug_ct = DebugLWE::new(ct, 5., None).unwrap();
+    /// assert!(debug_ct.with_secret_key(&secret_key).is_err());
+    /// ```
+    pub fn with_secret_key(
+        self,
+        secret_key: &Arc<crate::LWESecretKey>,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext,
+            self.shadow,
+            Some(secret_key.clone()),
+            "with_secret_key",
+        )
+    }
+
+    /// Decrypt the ciphertext (see `LWE::decrypt_decode`)
+    pub fn decrypt_decode(&self, sk: &crate::LWESecretKey) -> Result<f64, CryptoAPIError> {
+        self.ciphertext.decrypt_decode(sk)
+    }
+
+    /// Decrypt the ciphertext in a rounding context (see `LWE::decrypt_decode_round`)
+    pub fn decrypt_decode_round(&self, sk: &crate::LWESecretKey) -> Result<f64, CryptoAPIError> {
+        self.ciphertext.decrypt_decode_round(sk)
+    }
+
+    /// Check the shadow of the ciphertext against its encoder and its decryption
+    ///
+    /// # Argument
+    /// * `operation` - the name of the last operation, used in the errors
+    ///
+    /// # Output
+    /// * ShadowIntervalError - if the shadow is not in the interval of the encoder
+    /// * ShadowPrecisionError - if the decryption of the ciphertext is too far from the shadow
+    pub fn check(&self, operation: &str) -> Result<(), CryptoAPIError> {
+        check_shadows(
+            operation,
+            false,
+            self.secret_key.as_deref(),
+            &[self.shadow],
+            std::slice::from_ref(&self.ciphertext.encoder),
+            |sk| Ok(vec![self.ciphertext.decrypt_decode(sk)?]),
+        )
+    }
+
+    /// Add a small message without changing the encoder (see `LWE::add_constant_static_encoder`)
+    pub fn add_constant_static_encoder(&self, message: f64) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.add_constant_static_encoder(message)?,
+            self.shadow + message,
+            self.secret_key.clone(),
+            "add_constant_static_encoder",
+        )
+    }
+
+    /// Add a message by changing the encoder (see `LWE::add_constant_dynamic_encoder`)
+    pub fn add_constant_dynamic_encoder(&self, message: f64) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.add_constant_dynamic_encoder(message)?,
+            self.shadow + message,
+            self.secret_key.clone(),
+            "add_constant_dynamic_encoder",
+        )
+    }
+
+    /// Add two ciphertexts with centered intervals (see `LWE::add_centered`)
+    pub fn add_centered(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.add_centered(&ct.ciphertext)?,
+            self.shadow + ct.shadow,
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "add_centered",
+        )
+    }
+
+    /// Add two ciphertexts by eating one bit of padding (see `LWE::add_with_padding`)
+    pub fn add_with_padding(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.add_with_padding(&ct.ciphertext)?,
+            self.shadow + ct.shadow,
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "add_with_padding",
+        )
+    }
+
+    /// Add two ciphertexts by eating one bit of padding and adding one bit of precision
+    /// (see `LWE::add_with_padding_exact`)
+    pub fn add_with_padding_exact(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.add_with_padding_exact(&ct.ciphertext)?,
+            self.shadow + ct.shadow,
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "add_with_padding_exact",
+        )
+    }
+
+    /// Subtract two ciphertexts by eating one bit of padding (see `LWE::sub_with_padding`)
+    pub fn sub_with_padding(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.sub_with_padding(&ct.ciphertext)?,
+            self.shadow - ct.shadow,
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "sub_with_padding",
+        )
+    }
+
+    /// Subtract two ciphertexts by eating one bit of padding and adding one bit of precision
+    /// (see `LWE::sub_with_padding_exact`)
+    pub fn sub_with_padding_exact(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.sub_with_padding_exact(&ct.ciphertext)?,
+            self.shadow - ct.shadow,
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "sub_with_padding_exact",
+        )
+    }
+
+    /// Multiply by a small integer without changing the encoder (see `LWE::mul_constant_static_encoder`)
+    pub fn mul_constant_static_encoder(&self, message: i32) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.mul_constant_static_encoder(message)?,
+            self.shadow * message as f64,
+            self.secret_key.clone(),
+            "mul_constant_static_encoder",
+        )
+    }
+
+    /// Multiply by a real constant by consuming some bits of padding (see `LWE::mul_constant_with_padding`)
+    pub fn mul_constant_with_padding(
+        &self,
+        constant: f64,
+        max_constant: f64,
+        nb_bit_padding: usize,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext
+                .mul_constant_with_padding(constant, max_constant, nb_bit_padding)?,
+            self.shadow * constant,
+            self.secret_key.clone(),
+            "mul_constant_with_padding",
+        )
+    }
+
+    /// Compute the opposite (see `LWE::opposite`)
+    pub fn opposite(&self) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.opposite()?,
+            -self.shadow,
+            self.secret_key.clone(),
+            "opposite",
+        )
+    }
+
+    /// Compute a key switch (see `LWE::keyswitch`)
+    pub fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.keyswitch(ksk)?,
+            self.shadow,
+            None,
+            "keyswitch",
+        )
+    }
+
+    /// Compute a bootstrap (see `LWE::bootstrap`)
+    pub fn bootstrap(&self, bsk: &crate::LWEBSK) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::checked(
+            self.ciphertext.bootstrap(bsk)?,
+            self.shadow,
+            None,
+            "bootstrap",
+        )
+    }
+
+    /// Compute a bootstrap applying a function, the shadow being the image of the input shadow
+    /// (see `LWE::bootstrap_with_function`)
+    pub fn bootstrap_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        let shadow = f(self.shadow);
+        DebugLWE::checked(
+            self.ciphertext
+                .bootstrap_with_function(bsk, f, encoder_output)?,
+            shadow,
+            None,
+            "bootstrap_with_function",
+        )
+    }
+
+    /// Build the output of an operation and check it
+    fn checked(
+        ciphertext: crate::LWE,
+        shadow: f64,
+        secret_key: Option<Arc<crate::LWESecretKey>>,
+        operation: &str,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        let res = DebugLWE {
+            ciphertext,
+            shadow,
+            secret_key,
+        };
+        res.check(operation)?;
+        Ok(res)
+    }
+}
+
+impl LWEOperations for DebugLWE {
+    type SecretKey = crate::LWESecretKey;
+    type BootstrapKey = crate::LWEBSK;
+    type KeySwitchKey = crate::LWEKSK;
+
+    fn encode_encrypt(
+        sk: &crate::LWESecretKey,
+        message: f64,
+        encoder: &crate::Encoder,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::encode_encrypt(sk, message, encoder)
+    }
+    fn decrypt_decode(&self, sk: &crate::LWESecretKey) -> Result<f64, CryptoAPIError> {
+        DebugLWE::decrypt_decode(self, sk)
+    }
+    fn decrypt_decode_round(&self, sk: &crate::LWESecretKey) -> Result<f64, CryptoAPIError> {
+        DebugLWE::decrypt_decode_round(self, sk)
+    }
+    fn get_encoder(&self) -> &crate::Encoder {
+        &self.ciphertext.encoder
+    }
+    fn get_variance(&self) -> f64 {
+        self.ciphertext.variance
+    }
+    fn add_constant_static_encoder(&self, message: f64) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::add_constant_static_encoder(self, message)
+    }
+    fn add_constant_dynamic_encoder(&self, message: f64) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::add_constant_dynamic_encoder(self, message)
+    }
+    fn add_centered(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::add_centered(self, ct)
+    }
+    fn add_with_padding(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::add_with_padding(self, ct)
+    }
+    fn sub_with_padding(&self, ct: &DebugLWE) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::sub_with_padding(self, ct)
+    }
+    fn mul_constant_static_encoder(&self, message: i32) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::mul_constant_static_encoder(self, message)
+    }
+    fn opposite(&self) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::opposite(self)
+    }
+    fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::keyswitch(self, ksk)
+    }
+    fn bootstrap_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<DebugLWE, CryptoAPIError> {
+        DebugLWE::bootstrap_with_function(self, bsk, f, encoder_output)
+    }
+}
+
+/// Structure containing a list of LWE ciphertexts and their shadow cleartexts
+///
+/// # Attributes
+/// * `ciphertexts` - the LWE ciphertexts
+/// * `shadows` - the messages the ciphertexts are expected to encrypt
+/// * `secret_key` - the secret key of the ciphertexts if it is known, used to check the decryptions
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugVectorLWE {
+    pub ciphertexts: crate::VectorLWE,
+    pub shadows: Vec<f64>,
+    pub secret_key: Option<Arc<crate::LWESecretKey>>,
+}
+
+impl DebugVectorLWE {
+    /// Attach shadow cleartexts to LWE ciphertexts and check them
+    ///
+    /// # Arguments
+    /// * `ciphertexts` - LWE ciphertexts
+    /// * `shadows` - the messages the ciphertexts are expected to encrypt
+    /// * `secret_key` - the secret key of the ciphertexts, None to only check the intervals
+    ///
+    /// # Output
+    /// * a DebugVectorLWE
+    /// * NbCTError - if the number of shadows and the number of ciphertexts differ
+    /// * ShadowIntervalError - if a shadow is not in the interval of its encoder
+    /// * ShadowPrecisionError - if the decryption of a ciphertext is too far from its shadow
+    pub fn new(
+        ciphertexts: crate::VectorLWE,
+        shadows: &[f64],
+        secret_key: Option<Arc<crate::LWESecretKey>>,
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        if shadows.len() != ciphertexts.nb_ciphertexts {
+            return Err(NbCTError!(shadows.len(), ciphertexts.nb_ciphertexts));
+        }
+        DebugVectorLWE::checked(ciphertexts, shadows.to_vec(), secret_key, "new")
+    }
+
+    /// Encode messages, encrypt them and use them as the shadows, the ciphertexts carrying a copy of
+    /// the key
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `messages` - a list of messages as f64
+    /// * `encoder` - an Encoder
+    ///
+    /// # Output
+    /// * a DebugVectorLWE
+    pub fn encode_encrypt(
+        sk: &crate::LWESecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        let ciphertexts = crate::VectorLWE::encode_encrypt(sk, messages, encoder)?;
+        DebugVectorLWE::checked(
+            ciphertexts,
+            messages.to_vec(),
+            Some(Arc::new(sk.clone())),
+            "encode_encrypt",
+        )
+    }
+
+    /// Attach the secret key of the ciphertexts, e.g. the output key of a key switch or a bootstrap,
+    /// and check the decryptions against the shadows (see `DebugLWE::with_secret_key`)
+    pub fn with_secret_key(
+        self,
+        secret_key: &Arc<crate::LWESecretKey>,
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts,
+            self.shadows,
+            Some(secret_key.clone()),
+            "with_secret_key",
+        )
+    }
+
+    /// Decrypt the ciphertexts (see `VectorLWE::decrypt_decode`)
+    pub fn decrypt_decode(&self, sk: &crate::LWESecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        self.ciphertexts.decrypt_decode(sk)
+    }
+
+    /// Decrypt the ciphertexts in a rounding context (see `VectorLWE::decrypt_decode_round`)
+    pub fn decrypt_decode_round(
+        &self,
+        sk: &crate::LWESecretKey,
+    ) -> Result<Vec<f64>, CryptoAPIError> {
+        self.ciphertexts.decrypt_decode_round(sk)
+    }
+
+    /// Check the shadows of the ciphertexts against their encoders and their decryptions
+    ///
+    /// # Argument
+    /// * `operation` - the name of the last operation, used in the errors
+    ///
+    /// # Output
+    /// * ShadowIntervalError - if a shadow is not in the interval of its encoder
+    /// * ShadowPrecisionError - if the decryption of a ciphertext is too far from its shadow
+    pub fn check(&self, operation: &str) -> Result<(), CryptoAPIError> {
+        check_shadows(
+            operation,
+            true,
+            self.secret_key.as_deref(),
+            &self.shadows,
+            &self.ciphertexts.encoders,
+            |sk| self.ciphertexts.decrypt_decode(sk),
+        )
+    }
+
+    /// Return a list containing only the n-th ciphertext (see `VectorLWE::extract_nth`)
+    pub fn extract_nth(&self, n: usize) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts.extract_nth(n)?,
+            vec![self.shadows[n]],
+            self.secret_key.clone(),
+            "extract_nth",
+        )
+    }
+
+    /// Add small messages without changing the encoders (see `VectorLWE::add_constant_static_encoder`)
+    pub fn add_constant_static_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts.add_constant_static_encoder(messages)?,
+            self.map_shadows(messages, |s, m| s + m),
+            self.secret_key.clone(),
+            "add_constant_static_encoder",
+        )
+    }
+
+    /// Add messages by changing the encoders (see `VectorLWE::add_constant_dynamic_encoder`)
+    pub fn add_constant_dynamic_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts.add_constant_dynamic_encoder(messages)?,
+            self.map_shadows(messages, |s, m| s + m),
+            self.secret_key.clone(),
+            "add_constant_dynamic_encoder",
+        )
+    }
+
+    /// Add two lists of ciphertexts with centered intervals (see `VectorLWE::add_centered`)
+    pub fn add_centered(&self, ct: &DebugVectorLWE) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts.add_centered(&ct.ciphertexts)?,
+            self.map_shadows(&ct.shadows, |s, t| s + t),
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "add_centered",
+        )
+    }
+
+    /// Add two lists of ciphertexts by eating one bit of padding (see `VectorLWE::add_with_padding`)
+    pub fn add_with_padding(&self, ct: &DebugVectorLWE) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts.add_with_padding(&ct.ciphertexts)?,
+            self.map_shadows(&ct.shadows, |s, t| s + t),
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "add_with_padding",
+        )
+    }
+
+    /// Subtract two lists of ciphertexts by eating one bit of padding (see `VectorLWE::sub_with_padding`)
+    pub fn sub_with_padding(&self, ct: &DebugVectorLWE) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts.sub_with_padding(&ct.ciphertexts)?,
+            self.map_shadows(&ct.shadows, |s, t| s - t),
+            common_secret_key(&self.secret_key, &ct.secret_key),
+            "sub_with_padding",
+        )
+    }
+
+    /// Multiply by small integers without changing the encoders
+    /// (see `VectorLWE::mul_constant_static_encoder`)
+    pub fn mul_constant_static_encoder(
+        &self,
+        messages: &[i32],
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        let constants: Vec<f64> = messages.iter().map(|m| *m as f64).collect();
+        DebugVectorLWE::checked(
+            self.ciphertexts.mul_constant_static_encoder(messages)?,
+            self.map_shadows(&constants, |s, m| s * m),
+            self.secret_key.clone(),
+            "mul_constant_static_encoder",
+        )
+    }
+
+    /// Compute the opposite of the n-th ciphertext (see `VectorLWE::opposite_nth`)
+    pub fn opposite_nth(&self, n: usize) -> Result<DebugVectorLWE, CryptoAPIError> {
+        let ciphertexts = self.ciphertexts.opposite_nth(n)?;
+        let mut shadows = self.shadows.clone();
+        shadows[n] = -shadows[n];
+        DebugVectorLWE::checked(
+            ciphertexts,
+            shadows,
+            self.secret_key.clone(),
+            "opposite_nth",
+        )
+    }
+
+    /// Compute a key switch of every ciphertext (see `VectorLWE::keyswitch`)
+    pub fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::checked(
+            self.ciphertexts.keyswitch(ksk)?,
+            self.shadows.clone(),
+            None,
+            "keyswitch",
+        )
+    }
+
+    /// Compute a bootstrap of the n-th ciphertext applying a function, the shadow being the image
+    /// of the input shadow (see `VectorLWE::bootstrap_nth_with_function`)
+    pub fn bootstrap_nth_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        let ciphertexts =
+            self.ciphertexts
+                .bootstrap_nth_with_function(bsk, &f, encoder_output, n)?;
+        DebugVectorLWE::checked(
+            ciphertexts,
+            vec![f(self.shadows[n])],
+            None,
+            "bootstrap_nth_with_function",
+        )
+    }
+
+    /// Combine the shadows with a list of values
+    fn map_shadows<G: Fn(f64, f64) -> f64>(&self, values: &[f64], g: G) -> Vec<f64> {
+        self.shadows
+            .iter()
+            .zip(values.iter())
+            .map(|(s, v)| g(*s, *v))
+            .collect()
+    }
+
+    /// Build the output of an operation and check it
+    fn checked(
+        ciphertexts: crate::VectorLWE,
+        shadows: Vec<f64>,
+        secret_key: Option<Arc<crate::LWESecretKey>>,
+        operation: &str,
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        let res = DebugVectorLWE {
+            ciphertexts,
+            shadows,
+            secret_key,
+        };
+        res.check(operation)?;
+        Ok(res)
+    }
+}
+
+impl VectorLWEOperations for DebugVectorLWE {
+    type SecretKey = crate::LWESecretKey;
+    type BootstrapKey = crate::LWEBSK;
+    type KeySwitchKey = crate::LWEKSK;
+
+    fn encode_encrypt(
+        sk: &crate::LWESecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::encode_encrypt(sk, messages, encoder)
+    }
+    fn decrypt_decode(&self, sk: &crate::LWESecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        DebugVectorLWE::decrypt_decode(self, sk)
+    }
+    fn decrypt_decode_round(&self, sk: &crate::LWESecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        DebugVectorLWE::decrypt_decode_round(self, sk)
+    }
+    fn get_encoders(&self) -> &[crate::Encoder] {
+        &self.ciphertexts.encoders
+    }
+    fn get_variances(&self) -> &[f64] {
+        &self.ciphertexts.variances
+    }
+    fn extract_nth(&self, n: usize) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::extract_nth(self, n)
+    }
+    fn add_constant_static_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::add_constant_static_encoder(self, messages)
+    }
+    fn add_constant_dynamic_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::add_constant_dynamic_encoder(self, messages)
+    }
+    fn add_centered(&self, ct: &DebugVectorLWE) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::add_centered(self, ct)
+    }
+    fn add_with_padding(&self, ct: &DebugVectorLWE) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::add_with_padding(self, ct)
+    }
+    fn sub_with_padding(&self, ct: &DebugVectorLWE) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::sub_with_padding(self, ct)
+    }
+    fn mul_constant_static_encoder(
+        &self,
+        messages: &[i32],
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::mul_constant_static_encoder(self, messages)
+    }
+    fn opposite_nth(&self, n: usize) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::opposite_nth(self, n)
+    }
+    fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::keyswitch(self, ksk)
+    }
+    fn bootstrap_nth_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<DebugVectorLWE, CryptoAPIError> {
+        DebugVectorLWE::bootstrap_nth_with_function(self, bsk, f, encoder_output, n)
+    }
+}
+
+/// Return the secret key of the output of an operation between two ciphertexts, i.e. the key of
+/// the first one if it is known, and the key of the second one otherwise
+fn common_secret_key(
+    secret_key_1: &Option<Arc<crate::LWESecretKey>>,
+    secret_key_2: &Option<Arc<crate::LWESecretKey>>,
+) -> Option<Arc<crate::LWESecretKey>> {
+    secret_key_1.clone().or_else(|| secret_key_2.clone())
+}
+
+/// Check shadows against their encoders and, if the secret key of the ciphertexts is known,
+/// against the decryptions of the ciphertexts
+///
+/// # Arguments
+/// * `operation` - the name of the last operation
+/// * `indexed` - true if the index of the ciphertext has to appear in the errors
+/// * `secret_key` - the secret key of the ciphertexts, if it is known
+/// * `shadows` - the expected messages
+/// * `encoders` - the encoders of the ciphertexts
+/// * `decrypt` - a function decrypting the ciphertexts with a secret key
+fn check_shadows<D: Fn(&crate::LWESecretKey) -> Result<Vec<f64>, CryptoAPIError>>(
+    operation: &str,
+    indexed: bool,
+    secret_key: Option<&crate::LWESecretKey>,
+    shadows: &[f64],
+    encoders: &[crate::Encoder],
+    decrypt: D,
+) -> Result<(), CryptoAPIError> {
+    let name = |n: usize| {
+        if indexed {
+            format!("{} (ciphertext {})", operation, n)
+        } else {
+            operation.to_string()
+        }
+    };
+
+    // the messages have to be in the intervals of the encoders
+    for (n, (shadow, encoder)) in shadows.iter().zip(encoders.iter()).enumerate() {
+        if *shadow < encoder.o || *shadow >= encoder.o + encoder.delta {
+            return Err(ShadowIntervalError!(
+                name(n),
+                *shadow,
+                encoder.o,
+                encoder.o + encoder.delta
+            ));
+        }
+    }
+
+    // the decryptions have to be close to the messages
+    if let Some(sk) = secret_key {
+        for (n, (d, (shadow, encoder))) in decrypt(sk)?
+            .iter()
+            .zip(shadows.iter().zip(encoders.iter()))
+            .enumerate()
+        {
+            if (d - shadow).abs() > encoder.get_granularity() {
+                return Err(ShadowPrecisionError!(
+                    name(n),
+                    *shadow,
+                    *d,
+                    encoder.get_granularity()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+#[test]
+fn test_debug_correct_pipeline() {
+    let sk = crate::LWESecretKey::new(&crate::LWE128_630);
+    let encoder = crate::Encoder::new(-10., 10., 6, 3).unwrap();
+    let (m_1, m_2) = (random_message!(-5., 5.), random_message!(-5., 5.));
+    let ct_1 = DebugLWE::encode_encrypt(&sk, m_1, &encoder).unwrap();
+    let ct_2 = DebugLWE::encode_encrypt(&sk, m_2, &encoder).unwrap();
+
+    // every operation keeps the message
+    let res = ct_1
+        .add_with_padding(&ct_2)
+        .unwrap()
+        .mul_constant_static_encoder(-2)
+        .unwrap()
+        .add_constant_dynamic_encoder(3.)
+        .unwrap()
+        .opposite()
+        .unwrap();
+    assert_eq!(res.shadow, -(-2. * (m_1 + m_2) + 3.));
+    assert_with_granularity!(
+        [res.shadow],
+        [res.decrypt_decode(&sk).unwrap()],
+        [res.ciphertext.encoder]
+    );
+
+    // same with the operators
+    let res = &(&ct_1 - &ct_2) * 2;
+    assert_eq!(res.shadow, 2. * (m_1 - m_2));
+    assert!(res.secret_key.is_some());
+}
+
+#[test]
+fn test_debug_interval_error() {
+    let sk = crate::LWESecretKey::new(&crate::LWE128_630);
+    let encoder = crate::Encoder::new(0., 10., 4, 2).unwrap();
+    let ct = DebugLWE::encode_encrypt(&sk, 9., &encoder).unwrap();
+
+    // the message leaves the interval without changing the encoder
+    match ct.add_constant_static_encoder(5.) {
+        Err(CryptoAPIError::ShadowIntervalError {
+            operation,
+            expected,
+            min,
+            max,
+            ..
+        }) => {
+            assert_eq!(operation, "add_constant_static_encoder");
+            assert_eq!(expected, 14.);
+            assert_eq!((min, max), (encoder.o, encoder.o + encoder.delta));
+        }
+        res => panic!("expected a ShadowIntervalError, got {:?}", res),
+    }
+
+    // the intervals are checked even without keys
+    let ct = DebugLWE::new(ct.ciphertext, 9., None).unwrap();
+    assert!(ct.mul_constant_static_encoder(2).is_err());
+    assert!(ct.add_constant_dynamic_encoder(5.).is_ok());
+}
+
+#[test]
+fn test_debug_precision_error() {
+    let sk = Arc::new(crate::LWESecretKey::new(&crate::LWE128_630));
+    let encoder = crate::Encoder::new(0., 10., 4, 2).unwrap();
+    let ct = crate::LWE::encode_encrypt(&sk, 3., &encoder).unwrap();
+
+    // a wrong shadow is only detected with the key
+    let debug_ct = DebugLWE::new(ct.clone(), 5., None).unwrap();
+    assert!(debug_ct.add_constant_static_encoder(1.).is_ok());
+    let debug_ct = DebugLWE {
+        secret_key: Some(sk.clone()),
+        ..debug_ct
+    };
+    match debug_ct.add_constant_static_encoder(1.) {
+        Err(CryptoAPIError::ShadowPrecisionError {
+            operation,
+            expected,
+            decrypted,
+            ..
+        }) => {
+            assert_eq!(operation, "add_constant_static_encoder");
+            assert_eq!(expected, 6.);
+            assert!((decrypted - 4.).abs() < 1.);
+        }
+        res => panic!("expected a ShadowPrecisionError, got {:?}", res),
+    }
+    assert!(DebugLWE::new(ct.clone(), 5., Some(sk.clone())).is_err());
+    assert!(DebugLWE::new(ct, 5., None)
+        .unwrap()
+        .with_secret_key(&sk)
+        .is_err());
+}
+
+#[test]
+fn test_debug_keys_of_same_dimension() {
+    let sk_1 = crate::LWESecretKey::new(&crate::LWE128_630);
+    let sk_2 = crate::LWESecretKey::new(&crate::LWE128_630);
+    let encoder = crate::Encoder::new(0., 10., 4, 2).unwrap();
+
+    // each ciphertext is decrypted with its own key
+    let ct_1 = DebugLWE::encode_encrypt(&sk_1, 3., &encoder).unwrap();
+    let ct_2 = DebugLWE::encode_encrypt(&sk_2, 7., &encoder).unwrap();
+    assert!(ct_1.add_constant_static_encoder(1.).is_ok());
+    assert!(ct_2.add_constant_static_encoder(1.).is_ok());
+
+    // including in other threads
+    let handle = std::thread::spawn(move || {
+        let wrong = DebugLWE { shadow: 5., ..ct_2 };
+        wrong.add_constant_static_encoder(1.).is_err()
+    });
+    assert!(handle.join().unwrap());
+}
+
+#[test]
+fn test_debug_vector_bootstrap() {
+    let rlwe_sk = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let sk_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let sk_output = Arc::new(rlwe_sk.to_lwe_secret_key());
+    let bsk = crate::LWEBSK::new(&sk_input, &rlwe_sk, 5, 3);
+
+    let encoder = crate::Encoder::new_rounding_context(0., 7., 3, 2).unwrap();
+    let encoder_output = crate::Encoder::new_rounding_context(0., 7., 3, 1).unwrap();
+    let cts = DebugVectorLWE::encode_encrypt(&sk_input, &[1., 2., 3.], &encoder).unwrap();
+
+    // the shadow follows the function, and the output key can be attached to check the decryption
+    let res = cts
+        .bootstrap_nth_with_function(&bsk, |x| (x * x) % 8., &encoder_output, 2)
+        .unwrap()
+        .with_secret_key(&sk_output)
+        .unwrap();
+    assert_eq!(res.shadows, vec![1.]);
+    assert_eq!(res.decrypt_decode_round(&sk_output).unwrap(), vec![1.]);
+
+    // the index of the failing ciphertext is reported
+    match cts.add_constant_static_encoder(&[0., 0., 6.]) {
+        Err(CryptoAPIError::ShadowIntervalError { operation, .. }) => {
+            assert_eq!(operation, "add_constant_static_encoder (ciphertext 2)")
+        }
+        res => panic!("expected a ShadowIntervalError, got {:?}", res),
+    }
+}
@@ -9,13 +9,13 @@ use serde::{Deserialize, Serialize};
 use crate::error::CryptoAPIError;
 use crate::Torus;
 use concrete_core::crypto;
-use concrete_core::math::decomposition::SignedDecomposable;
+use concrete_core::math::decomposition::SignedDecomposer;
 use concrete_npe as npe;
 
 use super::{read_from_file, write_to_file};
 use crate::plaintext::Plaintext;
-use concrete_commons::Numeric;
-use concrete_core::math::decomposition::{DecompositionBaseLog, DecompositionLevelCount};
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::{DecompositionBaseLog, DecompositionLevelCount};
 
 /// Structure describing one particular Encoding
 /// # Attributes
@@ -23,6 +23,9 @@ use concrete_core::math::decomposition::{DecompositionBaseLog, DecompositionLeve
 /// * `delta` - the delta of the encoding
 /// * `nb_bit_precision` - the minimum number of bits to represent a plaintext
 /// * `nb_bit_padding` - the number of bits set to zero in the MSB
+/// * `failure_probability` - the largest probability that the noise exceeds the precision kept by
+///   `update_precision_from_variance`, None for a noise bounded by 3 standard deviations (about
+///   99.7%)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Encoder {
     pub o: f64,     // with margin between 1 and 0
@@ -30,6 +33,8 @@ pub struct Encoder {
     pub nb_bit_precision: usize,
     pub nb_bit_padding: usize,
     pub round: bool,
+    #[serde(default)]
+    pub failure_probability: Option<f64>,
 }
 
 impl Encoder {
@@ -77,6 +82,7 @@ impl Encoder {
             nb_bit_precision,
             nb_bit_padding,
             round: false,
+            failure_probability: None,
         })
     }
 
@@ -125,10 +131,12 @@ impl Encoder {
             nb_bit_precision,
             nb_bit_padding,
             round: true,
+            failure_probability: None,
         })
     }
 
-    /// After an homomorphic operation, update an encoder using the variance
+    /// After an homomorphic operation, update an encoder using the variance, following the
+    /// failure probability of the encoder
     /// # Arguments
     /// * `variance` - variance
     /// # Output
@@ -147,14 +155,71 @@ impl Encoder {
     /// let mut encoder = Encoder::new(min, max, nb_bit_precision, nb_bit_padding).unwrap();
     /// let variance: f64 = f64::powi(2., -30);
     /// let nb_bit_overlap: usize = encoder.update_precision_from_variance(variance).unwrap();
+    ///
+    /// // a circuit of 2^20 operations failing with a probability of 2^-20
+    /// let mut encoder = Encoder::new(min, max, nb_bit_precision, nb_bit_padding).unwrap();
+    /// encoder.failure_probability = Some(concrete_npe::failure_probability_per_operation(
+    ///     f64::powi(2., -20),
+    ///     1 << 20,
+    /// ));
+    /// let nb_bit_overlap: usize = encoder.update_precision_from_variance(variance).unwrap();
     /// ```
     pub fn update_precision_from_variance(
         &mut self,
         variance: f64,
     ) -> Result<usize, CryptoAPIError> {
-        // check output noise
-        let nb_noise_bit: usize = npe::nb_bit_from_variance_99(variance, <Torus as Numeric>::BITS);
+        match self.failure_probability {
+            Some(failure_probability) => self
+                .update_precision_from_variance_with_failure_probability(
+                    variance,
+                    failure_probability,
+                ),
+            None => self.update_precision_from_nb_noise_bit(
+                npe::nb_bit_from_variance_99(variance, <Torus as Numeric>::BITS),
+                variance,
+            ),
+        }
+    }
 
+    /// After an homomorphic operation, update an encoder using the variance, such that the noise
+    /// exceeds the remaining precision with a probability smaller than `failure_probability`
+    /// # Arguments
+    /// * `variance` - variance
+    /// * `failure_probability` - the largest probability that the noise exceeds the precision
+    /// # Output
+    /// * return the number of bits of precision affected by the noise
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// let mut encoder = Encoder::new(0., 1., 8, 4).unwrap();
+    /// let variance: f64 = f64::powf(2., -28.4);
+    ///
+    /// // the 99% rule keeps the 8 bits, not a failure probability of 2^-40
+    /// assert_eq!(encoder.clone().update_precision_from_variance(variance).unwrap(), 0);
+    /// let nb_bit_overlap = encoder
+    ///     .update_precision_from_variance_with_failure_probability(variance, f64::powi(2., -40))
+    ///     .unwrap();
+    /// assert_eq!(nb_bit_overlap, 1);
+    /// assert_eq!(encoder.nb_bit_precision, 7);
+    /// ```
+    pub fn update_precision_from_variance_with_failure_probability(
+        &mut self,
+        variance: f64,
+        failure_probability: f64,
+    ) -> Result<usize, CryptoAPIError> {
+        self.update_precision_from_nb_noise_bit(
+            npe::nb_bit_from_variance(variance, <Torus as Numeric>::BITS, failure_probability),
+            variance,
+        )
+    }
+
+    /// Update an encoder given the number of bits of the torus affected by the noise
+    fn update_precision_from_nb_noise_bit(
+        &mut self,
+        nb_noise_bit: usize,
+        variance: f64,
+    ) -> Result<usize, CryptoAPIError> {
         // check if there actually some noise in the ciphertext
         if nb_noise_bit == 0 {
             Err(NoNoiseInCiphertext!(variance))
@@ -294,6 +359,7 @@ impl Encoder {
             nb_bit_precision: 0,
             nb_bit_padding: 0,
             round: false,
+            failure_probability: None,
         }
     }
 
@@ -405,41 +471,133 @@ impl Encoder {
             return Err(NotEnoughPaddingError!(self.nb_bit_padding, 1));
         }
 
-        if self.o < 0. && self.o + self.delta < 0. {
+        let mut res = if self.o < 0. && self.o + self.delta < 0. {
             // only negative values in the interval
             let new_max = (self.o * self.o) / 4.;
             let old_max = self.o + self.delta - self.get_granularity();
             let new_min = (old_max * old_max) / 4.;
-            Ok(Encoder::new(
+            Encoder::new(
                 new_min,
                 new_max,
                 self.nb_bit_precision,
                 nb_bit_padding,
-            )?)
+            )?
         } else if self.o > 0. {
             // only positive values in the interval
             let new_min = (self.o * self.o) / 4.;
             let old_max = self.o + self.delta - self.get_granularity();
             let new_max = (old_max * old_max) / 4.;
-            Ok(Encoder::new(
+            Encoder::new(
                 new_min,
                 new_max,
                 self.nb_bit_precision,
                 nb_bit_padding,
-            )?)
+            )?
         } else {
             // 0 is in the interval
             let new_min: f64 = 0.;
             let old_max = self.o + self.delta - self.get_granularity();
             let max = old_max.max(-self.o);
             let new_max = max * max / 4.;
-            Ok(Encoder::new(
+            Encoder::new(
                 new_min,
                 new_max,
                 self.nb_bit_precision,
                 nb_bit_padding,
-            )?)
+            )?
+        };
+        res.failure_probability = self.failure_probability;
+        Ok(res)
+    }
+
+    /// Instantiate an encoder whose interval contains the intervals of several encoders,
+    /// with a granularity as small as the smallest of their granularities and at least two bits of padding.
+    /// Ciphertexts encoded with this encoder can be compared with a bootstrap of their difference.
+    /// If the encoders already share the same interval and at least two bits of padding,
+    /// their interval is kept with the largest precision.
+    ///
+    /// # Argument
+    /// * `encoders` - a slice of encoders
+    ///
+    /// # Output
+    /// * a new Encoder
+    /// * ZeroCiphertextsInStructureError - if the slice is empty
+    /// * InvalidEncoderError - if one of the encoders is not valid
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::Encoder;
+    ///
+    /// let encoder_1 = Encoder::new_rounding_context(0., 7., 3, 1).unwrap();
+    /// let encoder_2 = Encoder::new_rounding_context(4., 11., 3, 1).unwrap();
+    ///
+    /// // [0, 16[ with a granularity of 1
+    /// let encoder = Encoder::new_covering(&[&encoder_1, &encoder_2]).unwrap();
+    /// assert_eq!(encoder.nb_bit_precision, 4);
+    /// ```
+    pub fn new_covering(encoders: &[&Encoder]) -> Result<Encoder, CryptoAPIError> {
+        let first = encoders
+            .first()
+            .ok_or_else(|| ZeroCiphertextsInStructureError!(0))?;
+        for encoder in encoders.iter() {
+            if !encoder.is_valid() {
+                return Err(InvalidEncoderError!(
+                    encoder.nb_bit_precision,
+                    encoder.delta
+                ));
+            }
         }
+
+        // same interval and enough padding: only the precision changes
+        if first.nb_bit_padding >= 2
+            && encoders.iter().all(|encoder| {
+                deltas_eq!(first.o, encoder.o)
+                    && deltas_eq!(first.delta, encoder.delta)
+                    && first.nb_bit_padding == encoder.nb_bit_padding
+            })
+        {
+            let mut res = (*first).clone();
+            res.nb_bit_precision = encoders
+                .iter()
+                .map(|encoder| encoder.nb_bit_precision)
+                .max()
+                .unwrap_or(first.nb_bit_precision);
+            res.round = encoders.iter().all(|encoder| encoder.round);
+            res.failure_probability = Encoder::smallest_failure_probability(encoders);
+            return Ok(res);
+        }
+
+        // smallest interval with the smallest granularity
+        let min = encoders.iter().map(|e| e.o).fold(f64::INFINITY, f64::min);
+        let max = encoders
+            .iter()
+            .map(|e| e.o + e.delta)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let granularity = encoders
+            .iter()
+            .map(|e| e.get_granularity())
+            .fold(f64::INFINITY, f64::min);
+        let nb_bit_precision = usize::max(
+            f64::log2((max - min) / granularity - 1e-9).ceil() as usize,
+            1,
+        );
+
+        Ok(Encoder {
+            o: min,
+            delta: granularity * f64::powi(2., nb_bit_precision as i32),
+            nb_bit_precision,
+            nb_bit_padding: 2,
+            round: encoders.iter().all(|encoder| encoder.round),
+            failure_probability: Encoder::smallest_failure_probability(encoders),
+        })
+    }
+
+    /// Return the smallest failure probability of several encoders, None if none of them has one
+    fn smallest_failure_probability(encoders: &[&Encoder]) -> Option<f64> {
+        encoders
+            .iter()
+            .filter_map(|encoder| encoder.failure_probability)
+            .fold(None, |res: Option<f64>, p| Some(res.map_or(p, |q| f64::min(p, q))))
     }
 
     /// Wrap the core_api encode function with the padding
@@ -506,10 +664,11 @@ impl Encoder {
 
         // round if in rounding context
         if self.round {
-            res = res.round_to_closest_multiple(
+            res = SignedDecomposer::new(
                 DecompositionBaseLog(self.nb_bit_precision),
                 DecompositionLevelCount(1),
-            );
+            )
+            .closest_representable(res);
         }
 
         // shift if there is some padding
@@ -551,10 +710,11 @@ impl Encoder {
 
         // round if asked
         let mut tmp: Torus = if self.round {
-            pt.round_to_closest_multiple(
+            SignedDecomposer::new(
                 DecompositionBaseLog(self.nb_bit_precision + self.nb_bit_padding),
                 DecompositionLevelCount(1),
             )
+            .closest_representable(pt)
         } else {
             pt
         };
@@ -568,10 +728,11 @@ impl Encoder {
         let starting_value_security_margin: Torus = ((1 << (self.nb_bit_precision + 1)) - 1)
             << (<Torus as Numeric>::BITS - self.nb_bit_precision);
         tmp = if tmp > starting_value_security_margin {
-            tmp.round_to_closest_multiple(
+            SignedDecomposer::new(
                 DecompositionBaseLog(self.nb_bit_precision),
                 DecompositionLevelCount(1),
             )
+            .closest_representable(tmp)
         } else {
             tmp
         };
@@ -637,6 +798,7 @@ impl fmt::Display for Encoder {
             -> granularity = {}
             -> nb bit padding = {}
             -> round = {}
+            -> failure probability = {:?}
         }}
             ",
             self.o,
@@ -646,7 +808,8 @@ impl fmt::Display for Encoder {
             self.nb_bit_precision,
             self.get_granularity(),
             self.nb_bit_padding,
-            self.round
+            self.round,
+            self.failure_probability
         )
     }
 }
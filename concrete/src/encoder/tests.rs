@@ -261,3 +261,41 @@ fn margins_with_reals() {
         }
     }
 }
+
+#[test]
+fn test_update_precision_from_variance_failure_probability() {
+    let encoder = crate::Encoder::new(0., 1., 8, 4).unwrap();
+    let variance: f64 = f64::powf(2., -28.4);
+
+    // 99% rule by default
+    let mut encoder_99 = encoder.clone();
+    assert_eq!(
+        encoder_99.update_precision_from_variance(variance).unwrap(),
+        0
+    );
+    assert_eq!(encoder_99.nb_bit_precision, 8);
+
+    // a failure probability of 2^-40 costs one bit
+    let mut encoder_40 = encoder.clone();
+    encoder_40.failure_probability = Some(f64::powi(2., -40));
+    assert_eq!(
+        encoder_40.update_precision_from_variance(variance).unwrap(),
+        1
+    );
+    assert_eq!(encoder_40.nb_bit_precision, 7);
+
+    // the ciphertexts encrypted with it follow it
+    let mut encoder_fp = encoder.clone();
+    encoder_fp.failure_probability = Some(f64::powi(2., -40));
+    let sk = crate::LWESecretKey::new(&crate::LWEParams::new(630, -14));
+    let ct = crate::LWE::encode_encrypt(&sk, 0.5, &encoder_fp).unwrap();
+    let mut encoder_ct = encoder_fp.clone();
+    encoder_ct
+        .update_precision_from_variance_with_failure_probability(ct.variance, f64::powi(2., -40))
+        .unwrap();
+    assert_eq!(ct.encoder, encoder_ct);
+
+    // and so do the results of the operations on them
+    let ct_add = ct.add_with_padding(&ct).unwrap();
+    assert_eq!(ct_add.encoder.failure_probability, Some(f64::powi(2., -40)));
+}
@@ -106,6 +106,91 @@ pub enum CryptoAPIError {
         polynomial_size: usize,
         description: String,
     },
+    NotNegacyclicError {
+        index: usize,
+        description: String,
+    },
+    CircuitParseError {
+        line: usize,
+        description: String,
+    },
+    CarryOverflowError {
+        degree: usize,
+        max_degree: usize,
+        description: String,
+    },
+    ModulusMismatchError {
+        modulus_1: usize,
+        modulus_2: usize,
+        description: String,
+    },
+    UnsupportedModuliError {
+        message_modulus: usize,
+        carry_modulus: usize,
+        description: String,
+    },
+    InvalidModuliError {
+        modulus: usize,
+        description: String,
+    },
+    NoiseMarginError {
+        modulus: usize,
+        nb_std_dev: f64,
+        description: String,
+    },
+    LookUpTableSizeError {
+        nb_cells: usize,
+        polynomial_size: usize,
+        description: String,
+    },
+    BitExtractionError {
+        nb_bits: usize,
+        nb_bit_precision: usize,
+        description: String,
+    },
+    UnknownNodeError {
+        node: usize,
+        nb_nodes: usize,
+        description: String,
+    },
+    FailureProbabilityError {
+        probability: f64,
+        max_probability: f64,
+        description: String,
+    },
+    NbInputsError {
+        nb_inputs: usize,
+        expected: usize,
+        description: String,
+    },
+    ShadowPrecisionError {
+        operation: String,
+        expected: f64,
+        decrypted: f64,
+        granularity: f64,
+        description: String,
+    },
+    ShadowIntervalError {
+        operation: String,
+        expected: f64,
+        min: f64,
+        max: f64,
+        description: String,
+    },
+    OptimizerError {
+        nb_bit_precision: usize,
+        nb_bit_padding: usize,
+        failure_probability: f64,
+        security_level: usize,
+        description: String,
+    },
+    InsecureParametersError {
+        dimension: usize,
+        log2_std_dev: i32,
+        security_bits: f64,
+        min_security_bits: usize,
+        description: String,
+    },
 }
 impl fmt::Display for CryptoAPIError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -154,6 +239,50 @@ impl fmt::Display for CryptoAPIError {
                 writeln!(f, "\n{}", description)
             }
             CryptoAPIError::LweToRlweError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::NotNegacyclicError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::CircuitParseError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::CarryOverflowError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::ModulusMismatchError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::UnsupportedModuliError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::InvalidModuliError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::NoiseMarginError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::LookUpTableSizeError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::BitExtractionError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::UnknownNodeError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::FailureProbabilityError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::NbInputsError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::ShadowPrecisionError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::ShadowIntervalError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::OptimizerError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::InsecureParametersError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
         }
     }
 }
@@ -205,6 +334,50 @@ impl fmt::Debug for CryptoAPIError {
                 writeln!(f, "\n{}", description)
             }
             CryptoAPIError::LweToRlweError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::NotNegacyclicError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::CircuitParseError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::CarryOverflowError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::ModulusMismatchError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::UnsupportedModuliError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::InvalidModuliError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::NoiseMarginError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::LookUpTableSizeError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::BitExtractionError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::UnknownNodeError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::FailureProbabilityError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::NbInputsError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::ShadowPrecisionError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::ShadowIntervalError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
+            CryptoAPIError::OptimizerError { description, .. } => writeln!(f, "\n{}", description),
+            CryptoAPIError::InsecureParametersError { description, .. } => {
+                writeln!(f, "\n{}", description)
+            }
         }
     }
 }
@@ -234,6 +407,22 @@ impl Error for CryptoAPIError {
             CryptoAPIError::WrongSizeError { description, .. } => description,
             CryptoAPIError::NotEnoughValidEncoderError { description, .. } => description,
             CryptoAPIError::LweToRlweError { description, .. } => description,
+            CryptoAPIError::NotNegacyclicError { description, .. } => description,
+            CryptoAPIError::CircuitParseError { description, .. } => description,
+            CryptoAPIError::CarryOverflowError { description, .. } => description,
+            CryptoAPIError::ModulusMismatchError { description, .. } => description,
+            CryptoAPIError::UnsupportedModuliError { description, .. } => description,
+            CryptoAPIError::InvalidModuliError { description, .. } => description,
+            CryptoAPIError::NoiseMarginError { description, .. } => description,
+            CryptoAPIError::LookUpTableSizeError { description, .. } => description,
+            CryptoAPIError::BitExtractionError { description, .. } => description,
+            CryptoAPIError::UnknownNodeError { description, .. } => description,
+            CryptoAPIError::FailureProbabilityError { description, .. } => description,
+            CryptoAPIError::NbInputsError { description, .. } => description,
+            CryptoAPIError::ShadowPrecisionError { description, .. } => description,
+            CryptoAPIError::ShadowIntervalError { description, .. } => description,
+            CryptoAPIError::OptimizerError { description, .. } => description,
+            CryptoAPIError::InsecureParametersError { description, .. } => description,
         }
     }
 }
@@ -606,3 +795,289 @@ macro_rules! LweToRlweError {
         }
     };
 }
+
+#[macro_export]
+macro_rules! NotNegacyclicError {
+    ($index: expr) => {
+        CryptoAPIError::NotNegacyclicError {
+            index: $index,
+            description: format!(
+                "{}: f(x + delta / 2) != -f(x) for the message of index {} in the input interval\n{:#?}\n",
+                "The function is not negacyclic".red().bold(),
+                $index,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! CircuitParseError {
+    ($line: expr, $reason: expr) => {
+        CryptoAPIError::CircuitParseError {
+            line: $line,
+            description: format!(
+                "{} at line {}: {}\n{:#?}\n",
+                "Invalid Bristol circuit".red().bold(),
+                $line,
+                $reason,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! CarryOverflowError {
+    ($degree: expr, $max_degree: expr) => {
+        CryptoAPIError::CarryOverflowError {
+            degree: $degree,
+            max_degree: $max_degree,
+            description: format!(
+                "{}: the result would have a degree of {} but the maximum is {}, consider clearing the carries first\n{:#?}\n",
+                "Not enough carry space".red().bold(),
+                $degree,
+                $max_degree,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! ModulusMismatchError {
+    ($modulus_1: expr, $modulus_2: expr) => {
+        CryptoAPIError::ModulusMismatchError {
+            modulus_1: $modulus_1,
+            modulus_2: $modulus_2,
+            description: format!(
+                "{}: {} != {}\n{:#?}\n",
+                "The moduli are different".red().bold(),
+                $modulus_1,
+                $modulus_2,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! UnsupportedModuliError {
+    ($message_modulus: expr, $carry_modulus: expr) => {
+        CryptoAPIError::UnsupportedModuliError {
+            message_modulus: $message_modulus,
+            carry_modulus: $carry_modulus,
+            description: format!(
+                "{}: message_modulus = {}, carry_modulus = {}, the radix integers need a message_modulus of 4 or 16 and a carry_modulus at least as large\n{:#?}\n",
+                "Unsupported moduli".red().bold(),
+                $message_modulus,
+                $carry_modulus,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! InvalidModuliError {
+    ($modulus: expr, $reason: expr) => {
+        CryptoAPIError::InvalidModuliError {
+            modulus: $modulus,
+            description: format!(
+                "{}: the modulus {} {}\n{:#?}\n",
+                "Invalid CRT moduli".red().bold(),
+                $modulus,
+                $reason,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! NoiseMarginError {
+    ($modulus: expr, $nb_std_dev: expr) => {
+        CryptoAPIError::NoiseMarginError {
+            modulus: $modulus,
+            nb_std_dev: $nb_std_dev,
+            description: format!(
+                "{}: only {} standard deviations of margin for the modulus {}, consider parameters with less noise\n{:#?}\n",
+                "Not enough noise margin".red().bold(),
+                $nb_std_dev,
+                $modulus,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! LookUpTableSizeError {
+    ($nb_cells: expr, $polynomial_size: expr) => {
+        CryptoAPIError::LookUpTableSizeError {
+            nb_cells: $nb_cells,
+            polynomial_size: $polynomial_size,
+            description: format!(
+                "{}: {} cells in the half torus but only {} coefficients, consider a larger polynomial size\n{:#?}\n",
+                "Look up table too large".red().bold(),
+                $nb_cells,
+                $polynomial_size,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! BitExtractionError {
+    ($nb_bits: expr, $nb_bit_precision: expr) => {
+        CryptoAPIError::BitExtractionError {
+            nb_bits: $nb_bits,
+            nb_bit_precision: $nb_bit_precision,
+            description: format!(
+                "{}: cannot extract {} bits from a ciphertext with {} bits of precision\n{:#?}\n",
+                "Bit extraction error".red().bold(),
+                $nb_bits,
+                $nb_bit_precision,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! UnknownNodeError {
+    ($node: expr, $nb_nodes: expr) => {
+        CryptoAPIError::UnknownNodeError {
+            node: $node,
+            nb_nodes: $nb_nodes,
+            description: format!(
+                "{}: the node {} does not belong to a graph of {} nodes\n{:#?}\n",
+                "Unknown node".red().bold(),
+                $node,
+                $nb_nodes,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! FailureProbabilityError {
+    ($probability: expr, $max_probability: expr) => {
+        CryptoAPIError::FailureProbabilityError {
+            probability: $probability,
+            max_probability: $max_probability,
+            description: format!(
+                "{}: {:e} > {:e} even after a bootstrap, consider parameters with less noise or less precision\n{:#?}\n",
+                "Failure probability too high".red().bold(),
+                $probability,
+                $max_probability,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! NbInputsError {
+    ($nb_inputs: expr, $expected: expr) => {
+        CryptoAPIError::NbInputsError {
+            nb_inputs: $nb_inputs,
+            expected: $expected,
+            description: format!(
+                "{}: {} ciphertexts provided for {} inputs\n{:#?}\n",
+                "Wrong number of inputs".red().bold(),
+                $nb_inputs,
+                $expected,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! ShadowPrecisionError {
+    ($operation: expr, $expected: expr, $decrypted: expr, $granularity: expr) => {{
+        let operation: String = $operation;
+        CryptoAPIError::ShadowPrecisionError {
+            description: format!(
+                "{}: after {}, {} was decrypted instead of {}, the granularity being {}\n{:#?}\n",
+                "Precision lost".red().bold(),
+                operation,
+                $decrypted,
+                $expected,
+                $granularity,
+                Backtrace::new()
+            ),
+            operation,
+            expected: $expected,
+            decrypted: $decrypted,
+            granularity: $granularity,
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! ShadowIntervalError {
+    ($operation: expr, $expected: expr, $min: expr, $max: expr) => {{
+        let operation: String = $operation;
+        CryptoAPIError::ShadowIntervalError {
+            description: format!(
+                "{}: after {}, the message {} is not in [{}, {}]\n{:#?}\n",
+                "Message outside of the interval".red().bold(),
+                operation,
+                $expected,
+                $min,
+                $max,
+                Backtrace::new()
+            ),
+            operation,
+            expected: $expected,
+            min: $min,
+            max: $max,
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! OptimizerError {
+    ($nb_bit_precision: expr, $nb_bit_padding: expr, $failure_probability: expr, $security_level: expr) => {
+        CryptoAPIError::OptimizerError {
+            nb_bit_precision: $nb_bit_precision,
+            nb_bit_padding: $nb_bit_padding,
+            failure_probability: $failure_probability,
+            security_level: $security_level,
+            description: format!(
+                "{}: no parameters with {} bits of security for {} bits of precision and {} bits of padding with a failure probability of {}\n{:#?}\n",
+                "Optimizer error".red().bold(),
+                $security_level,
+                $nb_bit_precision,
+                $nb_bit_padding,
+                $failure_probability,
+                Backtrace::new()
+            ),
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! InsecureParametersError {
+    ($dimension: expr, $log2_std_dev: expr, $security_bits: expr, $min_security_bits: expr) => {
+        CryptoAPIError::InsecureParametersError {
+            dimension: $dimension,
+            log2_std_dev: $log2_std_dev,
+            security_bits: $security_bits,
+            min_security_bits: $min_security_bits,
+            description: format!(
+                "{}: a dimension of {} with a standard deviation of 2^{} gives about {:.1} bits of security instead of {}\n{:#?}\n",
+                "Insecure parameters".red().bold(),
+                $dimension,
+                $log2_std_dev,
+                $security_bits,
+                $min_security_bits,
+                Backtrace::new()
+            ),
+        }
+    };
+}
@@ -0,0 +1,980 @@
+//! graph module: computation graphs on encrypted values with automatic bootstrap placement
+//!
+//! A `Graph` records operations on symbolic encrypted values: additions, additions and
+//! multiplications by constants, and univariate functions. `Graph::compile` propagates the
+//! encoders and the noise variances (with the NPE) through the graph and returns a `GraphPlan`,
+//! in which bootstraps and key switches are inserted only where they are needed:
+//! * every univariate function is evaluated with a bootstrap,
+//! * the operands of an addition are re-encoded with a bootstrap when their encoders differ,
+//! * an operand is refreshed with a bootstrap when it lacks padding for an operation, or when the
+//!   result would exceed the maximum failure probability,
+//! * a key switch is computed only when a bootstrapped value has to be bootstrapped again, added to
+//!   a value encrypted under the input key, or returned as an output.
+//!
+//! The plan can then be executed on real ciphertexts with `GraphPlan::execute`.
+//! The inputs and the outputs of a plan are encrypted under the input key of the bootstrapping
+//! key, the key switching key going from the output key of the bootstrap back to that key.
+
+#[cfg(test)]
+mod tests;
+
+use crate::error::CryptoAPIError;
+use crate::Torus;
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_core::crypto::encoding::Cleartext;
+use concrete_npe as npe;
+use std::fmt;
+use std::rc::Rc;
+
+/// Default maximum failure probability of each bootstrap and each output of a plan
+pub const GRAPH_MAX_FAILURE_PROBABILITY: f64 = 1e-9;
+
+/// Identifier of a node of a graph, returned by the methods recording the operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeId(pub usize);
+
+/// The operations recorded in a graph
+#[derive(Clone)]
+pub enum GraphNode {
+    /// the index-th input of the graph, with its encoder and the variance of its noise
+    Input {
+        index: usize,
+        encoder: crate::Encoder,
+        variance: f64,
+    },
+    /// addition of two nodes, the precision increasing by one bit
+    Add { left: NodeId, right: NodeId },
+    /// addition of a real constant
+    AddConstant { input: NodeId, constant: f64 },
+    /// multiplication by an integer constant, the interval being multiplied by the constant
+    MulConstant { input: NodeId, constant: i32 },
+    /// evaluation of a univariate function with a bootstrap
+    Function {
+        input: NodeId,
+        function: Rc<dyn Fn(f64) -> f64>,
+        encoder_output: crate::Encoder,
+    },
+}
+
+impl GraphNode {
+    /// Return the nodes used by this node
+    pub fn get_operands(&self) -> Vec<NodeId> {
+        match self {
+            GraphNode::Input { .. } => vec![],
+            GraphNode::Add { left, right } => vec![*left, *right],
+            GraphNode::AddConstant { input, .. }
+            | GraphNode::MulConstant { input, .. }
+            | GraphNode::Function { input, .. } => vec![*input],
+        }
+    }
+}
+
+/// Structure describing the keys a graph is compiled for
+/// # Attributes
+/// * `lwe_dimension` - the dimension of the inputs and the outputs of the graph, and of the inputs of the bootstraps
+/// * `rlwe_dimension` - the dimension of the RLWE secret key of the bootstrapping key
+/// * `polynomial_size` - the number of coefficients of the polynomials of the bootstrapping key
+/// * `bsk_base_log` - the log2 of the decomposition base of the bootstrapping key
+/// * `bsk_level` - the number of levels of the decomposition of the bootstrapping key
+/// * `bsk_variance` - the variance of the noise of the bootstrapping key
+/// * `ksk_base_log` - the log2 of the decomposition base of the key switching key
+/// * `ksk_level` - the number of levels of the decomposition of the key switching key
+/// * `ksk_variance` - the variance of the noise of the key switching key
+/// * `nb_bit_padding` - the number of bits of padding of the values refreshed or re-encoded by a bootstrap
+/// * `max_failure_probability` - the maximum failure probability of each bootstrap and each output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphParams {
+    pub lwe_dimension: usize,
+    pub rlwe_dimension: usize,
+    pub polynomial_size: usize,
+    pub bsk_base_log: usize,
+    pub bsk_level: usize,
+    pub bsk_variance: f64,
+    pub ksk_base_log: usize,
+    pub ksk_level: usize,
+    pub ksk_variance: f64,
+    pub nb_bit_padding: usize,
+    pub max_failure_probability: f64,
+}
+
+impl GraphParams {
+    /// Instantiate the parameters of a bootstrapping key and a key switching key,
+    /// with 2 bits of padding for the refreshed values and `GRAPH_MAX_FAILURE_PROBABILITY`
+    ///
+    /// # Arguments
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to its input key
+    ///
+    /// # Output
+    /// * a GraphParams
+    /// * DimensionError - if the keys have incompatible dimensions
+    pub fn new(bsk: &crate::LWEBSK, ksk: &crate::LWEKSK) -> Result<GraphParams, CryptoAPIError> {
+        if ksk.dimension_before != bsk.dimension * bsk.polynomial_size {
+            return Err(DimensionError!(
+                ksk.dimension_before,
+                bsk.dimension * bsk.polynomial_size
+            ));
+        }
+        if ksk.dimension_after != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(
+                ksk.dimension_after,
+                bsk.get_lwe_dimension()
+            ));
+        }
+        Ok(GraphParams {
+            lwe_dimension: bsk.get_lwe_dimension(),
+            rlwe_dimension: bsk.dimension,
+            polynomial_size: bsk.polynomial_size,
+            bsk_base_log: bsk.base_log,
+            bsk_level: bsk.level,
+            bsk_variance: bsk.variance,
+            ksk_base_log: ksk.base_log,
+            ksk_level: ksk.level,
+            ksk_variance: ksk.variance,
+            nb_bit_padding: 2,
+            max_failure_probability: GRAPH_MAX_FAILURE_PROBABILITY,
+        })
+    }
+
+    /// Return the variance of the output of a bootstrap
+    pub fn get_bootstrap_variance(&self) -> f64 {
+        <Torus as npe::Cross>::bootstrap(
+            self.lwe_dimension,
+            self.rlwe_dimension,
+            self.bsk_level,
+            self.bsk_base_log,
+            self.polynomial_size,
+            self.bsk_variance,
+        )
+    }
+
+    /// Return the variance of the output of a key switch
+    ///
+    /// # Argument
+    /// * `variance` - the variance of the input of the key switch
+    pub fn get_keyswitch_variance(&self, variance: f64) -> f64 {
+        <Torus as npe::LWE>::key_switch(
+            self.rlwe_dimension * self.polynomial_size,
+            self.ksk_level,
+            self.ksk_base_log,
+            self.ksk_variance,
+            variance,
+        )
+    }
+
+    /// Return the probability that a bootstrap outputs a wrong value, i.e. that the noise of
+    /// its input and the drift due to the rounding of the mask exceed half of the granularity
+    ///
+    /// # Arguments
+    /// * `encoder` - the encoder of the input of the bootstrap
+    /// * `variance` - the variance of the input of the bootstrap
+    pub fn get_bootstrap_failure_probability(
+        &self,
+        encoder: &crate::Encoder,
+        variance: f64,
+    ) -> f64 {
+        // the bootstrap removes the padding but one bit
+        let padding = i32::max(encoder.nb_bit_padding as i32 - 1, 0);
+        let two_n = (2 * self.polynomial_size) as f64;
+        let var = variance * f64::powi(4., padding)
+            + npe::cross::drift_index_lut(self.lwe_dimension) / (two_n * two_n);
+        let half_granularity = f64::powi(2., -(encoder.nb_bit_precision as i32 + 2));
+        npe::erfc(half_granularity / f64::sqrt(2. * var))
+    }
+}
+
+/// Return the probability that a decryption outputs a wrong value, i.e. that the noise
+/// exceeds half of the granularity
+///
+/// # Arguments
+/// * `encoder` - the encoder of the ciphertext
+/// * `variance` - the variance of the noise of the ciphertext
+pub fn get_decryption_failure_probability(encoder: &crate::Encoder, variance: f64) -> f64 {
+    let half_granularity = f64::powi(
+        2.,
+        -((encoder.nb_bit_padding + encoder.nb_bit_precision) as i32 + 1),
+    );
+    npe::erfc(half_granularity / f64::sqrt(2. * variance))
+}
+
+/// The operations of a plan, the operands being indexes of previous steps
+#[derive(Clone)]
+pub enum PlanOperation {
+    /// the index-th input of the plan
+    Input(usize),
+    /// addition with `LWE::add_with_padding_exact`
+    Add(usize, usize),
+    /// addition of a constant with `LWE::add_constant_dynamic_encoder`
+    AddConstant(usize, f64),
+    /// multiplication by an integer constant, see `GraphNode::MulConstant`
+    MulConstant(usize, i32),
+    /// bootstrap with `LWE::bootstrap_with_function`, the identity being used without function
+    Bootstrap {
+        input: usize,
+        function: Option<Rc<dyn Fn(f64) -> f64>>,
+        encoder_output: crate::Encoder,
+    },
+    /// key switch with `LWE::keyswitch`
+    KeySwitch(usize),
+}
+
+/// Structure describing a step of a plan and the ciphertext it outputs
+/// # Attributes
+/// * `operation` - the operation of the step
+/// * `encoder` - the expected encoder of the output
+/// * `variance` - the expected variance of the noise of the output
+/// * `in_input_key` - true if the output is encrypted under the input key of the bootstrap
+/// * `failure_probability` - the failure probability of the step, zero except for the bootstraps
+#[derive(Clone)]
+pub struct PlanStep {
+    pub operation: PlanOperation,
+    pub encoder: crate::Encoder,
+    pub variance: f64,
+    pub in_input_key: bool,
+    pub failure_probability: f64,
+}
+
+/// Structure describing the cost and the reliability of a plan
+/// # Attributes
+/// * `nb_bootstraps` - the total number of bootstraps
+/// * `nb_function_bootstraps` - the number of bootstraps evaluating a function of the graph
+/// * `nb_keyswitches` - the number of key switches
+/// * `failure_probability` - an upper bound of the probability that at least one bootstrap or one output is wrong
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphReport {
+    pub nb_bootstraps: usize,
+    pub nb_function_bootstraps: usize,
+    pub nb_keyswitches: usize,
+    pub failure_probability: f64,
+}
+
+impl fmt::Display for GraphReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "GraphReport {{\n         -> nb_bootstraps = {} ({} for the functions)\n         -> nb_keyswitches = {}\n         -> failure_probability = {:e}\n}}",
+            self.nb_bootstraps,
+            self.nb_function_bootstraps,
+            self.nb_keyswitches,
+            self.failure_probability
+        )
+    }
+}
+
+/// Structure containing a computation graph on encrypted values
+/// # Attributes
+/// * `nodes` - the nodes of the graph, each node only using previous nodes
+/// * `outputs` - the nodes returned by the graph
+/// * `nb_inputs` - the number of inputs of the graph
+#[derive(Clone, Default)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub outputs: Vec<NodeId>,
+    pub nb_inputs: usize,
+}
+
+impl Graph {
+    /// Instantiate an empty graph
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let encoder = Encoder::new_rounding_context(0., 3., 2, 3).unwrap();
+    ///
+    /// // record x * 2 + y
+    /// let mut graph = Graph::new();
+    /// let x = graph.input(&encoder, f64::powi(2., -50));
+    /// let y = graph.input(&encoder, f64::powi(2., -50));
+    /// let x_2 = graph.mul_constant(x, 2).unwrap();
+    /// let z = graph.add(x_2, y).unwrap();
+    /// graph.output(z).unwrap();
+    /// ```
+    pub fn new() -> Graph {
+        Graph {
+            nodes: Vec::new(),
+            outputs: Vec::new(),
+            nb_inputs: 0,
+        }
+    }
+
+    /// Add a new input to the graph, the inputs being numbered in the order of the calls
+    ///
+    /// # Arguments
+    /// * `encoder` - the encoder of the input ciphertexts
+    /// * `variance` - the variance of the noise of the input ciphertexts
+    ///
+    /// # Output
+    /// * the node of the input
+    pub fn input(&mut self, encoder: &crate::Encoder, variance: f64) -> NodeId {
+        self.nb_inputs += 1;
+        self.push(GraphNode::Input {
+            index: self.nb_inputs - 1,
+            encoder: encoder.clone(),
+            variance,
+        })
+    }
+
+    /// Add two nodes, the output interval being the sum of the input intervals
+    ///
+    /// # Arguments
+    /// * `left` - a node of the graph
+    /// * `right` - a node of the graph
+    ///
+    /// # Output
+    /// * the node of the sum
+    /// * UnknownNodeError - if a node does not belong to the graph
+    pub fn add(&mut self, left: NodeId, right: NodeId) -> Result<NodeId, CryptoAPIError> {
+        self.check_node(left)?;
+        self.check_node(right)?;
+        Ok(self.push(GraphNode::Add { left, right }))
+    }
+
+    /// Add a real constant to a node
+    ///
+    /// # Arguments
+    /// * `input` - a node of the graph
+    /// * `constant` - the constant
+    ///
+    /// # Output
+    /// * the node of the sum
+    /// * UnknownNodeError - if the node does not belong to the graph
+    pub fn add_constant(&mut self, input: NodeId, constant: f64) -> Result<NodeId, CryptoAPIError> {
+        self.check_node(input)?;
+        Ok(self.push(GraphNode::AddConstant { input, constant }))
+    }
+
+    /// Multiply a node by an integer constant, the output interval being the input interval
+    /// multiplied by the constant
+    ///
+    /// # Arguments
+    /// * `input` - a node of the graph
+    /// * `constant` - the constant
+    ///
+    /// # Output
+    /// * the node of the product
+    /// * UnknownNodeError - if the node does not belong to the graph
+    pub fn mul_constant(&mut self, input: NodeId, constant: i32) -> Result<NodeId, CryptoAPIError> {
+        self.check_node(input)?;
+        Ok(self.push(GraphNode::MulConstant { input, constant }))
+    }
+
+    /// Apply a univariate function to a node
+    ///
+    /// # Arguments
+    /// * `input` - a node of the graph
+    /// * `f` - the function
+    /// * `encoder_output` - the encoder of the output of the function
+    ///
+    /// # Output
+    /// * the node of the output of the function
+    /// * UnknownNodeError - if the node does not belong to the graph
+    pub fn function<F: Fn(f64) -> f64 + 'static>(
+        &mut self,
+        input: NodeId,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<NodeId, CryptoAPIError> {
+        self.check_node(input)?;
+        Ok(self.push(GraphNode::Function {
+            input,
+            function: Rc::new(f),
+            encoder_output: encoder_output.clone(),
+        }))
+    }
+
+    /// Mark a node as an output of the graph, the outputs being numbered in the order of the calls
+    ///
+    /// # Argument
+    /// * `node` - a node of the graph
+    ///
+    /// # Output
+    /// * UnknownNodeError - if the node does not belong to the graph
+    pub fn output(&mut self, node: NodeId) -> Result<(), CryptoAPIError> {
+        self.check_node(node)?;
+        self.outputs.push(node);
+        Ok(())
+    }
+
+    /// Compute the plan of the graph for some keys: the encoders and the variances are propagated
+    /// through the graph and the bootstraps and the key switches are inserted where they are needed
+    ///
+    /// # Argument
+    /// * `params` - the parameters of the keys
+    ///
+    /// # Output
+    /// * a GraphPlan
+    /// * NotEnoughPaddingError - if an input or the output of a function has no bit of padding
+    /// * FailureProbabilityError - if a bootstrap or an output exceeds the maximum failure probability
+    pub fn compile(&self, params: &GraphParams) -> Result<GraphPlan, CryptoAPIError> {
+        // the nodes whose value is the input of a function
+        let mut bootstrapped = vec![false; self.nodes.len()];
+        for node in self.nodes.iter() {
+            if let GraphNode::Function { input, .. } = node {
+                bootstrapped[input.0] = true;
+            }
+        }
+
+        let mut compiler = Compiler {
+            params: *params,
+            steps: Vec::new(),
+            states: Vec::with_capacity(self.nodes.len()),
+        };
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            let state = match node {
+                GraphNode::Input {
+                    index,
+                    encoder,
+                    variance,
+                } => {
+                    if encoder.nb_bit_padding == 0 {
+                        return Err(NotEnoughPaddingError!(encoder.nb_bit_padding, 1));
+                    }
+                    let step = compiler.push(
+                        PlanOperation::Input(*index),
+                        encoder.clone(),
+                        *variance,
+                        true,
+                    );
+                    NodeState::new(step, true)
+                }
+                GraphNode::AddConstant { input, constant } => {
+                    let input = compiler.states[input.0].clone();
+                    let mut encoder = compiler.steps[input.home].encoder.clone();
+                    encoder.o += constant;
+                    let step = compiler.push(
+                        PlanOperation::AddConstant(input.home, *constant),
+                        encoder,
+                        compiler.steps[input.home].variance,
+                        compiler.steps[input.home].in_input_key,
+                    );
+                    NodeState::new(step, input.is_small())
+                }
+                GraphNode::Add { .. } | GraphNode::MulConstant { .. } => {
+                    compiler.compile_linear(node, bootstrapped[id])?
+                }
+                GraphNode::Function {
+                    input,
+                    function,
+                    encoder_output,
+                } => {
+                    if encoder_output.nb_bit_padding == 0 {
+                        return Err(NotEnoughPaddingError!(encoder_output.nb_bit_padding, 1));
+                    }
+                    let mut state = compiler.states[input.0].clone();
+                    let output =
+                        compiler.bootstrap(&mut state, Some(function.clone()), encoder_output)?;
+                    compiler.states[input.0] = state;
+                    output
+                }
+            };
+            compiler.states.push(state);
+        }
+
+        // the outputs are key switched to the input key
+        let mut outputs: Vec<usize> = Vec::with_capacity(self.outputs.len());
+        let mut failure_probability: f64 = 0.;
+        for node in self.outputs.iter() {
+            let mut state = compiler.states[node.0].clone();
+            let step = compiler.keyswitch(&mut state)?;
+            compiler.states[node.0] = state;
+            let p = get_decryption_failure_probability(
+                &compiler.steps[step].encoder,
+                compiler.steps[step].variance,
+            );
+            if p > params.max_failure_probability {
+                return Err(FailureProbabilityError!(p, params.max_failure_probability));
+            }
+            failure_probability += p;
+            outputs.push(step);
+        }
+
+        // union bound over the bootstraps and the outputs
+        for step in compiler.steps.iter() {
+            failure_probability += step.failure_probability;
+        }
+
+        let nb_bootstraps = compiler
+            .steps
+            .iter()
+            .filter(|step| matches!(step.operation, PlanOperation::Bootstrap { .. }))
+            .count();
+        let report = GraphReport {
+            nb_bootstraps,
+            nb_function_bootstraps: self
+                .nodes
+                .iter()
+                .filter(|node| matches!(node, GraphNode::Function { .. }))
+                .count(),
+            nb_keyswitches: compiler
+                .steps
+                .iter()
+                .filter(|step| matches!(step.operation, PlanOperation::KeySwitch(_)))
+                .count(),
+            failure_probability: f64::min(failure_probability, 1.),
+        };
+
+        Ok(GraphPlan {
+            steps: compiler.steps,
+            outputs,
+            nb_inputs: self.nb_inputs,
+            params: *params,
+            report,
+        })
+    }
+
+    /// Add a node at the end of the graph
+    fn push(&mut self, node: GraphNode) -> NodeId {
+        self.nodes.push(node);
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Check that a node belongs to the graph
+    fn check_node(&self, node: NodeId) -> Result<(), CryptoAPIError> {
+        if node.0 >= self.nodes.len() {
+            return Err(UnknownNodeError!(node.0, self.nodes.len()));
+        }
+        Ok(())
+    }
+}
+
+/// Structure containing the steps computing a graph on ciphertexts
+/// # Attributes
+/// * `steps` - the steps of the plan, each step only using previous steps
+/// * `outputs` - the steps returned by the plan
+/// * `nb_inputs` - the number of input ciphertexts
+/// * `params` - the parameters of the keys the plan was compiled for
+/// * `report` - the cost and the failure probability of the plan
+#[derive(Clone)]
+pub struct GraphPlan {
+    pub steps: Vec<PlanStep>,
+    pub outputs: Vec<usize>,
+    pub nb_inputs: usize,
+    pub params: GraphParams,
+    pub report: GraphReport,
+}
+
+impl GraphPlan {
+    /// Execute the plan on ciphertexts
+    ///
+    /// # Arguments
+    /// * `inputs` - the input ciphertexts, encrypted under the input key of the bootstrap
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to its input key
+    ///
+    /// # Output
+    /// * the output ciphertexts, encrypted under the input key of the bootstrap
+    /// * NbInputsError - if the number of ciphertexts is not the number of inputs of the graph
+    /// * DimensionError - if an input ciphertext does not have the dimension of the plan
+    /// * DeltaError - if an input ciphertext does not have the interval of its encoder in the graph
+    /// * PaddingError - if an input ciphertext does not have the padding of its encoder in the graph
+    pub fn execute(
+        &self,
+        inputs: &[crate::LWE],
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<Vec<crate::LWE>, CryptoAPIError> {
+        if inputs.len() != self.nb_inputs {
+            return Err(NbInputsError!(inputs.len(), self.nb_inputs));
+        }
+
+        let mut values: Vec<crate::LWE> = Vec::with_capacity(self.steps.len());
+        for step in self.steps.iter() {
+            let value = match &step.operation {
+                PlanOperation::Input(index) => {
+                    let input = &inputs[*index];
+                    if input.dimension != self.params.lwe_dimension {
+                        return Err(DimensionError!(input.dimension, self.params.lwe_dimension));
+                    } else if !deltas_eq!(input.encoder.delta, step.encoder.delta) {
+                        return Err(DeltaError!(input.encoder.delta, step.encoder.delta));
+                    } else if input.encoder.nb_bit_padding != step.encoder.nb_bit_padding {
+                        return Err(PaddingError!(
+                            input.encoder.nb_bit_padding,
+                            step.encoder.nb_bit_padding
+                        ));
+                    }
+                    input.clone()
+                }
+                PlanOperation::Add(left, right) => {
+                    values[*left].add_with_padding_exact(&values[*right])?
+                }
+                PlanOperation::AddConstant(input, constant) => {
+                    values[*input].add_constant_dynamic_encoder(*constant)?
+                }
+                PlanOperation::MulConstant(input, constant) => {
+                    mul_constant_exact(&values[*input], *constant)?
+                }
+                PlanOperation::Bootstrap {
+                    input,
+                    function: Some(function),
+                    encoder_output,
+                } => {
+                    values[*input].bootstrap_with_function(bsk, |x| function(x), encoder_output)?
+                }
+                PlanOperation::Bootstrap {
+                    input,
+                    function: None,
+                    encoder_output,
+                } => values[*input].bootstrap_with_function(bsk, |x| x, encoder_output)?,
+                PlanOperation::KeySwitch(input) => values[*input].keyswitch(ksk)?,
+            };
+            values.push(value);
+        }
+
+        Ok(self
+            .outputs
+            .iter()
+            .map(|step| values[*step].clone())
+            .collect())
+    }
+
+    /// Return the cost and the failure probability of the plan
+    pub fn get_report(&self) -> GraphReport {
+        self.report.clone()
+    }
+}
+
+/// Where the value of a node is during the compilation
+/// # Attributes
+/// * `home` - the step computing the value
+/// * `small` - the step computing the value under the input key of the bootstrap, if any
+#[derive(Debug, Clone, PartialEq)]
+struct NodeState {
+    home: usize,
+    small: Option<usize>,
+}
+
+impl NodeState {
+    fn new(step: usize, in_input_key: bool) -> NodeState {
+        NodeState {
+            home: step,
+            small: if in_input_key { Some(step) } else { None },
+        }
+    }
+
+    fn is_small(&self) -> bool {
+        self.small == Some(self.home)
+    }
+}
+
+struct Compiler {
+    params: GraphParams,
+    steps: Vec<PlanStep>,
+    states: Vec<NodeState>,
+}
+
+impl Compiler {
+    /// Add a step to the plan and return its index
+    fn push(
+        &mut self,
+        operation: PlanOperation,
+        encoder: crate::Encoder,
+        variance: f64,
+        in_input_key: bool,
+    ) -> usize {
+        self.steps.push(PlanStep {
+            operation,
+            encoder,
+            variance,
+            in_input_key,
+            failure_probability: 0.,
+        });
+        self.steps.len() - 1
+    }
+
+    /// Return the step computing a value under the input key of the bootstrap,
+    /// adding a key switch if needed
+    fn keyswitch(&mut self, state: &mut NodeState) -> Result<usize, CryptoAPIError> {
+        if let Some(step) = state.small {
+            return Ok(step);
+        }
+        let variance = self
+            .params
+            .get_keyswitch_variance(self.steps[state.home].variance);
+        let mut encoder = self.steps[state.home].encoder.clone();
+        encoder.update_precision_from_variance(variance)?;
+        let step = self.push(
+            PlanOperation::KeySwitch(state.home),
+            encoder,
+            variance,
+            true,
+        );
+        state.small = Some(step);
+        Ok(step)
+    }
+
+    /// Add a bootstrap of a value and return the state of its output
+    fn bootstrap(
+        &mut self,
+        state: &mut NodeState,
+        function: Option<Rc<dyn Fn(f64) -> f64>>,
+        encoder_output: &crate::Encoder,
+    ) -> Result<NodeState, CryptoAPIError> {
+        let input = self.keyswitch(state)?;
+        let p = self.params.get_bootstrap_failure_probability(
+            &self.steps[input].encoder,
+            self.steps[input].variance,
+        );
+        if p > self.params.max_failure_probability {
+            return Err(FailureProbabilityError!(
+                p,
+                self.params.max_failure_probability
+            ));
+        }
+
+        // same precision as the output of LWE::bootstrap_with_function
+        let variance = self.params.get_bootstrap_variance();
+        let mut encoder = encoder_output.clone();
+        encoder.update_precision_from_variance(variance)?;
+        let nb_rounding_noise_bit: usize =
+            (npe::lwe::log2_rounding_noise(self.params.lwe_dimension)).ceil() as usize + 1;
+        let polynomial_size_log = f64::log2(self.params.polynomial_size as f64) as usize;
+        if nb_rounding_noise_bit + 1 + encoder.nb_bit_precision > polynomial_size_log + 1 {
+            encoder.nb_bit_precision = polynomial_size_log.saturating_sub(nb_rounding_noise_bit);
+        }
+
+        let step = self.push(
+            PlanOperation::Bootstrap {
+                input,
+                function,
+                encoder_output: encoder_output.clone(),
+            },
+            encoder,
+            variance,
+            false,
+        );
+        self.steps[step].failure_probability = p;
+        Ok(NodeState::new(step, false))
+    }
+
+    /// Return true if a bootstrap reduces the noise of a value
+    fn is_refreshable(&self, state: &NodeState) -> bool {
+        self.steps[state.home].variance > self.params.get_bootstrap_variance()
+    }
+
+    /// Compile an addition or a multiplication by a constant, refreshing the operands if the
+    /// result exceeds the maximum failure probability
+    fn compile_linear(
+        &mut self,
+        node: &GraphNode,
+        bootstrapped: bool,
+    ) -> Result<NodeState, CryptoAPIError> {
+        let nb_steps = self.steps.len();
+        let operands = node.get_operands();
+        let states: Vec<NodeState> = operands
+            .iter()
+            .map(|id| self.states[id.0].clone())
+            .collect();
+
+        let (state, p) = self.try_linear(node, bootstrapped, false)?;
+        if p <= self.params.max_failure_probability {
+            return Ok(state);
+        }
+        if !states.iter().any(|state| self.is_refreshable(state)) {
+            return Err(FailureProbabilityError!(
+                p,
+                self.params.max_failure_probability
+            ));
+        }
+
+        // start again from refreshed operands
+        self.steps.truncate(nb_steps);
+        for (id, state) in operands.iter().zip(states) {
+            self.states[id.0] = state;
+        }
+        let (state, p) = self.try_linear(node, bootstrapped, true)?;
+        if p > self.params.max_failure_probability {
+            return Err(FailureProbabilityError!(
+                p,
+                self.params.max_failure_probability
+            ));
+        }
+        Ok(state)
+    }
+
+    /// Prepare an operand of an addition or a multiplication by a constant: the operand is
+    /// re-encoded with a bootstrap if its encoder is not the target one, and refreshed with a
+    /// bootstrap if asked, a refreshed operand replacing the value of the node
+    fn prepare_operand(
+        &mut self,
+        node: usize,
+        target: &crate::Encoder,
+        refresh: bool,
+    ) -> Result<NodeState, CryptoAPIError> {
+        let mut state = self.states[node].clone();
+        let current = self.steps[state.home].encoder.clone();
+        let reencoded = current.nb_bit_padding != target.nb_bit_padding
+            || !deltas_eq!(current.delta, target.delta);
+        if reencoded {
+            let output = self.bootstrap(&mut state, None, target)?;
+            self.states[node] = state;
+            Ok(output)
+        } else if refresh && self.is_refreshable(&state) {
+            let output = self.bootstrap(&mut state, None, &current)?;
+            self.states[node] = output.clone();
+            Ok(output)
+        } else {
+            Ok(state)
+        }
+    }
+
+    /// Add the steps of an addition or a multiplication by a constant and return the state of
+    /// the result with its failure probability, the failure probability of a bootstrap if
+    /// the result is bootstrapped
+    fn try_linear(
+        &mut self,
+        node: &GraphNode,
+        bootstrapped: bool,
+        refresh: bool,
+    ) -> Result<(NodeState, f64), CryptoAPIError> {
+        let (operation, mut encoder, variance, in_input_key) = match node {
+            GraphNode::Add { left, right } => {
+                let (left, right) = (left.0, right.0);
+
+                // the operands are re-encoded on a common encoder if they are not compatible
+                let encoder_left = self.steps[self.states[left].home].encoder.clone();
+                let encoder_right = self.steps[self.states[right].home].encoder.clone();
+                let compatible = encoder_left.nb_bit_padding == encoder_right.nb_bit_padding
+                    && encoder_left.nb_bit_padding > 1
+                    && deltas_eq!(encoder_left.delta, encoder_right.delta);
+                let target = |encoder: &crate::Encoder| {
+                    let mut target = encoder.clone();
+                    if !compatible {
+                        target.delta = f64::max(encoder_left.delta, encoder_right.delta);
+                        target.nb_bit_padding = usize::max(self.params.nb_bit_padding, 2);
+                        target.nb_bit_precision = usize::max(
+                            encoder_left.nb_bit_precision,
+                            encoder_right.nb_bit_precision,
+                        );
+                    }
+                    target
+                };
+                let (target_left, target_right) = (target(&encoder_left), target(&encoder_right));
+                let mut state_left = self.prepare_operand(left, &target_left, refresh)?;
+                let mut state_right = if left == right {
+                    state_left.clone()
+                } else {
+                    self.prepare_operand(right, &target_right, refresh)?
+                };
+
+                // the addition is computed under the output key of the bootstrap if possible
+                let (step_left, step_right) = if !state_left.is_small() && !state_right.is_small() {
+                    (state_left.home, state_right.home)
+                } else {
+                    (
+                        self.keyswitch(&mut state_left)?,
+                        self.keyswitch(&mut state_right)?,
+                    )
+                };
+                let (a, b) = (&self.steps[step_left], &self.steps[step_right]);
+                let mut encoder = a.encoder.clone();
+                encoder.o += b.encoder.o;
+                encoder.delta *= 2.;
+                encoder.nb_bit_padding -= 1;
+                encoder.nb_bit_precision =
+                    usize::max(a.encoder.nb_bit_precision, b.encoder.nb_bit_precision) + 1;
+                (
+                    PlanOperation::Add(step_left, step_right),
+                    encoder,
+                    npe::add_ciphertexts(a.variance, b.variance),
+                    a.in_input_key,
+                )
+            }
+            GraphNode::MulConstant { input, constant } => {
+                // the operand is re-encoded if there is not enough padding
+                let nb_bit = get_nb_bit_constant(*constant);
+                let mut target = self.steps[self.states[input.0].home].encoder.clone();
+                target.nb_bit_padding = if target.nb_bit_padding < nb_bit + 1 {
+                    usize::max(self.params.nb_bit_padding, nb_bit + 1)
+                } else {
+                    target.nb_bit_padding
+                };
+                let state = self.prepare_operand(input.0, &target, refresh)?;
+
+                let a = &self.steps[state.home];
+                let c_abs = (*constant as i64).unsigned_abs();
+                let mut encoder = a.encoder.clone();
+                update_mul_encoder(&mut encoder, *constant);
+                (
+                    PlanOperation::MulConstant(state.home, *constant),
+                    encoder,
+                    <Torus as npe::LWE>::single_scalar_mul(a.variance, c_abs),
+                    a.in_input_key,
+                )
+            }
+            _ => unreachable!(),
+        };
+
+        if let PlanOperation::MulConstant(_, constant) = operation {
+            if constant != 0 {
+                encoder.update_precision_from_variance(variance)?;
+            }
+            if constant < 0 {
+                encoder.opposite_inplace()?;
+            }
+        } else {
+            encoder.update_precision_from_variance(variance)?;
+        }
+
+        let p = if bootstrapped {
+            let variance = if in_input_key {
+                variance
+            } else {
+                self.params.get_keyswitch_variance(variance)
+            };
+            self.params
+                .get_bootstrap_failure_probability(&encoder, variance)
+        } else {
+            get_decryption_failure_probability(&encoder, variance)
+        };
+        let step = self.push(operation, encoder, variance, in_input_key);
+        Ok((NodeState::new(step, in_input_key), p))
+    }
+}
+
+/// Return the number of bits of padding consumed by a multiplication by a constant
+fn get_nb_bit_constant(constant: i32) -> usize {
+    let c_abs = (constant as i64).abs();
+    if c_abs <= 1 {
+        0
+    } else {
+        64 - ((c_abs - 1) as u64).leading_zeros() as usize
+    }
+}
+
+/// Update the encoder of a ciphertext multiplied by the absolute value of a constant:
+/// the interval is multiplied by the constant and the granularity stays the same
+fn update_mul_encoder(encoder: &mut crate::Encoder, constant: i32) {
+    let nb_bit = get_nb_bit_constant(constant);
+    encoder.o *= (constant as f64).abs();
+    encoder.delta *= f64::powi(2., nb_bit as i32);
+    encoder.nb_bit_precision += nb_bit;
+    encoder.nb_bit_padding -= nb_bit;
+}
+
+/// Multiply an LWE ciphertext by an integer constant, consuming as many bits of padding as needed
+/// to keep every product in the interval of the encoder
+fn mul_constant_exact(ct: &crate::LWE, constant: i32) -> Result<crate::LWE, CryptoAPIError> {
+    let nb_bit = get_nb_bit_constant(constant);
+    if ct.encoder.nb_bit_padding < nb_bit {
+        return Err(NotEnoughPaddingError!(ct.encoder.nb_bit_padding, nb_bit));
+    }
+
+    // multiplication by the absolute value
+    let c_abs = (constant as i64).unsigned_abs();
+    let mut res = ct.clone();
+    res.ciphertext.update_with_scalar_mul(Cleartext(c_abs));
+    res.variance = <Torus as npe::LWE>::single_scalar_mul(res.variance, c_abs);
+    update_mul_encoder(&mut res.encoder, constant);
+    if c_abs != 0 {
+        res.encoder.update_precision_from_variance(res.variance)?;
+    }
+
+    // opposite for a negative constant
+    if constant < 0 {
+        res.opposite_inplace()?;
+    }
+    Ok(res)
+}
@@ -0,0 +1,179 @@
+use super::*;
+
+/// parameters of keys with a large polynomial size, the plans being compiled without the keys
+fn get_params() -> GraphParams {
+    GraphParams {
+        lwe_dimension: 1024,
+        rlwe_dimension: 1,
+        polynomial_size: 4096,
+        bsk_base_log: 8,
+        bsk_level: 3,
+        bsk_variance: f64::powi(2., -100),
+        ksk_base_log: 3,
+        ksk_level: 8,
+        ksk_variance: f64::powi(2., -50),
+        nb_bit_padding: 2,
+        max_failure_probability: GRAPH_MAX_FAILURE_PROBABILITY,
+    }
+}
+
+#[test]
+fn test_graph_compile_reencode() {
+    // two inputs without enough padding for an addition
+    let encoder = crate::Encoder::new_rounding_context(0., 3., 2, 1).unwrap();
+    let mut graph = Graph::new();
+    let x = graph.input(&encoder, f64::powi(2., -40));
+    let y = graph.input(&encoder, f64::powi(2., -40));
+    let z = graph.add(x, y).unwrap();
+    graph.output(z).unwrap();
+
+    // both inputs are re-encoded and the sum is key switched to the input key
+    let plan = graph.compile(&get_params()).unwrap();
+    let report = plan.get_report();
+    assert_eq!(report.nb_bootstraps, 2);
+    assert_eq!(report.nb_function_bootstraps, 0);
+    assert_eq!(report.nb_keyswitches, 1);
+    assert!(matches!(plan.steps[4].operation, PlanOperation::Add(2, 3)));
+    assert_eq!(plan.steps[4].encoder.nb_bit_padding, 1);
+    assert!(plan.steps[plan.outputs[0]].in_input_key);
+
+    // the same input is re-encoded only once
+    let mut graph = Graph::new();
+    let x = graph.input(&encoder, f64::powi(2., -40));
+    let z = graph.add(x, x).unwrap();
+    graph.output(z).unwrap();
+    assert_eq!(
+        graph.compile(&get_params()).unwrap().report.nb_bootstraps,
+        1
+    );
+
+    // a multiplication by 4 needs 3 bits of padding
+    let mut graph = Graph::new();
+    let x = graph.input(&encoder, f64::powi(2., -40));
+    let z = graph.mul_constant(x, 4).unwrap();
+    graph.output(z).unwrap();
+    let plan = graph.compile(&get_params()).unwrap();
+    assert_eq!(plan.report.nb_bootstraps, 1);
+    assert_eq!(plan.steps[1].encoder.nb_bit_padding, 3);
+    assert_eq!(plan.steps[2].encoder.nb_bit_padding, 1);
+    assert_eq!(plan.steps[2].encoder.nb_bit_precision, 4);
+}
+
+#[test]
+fn test_graph_compile_refresh() {
+    // noisy inputs: their sum times 2 is too noisy for a bootstrap
+    let encoder = crate::Encoder::new_rounding_context(0., 3., 2, 4).unwrap();
+    let encoder_output = crate::Encoder::new_rounding_context(0., 7., 3, 2).unwrap();
+    let mut graph = Graph::new();
+    let x = graph.input(&encoder, f64::powi(2., -22));
+    let y = graph.input(&encoder, f64::powi(2., -22));
+    let s = graph.add(x, y).unwrap();
+    let m = graph.mul_constant(s, 2).unwrap();
+    let f = graph.function(m, |v| v % 8., &encoder_output).unwrap();
+    graph.output(f).unwrap();
+
+    // the sum is refreshed before the multiplication
+    let plan = graph.compile(&get_params()).unwrap();
+    assert!(matches!(
+        plan.steps[3].operation,
+        PlanOperation::Bootstrap {
+            input: 2,
+            function: None,
+            ..
+        }
+    ));
+    assert!(matches!(
+        plan.steps[4].operation,
+        PlanOperation::MulConstant(3, 2)
+    ));
+    let report = plan.get_report();
+    assert_eq!(report.nb_bootstraps, 2);
+    assert_eq!(report.nb_function_bootstraps, 1);
+    assert_eq!(report.nb_keyswitches, 2);
+    assert!(report.failure_probability <= 3. * GRAPH_MAX_FAILURE_PROBABILITY);
+
+    // a too noisy input can not be refreshed
+    let encoder = crate::Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    let mut graph = Graph::new();
+    let x = graph.input(&encoder, f64::powi(2., -16));
+    let f = graph.function(x, |v| v, &encoder).unwrap();
+    graph.output(f).unwrap();
+    assert!(graph.compile(&get_params()).is_err());
+}
+
+#[test]
+fn test_graph_errors() {
+    let encoder = crate::Encoder::new_rounding_context(0., 3., 2, 0).unwrap();
+    let mut graph = Graph::new();
+    let x = graph.input(&encoder, f64::powi(2., -40));
+
+    // unknown nodes
+    assert!(graph.add(x, NodeId(1)).is_err());
+    assert!(graph.mul_constant(NodeId(3), 2).is_err());
+    assert!(graph.output(NodeId(1)).is_err());
+
+    // an input without padding can not be bootstrapped
+    graph.output(x).unwrap();
+    assert!(graph.compile(&get_params()).is_err());
+}
+
+#[test]
+fn test_encode_encrypt_x_graph_execute_x_decrypt() {
+    // settings
+    let encoder = crate::Encoder::new_rounding_context(0., 3., 2, 3).unwrap();
+    let encoder_output = crate::Encoder::new_rounding_context(0., 7., 3, 2).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_1024);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping and key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 5);
+    let ksk = crate::LWEKSK::new(&secret_key_output, &secret_key_input, 3, 8);
+
+    // g = (x + y)^2 % 8 + x and h = 10 - 3 * x
+    let mut graph = Graph::new();
+    let x = graph.input(&encoder, secret_key_input.get_variance());
+    let y = graph.input(&encoder, secret_key_input.get_variance());
+    let s = graph.add(x, y).unwrap();
+    let f = graph
+        .function(s, |v| (v * v) % 8., &encoder_output)
+        .unwrap();
+    let g = graph.add(f, x).unwrap();
+    let m = graph.mul_constant(x, -3).unwrap();
+    let h = graph.add_constant(m, 10.).unwrap();
+    graph.output(g).unwrap();
+    graph.output(h).unwrap();
+
+    // one bootstrap for the function, one to re-encode x, and one key switch for g
+    let plan = graph
+        .compile(&GraphParams::new(&bsk, &ksk).unwrap())
+        .unwrap();
+    let report = plan.get_report();
+    assert_eq!(report.nb_bootstraps, 2);
+    assert_eq!(report.nb_function_bootstraps, 1);
+    assert_eq!(report.nb_keyswitches, 1);
+    assert!(report.failure_probability < 1e-6);
+
+    for _ in 0..3 {
+        // random messages on the grid of the encoder
+        let m_x = random_index!(4) as f64;
+        let m_y = random_index!(4) as f64;
+
+        // encode, encrypt and execute the plan
+        let ct_x = crate::LWE::encode_encrypt(&secret_key_input, m_x, &encoder).unwrap();
+        let ct_y = crate::LWE::encode_encrypt(&secret_key_input, m_y, &encoder).unwrap();
+        let outputs = plan.execute(&[ct_x, ct_y], &bsk, &ksk).unwrap();
+
+        // decrypt with the input key
+        let d_g = outputs[0].decrypt_decode_round(&secret_key_input).unwrap();
+        let d_h = outputs[1].decrypt_decode_round(&secret_key_input).unwrap();
+        assert_eq!(d_g, ((m_x + m_y) * (m_x + m_y)) % 8. + m_x);
+        assert_eq!(d_h, 10. - 3. * m_x);
+    }
+
+    // the number of inputs must match the graph
+    let ct_x = crate::LWE::encode_encrypt(&secret_key_input, 0., &encoder).unwrap();
+    assert!(plan.execute(&[ct_x], &bsk, &ksk).is_err());
+}
@@ -0,0 +1,887 @@
+//! crt module: integers represented by their residues modulo several coprime odd moduli
+//!
+//! Each residue is an LWE whose encoder covers [0, modulus[ on the whole torus, so additions,
+//! subtractions and scalar operations wrap modulo the modulus without any carry or bootstrap.
+//! As the moduli are odd, any function of a residue is evaluated with a single bootstrap:
+//! shifted by a quarter of a cell, the residues r and r + (modulus + 1) / 2 fall in different
+//! cells of the half torus, one of them being negated by the negacyclic look up table.
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+    check_moduli, get_digits, lwe_to_vector, par_map_index, vector_to_lwe, ClearUint, FheUint,
+    IntegerClientKey, IntegerServerKey,
+};
+use crate::error::CryptoAPIError;
+use crate::shortint::{ShortInt, ShortIntParams};
+use crate::{read_from_file, write_to_file, Torus};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::numeric::Numeric;
+use concrete_core::{
+    crypto::{
+        encoding::{Cleartext, Plaintext},
+        secret::generators::{EncryptionRandomGenerator, SecretRandomGenerator},
+    },
+    math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor},
+};
+use concrete_npe as npe;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Number of standard deviations between a residue and the border of its cell
+/// required at the input of a bootstrap
+pub const CRT_NB_STD_DEV: f64 = 6.;
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Return the inverse of a modulo a small modulus
+fn mod_inverse(a: u128, modulus: usize) -> u128 {
+    let a = (a % modulus as u128) as usize;
+    (1..modulus).find(|x| (a * x) % modulus == 1).unwrap_or(0) as u128
+}
+
+/// Return the encoder of the residues modulo a modulus, the interval [0, modulus[ covering the whole torus
+fn get_residue_encoder(modulus: usize) -> crate::Encoder {
+    crate::Encoder {
+        o: 0.,
+        delta: modulus as f64,
+        nb_bit_precision: (modulus as f64).log2().ceil() as usize,
+        nb_bit_padding: 0,
+        round: false,
+        failure_probability: None,
+    }
+}
+
+/// Return the plaintext of a residue, i.e. the closest Torus value to residue / modulus
+fn encode_residue(residue: usize, modulus: usize) -> Torus {
+    (((((residue % modulus) as u128) << <Torus as Numeric>::BITS) + (modulus / 2) as u128)
+        / modulus as u128) as Torus
+}
+
+/// Return the residue closest to a phase
+fn decode_residue(phase: Torus, modulus: usize) -> usize {
+    let bits = <Torus as Numeric>::BITS;
+    (((phase as u128 * modulus as u128) + (1u128 << (bits - 1))) >> bits) as usize % modulus
+}
+
+/// Structure describing the moduli of CRT integers and the noise bounds of their residues.
+///
+/// # Attributes
+/// * `moduli` - the pairwise coprime odd moduli
+/// * `max_variances` - for each modulus, the largest variance of a residue at the input of a bootstrap,
+///   computed with concrete-npe
+/// * `key_params` - the parameters of the keys
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrtParams {
+    pub moduli: Vec<usize>,
+    pub max_variances: Vec<f64>,
+    pub key_params: ShortIntParams,
+}
+
+impl CrtParams {
+    /// Check a list of moduli and compute the noise bound of each modulus with concrete-npe
+    ///
+    /// # Arguments
+    /// * `moduli` - pairwise coprime odd moduli, their product must be smaller than 2^63
+    /// * `key_params` - the parameters of the keys
+    ///
+    /// # Output
+    /// * a new CrtParams
+    /// * InvalidModuliError - if a modulus is even, too large or not coprime with another one
+    /// * NoiseMarginError - if the output noise of a bootstrap is too large for a modulus
+    /// * UnsupportedModuliError - if the keys cannot be used for radix integers
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let params = CrtParams::new(&[3, 5, 7, 11, 13], &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// assert_eq!(params.get_modulus_product(), 15015);
+    /// ```
+    pub fn new(moduli: &[usize], key_params: &ShortIntParams) -> Result<CrtParams, CryptoAPIError> {
+        // the conversion to radix integers uses the same keys
+        check_moduli(key_params.message_modulus, key_params.carry_modulus)?;
+
+        // check the moduli
+        if moduli.is_empty() {
+            return Err(ZeroCiphertextsInStructureError!(0));
+        }
+        let mut product: u128 = 1;
+        for (i, modulus) in moduli.iter().enumerate() {
+            if modulus % 2 == 0 || *modulus < 3 {
+                return Err(InvalidModuliError!(
+                    *modulus,
+                    "is not an odd integer larger than 2"
+                ));
+            }
+            if *modulus > key_params.rlwe_params.polynomial_size {
+                return Err(InvalidModuliError!(
+                    *modulus,
+                    "is larger than the polynomial size"
+                ));
+            }
+            if let Some(other) = moduli[..i].iter().find(|other| gcd(**other, *modulus) != 1) {
+                return Err(InvalidModuliError!(
+                    *modulus,
+                    format!("is not coprime with {}", other)
+                ));
+            }
+            product *= *modulus as u128;
+            if product >= 1 << 63 {
+                return Err(InvalidModuliError!(
+                    *modulus,
+                    "makes the product of the moduli larger than 2^63"
+                ));
+            }
+        }
+
+        // noise of the inputs of the bootstraps: fresh encryptions and sums of two refreshed residues
+        let var_bootstrap = key_params.get_bootstrap_output_variance();
+        let var_needed = f64::max(
+            f64::powi(key_params.lwe_params.get_std_dev(), 2),
+            npe::add_ciphertexts(var_bootstrap, var_bootstrap),
+        );
+
+        // drift of the rounding of the mask in [0, 2N[
+        let var_drift = crate::bootstrap_drift_variance(
+            key_params.lwe_params.dimension,
+            key_params.rlwe_params.polynomial_size,
+        );
+
+        let max_variances = moduli
+            .iter()
+            .map(|modulus| {
+                // a residue is a quarter of a cell away from the border of its cell
+                let quarter_cell = 1. / (4 * modulus) as f64;
+                let max_variance = f64::powi(quarter_cell / CRT_NB_STD_DEV, 2) - var_drift;
+                if var_needed > max_variance {
+                    return Err(NoiseMarginError!(
+                        *modulus,
+                        quarter_cell / f64::sqrt(var_needed + var_drift)
+                    ));
+                }
+                Ok(max_variance)
+            })
+            .collect::<Result<Vec<f64>, CryptoAPIError>>()?;
+
+        Ok(CrtParams {
+            moduli: moduli.to_vec(),
+            max_variances,
+            key_params: key_params.clone(),
+        })
+    }
+
+    /// Select the first key parameters giving enough noise margin to every modulus
+    ///
+    /// # Arguments
+    /// * `moduli` - pairwise coprime odd moduli
+    /// * `candidates` - key parameters, sorted from the cheapest
+    ///
+    /// # Output
+    /// * a new CrtParams
+    /// * the error of the last candidate if none of them can be used
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let params = CrtParams::select(
+    ///     &[3, 5, 7, 11, 13],
+    ///     &[SHORTINT128_MESSAGE_1_CARRY_1, SHORTINT128_MESSAGE_2_CARRY_2],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(params.key_params, SHORTINT128_MESSAGE_2_CARRY_2);
+    /// ```
+    pub fn select(
+        moduli: &[usize],
+        candidates: &[ShortIntParams],
+    ) -> Result<CrtParams, CryptoAPIError> {
+        let mut error = ZeroCiphertextsInStructureError!(0);
+        for key_params in candidates.iter() {
+            match CrtParams::new(moduli, key_params) {
+                Ok(params) => return Ok(params),
+                Err(err) => error = err,
+            }
+        }
+        Err(error)
+    }
+
+    /// Return the product of the moduli, the CRT integers are computed modulo this product
+    pub fn get_modulus_product(&self) -> u64 {
+        self.moduli.iter().map(|modulus| *modulus as u64).product()
+    }
+
+    /// Return the distance between a residue of variance `variance` and the border of its cell
+    /// at the input of a bootstrap, counted in standard deviations
+    ///
+    /// # Arguments
+    /// * `index` - the index of the modulus
+    /// * `variance` - the variance of the residue
+    pub fn get_nb_std_dev(&self, index: usize, variance: f64) -> f64 {
+        let quarter_cell = 1. / (4 * self.moduli[index]) as f64;
+        let var_drift = f64::powi(quarter_cell / CRT_NB_STD_DEV, 2) - self.max_variances[index];
+        quarter_cell / f64::sqrt(variance + var_drift)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<CrtParams, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Structure containing an integer encrypted as its residues modulo several moduli.
+///
+/// # Attributes
+/// * `residues` - one LWE ciphertext per modulus, whose encoder covers [0, modulus[
+/// * `moduli` - the moduli of the residues
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrtInt {
+    pub residues: crate::VectorLWE,
+    pub moduli: Vec<usize>,
+}
+
+impl CrtInt {
+    /// Create a noiseless encryption of a value, which can be decrypted with any key of the right dimension
+    ///
+    /// # Arguments
+    /// * `value` - the value to encrypt, reduced modulo the product of the moduli
+    /// * `dimension` - the length the LWE mask
+    /// * `moduli` - the moduli of the residues
+    ///
+    /// # Output
+    /// * a new CrtInt
+    pub fn trivial(
+        value: u64,
+        dimension: usize,
+        moduli: &[usize],
+    ) -> Result<CrtInt, CryptoAPIError> {
+        let residues = moduli
+            .iter()
+            .map(|modulus| {
+                let mut residue = crate::LWE::zero(dimension)?;
+                residue.encoder = get_residue_encoder(*modulus);
+                residue.ciphertext.as_mut_tensor().as_mut_slice()[dimension] =
+                    encode_residue((value % *modulus as u64) as usize, *modulus);
+                Ok(residue)
+            })
+            .collect::<Result<Vec<crate::LWE>, CryptoAPIError>>()?;
+        CrtInt::from_residues(&residues, moduli)
+    }
+
+    /// Build a CRT integer from its residues
+    ///
+    /// # Arguments
+    /// * `residues` - the LWE ciphertexts of the residues
+    /// * `moduli` - the moduli of the residues
+    ///
+    /// # Output
+    /// * a new CrtInt
+    /// * NbCTError - if there are not as many residues as moduli
+    /// * DimensionError - if the residues have different dimensions
+    pub fn from_residues(
+        residues: &[crate::LWE],
+        moduli: &[usize],
+    ) -> Result<CrtInt, CryptoAPIError> {
+        if residues.len() != moduli.len() {
+            return Err(NbCTError!(residues.len(), moduli.len()));
+        }
+        Ok(CrtInt {
+            residues: lwe_to_vector(&residues.iter().collect::<Vec<&crate::LWE>>())?,
+            moduli: moduli.to_vec(),
+        })
+    }
+
+    /// Return a copy of the residues
+    pub fn get_residues(&self) -> Vec<crate::LWE> {
+        vector_to_lwe(&self.residues)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<CrtInt, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+impl fmt::Display for CrtInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "CrtInt {{\n         -> moduli = {:?}\n         -> variances = {:?}\n}}",
+            self.moduli, self.residues.variances
+        )
+    }
+}
+
+/// Structure containing the secret keys needed to encrypt and decrypt CRT integers.
+///
+/// # Attributes
+/// * `integer` - the integer client key, also used to decrypt the radix integers
+/// * `params` - the moduli and their noise bounds
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CrtClientKey {
+    pub integer: IntegerClientKey,
+    pub params: CrtParams,
+}
+
+impl CrtClientKey {
+    /// Generate a new client key from a CrtParams
+    ///
+    /// # Argument
+    /// * `params` - a CrtParams instance
+    ///
+    /// # Output
+    /// * a new CrtClientKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let params = CrtParams::new(&[3, 5, 7, 11, 13], &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let client_key = CrtClientKey::new(&params).unwrap();
+    ///
+    /// let ct = client_key.encrypt(12345).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct).unwrap(), 12345);
+    /// ```
+    pub fn new(params: &CrtParams) -> Result<CrtClientKey, CryptoAPIError> {
+        CrtClientKey::new_with_generator(params, &mut SecretRandomGenerator::new(None))
+    }
+
+    /// Generate a new client key from a CrtParams with a provided random generator
+    ///
+    /// # Arguments
+    /// * `params` - a CrtParams instance
+    /// * `generator` - the random generator sampling the secret keys
+    ///
+    /// # Output
+    /// * a new CrtClientKey
+    pub fn new_with_generator(
+        params: &CrtParams,
+        generator: &mut SecretRandomGenerator,
+    ) -> Result<CrtClientKey, CryptoAPIError> {
+        Ok(CrtClientKey {
+            integer: IntegerClientKey::new_with_generator(&params.key_params, generator)?,
+            params: params.clone(),
+        })
+    }
+
+    /// Generate the server key matching the client key
+    ///
+    /// # Output
+    /// * a new CrtServerKey
+    pub fn get_server_key(&self) -> CrtServerKey {
+        self.get_server_key_with_generator(&mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Generate the server key matching the client key with a provided encryption generator
+    ///
+    /// # Argument
+    /// * `generator` - the encryption generator sampling the masks and the noises of the keys
+    ///
+    /// # Output
+    /// * a new CrtServerKey
+    pub fn get_server_key_with_generator(
+        &self,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> CrtServerKey {
+        CrtServerKey {
+            integer: self.integer.get_server_key_with_generator(generator),
+            params: self.params.clone(),
+        }
+    }
+
+    /// Encrypt a value as its residues
+    ///
+    /// # Argument
+    /// * `value` - the value to encrypt, reduced modulo the product of the moduli
+    ///
+    /// # Output
+    /// * a new CrtInt
+    pub fn encrypt(&self, value: u64) -> Result<CrtInt, CryptoAPIError> {
+        self.encrypt_with_generator(value, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt a value as its residues with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `value` - the value to encrypt, reduced modulo the product of the moduli
+    /// * `generator` - the encryption generator sampling the masks and the noises of the residues
+    ///
+    /// # Output
+    /// * a new CrtInt
+    pub fn encrypt_with_generator(
+        &self,
+        value: u64,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> Result<CrtInt, CryptoAPIError> {
+        let sk = &self.integer.shortint.lwe_secret_key;
+        let mut residues = CrtInt::trivial(0, sk.dimension, &self.params.moduli)?.get_residues();
+        for (residue, modulus) in residues.iter_mut().zip(self.params.moduli.iter()) {
+            residue.encrypt_raw_with_generator(
+                sk,
+                encode_residue((value % *modulus as u64) as usize, *modulus),
+                generator,
+            )?;
+        }
+        CrtInt::from_residues(&residues, &self.params.moduli)
+    }
+
+    /// Decrypt a CRT integer and recombine its residues
+    ///
+    /// # Argument
+    /// * `ct` - a CrtInt
+    ///
+    /// # Output
+    /// * the value modulo the product of the moduli
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    pub fn decrypt(&self, ct: &CrtInt) -> Result<u64, CryptoAPIError> {
+        let sk = &self.integer.shortint.lwe_secret_key;
+        if ct.residues.dimension != sk.dimension {
+            return Err(DimensionError!(ct.residues.dimension, sk.dimension));
+        }
+
+        let product = ct.moduli.iter().map(|m| *m as u128).product::<u128>();
+        let mut value: u128 = 0;
+        for (i, residue) in ct.get_residues().iter().enumerate() {
+            let mut phase = Plaintext(0);
+            sk.val.decrypt_lwe(&mut phase, &residue.ciphertext);
+            let r = decode_residue(phase.0, ct.moduli[i]) as u128;
+            value = (value + r * get_crt_coefficient(&ct.moduli, i)) % product;
+        }
+        Ok(value as u64)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<CrtClientKey, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Return the coefficient of the residue `index` in the CRT recombination,
+/// i.e. the integer equal to 1 modulo moduli[index] and to 0 modulo the other moduli
+fn get_crt_coefficient(moduli: &[usize], index: usize) -> u128 {
+    let product = moduli.iter().map(|m| *m as u128).product::<u128>();
+    let others = product / moduli[index] as u128;
+    (others * mod_inverse(others, moduli[index])) % product
+}
+
+/// Structure containing the public keys needed to compute on CRT integers.
+///
+/// # Attributes
+/// * `integer` - the integer server key, also used to compute on the radix integers
+/// * `params` - the moduli and their noise bounds
+#[derive(Debug, PartialEq)]
+pub struct CrtServerKey {
+    pub integer: IntegerServerKey,
+    pub params: CrtParams,
+}
+
+impl CrtServerKey {
+    /// Check that a ciphertext can be used with this server key
+    fn check_ciphertext(&self, ct: &CrtInt) -> Result<(), CryptoAPIError> {
+        if ct.residues.dimension != self.integer.shortint.ksk.dimension_after {
+            return Err(DimensionError!(
+                ct.residues.dimension,
+                self.integer.shortint.ksk.dimension_after
+            ));
+        }
+        if ct.moduli.len() != self.params.moduli.len() {
+            return Err(NbCTError!(ct.moduli.len(), self.params.moduli.len()));
+        }
+        for (modulus_1, modulus_2) in ct.moduli.iter().zip(self.params.moduli.iter()) {
+            if modulus_1 != modulus_2 {
+                return Err(ModulusMismatchError!(*modulus_1, *modulus_2));
+            }
+        }
+        Ok(())
+    }
+
+    /// Bootstrap a residue with a look up table `g` from the residues to Torus values,
+    /// and key switch the result
+    fn bootstrap_residue<G: Fn(usize) -> Torus>(
+        &self,
+        residue: &crate::LWE,
+        modulus: usize,
+        g: G,
+    ) -> crate::LWE {
+        // shift the residues by a quarter of a cell of the half torus
+        let mut input = residue.clone();
+        let quarter_cell = ((1u128 << <Torus as Numeric>::BITS) / (4 * modulus) as u128) as Torus;
+        let update =
+            input.ciphertext.as_tensor().as_slice()[input.dimension].wrapping_add(quarter_cell);
+        input.ciphertext.as_mut_tensor().as_mut_slice()[input.dimension] = update;
+
+        // the cell 2r holds the residue r, the cell 2r - modulus holds the negated residue r
+        let lut = self
+            .integer
+            .shortint
+            .bsk
+            .generate_cell_look_up_table(modulus, |cell| {
+                if cell % 2 == 0 {
+                    g(cell / 2)
+                } else {
+                    g((cell + modulus) / 2).wrapping_neg()
+                }
+            });
+        self.integer.shortint.bootstrap_keyswitch_raw(&input, &lut)
+    }
+
+    /// Reset the noise of a residue with a bootstrap
+    fn refresh_residue(&self, residue: &crate::LWE, modulus: usize) -> crate::LWE {
+        self.bootstrap_residue(residue, modulus, |r| encode_residue(r, modulus))
+    }
+
+    /// Refresh the residues of index `index` of two ciphertexts, the noisiest first,
+    /// until the variance computed by `variance` fits the bound of the modulus
+    fn fit_residues<V: Fn(f64, f64) -> f64>(
+        &self,
+        residue_1: &crate::LWE,
+        residue_2: &crate::LWE,
+        index: usize,
+        variance: V,
+    ) -> Result<(crate::LWE, crate::LWE), CryptoAPIError> {
+        let (modulus, max_variance) = (self.params.moduli[index], self.params.max_variances[index]);
+        let (mut res_1, mut res_2) = (residue_1.clone(), residue_2.clone());
+        for _ in 0..2 {
+            if variance(res_1.variance, res_2.variance) <= max_variance {
+                break;
+            }
+            if res_1.variance >= res_2.variance {
+                res_1 = self.refresh_residue(&res_1, modulus);
+            } else {
+                res_2 = self.refresh_residue(&res_2, modulus);
+            }
+        }
+
+        let var = variance(res_1.variance, res_2.variance);
+        if var > max_variance {
+            return Err(NoiseMarginError!(
+                modulus,
+                self.params.get_nb_std_dev(index, var)
+            ));
+        }
+        Ok((res_1, res_2))
+    }
+
+    /// Apply a linear operation residue by residue, refreshing the inputs when needed
+    fn linear_op<F, V>(
+        &self,
+        ct_1: &CrtInt,
+        ct_2: &CrtInt,
+        op: F,
+        variance: V,
+    ) -> Result<CrtInt, CryptoAPIError>
+    where
+        F: Fn(&mut crate::LWE, &crate::LWE) + Sync + Send,
+        V: Fn(f64, f64) -> f64 + Sync + Send,
+    {
+        self.check_ciphertext(ct_1)?;
+        self.check_ciphertext(ct_2)?;
+        let (residues_1, residues_2) = (ct_1.get_residues(), ct_2.get_residues());
+        let residues = par_map_index(residues_1.len(), |i| {
+            let (mut res_1, res_2) =
+                self.fit_residues(&residues_1[i], &residues_2[i], i, &variance)?;
+            res_1.variance = variance(res_1.variance, res_2.variance);
+            op(&mut res_1, &res_2);
+            Ok(res_1)
+        })?;
+        CrtInt::from_residues(&residues, &ct_1.moduli)
+    }
+
+    /// Add a constant residue by residue
+    fn add_constant(
+        &self,
+        ct: &CrtInt,
+        f: impl Fn(usize) -> usize,
+    ) -> Result<CrtInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let mut residues = ct.get_residues();
+        for (residue, modulus) in residues.iter_mut().zip(ct.moduli.iter()) {
+            let update = residue.ciphertext.as_tensor().as_slice()[residue.dimension]
+                .wrapping_add(encode_residue(f(*modulus), *modulus));
+            residue.ciphertext.as_mut_tensor().as_mut_slice()[residue.dimension] = update;
+        }
+        CrtInt::from_residues(&residues, &ct.moduli)
+    }
+
+    /// Create a noiseless encryption of a value with the dimension and the moduli of the key
+    pub fn trivial(&self, value: u64) -> Result<CrtInt, CryptoAPIError> {
+        CrtInt::trivial(
+            value,
+            self.integer.shortint.ksk.dimension_after,
+            &self.params.moduli,
+        )
+    }
+
+    /// Compute ct_1 + ct_2 modulo the product of the moduli, residue by residue
+    ///
+    /// # Arguments
+    /// * `ct_1` - a CrtInt
+    /// * `ct_2` - a CrtInt
+    ///
+    /// # Output
+    /// * a new CrtInt
+    /// * NoiseMarginError - if the noise is too large even after refreshing the inputs
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let params = CrtParams::new(&[3, 5, 7, 11, 13], &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let client_key = CrtClientKey::new(&params).unwrap();
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(10000).unwrap();
+    /// let ct_2 = client_key.encrypt(7000).unwrap();
+    ///
+    /// let ct_res = server_key.add(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 17000 % 15015);
+    /// ```
+    pub fn add(&self, ct_1: &CrtInt, ct_2: &CrtInt) -> Result<CrtInt, CryptoAPIError> {
+        self.linear_op(
+            ct_1,
+            ct_2,
+            |res, ct| res.ciphertext.update_with_add(&ct.ciphertext),
+            npe::add_ciphertexts,
+        )
+    }
+
+    /// Compute ct_1 - ct_2 modulo the product of the moduli, residue by residue
+    pub fn sub(&self, ct_1: &CrtInt, ct_2: &CrtInt) -> Result<CrtInt, CryptoAPIError> {
+        self.linear_op(
+            ct_1,
+            ct_2,
+            |res, ct| res.ciphertext.update_with_sub(&ct.ciphertext),
+            npe::add_ciphertexts,
+        )
+    }
+
+    /// Compute -ct modulo the product of the moduli, residue by residue
+    pub fn neg(&self, ct: &CrtInt) -> Result<CrtInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let mut residues = ct.get_residues();
+        for residue in residues.iter_mut() {
+            residue.ciphertext.update_with_neg();
+        }
+        CrtInt::from_residues(&residues, &ct.moduli)
+    }
+
+    /// Compute ct + scalar modulo the product of the moduli
+    pub fn scalar_add(&self, ct: &CrtInt, scalar: u64) -> Result<CrtInt, CryptoAPIError> {
+        self.add_constant(ct, |modulus| (scalar % modulus as u64) as usize)
+    }
+
+    /// Compute ct - scalar modulo the product of the moduli
+    pub fn scalar_sub(&self, ct: &CrtInt, scalar: u64) -> Result<CrtInt, CryptoAPIError> {
+        self.add_constant(ct, |modulus| modulus - (scalar % modulus as u64) as usize)
+    }
+
+    /// Compute ct * scalar modulo the product of the moduli, each residue is multiplied by
+    /// the representative of the scalar of smallest absolute value
+    pub fn scalar_mul(&self, ct: &CrtInt, scalar: u64) -> Result<CrtInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let residues = ct.get_residues();
+        let residues = par_map_index(residues.len(), |i| {
+            let modulus = ct.moduli[i];
+            let mut k = (scalar % modulus as u64) as i64;
+            if k > modulus as i64 / 2 {
+                k -= modulus as i64;
+            }
+            let variance =
+                |var: f64, _: f64| <Torus as npe::LWE>::single_scalar_mul(var, k as Torus);
+            let (mut res, _) = self.fit_residues(&residues[i], &residues[i], i, variance)?;
+            res.variance = variance(res.variance, 0.);
+            res.ciphertext.update_with_scalar_mul(Cleartext(k as Torus));
+            Ok(res)
+        })?;
+        CrtInt::from_residues(&residues, &ct.moduli)
+    }
+
+    /// Compute ct_1 * ct_2 modulo the product of the moduli, residue by residue,
+    /// as ((ct_1 + ct_2)^2 - (ct_1 - ct_2)^2) / 4 with two bootstraps per residue
+    ///
+    /// # Arguments
+    /// * `ct_1` - a CrtInt
+    /// * `ct_2` - a CrtInt
+    ///
+    /// # Output
+    /// * a new CrtInt
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let params = CrtParams::new(&[3, 5, 7, 11, 13], &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let client_key = CrtClientKey::new(&params).unwrap();
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(123).unwrap();
+    /// let ct_2 = client_key.encrypt(45).unwrap();
+    ///
+    /// let ct_res = server_key.mul(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 123 * 45);
+    /// ```
+    pub fn mul(&self, ct_1: &CrtInt, ct_2: &CrtInt) -> Result<CrtInt, CryptoAPIError> {
+        self.check_ciphertext(ct_1)?;
+        self.check_ciphertext(ct_2)?;
+        let (residues_1, residues_2) = (ct_1.get_residues(), ct_2.get_residues());
+        let residues = par_map_index(residues_1.len(), |i| {
+            let modulus = ct_1.moduli[i];
+            let (res_1, res_2) =
+                self.fit_residues(&residues_1[i], &residues_2[i], i, npe::add_ciphertexts)?;
+
+            // x -> x^2 / 4 modulo the modulus
+            let inverse_of_four = mod_inverse(4, modulus) as usize;
+            let square = |x: usize| encode_residue((x * x % modulus) * inverse_of_four, modulus);
+
+            let mut sum = res_1.clone();
+            sum.ciphertext.update_with_add(&res_2.ciphertext);
+            let mut difference = res_1;
+            difference.ciphertext.update_with_sub(&res_2.ciphertext);
+
+            let mut res = self.bootstrap_residue(&sum, modulus, square);
+            let square_difference = self.bootstrap_residue(&difference, modulus, square);
+            res.ciphertext
+                .update_with_sub(&square_difference.ciphertext);
+            res.variance = npe::add_ciphertexts(res.variance, square_difference.variance);
+            Ok(res)
+        })?;
+        CrtInt::from_residues(&residues, &ct_1.moduli)
+    }
+
+    /// Reset the noise of every residue with a bootstrap
+    pub fn refresh(&self, ct: &CrtInt) -> Result<CrtInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let residues = ct.get_residues();
+        let residues = par_map_index(residues.len(), |i| {
+            Ok(self.refresh_residue(&residues[i], ct.moduli[i]))
+        })?;
+        CrtInt::from_residues(&residues, &ct.moduli)
+    }
+
+    /// Convert a CRT integer into a radix integer, to compare it or to decrypt it with the radix client key.
+    /// Each residue is multiplied by its CRT coefficient and decomposed into radix blocks with
+    /// one bootstrap per block, then the partial integers are summed with a tree, each sum
+    /// being reduced modulo the product of the moduli P as min(sum, sum - P).
+    ///
+    /// # Argument
+    /// * `ct` - a CrtInt
+    ///
+    /// # Output
+    /// * a new FheUint encrypting the value in [0, P[
+    /// * MessageTooBigError - if 2 * P does not fit in the radix integer
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let params = CrtParams::new(&[3, 5, 7, 11, 13], &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let client_key = CrtClientKey::new(&params).unwrap();
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct = client_key.encrypt(9876).unwrap();
+    ///
+    /// let ct_radix: FheUint16 = server_key.to_radix(&ct).unwrap();
+    /// assert_eq!(client_key.integer.decrypt(&ct_radix).unwrap(), 9876);
+    /// ```
+    pub fn to_radix<T: ClearUint>(&self, ct: &CrtInt) -> Result<FheUint<T>, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let product = self.params.get_modulus_product();
+        if 2 * product as u128 > 1 << T::NB_BITS {
+            return Err(MessageTooBigError!(
+                (2 * product) as f64,
+                f64::powi(2., T::NB_BITS as i32)
+            ));
+        }
+
+        let (message_modulus, carry_modulus) = (
+            self.integer.shortint.message_modulus,
+            self.integer.shortint.carry_modulus,
+        );
+        let nb_blocks = FheUint::<T>::get_nb_blocks(message_modulus);
+        let delta = ShortInt::get_delta(message_modulus, carry_modulus);
+        let encoder = ShortInt::get_encoder(message_modulus, carry_modulus)?;
+
+        // partial integers residue * coefficient modulo P
+        let residues = ct.get_residues();
+        let mut partials = par_map_index(residues.len(), |i| {
+            let modulus = ct.moduli[i];
+            let coefficient = get_crt_coefficient(&ct.moduli, i);
+            let digits: Vec<Vec<usize>> = (0..modulus)
+                .map(|r| {
+                    let value = (r as u128 * coefficient) % product as u128;
+                    get_digits(value as u64, message_modulus, nb_blocks)
+                })
+                .collect();
+
+            let blocks = par_map_index(nb_blocks, |j| {
+                let mut ciphertext = self
+                    .bootstrap_residue(&residues[i], modulus, |r| digits[r][j] as Torus * delta);
+                ciphertext.encoder = encoder.clone();
+                Ok(ShortInt {
+                    ciphertext,
+                    message_modulus,
+                    carry_modulus,
+                    degree: digits.iter().map(|d| d[j]).max().unwrap_or(0),
+                })
+            })?;
+            FheUint::from_blocks(&blocks)
+        })?;
+
+        // sum the partial integers, every sum is smaller than 2 * P
+        while partials.len() > 1 {
+            partials = par_map_index(partials.len().div_ceil(2), |k| {
+                if 2 * k + 1 < partials.len() {
+                    let sum = self.integer.add(&partials[2 * k], &partials[2 * k + 1])?;
+                    let reduced = self.integer.scalar_sub(&sum, T::from_u64(product))?;
+                    self.integer.min(&sum, &reduced)
+                } else {
+                    Ok(partials[2 * k].clone())
+                }
+            })?;
+        }
+        Ok(partials.remove(0))
+    }
+
+    /// Save the bootstrapping key and the key switching key in two files
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    pub fn save(&self, path_bsk: &str, path_ksk: &str) {
+        self.integer.save(path_bsk, path_ksk);
+    }
+
+    /// Load a server key saved with `CrtServerKey::save`
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    /// * `params` - the parameters used to generate the keys
+    ///
+    /// # Output
+    /// * a CrtServerKey
+    pub fn load(path_bsk: &str, path_ksk: &str, params: &CrtParams) -> CrtServerKey {
+        CrtServerKey {
+            integer: IntegerServerKey::load(path_bsk, path_ksk, &params.key_params),
+            params: params.clone(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::*;
+use crate::{FheUint16, SHORTINT128_MESSAGE_1_CARRY_1, SHORTINT128_MESSAGE_2_CARRY_2};
+
+const NB_TESTS: usize = 3;
+const MODULI: [usize; 5] = [3, 5, 7, 11, 13];
+
+fn random_u64() -> u64 {
+    let rs: Vec<u64> = concrete_core::math::random::RandomGenerator::new(None)
+        .random_uniform_tensor(1)
+        .into_container();
+    rs[0]
+}
+
+#[test]
+fn test_params() {
+    // the moduli must be odd and pairwise coprime
+    assert!(CrtParams::new(&[3, 4, 7], &SHORTINT128_MESSAGE_2_CARRY_2).is_err());
+    assert!(CrtParams::new(&[3, 5, 9], &SHORTINT128_MESSAGE_2_CARRY_2).is_err());
+    assert!(CrtParams::new(&[], &SHORTINT128_MESSAGE_2_CARRY_2).is_err());
+
+    let params = CrtParams::new(&MODULI, &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    assert_eq!(params.get_modulus_product(), 15015);
+    for i in 0..MODULI.len() {
+        let var = params.key_params.get_bootstrap_output_variance();
+        assert!(params.get_nb_std_dev(i, var) >= CRT_NB_STD_DEV);
+    }
+
+    // the first candidates do not leave enough margin to the largest moduli
+    let params = CrtParams::select(
+        &MODULI,
+        &[SHORTINT128_MESSAGE_1_CARRY_1, SHORTINT128_MESSAGE_2_CARRY_2],
+    )
+    .unwrap();
+    assert_eq!(params.key_params, SHORTINT128_MESSAGE_2_CARRY_2);
+}
+
+#[test]
+fn test_encrypt_x_decrypt() {
+    let params = CrtParams::new(&MODULI, &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let client_key = CrtClientKey::new(&params).unwrap();
+    let server_key = client_key.get_server_key();
+    let product = params.get_modulus_product();
+
+    for _ in 0..NB_TESTS {
+        let clear = random_u64() % product;
+        let ct = client_key.encrypt(clear).unwrap();
+        assert_eq!(ct.residues.nb_ciphertexts, MODULI.len());
+        assert_eq!(clear, client_key.decrypt(&ct).unwrap());
+
+        let ct = server_key.trivial(clear).unwrap();
+        assert_eq!(clear, client_key.decrypt(&ct).unwrap());
+
+        let ct = server_key.refresh(&ct).unwrap();
+        assert_eq!(clear, client_key.decrypt(&ct).unwrap());
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let params = CrtParams::new(&MODULI, &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let client_key = CrtClientKey::new(&params).unwrap();
+    let server_key = client_key.get_server_key();
+    let product = params.get_modulus_product();
+
+    for _ in 0..NB_TESTS {
+        let clear_1 = random_u64() % product;
+        let clear_2 = random_u64() % product;
+        let ct_1 = client_key.encrypt(clear_1).unwrap();
+        let ct_2 = client_key.encrypt(clear_2).unwrap();
+
+        let decrypt = |ct: CrtInt| client_key.decrypt(&ct).unwrap();
+        assert_eq!(
+            (clear_1 + clear_2) % product,
+            decrypt(server_key.add(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 + product - clear_2) % product,
+            decrypt(server_key.sub(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            (product - clear_1) % product,
+            decrypt(server_key.neg(&ct_1).unwrap())
+        );
+        assert_eq!(
+            (clear_1 * clear_2) % product,
+            decrypt(server_key.mul(&ct_1, &ct_2).unwrap())
+        );
+
+        // scalar operations
+        assert_eq!(
+            (clear_1 + clear_2) % product,
+            decrypt(server_key.scalar_add(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 + product - clear_2) % product,
+            decrypt(server_key.scalar_sub(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 * clear_2) % product,
+            decrypt(server_key.scalar_mul(&ct_1, clear_2).unwrap())
+        );
+    }
+}
+
+#[test]
+fn test_refresh_on_noise_growth() {
+    let params = CrtParams::new(&MODULI, &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let client_key = CrtClientKey::new(&params).unwrap();
+    let server_key = client_key.get_server_key();
+    let product = params.get_modulus_product();
+
+    // the residues are refreshed when their variance reaches the bounds of the moduli
+    let clear = random_u64() % product;
+    let ct = client_key.encrypt(clear).unwrap();
+    let mut ct_res = ct.clone();
+    for _ in 0..100 {
+        ct_res = server_key.add(&ct_res, &ct).unwrap();
+        for (variance, max_variance) in ct_res
+            .residues
+            .variances
+            .iter()
+            .zip(params.max_variances.iter())
+        {
+            assert!(variance <= max_variance);
+        }
+    }
+    assert_eq!(
+        (101 * clear) % product,
+        client_key.decrypt(&ct_res).unwrap()
+    );
+}
+
+#[test]
+fn test_to_radix() {
+    let params = CrtParams::new(&MODULI, &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let client_key = CrtClientKey::new(&params).unwrap();
+    let server_key = client_key.get_server_key();
+    let product = params.get_modulus_product();
+
+    // 2 * 15015 does not fit in 8 bits
+    let ct = client_key.encrypt(0).unwrap();
+    assert!(server_key.to_radix::<u8>(&ct).is_err());
+
+    for _ in 0..NB_TESTS {
+        let clear = random_u64() % product;
+        let ct = client_key.encrypt(clear).unwrap();
+        let ct_radix: FheUint16 = server_key.to_radix(&ct).unwrap();
+        assert_eq!(clear as u16, client_key.integer.decrypt(&ct_radix).unwrap());
+    }
+}
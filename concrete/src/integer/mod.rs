@@ -0,0 +1,221 @@
+//! integer module: encrypted integers made of several short integer blocks
+//!
+//! The radix integers split a value into digits, the CRT integers into residues modulo coprime moduli.
+//! The keys are the short integer keys, the integers only need message and carry spaces
+//! large enough to pack the intermediate states of the carry propagation and of the comparisons.
+
+use crate::error::CryptoAPIError;
+use crate::shortint::{ShortIntClientKey, ShortIntParams, ShortIntServerKey};
+use crate::Torus;
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::parameters::LweSize;
+use concrete_core::{
+    crypto::{
+        lwe::{LweCiphertext, LweList},
+        secret::generators::{EncryptionRandomGenerator, SecretRandomGenerator},
+    },
+    math::tensor::{AsRefSlice, AsRefTensor},
+};
+#[cfg(feature = "multithread")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+pub mod crt;
+pub mod radix;
+pub use crt::*;
+pub use radix::*;
+
+/// Check that short integers with these moduli can be used as the blocks of a radix integer
+fn check_moduli(message_modulus: usize, carry_modulus: usize) -> Result<(), CryptoAPIError> {
+    if !message_modulus.is_power_of_two()
+        || message_modulus < 4
+        || 8 % message_modulus.trailing_zeros() != 0
+        || carry_modulus < message_modulus
+    {
+        return Err(UnsupportedModuliError!(message_modulus, carry_modulus));
+    }
+    Ok(())
+}
+
+/// Return the digits of a value in base message_modulus, least significant first
+fn get_digits(value: u64, message_modulus: usize, nb_digits: usize) -> Vec<usize> {
+    let bits_per_digit = message_modulus.trailing_zeros() as usize;
+    (0..nb_digits)
+        .map(|i| ((value >> (i * bits_per_digit)) as usize) % message_modulus)
+        .collect()
+}
+
+/// Concatenate LWE ciphertexts into a VectorLWE
+fn lwe_to_vector(ciphertexts: &[&crate::LWE]) -> Result<crate::VectorLWE, CryptoAPIError> {
+    let dimension = ciphertexts
+        .first()
+        .ok_or_else(|| ZeroCiphertextsInStructureError!(0))?
+        .dimension;
+    let mut container: Vec<Torus> = Vec::with_capacity(ciphertexts.len() * (dimension + 1));
+    for ct in ciphertexts.iter() {
+        if ct.dimension != dimension {
+            return Err(DimensionError!(ct.dimension, dimension));
+        }
+        container.extend_from_slice(ct.ciphertext.as_tensor().as_slice());
+    }
+    Ok(crate::VectorLWE {
+        ciphertexts: LweList::from_container(container, LweSize(dimension + 1)),
+        variances: ciphertexts.iter().map(|ct| ct.variance).collect(),
+        dimension,
+        nb_ciphertexts: ciphertexts.len(),
+        encoders: ciphertexts.iter().map(|ct| ct.encoder.clone()).collect(),
+    })
+}
+
+/// Split a VectorLWE into its LWE ciphertexts
+fn vector_to_lwe(vector: &crate::VectorLWE) -> Vec<crate::LWE> {
+    let ct_size = vector.get_ciphertext_size();
+    (0..vector.nb_ciphertexts)
+        .map(|i| crate::LWE {
+            ciphertext: LweCiphertext::from_container(
+                vector
+                    .ciphertexts
+                    .as_tensor()
+                    .get_sub((i * ct_size)..((i + 1) * ct_size))
+                    .as_slice()
+                    .to_vec(),
+            ),
+            variance: vector.variances[i],
+            dimension: vector.dimension,
+            encoder: vector.encoders[i].clone(),
+        })
+        .collect()
+}
+
+/// Evaluate a function on the indices 0..n, in parallel with the multithread feature
+fn par_map_index<O, F>(n: usize, f: F) -> Result<Vec<O>, CryptoAPIError>
+where
+    O: Send,
+    F: Fn(usize) -> Result<O, CryptoAPIError> + Sync + Send,
+{
+    #[cfg(feature = "multithread")]
+    let res = (0..n).into_par_iter().map(f).collect();
+    #[cfg(not(feature = "multithread"))]
+    let res = (0..n).map(f).collect();
+    res
+}
+
+/// Structure containing the secret keys needed to encrypt and decrypt integers.
+///
+/// # Attributes
+/// * `shortint` - the client key of the blocks
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct IntegerClientKey {
+    pub shortint: ShortIntClientKey,
+}
+
+impl IntegerClientKey {
+    /// Generate a new client key from the parameters of the blocks
+    ///
+    /// # Argument
+    /// * `params` - a ShortIntParams instance
+    ///
+    /// # Output
+    /// * a new IntegerClientKey
+    /// * UnsupportedModuliError - if the message and carry spaces of the blocks are too small
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// ```
+    pub fn new(params: &ShortIntParams) -> Result<IntegerClientKey, CryptoAPIError> {
+        IntegerClientKey::new_with_generator(params, &mut SecretRandomGenerator::new(None))
+    }
+
+    /// Generate a new client key from the parameters of the blocks with a provided random generator
+    ///
+    /// # Arguments
+    /// * `params` - a ShortIntParams instance
+    /// * `generator` - the random generator sampling the secret keys
+    ///
+    /// # Output
+    /// * a new IntegerClientKey
+    /// * UnsupportedModuliError - if the message and carry spaces of the blocks are too small
+    pub fn new_with_generator(
+        params: &ShortIntParams,
+        generator: &mut SecretRandomGenerator,
+    ) -> Result<IntegerClientKey, CryptoAPIError> {
+        check_moduli(params.message_modulus, params.carry_modulus)?;
+        Ok(IntegerClientKey {
+            shortint: ShortIntClientKey::new_with_generator(params, generator),
+        })
+    }
+
+    /// Generate the server key matching the client key
+    ///
+    /// # Output
+    /// * a new IntegerServerKey
+    pub fn get_server_key(&self) -> IntegerServerKey {
+        self.get_server_key_with_generator(&mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Generate the server key matching the client key with a provided encryption generator
+    ///
+    /// # Argument
+    /// * `generator` - the encryption generator sampling the masks and the noises of the keys
+    ///
+    /// # Output
+    /// * a new IntegerServerKey
+    pub fn get_server_key_with_generator(
+        &self,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> IntegerServerKey {
+        IntegerServerKey {
+            shortint: self.shortint.get_server_key_with_generator(generator),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        self.shortint.save(path)
+    }
+
+    pub fn load(path: &str) -> Result<IntegerClientKey, Box<dyn Error>> {
+        Ok(IntegerClientKey {
+            shortint: ShortIntClientKey::load(path)?,
+        })
+    }
+}
+
+/// Structure containing the public keys needed to compute on integers.
+///
+/// # Attributes
+/// * `shortint` - the server key of the blocks
+#[derive(Debug, PartialEq)]
+pub struct IntegerServerKey {
+    pub shortint: ShortIntServerKey,
+}
+
+impl IntegerServerKey {
+    /// Save the bootstrapping key and the key switching key in two files
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    pub fn save(&self, path_bsk: &str, path_ksk: &str) {
+        self.shortint.save(path_bsk, path_ksk);
+    }
+
+    /// Load a server key saved with `IntegerServerKey::save`
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    /// * `params` - the parameters used to generate the keys
+    ///
+    /// # Output
+    /// * an IntegerServerKey
+    pub fn load(path_bsk: &str, path_ksk: &str, params: &ShortIntParams) -> IntegerServerKey {
+        IntegerServerKey {
+            shortint: ShortIntServerKey::load(path_bsk, path_ksk, params),
+        }
+    }
+}
@@ -0,0 +1,1067 @@
+//! radix module: unsigned integers decomposed in base message_modulus, one short integer per digit
+//!
+//! The blocks are stored least significant first in a VectorLWE and every output of the server key
+//! has empty carry spaces. Carries are propagated with a parallel prefix over the blocks and
+//! comparisons are reduced with a tree, so the number of sequential bootstraps grows with the log
+//! of the number of blocks.
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+    check_moduli, get_digits, lwe_to_vector, par_map_index, vector_to_lwe, IntegerClientKey,
+    IntegerServerKey,
+};
+use crate::error::CryptoAPIError;
+use crate::read_from_file;
+use crate::shortint::ShortInt;
+use crate::write_to_file;
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// carry state of a block without carry out
+const NO_CARRY: usize = 0;
+/// carry state of a block with a carry out whatever its carry in
+const GENERATE: usize = 1;
+/// carry state of a block with a carry out only if it has a carry in
+const PROPAGATE: usize = 2;
+
+/// comparison states
+const LESS: usize = 0;
+const EQUAL: usize = 1;
+const GREATER: usize = 2;
+
+/// Native unsigned integers which can be encrypted as radix integers
+pub trait ClearUint: Copy + fmt::Debug + PartialEq + Send + Sync {
+    /// the number of bits of the integer
+    const NB_BITS: usize;
+
+    /// Convert the integer into a u64
+    fn to_u64(self) -> u64;
+
+    /// Convert a u64 into the integer, keeping only its NB_BITS least significant bits
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_clear_uint {
+    ($T: ty) => {
+        impl ClearUint for $T {
+            const NB_BITS: usize = std::mem::size_of::<$T>() * 8;
+
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            fn from_u64(value: u64) -> Self {
+                value as $T
+            }
+        }
+    };
+}
+
+impl_clear_uint!(u8);
+impl_clear_uint!(u16);
+impl_clear_uint!(u32);
+impl_clear_uint!(u64);
+
+/// Structure containing an encrypted unsigned integer with the same number of bits as T.
+///
+/// # Attributes
+/// * `blocks` - one LWE ciphertext per digit in base message_modulus, least significant first
+/// * `degrees` - the degree of each block, smaller than message_modulus
+/// * `message_modulus` - the size of the message space of the blocks
+/// * `carry_modulus` - the size of the carry space of the blocks
+/// * `phantom` - the native type of the integer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FheUint<T: ClearUint> {
+    pub blocks: crate::VectorLWE,
+    pub degrees: Vec<usize>,
+    pub message_modulus: usize,
+    pub carry_modulus: usize,
+    pub phantom: PhantomData<T>,
+}
+
+pub type FheUint8 = FheUint<u8>;
+pub type FheUint16 = FheUint<u16>;
+pub type FheUint32 = FheUint<u32>;
+pub type FheUint64 = FheUint<u64>;
+
+impl<T: ClearUint> FheUint<T> {
+    /// Return the number of blocks of an integer for a given message space
+    pub fn get_nb_blocks(message_modulus: usize) -> usize {
+        T::NB_BITS / message_modulus.trailing_zeros() as usize
+    }
+
+    /// Create a noiseless encryption of a value, which can be decrypted with any key of the right dimension
+    ///
+    /// # Arguments
+    /// * `value` - the value to encrypt
+    /// * `dimension` - the length the LWE mask
+    /// * `message_modulus` - the size of the message space of the blocks
+    /// * `carry_modulus` - the size of the carry space of the blocks
+    ///
+    /// # Output
+    /// * a new FheUint
+    /// * UnsupportedModuliError - if the moduli cannot be used for radix integers
+    pub fn trivial(
+        value: T,
+        dimension: usize,
+        message_modulus: usize,
+        carry_modulus: usize,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        check_moduli(message_modulus, carry_modulus)?;
+        let blocks = get_digits(
+            value.to_u64(),
+            message_modulus,
+            FheUint::<T>::get_nb_blocks(message_modulus),
+        )
+        .iter()
+        .map(|digit| ShortInt::trivial(*digit, dimension, message_modulus, carry_modulus))
+        .collect::<Result<Vec<ShortInt>, CryptoAPIError>>()?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Build an integer from its blocks, least significant first
+    ///
+    /// # Argument
+    /// * `blocks` - ShortInt with empty carry spaces
+    ///
+    /// # Output
+    /// * a new FheUint
+    /// * ZeroCiphertextsInStructureError - if there is no block
+    /// * UnsupportedModuliError - if the moduli cannot be used for radix integers
+    /// * NbCTError - if the number of blocks does not match the number of bits of T
+    /// * DimensionError - if the blocks have different dimensions
+    /// * ModulusMismatchError - if the blocks have different moduli
+    /// * CarryOverflowError - if a block has a non-empty carry space
+    pub fn from_blocks(blocks: &[ShortInt]) -> Result<FheUint<T>, CryptoAPIError> {
+        let first = blocks
+            .first()
+            .ok_or_else(|| ZeroCiphertextsInStructureError!(0))?;
+        let (message_modulus, carry_modulus) = (first.message_modulus, first.carry_modulus);
+        check_moduli(message_modulus, carry_modulus)?;
+        let nb_blocks = FheUint::<T>::get_nb_blocks(message_modulus);
+        if blocks.len() != nb_blocks {
+            return Err(NbCTError!(blocks.len(), nb_blocks));
+        }
+
+        for block in blocks.iter() {
+            if block.message_modulus != message_modulus {
+                return Err(ModulusMismatchError!(block.message_modulus, message_modulus));
+            }
+            if block.carry_modulus != carry_modulus {
+                return Err(ModulusMismatchError!(block.carry_modulus, carry_modulus));
+            }
+            if block.has_carry() {
+                return Err(CarryOverflowError!(block.degree, message_modulus - 1));
+            }
+        }
+
+        Ok(FheUint {
+            blocks: lwe_to_vector(&blocks.iter().map(|b| &b.ciphertext).collect::<Vec<_>>())?,
+            degrees: blocks.iter().map(|b| b.degree).collect(),
+            message_modulus,
+            carry_modulus,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Return a copy of the blocks of the integer, least significant first
+    pub fn get_blocks(&self) -> Vec<ShortInt> {
+        vector_to_lwe(&self.blocks)
+            .into_iter()
+            .zip(self.degrees.iter())
+            .map(|(ciphertext, degree)| ShortInt {
+                ciphertext,
+                message_modulus: self.message_modulus,
+                carry_modulus: self.carry_modulus,
+                degree: *degree,
+            })
+            .collect()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<FheUint<T>, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+impl<T: ClearUint> fmt::Display for FheUint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "FheUint {{\n         -> nb_bits = {}\n         -> nb_blocks = {}\n         -> message_modulus = {}\n         -> carry_modulus = {}\n         -> variances = {:?}\n}}",
+            T::NB_BITS,
+            self.blocks.nb_ciphertexts,
+            self.message_modulus,
+            self.carry_modulus,
+            self.blocks.variances
+        )
+    }
+}
+
+impl IntegerClientKey {
+    /// Encrypt a native unsigned integer, one block per digit in base message_modulus
+    ///
+    /// # Argument
+    /// * `value` - the integer to encrypt
+    ///
+    /// # Output
+    /// * a new FheUint
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    ///
+    /// let ct: FheUint8 = client_key.encrypt(213u8).unwrap();
+    /// assert_eq!(ct.blocks.nb_ciphertexts, 4);
+    /// assert_eq!(client_key.decrypt(&ct).unwrap(), 213u8);
+    /// ```
+    pub fn encrypt<T: ClearUint>(&self, value: T) -> Result<FheUint<T>, CryptoAPIError> {
+        self.encrypt_with_generator(value, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt a native unsigned integer with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `value` - the integer to encrypt
+    /// * `generator` - the encryption generator sampling the masks and the noises of the blocks
+    ///
+    /// # Output
+    /// * a new FheUint
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+    ///
+    /// let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    ///
+    /// // two generators with the same seeds produce the same ciphertexts
+    /// let ct_1: FheUint8 = client_key
+    ///     .encrypt_with_generator(213u8, &mut EncryptionRandomGenerator::new_deterministic(1, 2))
+    ///     .unwrap();
+    /// let ct_2: FheUint8 = client_key
+    ///     .encrypt_with_generator(213u8, &mut EncryptionRandomGenerator::new_deterministic(1, 2))
+    ///     .unwrap();
+    /// assert_eq!(ct_1, ct_2);
+    /// ```
+    pub fn encrypt_with_generator<T: ClearUint>(
+        &self,
+        value: T,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let message_modulus = self.shortint.params.message_modulus;
+        let blocks = get_digits(
+            value.to_u64(),
+            message_modulus,
+            FheUint::<T>::get_nb_blocks(message_modulus),
+        )
+        .iter()
+        .map(|digit| self.shortint.encrypt_with_generator(*digit, generator))
+        .collect::<Result<Vec<ShortInt>, CryptoAPIError>>()?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Decrypt an integer
+    ///
+    /// # Argument
+    /// * `ct` - a FheUint
+    ///
+    /// # Output
+    /// * the decrypted integer
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    pub fn decrypt<T: ClearUint>(&self, ct: &FheUint<T>) -> Result<T, CryptoAPIError> {
+        let mut value: u64 = 0;
+        for block in ct.get_blocks().iter().rev() {
+            value = value
+                .wrapping_mul(ct.message_modulus as u64)
+                .wrapping_add(self.shortint.decrypt_message_and_carry(block)? as u64);
+        }
+        Ok(T::from_u64(value))
+    }
+}
+
+impl IntegerServerKey {
+    /// Return a noiseless encryption of zero with the dimension and the moduli of the key
+    fn zero_block(&self) -> Result<ShortInt, CryptoAPIError> {
+        ShortInt::trivial(
+            0,
+            self.shortint.bsk.get_lwe_dimension(),
+            self.shortint.message_modulus,
+            self.shortint.carry_modulus,
+        )
+    }
+
+    /// Propagate the carries of blocks whose values are smaller than 2 * message_modulus,
+    /// the carry spaces of the output blocks are empty and the last carry is dropped
+    ///
+    /// The carry out of each block is computed with a Hillis-Steele prefix over the states
+    /// no carry / generate / propagate, which takes about log2(nb_blocks) sequential bootstraps.
+    fn propagate_carries(&self, blocks: Vec<ShortInt>) -> Result<Vec<ShortInt>, CryptoAPIError> {
+        let sk = &self.shortint;
+        let message_modulus = sk.message_modulus;
+        if blocks.iter().all(|block| !block.has_carry()) {
+            return Ok(blocks);
+        }
+        let nb_blocks = blocks.len();
+
+        // state of each block, the first block has no carry in so it never propagates
+        let mut states = par_map_index(nb_blocks - 1, |i| {
+            sk.apply_lookup_table(&blocks[i], |x| {
+                if x >= message_modulus {
+                    GENERATE
+                } else if x == message_modulus - 1 && i > 0 {
+                    PROPAGATE
+                } else {
+                    NO_CARRY
+                }
+            })
+        })?;
+
+        // prefix: at the end states[i] is the carry out of the block i
+        let mut distance = 1;
+        while distance < states.len() {
+            states = par_map_index(states.len(), |i| {
+                if i < distance {
+                    Ok(states[i].clone())
+                } else {
+                    sk.apply_bivariate_lookup_table(
+                        &states[i],
+                        &states[i - distance],
+                        |state, previous| {
+                            if state == PROPAGATE {
+                                previous
+                            } else {
+                                state
+                            }
+                        },
+                    )
+                }
+            })?;
+            distance *= 2;
+        }
+
+        // add the carries and clear the carry spaces
+        par_map_index(nb_blocks, |i| {
+            if i == 0 {
+                sk.message_extract(&blocks[0])
+            } else {
+                sk.message_extract(&sk.add(&blocks[i], &states[i - 1])?)
+            }
+        })
+    }
+
+    /// Add two lists of blocks with empty carry spaces and propagate the carries
+    fn add_blocks(
+        &self,
+        blocks_1: &[ShortInt],
+        blocks_2: &[ShortInt],
+    ) -> Result<Vec<ShortInt>, CryptoAPIError> {
+        let sums = blocks_1
+            .iter()
+            .zip(blocks_2.iter())
+            .map(|(block_1, block_2)| self.shortint.add(block_1, block_2))
+            .collect::<Result<Vec<ShortInt>, CryptoAPIError>>()?;
+        self.propagate_carries(sums)
+    }
+
+    /// Sum the rows of a schoolbook multiplication, `lsb(i, j)` and `msb(i, j)` returning the
+    /// least and most significant digits of the product of the block i by the digit j,
+    /// the digits which are not listed being zero
+    fn sum_partial_products<F, G>(
+        &self,
+        nb_blocks: usize,
+        digits: &[usize],
+        lsb: F,
+        msb: G,
+    ) -> Result<Vec<ShortInt>, CryptoAPIError>
+    where
+        F: Fn(usize, usize) -> Result<ShortInt, CryptoAPIError> + Sync + Send,
+        G: Fn(usize, usize) -> Result<ShortInt, CryptoAPIError> + Sync + Send,
+    {
+        let zero = self.zero_block()?;
+
+        // one row per digit, shifted by the index of the digit
+        let mut rows = par_map_index(digits.len(), |row| {
+            let j = digits[row];
+            let nb_products = nb_blocks - j;
+
+            // the most significant part of the last product is dropped
+            let products = par_map_index(2 * nb_products - 1, |k| {
+                if k < nb_products {
+                    lsb(k, j)
+                } else {
+                    msb(k - nb_products, j)
+                }
+            })?;
+            let mut blocks = vec![zero.clone(); j];
+            blocks.push(products[0].clone());
+            for i in 1..nb_products {
+                blocks.push(
+                    self.shortint
+                        .add(&products[i], &products[nb_products + i - 1])?,
+                );
+            }
+            self.propagate_carries(blocks)
+        })?;
+
+        // sum the rows with a tree
+        while rows.len() > 1 {
+            rows = par_map_index(rows.len().div_ceil(2), |k| {
+                if 2 * k + 1 < rows.len() {
+                    self.add_blocks(&rows[2 * k], &rows[2 * k + 1])
+                } else {
+                    Ok(rows[2 * k].clone())
+                }
+            })?;
+        }
+        Ok(rows.pop().unwrap_or_else(|| vec![zero; nb_blocks]))
+    }
+
+    /// Compare two lists of blocks and reduce the states of the blocks with a tree,
+    /// the output encrypts LESS, EQUAL or GREATER
+    fn compare(
+        &self,
+        blocks_1: &[ShortInt],
+        blocks_2: &[ShortInt],
+    ) -> Result<ShortInt, CryptoAPIError> {
+        let sk = &self.shortint;
+        let mut states = par_map_index(blocks_1.len(), |i| {
+            sk.apply_bivariate_lookup_table(&blocks_1[i], &blocks_2[i], |x, y| {
+                if x < y {
+                    LESS
+                } else if x == y {
+                    EQUAL
+                } else {
+                    GREATER
+                }
+            })
+        })?;
+
+        // the most significant block decides unless it is equal
+        while states.len() > 1 {
+            states = par_map_index(states.len().div_ceil(2), |k| {
+                if 2 * k + 1 < states.len() {
+                    sk.apply_bivariate_lookup_table(
+                        &states[2 * k + 1],
+                        &states[2 * k],
+                        |high, low| {
+                            if high == EQUAL {
+                                low
+                            } else {
+                                high
+                            }
+                        },
+                    )
+                } else {
+                    Ok(states[2 * k].clone())
+                }
+            })?;
+        }
+        Ok(states.remove(0))
+    }
+
+    /// Compare two integers and return an encryption of 1 if the predicate holds on the state, 0 otherwise
+    fn compare_with<T: ClearUint, F: Fn(usize) -> bool>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+        predicate: F,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let state = self.compare(&ct_1.get_blocks(), &ct_2.get_blocks())?;
+        let mut blocks = self.trivial(T::from_u64(0))?.get_blocks();
+        blocks[0] = self
+            .shortint
+            .apply_lookup_table(&state, |s| predicate(s) as usize)?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Compare two integers and keep the blocks of ct_1 if the predicate holds on the state,
+    /// the blocks of ct_2 otherwise
+    fn select<T: ClearUint, F: Fn(usize) -> bool + Sync + Send>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+        keep_first: F,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let sk = &self.shortint;
+        let (blocks_1, blocks_2) = (ct_1.get_blocks(), ct_2.get_blocks());
+        let state = self.compare(&blocks_1, &blocks_2)?;
+        let blocks = par_map_index(blocks_1.len(), |i| {
+            let kept_1 = sk.apply_bivariate_lookup_table(&state, &blocks_1[i], |s, x| {
+                if keep_first(s) {
+                    x
+                } else {
+                    0
+                }
+            })?;
+            let kept_2 = sk.apply_bivariate_lookup_table(&state, &blocks_2[i], |s, x| {
+                if keep_first(s) {
+                    0
+                } else {
+                    x
+                }
+            })?;
+
+            // one of the two terms is zero
+            let mut res = sk.add(&kept_1, &kept_2)?;
+            res.degree = kept_1.degree.max(kept_2.degree);
+            Ok(res)
+        })?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Apply a bivariate function block by block
+    fn blockwise<T: ClearUint, F>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+        f: F,
+    ) -> Result<FheUint<T>, CryptoAPIError>
+    where
+        F: Fn(&ShortInt, &ShortInt) -> Result<ShortInt, CryptoAPIError> + Sync + Send,
+    {
+        let (blocks_1, blocks_2) = (ct_1.get_blocks(), ct_2.get_blocks());
+        let blocks = par_map_index(blocks_1.len(), |i| f(&blocks_1[i], &blocks_2[i]))?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Apply a function to each block and the matching digit of a scalar
+    fn blockwise_scalar<T: ClearUint, F>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+        f: F,
+    ) -> Result<FheUint<T>, CryptoAPIError>
+    where
+        F: Fn(&ShortInt, usize) -> Result<ShortInt, CryptoAPIError> + Sync + Send,
+    {
+        let blocks = ct.get_blocks();
+        let digits = get_digits(scalar.to_u64(), ct.message_modulus, blocks.len());
+        let blocks = par_map_index(blocks.len(), |i| f(&blocks[i], digits[i]))?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Create a noiseless encryption of a value with the dimension and the moduli of the key
+    ///
+    /// # Argument
+    /// * `value` - the integer to encrypt
+    ///
+    /// # Output
+    /// * a new FheUint
+    pub fn trivial<T: ClearUint>(&self, value: T) -> Result<FheUint<T>, CryptoAPIError> {
+        FheUint::trivial(
+            value,
+            self.shortint.bsk.get_lwe_dimension(),
+            self.shortint.message_modulus,
+            self.shortint.carry_modulus,
+        )
+    }
+
+    /// Compute ct_1 + ct_2 with wrapping
+    ///
+    /// # Arguments
+    /// * `ct_1` - a FheUint
+    /// * `ct_2` - a FheUint
+    ///
+    /// # Output
+    /// * a new FheUint
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1: FheUint8 = client_key.encrypt(200u8).unwrap();
+    /// let ct_2: FheUint8 = client_key.encrypt(100u8).unwrap();
+    ///
+    /// let ct_res = server_key.add(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 200u8.wrapping_add(100));
+    /// ```
+    pub fn add<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        FheUint::from_blocks(&self.add_blocks(&ct_1.get_blocks(), &ct_2.get_blocks())?)
+    }
+
+    /// Compute ct_1 - ct_2 with wrapping
+    ///
+    /// # Arguments
+    /// * `ct_1` - a FheUint
+    /// * `ct_2` - a FheUint
+    ///
+    /// # Output
+    /// * a new FheUint
+    pub fn sub<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        // the offsets m, m-1, ..., m-1 of the blocks sum to m^nb_blocks which is zero
+        let message_modulus = self.shortint.message_modulus;
+        let differences = ct_1
+            .get_blocks()
+            .iter()
+            .zip(ct_2.get_blocks().iter())
+            .enumerate()
+            .map(|(i, (block_1, block_2))| {
+                let offset = if i == 0 {
+                    message_modulus
+                } else {
+                    message_modulus - 1
+                };
+                self.shortint.sub_with_offset(block_1, block_2, offset)
+            })
+            .collect::<Result<Vec<ShortInt>, CryptoAPIError>>()?;
+        FheUint::from_blocks(&self.propagate_carries(differences)?)
+    }
+
+    /// Compute -ct with wrapping
+    pub fn neg<T: ClearUint>(&self, ct: &FheUint<T>) -> Result<FheUint<T>, CryptoAPIError> {
+        self.sub(&self.trivial(T::from_u64(0))?, ct)
+    }
+
+    /// Compute ct + scalar with wrapping
+    pub fn scalar_add<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let blocks = ct.get_blocks();
+        let sums = get_digits(scalar.to_u64(), ct.message_modulus, blocks.len())
+            .iter()
+            .zip(blocks.iter())
+            .map(|(digit, block)| self.shortint.scalar_add(block, *digit))
+            .collect::<Result<Vec<ShortInt>, CryptoAPIError>>()?;
+        FheUint::from_blocks(&self.propagate_carries(sums)?)
+    }
+
+    /// Compute ct - scalar with wrapping
+    pub fn scalar_sub<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.scalar_add(ct, T::from_u64(scalar.to_u64().wrapping_neg()))
+    }
+
+    /// Compute ct_1 * ct_2 with wrapping
+    ///
+    /// # Arguments
+    /// * `ct_1` - a FheUint
+    /// * `ct_2` - a FheUint
+    ///
+    /// # Output
+    /// * a new FheUint
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1: FheUint8 = client_key.encrypt(27u8).unwrap();
+    /// let ct_2: FheUint8 = client_key.encrypt(11u8).unwrap();
+    ///
+    /// let ct_res = server_key.mul(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 27u8.wrapping_mul(11));
+    /// ```
+    pub fn mul<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let (blocks_1, blocks_2) = (ct_1.get_blocks(), ct_2.get_blocks());
+        let nb_blocks = blocks_1.len();
+        let digits: Vec<usize> = (0..nb_blocks).collect();
+        let blocks = self.sum_partial_products(
+            nb_blocks,
+            &digits,
+            |i, j| self.shortint.mul_lsb(&blocks_1[i], &blocks_2[j]),
+            |i, j| self.shortint.mul_msb(&blocks_1[i], &blocks_2[j]),
+        )?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Compute ct * scalar with wrapping, the zero digits of the scalar are skipped
+    pub fn scalar_mul<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let blocks = ct.get_blocks();
+        let nb_blocks = blocks.len();
+        let message_modulus = ct.message_modulus;
+        let digits = get_digits(scalar.to_u64(), message_modulus, nb_blocks);
+        let non_zero: Vec<usize> = (0..nb_blocks).filter(|j| digits[*j] != 0).collect();
+        let blocks = self.sum_partial_products(
+            nb_blocks,
+            &non_zero,
+            |i, j| {
+                let digit = digits[j];
+                self.shortint
+                    .apply_lookup_table(&blocks[i], |x| (x * digit) % message_modulus)
+            },
+            |i, j| {
+                let digit = digits[j];
+                self.shortint
+                    .apply_lookup_table(&blocks[i], |x| (x * digit) / message_modulus)
+            },
+        )?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Compute ct_1 == ct_2 as an encrypted 0 or 1
+    pub fn eq<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.compare_with(ct_1, ct_2, |s| s == EQUAL)
+    }
+
+    /// Compute ct_1 != ct_2 as an encrypted 0 or 1
+    pub fn ne<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.compare_with(ct_1, ct_2, |s| s != EQUAL)
+    }
+
+    /// Compute ct_1 < ct_2 as an encrypted 0 or 1
+    ///
+    /// # Arguments
+    /// * `ct_1` - a FheUint
+    /// * `ct_2` - a FheUint
+    ///
+    /// # Output
+    /// * a new FheUint encrypting 0 or 1
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1: FheUint8 = client_key.encrypt(97u8).unwrap();
+    /// let ct_2: FheUint8 = client_key.encrypt(98u8).unwrap();
+    ///
+    /// let ct_res = server_key.lt(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 1);
+    /// ```
+    pub fn lt<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.compare_with(ct_1, ct_2, |s| s == LESS)
+    }
+
+    /// Compute ct_1 <= ct_2 as an encrypted 0 or 1
+    pub fn le<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.compare_with(ct_1, ct_2, |s| s != GREATER)
+    }
+
+    /// Compute ct_1 > ct_2 as an encrypted 0 or 1
+    pub fn gt<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.compare_with(ct_1, ct_2, |s| s == GREATER)
+    }
+
+    /// Compute ct_1 >= ct_2 as an encrypted 0 or 1
+    pub fn ge<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.compare_with(ct_1, ct_2, |s| s != LESS)
+    }
+
+    /// Compute ct == scalar as an encrypted 0 or 1
+    pub fn scalar_eq<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.eq(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute ct != scalar as an encrypted 0 or 1
+    pub fn scalar_ne<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.ne(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute ct < scalar as an encrypted 0 or 1
+    pub fn scalar_lt<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.lt(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute ct <= scalar as an encrypted 0 or 1
+    pub fn scalar_le<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.le(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute ct > scalar as an encrypted 0 or 1
+    pub fn scalar_gt<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.gt(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute ct >= scalar as an encrypted 0 or 1
+    pub fn scalar_ge<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.ge(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute the minimum of ct_1 and ct_2
+    pub fn min<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.select(ct_1, ct_2, |s| s != GREATER)
+    }
+
+    /// Compute the maximum of ct_1 and ct_2
+    pub fn max<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.select(ct_1, ct_2, |s| s != LESS)
+    }
+
+    /// Compute the minimum of ct and scalar
+    pub fn scalar_min<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.min(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute the maximum of ct and scalar
+    pub fn scalar_max<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.max(ct, &self.trivial(scalar)?)
+    }
+
+    /// Compute ct << shift, the shift being reduced modulo the number of bits as with wrapping_shl
+    ///
+    /// # Arguments
+    /// * `ct` - a FheUint
+    /// * `shift` - the number of bits to shift
+    ///
+    /// # Output
+    /// * a new FheUint
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct: FheUint8 = client_key.encrypt(0b1011_0110u8).unwrap();
+    ///
+    /// let ct_res = server_key.shl(&ct, 3).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 0b1011_0000u8);
+    /// ```
+    pub fn shl<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        shift: u32,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let sk = &self.shortint;
+        let blocks = ct.get_blocks();
+        let message_modulus = ct.message_modulus;
+        let bits_per_block = message_modulus.trailing_zeros() as usize;
+        let shift = shift as usize % T::NB_BITS;
+        let (block_shift, bit_shift) = (shift / bits_per_block, shift % bits_per_block);
+        let zero = self.zero_block()?;
+
+        // the output block k gathers the low bits of the block k - block_shift
+        // and the high bits of the block k - block_shift - 1
+        let blocks = par_map_index(blocks.len(), |k| {
+            if k < block_shift {
+                return Ok(zero.clone());
+            }
+            let i = k - block_shift;
+            if bit_shift == 0 {
+                return Ok(blocks[i].clone());
+            }
+            let low = sk.apply_lookup_table(&blocks[i], |x| (x << bit_shift) % message_modulus)?;
+            if i == 0 {
+                return Ok(low);
+            }
+            let high =
+                sk.apply_lookup_table(&blocks[i - 1], |x| x >> (bits_per_block - bit_shift))?;
+            sk.add(&low, &high)
+        })?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Compute ct >> shift, the shift being reduced modulo the number of bits as with wrapping_shr
+    pub fn shr<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        shift: u32,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        let sk = &self.shortint;
+        let blocks = ct.get_blocks();
+        let nb_blocks = blocks.len();
+        let message_modulus = ct.message_modulus;
+        let bits_per_block = message_modulus.trailing_zeros() as usize;
+        let shift = shift as usize % T::NB_BITS;
+        let (block_shift, bit_shift) = (shift / bits_per_block, shift % bits_per_block);
+        let zero = self.zero_block()?;
+
+        // the output block k gathers the high bits of the block k + block_shift
+        // and the low bits of the block k + block_shift + 1
+        let blocks = par_map_index(nb_blocks, |k| {
+            let i = k + block_shift;
+            if i >= nb_blocks {
+                return Ok(zero.clone());
+            }
+            if bit_shift == 0 {
+                return Ok(blocks[i].clone());
+            }
+            let high = sk.apply_lookup_table(&blocks[i], |x| x >> bit_shift)?;
+            if i + 1 == nb_blocks {
+                return Ok(high);
+            }
+            let low = sk.apply_lookup_table(&blocks[i + 1], |x| {
+                (x << (bits_per_block - bit_shift)) % message_modulus
+            })?;
+            sk.add(&high, &low)
+        })?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Compute the bitwise and of ct_1 and ct_2
+    pub fn bitand<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.blockwise(ct_1, ct_2, |block_1, block_2| {
+            self.shortint.bitand(block_1, block_2)
+        })
+    }
+
+    /// Compute the bitwise or of ct_1 and ct_2
+    pub fn bitor<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.blockwise(ct_1, ct_2, |block_1, block_2| {
+            self.shortint.bitor(block_1, block_2)
+        })
+    }
+
+    /// Compute the bitwise xor of ct_1 and ct_2
+    pub fn bitxor<T: ClearUint>(
+        &self,
+        ct_1: &FheUint<T>,
+        ct_2: &FheUint<T>,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.blockwise(ct_1, ct_2, |block_1, block_2| {
+            self.shortint.bitxor(block_1, block_2)
+        })
+    }
+
+    /// Compute the bitwise not of ct, which only needs linear operations
+    pub fn bitnot<T: ClearUint>(&self, ct: &FheUint<T>) -> Result<FheUint<T>, CryptoAPIError> {
+        let zero = self.zero_block()?;
+        let message_modulus = self.shortint.message_modulus;
+        let blocks = ct
+            .get_blocks()
+            .iter()
+            .map(|block| {
+                self.shortint
+                    .sub_with_offset(&zero, block, message_modulus - 1)
+            })
+            .collect::<Result<Vec<ShortInt>, CryptoAPIError>>()?;
+        FheUint::from_blocks(&blocks)
+    }
+
+    /// Compute the bitwise and of ct and scalar
+    pub fn scalar_bitand<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.blockwise_scalar(ct, scalar, |block, digit| {
+            self.shortint.apply_lookup_table(block, |x| x & digit)
+        })
+    }
+
+    /// Compute the bitwise or of ct and scalar
+    pub fn scalar_bitor<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.blockwise_scalar(ct, scalar, |block, digit| {
+            self.shortint.apply_lookup_table(block, |x| x | digit)
+        })
+    }
+
+    /// Compute the bitwise xor of ct and scalar
+    pub fn scalar_bitxor<T: ClearUint>(
+        &self,
+        ct: &FheUint<T>,
+        scalar: T,
+    ) -> Result<FheUint<T>, CryptoAPIError> {
+        self.blockwise_scalar(ct, scalar, |block, digit| {
+            self.shortint.apply_lookup_table(block, |x| x ^ digit)
+        })
+    }
+}
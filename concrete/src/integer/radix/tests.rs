@@ -0,0 +1,249 @@
+use super::*;
+use crate::{SHORTINT128_MESSAGE_1_CARRY_1, SHORTINT128_MESSAGE_2_CARRY_2};
+
+const NB_TESTS: usize = 3;
+
+fn random_u64() -> u64 {
+    let rs: Vec<u64> = concrete_core::math::random::RandomGenerator::new(None)
+        .random_uniform_tensor(1)
+        .into_container();
+    rs[0]
+}
+
+#[test]
+fn test_encrypt_x_decrypt() {
+    // the blocks of the radix integers must be able to store the carry states
+    assert!(IntegerClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1).is_err());
+
+    let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let server_key = client_key.get_server_key();
+
+    let clear_8 = random_u64() as u8;
+    let ct: FheUint8 = client_key.encrypt(clear_8).unwrap();
+    assert_eq!(ct.blocks.nb_ciphertexts, 4);
+    assert_eq!(clear_8, client_key.decrypt(&ct).unwrap());
+
+    let clear_64 = random_u64();
+    let ct: FheUint64 = client_key.encrypt(clear_64).unwrap();
+    assert_eq!(ct.blocks.nb_ciphertexts, 32);
+    assert_eq!(clear_64, client_key.decrypt(&ct).unwrap());
+
+    let clear_16 = random_u64() as u16;
+    let ct: FheUint16 = server_key.trivial(clear_16).unwrap();
+    assert_eq!(clear_16, client_key.decrypt(&ct).unwrap());
+
+    // the number of blocks must match the type
+    let blocks = ct.get_blocks();
+    assert!(FheUint8::from_blocks(&blocks).is_err());
+    assert_eq!(FheUint16::from_blocks(&blocks).unwrap(), ct);
+}
+
+#[test]
+fn test_arithmetic_u8() {
+    let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let server_key = client_key.get_server_key();
+
+    for _ in 0..NB_TESTS {
+        let clear_1 = random_u64() as u8;
+        let clear_2 = random_u64() as u8;
+        let ct_1: FheUint8 = client_key.encrypt(clear_1).unwrap();
+        let ct_2: FheUint8 = client_key.encrypt(clear_2).unwrap();
+
+        let ct_res = server_key.add(&ct_1, &ct_2).unwrap();
+        assert_eq!(
+            clear_1.wrapping_add(clear_2),
+            client_key.decrypt(&ct_res).unwrap()
+        );
+        let ct_res = server_key.sub(&ct_1, &ct_2).unwrap();
+        assert_eq!(
+            clear_1.wrapping_sub(clear_2),
+            client_key.decrypt(&ct_res).unwrap()
+        );
+        let ct_res = server_key.neg(&ct_1).unwrap();
+        assert_eq!(clear_1.wrapping_neg(), client_key.decrypt(&ct_res).unwrap());
+        let ct_res = server_key.mul(&ct_1, &ct_2).unwrap();
+        assert_eq!(
+            clear_1.wrapping_mul(clear_2),
+            client_key.decrypt(&ct_res).unwrap()
+        );
+
+        // scalar operations
+        let ct_res = server_key.scalar_add(&ct_1, clear_2).unwrap();
+        assert_eq!(
+            clear_1.wrapping_add(clear_2),
+            client_key.decrypt(&ct_res).unwrap()
+        );
+        let ct_res = server_key.scalar_sub(&ct_1, clear_2).unwrap();
+        assert_eq!(
+            clear_1.wrapping_sub(clear_2),
+            client_key.decrypt(&ct_res).unwrap()
+        );
+        let ct_res = server_key.scalar_mul(&ct_1, clear_2).unwrap();
+        assert_eq!(
+            clear_1.wrapping_mul(clear_2),
+            client_key.decrypt(&ct_res).unwrap()
+        );
+    }
+
+    // carries going through every block
+    let ct_1: FheUint8 = client_key.encrypt(255).unwrap();
+    let ct_2: FheUint8 = client_key.encrypt(1).unwrap();
+    let ct_res = server_key.add(&ct_1, &ct_2).unwrap();
+    assert_eq!(0, client_key.decrypt(&ct_res).unwrap());
+    let ct_res = server_key.sub(&ct_2, &ct_1).unwrap();
+    assert_eq!(2, client_key.decrypt(&ct_res).unwrap());
+    let ct_res = server_key.scalar_mul(&ct_1, 0).unwrap();
+    assert_eq!(0, client_key.decrypt(&ct_res).unwrap());
+}
+
+#[test]
+fn test_arithmetic_u16() {
+    let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let server_key = client_key.get_server_key();
+
+    let clear_1 = random_u64() as u16;
+    let clear_2 = random_u64() as u16;
+    let ct_1: FheUint16 = client_key.encrypt(clear_1).unwrap();
+    let ct_2: FheUint16 = client_key.encrypt(clear_2).unwrap();
+
+    let ct_res = server_key.add(&ct_1, &ct_2).unwrap();
+    assert_eq!(
+        clear_1.wrapping_add(clear_2),
+        client_key.decrypt(&ct_res).unwrap()
+    );
+    let ct_res = server_key.sub(&ct_1, &ct_2).unwrap();
+    assert_eq!(
+        clear_1.wrapping_sub(clear_2),
+        client_key.decrypt(&ct_res).unwrap()
+    );
+    let ct_res = server_key.mul(&ct_1, &ct_2).unwrap();
+    assert_eq!(
+        clear_1.wrapping_mul(clear_2),
+        client_key.decrypt(&ct_res).unwrap()
+    );
+}
+
+#[test]
+fn test_comparisons_u8() {
+    let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let server_key = client_key.get_server_key();
+
+    // the last pair is equal
+    let mut pairs: Vec<(u8, u8)> = (0..NB_TESTS)
+        .map(|_| (random_u64() as u8, random_u64() as u8))
+        .collect();
+    let clear = random_u64() as u8;
+    pairs.push((clear, clear));
+
+    for (clear_1, clear_2) in pairs.iter().cloned() {
+        let ct_1: FheUint8 = client_key.encrypt(clear_1).unwrap();
+        let ct_2: FheUint8 = client_key.encrypt(clear_2).unwrap();
+
+        let decrypt = |ct: FheUint8| client_key.decrypt(&ct).unwrap();
+        assert_eq!(
+            (clear_1 == clear_2) as u8,
+            decrypt(server_key.eq(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 != clear_2) as u8,
+            decrypt(server_key.ne(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 < clear_2) as u8,
+            decrypt(server_key.lt(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 <= clear_2) as u8,
+            decrypt(server_key.le(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 > clear_2) as u8,
+            decrypt(server_key.gt(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 >= clear_2) as u8,
+            decrypt(server_key.ge(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            clear_1.min(clear_2),
+            decrypt(server_key.min(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            clear_1.max(clear_2),
+            decrypt(server_key.max(&ct_1, &ct_2).unwrap())
+        );
+
+        // scalar comparisons
+        assert_eq!(
+            (clear_1 == clear_2) as u8,
+            decrypt(server_key.scalar_eq(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 < clear_2) as u8,
+            decrypt(server_key.scalar_lt(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            (clear_1 >= clear_2) as u8,
+            decrypt(server_key.scalar_ge(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            clear_1.min(clear_2),
+            decrypt(server_key.scalar_min(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            clear_1.max(clear_2),
+            decrypt(server_key.scalar_max(&ct_1, clear_2).unwrap())
+        );
+    }
+}
+
+#[test]
+fn test_shifts_and_bitwise_u8() {
+    let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let server_key = client_key.get_server_key();
+
+    for _ in 0..NB_TESTS {
+        let clear_1 = random_u64() as u8;
+        let clear_2 = random_u64() as u8;
+        let shift = (random_u64() % 16) as u32;
+        let ct_1: FheUint8 = client_key.encrypt(clear_1).unwrap();
+        let ct_2: FheUint8 = client_key.encrypt(clear_2).unwrap();
+
+        let decrypt = |ct: FheUint8| client_key.decrypt(&ct).unwrap();
+        assert_eq!(
+            clear_1.wrapping_shl(shift),
+            decrypt(server_key.shl(&ct_1, shift).unwrap())
+        );
+        assert_eq!(
+            clear_1.wrapping_shr(shift),
+            decrypt(server_key.shr(&ct_1, shift).unwrap())
+        );
+        assert_eq!(
+            clear_1 & clear_2,
+            decrypt(server_key.bitand(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            clear_1 | clear_2,
+            decrypt(server_key.bitor(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(
+            clear_1 ^ clear_2,
+            decrypt(server_key.bitxor(&ct_1, &ct_2).unwrap())
+        );
+        assert_eq!(!clear_1, decrypt(server_key.bitnot(&ct_1).unwrap()));
+
+        // scalar bitwise operations
+        assert_eq!(
+            clear_1 & clear_2,
+            decrypt(server_key.scalar_bitand(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            clear_1 | clear_2,
+            decrypt(server_key.scalar_bitor(&ct_1, clear_2).unwrap())
+        );
+        assert_eq!(
+            clear_1 ^ clear_2,
+            decrypt(server_key.scalar_bitxor(&ct_1, clear_2).unwrap())
+        );
+    }
+}
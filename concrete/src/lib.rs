@@ -9,13 +9,18 @@ use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::Path;
 
+use concrete_npe as npe;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 pub use error::*;
 
-pub use crate::traits::GenericAdd;
+pub use crate::traits::{
+    GenericAdd, GenericMul, GenericNeg, GenericPolicyOps, GenericSub, LWEOperations,
+    OperatorPolicy, VectorLWEOperations, WithPolicy,
+};
 
+#[macro_use]
 pub mod traits;
 
 pub type Torus = u64;
@@ -151,6 +156,63 @@ fn read_from_file<P: AsRef<Path>, U: DeserializeOwned>(path: P) -> Result<U, Box
     Ok(u)
 }
 
+/// Compute with concrete-npe the variance of the noise after a bootstrap followed by a key switch
+///
+/// # Argument
+/// * `lwe_params` - the parameters of the LWE secret key of the input and of the output
+/// * `rlwe_params` - the parameters of the RLWE secret key used in the bootstrapping key
+/// * `bsk_base_log` - the log2 of the decomposition base of the bootstrapping key
+/// * `bsk_level` - the number of levels of the decomposition of the bootstrapping key
+/// * `ksk_base_log` - the log2 of the decomposition base of the key switching key
+/// * `ksk_level` - the number of levels of the decomposition of the key switching key
+///
+/// # Output
+/// * the variance of the output of the key switch
+pub(crate) fn bootstrap_key_switch_variance(
+    lwe_params: &LWEParams,
+    rlwe_params: &RLWEParams,
+    bsk_base_log: usize,
+    bsk_level: usize,
+    ksk_base_log: usize,
+    ksk_level: usize,
+) -> f64 {
+    let var_bsk = f64::powi(f64::powi(2., rlwe_params.log2_std_dev), 2);
+    let var_ksk = f64::powi(f64::powi(2., lwe_params.log2_std_dev), 2);
+
+    // noise after the bootstrap
+    let var_bootstrap = <Torus as npe::Cross>::bootstrap(
+        lwe_params.dimension,
+        rlwe_params.dimension,
+        bsk_level,
+        bsk_base_log,
+        rlwe_params.polynomial_size,
+        var_bsk,
+    );
+
+    // noise after the key switch
+    <Torus as npe::LWE>::key_switch(
+        rlwe_params.dimension * rlwe_params.polynomial_size,
+        ksk_level,
+        ksk_base_log,
+        var_ksk,
+        var_bootstrap,
+    )
+}
+
+/// Compute with concrete-npe the variance of the drift added to the input of a bootstrap by the
+/// rounding of its mask in [0, 2N[
+///
+/// # Argument
+/// * `lwe_dimension` - the size of the LWE mask of the input of the bootstrap
+/// * `polynomial_size` - the number of coefficients of the polynomials of the bootstrapping key
+///
+/// # Output
+/// * the variance of the drift
+pub(crate) fn bootstrap_drift_variance(lwe_dimension: usize, polynomial_size: usize) -> f64 {
+    let two_n = (2 * polynomial_size) as f64;
+    npe::cross::drift_index_lut(lwe_dimension) / (two_n * two_n)
+}
+
 // #[derive(Serialize, Deserialize)]
 // #[serde(remote = "Complex64")]
 // struct SerdeCtorus {
@@ -212,6 +274,16 @@ pub_mod_use!(lwe_bsk);
 pub_mod_use!(lwe_secret_key);
 pub_mod_use!(rlwe_params);
 pub_mod_use!(rlwe_secret_key);
+pub_mod_use!(boolean);
+pub_mod_use!(shortint);
+pub_mod_use!(integer);
+pub_mod_use!(graph);
+pub_mod_use!(simulation);
+pub_mod_use!(debug);
+pub_mod_use!(noise_measurement);
+pub_mod_use!(optimizer);
 
+#[cfg(test)]
+mod tests_security;
 #[cfg(test)]
 mod tests_serde;
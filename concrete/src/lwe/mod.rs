@@ -4,20 +4,22 @@
 mod tests;
 
 use crate::error::CryptoAPIError;
-use crate::traits::GenericAdd;
+use crate::traits::{
+    GenericAdd, GenericMul, GenericNeg, GenericPolicyOps, GenericSub, LWEOperations,
+    OperatorPolicy,
+};
 use crate::{read_from_file, write_to_file, Torus};
 use backtrace::Backtrace;
 use colored::Colorize;
-use concrete_commons::{Numeric, StandardDev};
-use concrete_core::math::polynomial::PolynomialSize;
-use concrete_core::math::random::EncryptionRandomGenerator;
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::LweSize;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
 use concrete_core::{
     crypto::{
         self,
         encoding::{Cleartext, Plaintext},
-        glwe::GlweCiphertext,
         lwe::LweCiphertext,
-        GlweSize, LweSize,
     },
     math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor},
 };
@@ -26,6 +28,9 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
 
+/// Failure probability of a bit extraction above which a warning is printed
+pub const BIT_EXTRACTION_MAX_FAILURE_PROBABILITY: f64 = 1e-9;
+
 /// Structure containing a single LWE ciphertext.
 ///
 /// # Attributes
@@ -52,10 +57,132 @@ impl GenericAdd<f64, CryptoAPIError> for LWE {
 
 impl GenericAdd<&LWE, CryptoAPIError> for LWE {
     fn add(&self, right: &LWE) -> Result<LWE, CryptoAPIError> {
-        self.add_with_padding(right)
+        self.add_with_policy(right, OperatorPolicy::Padding)
     }
     fn add_inplace(&mut self, right: &LWE) -> Result<(), CryptoAPIError> {
-        self.add_with_padding_inplace(right)
+        self.add_with_policy_inplace(right, OperatorPolicy::Padding)
+    }
+}
+
+impl GenericPolicyOps<CryptoAPIError> for LWE {
+    fn add_with_policy_inplace(
+        &mut self,
+        right: &LWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        match policy {
+            OperatorPolicy::Padding => self.add_with_padding_inplace(right),
+            OperatorPolicy::Centered => self.add_centered_inplace(right),
+            OperatorPolicy::Exact => self.add_with_padding_exact_inplace(right),
+        }
+    }
+
+    fn sub_with_policy_inplace(
+        &mut self,
+        right: &LWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        match policy {
+            OperatorPolicy::Padding => self.sub_with_padding_inplace(right),
+            OperatorPolicy::Centered => self.add_centered_inplace(&right.opposite()?),
+            OperatorPolicy::Exact => self.sub_with_padding_exact_inplace(right),
+        }
+    }
+}
+
+impl GenericSub<f64, CryptoAPIError> for LWE {
+    fn sub(&self, right: f64) -> Result<LWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(-right)
+    }
+    fn sub_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        self.add_constant_dynamic_encoder_inplace(-right)
+    }
+}
+
+impl GenericSub<&LWE, CryptoAPIError> for LWE {
+    fn sub(&self, right: &LWE) -> Result<LWE, CryptoAPIError> {
+        self.sub_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn sub_inplace(&mut self, right: &LWE) -> Result<(), CryptoAPIError> {
+        self.sub_with_policy_inplace(right, OperatorPolicy::Padding)
+    }
+}
+
+impl GenericMul<i32, CryptoAPIError> for LWE {
+    fn mul(&self, right: i32) -> Result<LWE, CryptoAPIError> {
+        self.mul_constant_static_encoder(right)
+    }
+    fn mul_inplace(&mut self, right: i32) -> Result<(), CryptoAPIError> {
+        self.mul_constant_static_encoder_inplace(right)
+    }
+}
+
+impl GenericNeg<CryptoAPIError> for LWE {
+    fn neg(&self) -> Result<LWE, CryptoAPIError> {
+        self.opposite()
+    }
+    fn neg_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        self.opposite_inplace()
+    }
+}
+
+impl_operators!(LWE);
+
+impl LWEOperations for LWE {
+    type SecretKey = crate::LWESecretKey;
+    type BootstrapKey = crate::LWEBSK;
+    type KeySwitchKey = crate::LWEKSK;
+
+    fn encode_encrypt(
+        sk: &crate::LWESecretKey,
+        message: f64,
+        encoder: &crate::Encoder,
+    ) -> Result<LWE, CryptoAPIError> {
+        LWE::encode_encrypt(sk, message, encoder)
+    }
+    fn decrypt_decode(&self, sk: &crate::LWESecretKey) -> Result<f64, CryptoAPIError> {
+        LWE::decrypt_decode(self, sk)
+    }
+    fn decrypt_decode_round(&self, sk: &crate::LWESecretKey) -> Result<f64, CryptoAPIError> {
+        LWE::decrypt_decode_round(self, sk)
+    }
+    fn get_encoder(&self) -> &crate::Encoder {
+        &self.encoder
+    }
+    fn get_variance(&self) -> f64 {
+        self.variance
+    }
+    fn add_constant_static_encoder(&self, message: f64) -> Result<LWE, CryptoAPIError> {
+        LWE::add_constant_static_encoder(self, message)
+    }
+    fn add_constant_dynamic_encoder(&self, message: f64) -> Result<LWE, CryptoAPIError> {
+        LWE::add_constant_dynamic_encoder(self, message)
+    }
+    fn add_centered(&self, ct: &LWE) -> Result<LWE, CryptoAPIError> {
+        LWE::add_centered(self, ct)
+    }
+    fn add_with_padding(&self, ct: &LWE) -> Result<LWE, CryptoAPIError> {
+        LWE::add_with_padding(self, ct)
+    }
+    fn sub_with_padding(&self, ct: &LWE) -> Result<LWE, CryptoAPIError> {
+        LWE::sub_with_padding(self, ct)
+    }
+    fn mul_constant_static_encoder(&self, message: i32) -> Result<LWE, CryptoAPIError> {
+        LWE::mul_constant_static_encoder(self, message)
+    }
+    fn opposite(&self) -> Result<LWE, CryptoAPIError> {
+        LWE::opposite(self)
+    }
+    fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<LWE, CryptoAPIError> {
+        LWE::keyswitch(self, ksk)
+    }
+    fn bootstrap_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<LWE, CryptoAPIError> {
+        LWE::bootstrap_with_function(self, bsk, f, encoder_output)
     }
 }
 
@@ -114,6 +241,56 @@ impl LWE {
         sk: &crate::LWESecretKey,
         message: f64,
         encoder: &crate::Encoder,
+    ) -> Result<LWE, CryptoAPIError> {
+        LWE::encode_encrypt_with_generator(
+            sk,
+            message,
+            encoder,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encode a message and then directly encrypt the plaintext into an LWE structure with a
+    /// provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `message` -  a  message as u64
+    /// * `encoder` - an Encoder
+    /// * `generator` - the encryption generator sampling the mask and the noise
+    ///
+    /// # Output
+    /// an LWE structure
+    ///
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+    ///
+    /// let encoder = Encoder::new(-2., 6., 4, 4).unwrap();
+    /// let secret_key = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // two generators with the same seeds produce the same ciphertexts
+    /// let ct_1 = LWE::encode_encrypt_with_generator(
+    ///     &secret_key,
+    ///     -1.,
+    ///     &encoder,
+    ///     &mut EncryptionRandomGenerator::new_deterministic(1, 2),
+    /// )
+    /// .unwrap();
+    /// let ct_2 = LWE::encode_encrypt_with_generator(
+    ///     &secret_key,
+    ///     -1.,
+    ///     &encoder,
+    ///     &mut EncryptionRandomGenerator::new_deterministic(1, 2),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(ct_1, ct_2);
+    /// ```
+    pub fn encode_encrypt_with_generator(
+        sk: &crate::LWESecretKey,
+        message: f64,
+        encoder: &crate::Encoder,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<LWE, CryptoAPIError> {
         let plaintext = encoder.encode_core(message)?;
         let mut result_encoder: crate::Encoder = encoder.clone();
@@ -135,7 +312,8 @@ impl LWE {
             dimension: sk.dimension,
             encoder: result_encoder,
         };
-        res.encrypt_raw(sk, plaintext).unwrap();
+        res.encrypt_raw_with_generator(sk, plaintext, generator)
+            .unwrap();
 
         Ok(res)
     }
@@ -167,6 +345,20 @@ impl LWE {
         &mut self,
         sk: &crate::LWESecretKey,
         plaintext: Torus,
+    ) -> Result<(), CryptoAPIError> {
+        self.encrypt_raw_with_generator(sk, plaintext, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt a raw plaintext with the provided key, standard deviation and encryption generator
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `plaintext` - a Torus element
+    /// * `generator` - the encryption generator sampling the mask and the noise
+    pub fn encrypt_raw_with_generator(
+        &mut self,
+        sk: &crate::LWESecretKey,
+        plaintext: Torus,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<(), CryptoAPIError> {
         // compute the variance
         let var = sk.get_variance();
@@ -184,7 +376,7 @@ impl LWE {
             &mut self.ciphertext,
             &Plaintext(plaintext),
             StandardDev::from_standard_dev(sk.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
+            generator,
         );
 
         Ok(())
@@ -280,6 +472,102 @@ impl LWE {
         Ok(result)
     }
 
+    /// Measure the noise of the ciphertext with the secret key, the message being the closest one
+    /// representable with the precision of the encoder
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// # Output
+    /// * a NoiseMeasurement
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * InvalidEncoderError - if the encoder of the ciphertext is not valid
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt a message
+    /// let encoder = Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    /// let sk = LWESecretKey::new(&LWE128_630);
+    /// let ct = LWE::encode_encrypt(&sk, 6., &encoder).unwrap();
+    ///
+    /// // measure its noise
+    /// let measurement = ct.measure_noise(&sk).unwrap();
+    /// assert!(!measurement.overlaps_message());
+    /// ```
+    pub fn measure_noise(
+        &self,
+        sk: &crate::LWESecretKey,
+    ) -> Result<crate::NoiseMeasurement, CryptoAPIError> {
+        // check dimensions
+        if sk.dimension != self.dimension {
+            return Err(DimensionError!(self.dimension, sk.dimension));
+        }
+
+        // check the encoder
+        if !self.encoder.is_valid() {
+            return Err(InvalidEncoderError!(
+                self.encoder.nb_bit_precision,
+                self.encoder.delta
+            ));
+        }
+
+        // compute the phase and its closest encoding
+        let mut phase = Plaintext(0);
+        sk.val.decrypt_lwe(&mut phase, &self.ciphertext);
+        let encoded = Plaintext(crate::noise_measurement::closest_encoding(
+            phase.0,
+            &self.encoder,
+        ));
+
+        Ok(crate::NoiseMeasurement::new(
+            self.ciphertext.compute_error(&sk.val, &encoded),
+            &self.encoder,
+            self.variance,
+        ))
+    }
+
+    /// Measure the noise of the ciphertext with the secret key and the message it encrypts
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `message` - the message encrypted in the ciphertext
+    /// # Output
+    /// * a NoiseMeasurement
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * MessageOutsideIntervalError - if the message is outside the interval of the encoder
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt a message
+    /// let encoder = Encoder::new(-5., 5., 8, 0).unwrap();
+    /// let sk = LWESecretKey::new(&LWE128_630);
+    /// let ct = LWE::encode_encrypt(&sk, -3.2, &encoder).unwrap();
+    ///
+    /// // measure its noise
+    /// let measurement = ct.measure_noise_with_message(&sk, -3.2).unwrap();
+    /// assert!(!measurement.overlaps_message());
+    /// ```
+    pub fn measure_noise_with_message(
+        &self,
+        sk: &crate::LWESecretKey,
+        message: f64,
+    ) -> Result<crate::NoiseMeasurement, CryptoAPIError> {
+        // check dimensions
+        if sk.dimension != self.dimension {
+            return Err(DimensionError!(self.dimension, sk.dimension));
+        }
+
+        // encode the message
+        let encoded = Plaintext(self.encoder.encode_core(message)?);
+
+        Ok(crate::NoiseMeasurement::new(
+            self.ciphertext.compute_error(&sk.val, &encoded),
+            &self.encoder,
+            self.variance,
+        ))
+    }
+
     /// Add a small message to a LWE ciphertext and does not change the encoding but changes the bodies of the ciphertext
     ///
     /// # Argument
@@ -1781,205 +2069,1296 @@ impl LWE {
         // generate the look up table (throw error if a bit of padding is missing)
         let lut = bsk.generate_functional_look_up_table(&self.encoder, encoder_output, f)?;
 
-        // build the trivial accumulator
-        let mut accumulator = GlweCiphertext::allocate(
-            0_u64,
-            PolynomialSize(bsk.polynomial_size),
-            GlweSize(bsk.dimension + 1),
-        );
-        accumulator
-            .as_mut_tensor()
-            .as_mut_slice()
-            .get_mut(
-                (bsk.dimension * bsk.polynomial_size)..((bsk.dimension + 1) * bsk.polynomial_size),
-            )
-            .unwrap()
-            .copy_from_slice(&lut);
-
-        // allocate the result
-        let mut result =
-            LweCiphertext::allocate(0, LweSize(bsk.dimension * bsk.polynomial_size + 1));
-
-        if self.encoder.nb_bit_padding > 1 {
+        let result = if self.encoder.nb_bit_padding > 1 {
             // remove the padding but one bit
             let mut self_clone = self.clone();
             self_clone.remove_padding_inplace(self.encoder.nb_bit_padding - 1)?;
 
             // compute the bootstrap
-            crypto::cross::bootstrap(
-                &mut result,
-                &self_clone.ciphertext,
-                &bsk.ciphertexts,
-                &mut accumulator,
-            );
+            bsk.bootstrap_raw(&self_clone.ciphertext, &lut)
         } else {
             // compute the bootstrap
-            crypto::cross::bootstrap(
-                &mut result,
-                &self.ciphertext,
-                &bsk.ciphertexts,
-                &mut accumulator,
-            );
-        }
-
-        // compute the new variance (without the drift)
-        let new_var = <Torus as npe::Cross>::bootstrap(
-            self.dimension,
-            bsk.dimension,
-            bsk.level,
-            bsk.base_log,
-            bsk.polynomial_size,
-            bsk.variance,
-        );
-
-        // create the output encoder
-        let mut new_encoder_output: crate::Encoder = encoder_output.clone();
-
-        // update the precision in case of the output noise (without drift) is too big and overlap the message
-        let nb_bit_overlap: usize = new_encoder_output.update_precision_from_variance(new_var)?;
-
-        // println!("call to npe : {}", new_var);
-        if nb_bit_overlap > 0 {
-            println!(
-                "{}: {} bit(s) of precision lost over {} bit(s) of message originally. Consider increasing the number of level and/or decreasing the log base.",
-                "Loss of precision during bootstrap".red().bold(),
-                nb_bit_overlap, self.encoder.nb_bit_precision
-            );
-        }
-
-        // calls the NPE to find out the amount of noise after rounding the input ciphertext (drift)
-        let nb_rounding_noise_bit: usize =
-            (npe::lwe::log2_rounding_noise(self.dimension)).ceil() as usize + 1;
-
-        // deals with the drift error
-        if nb_rounding_noise_bit + 1 + new_encoder_output.nb_bit_precision
-            > bsk.get_polynomial_size_log() + 1
-        {
-            let nb_bit_loss = 1 + new_encoder_output.nb_bit_precision + nb_rounding_noise_bit
-                - bsk.get_polynomial_size_log()
-                - 1;
-
-            new_encoder_output.nb_bit_precision = i32::max(
-                new_encoder_output.nb_bit_precision as i32 - nb_bit_loss as i32,
-                0i32,
-            ) as usize;
-            // drift
-            println!(
-                "{}: {} bit(s) of precision lost over {} bit(s) of message originally ({} bits are affected by the noise). Consider increasing the polynomial size of the RLWE secret key.",
-                "Loss of precision during bootstrap due to the rounding".red().bold(),
-                nb_bit_loss, self.encoder.nb_bit_precision,nb_rounding_noise_bit
-            );
-        }
-
-        // construct the output
-        let lwe = crate::LWE {
-            variance: new_var,
-            ciphertext: result,
-            dimension: bsk.polynomial_size * bsk.dimension,
-            encoder: new_encoder_output,
+            bsk.bootstrap_raw(&self.ciphertext, &lut)
         };
 
-        Ok(lwe)
+        // construct the output
+        self.bootstrap_output(bsk, result, encoder_output, 1)
     }
 
-    /// Multiply two LWE ciphertexts thanks to two bootstrapping procedures
-    /// need to have 2 bits of padding at least
+    /// Compute a bootstrap and apply an arbitrary function to an LWE ciphertext without any bit of padding,
+    /// the whole torus being used to encode the messages of the input encoder.
+    /// If the function is negacyclic (see `LWEBSK::check_negacyclic_function`) a single bootstrap is computed,
+    /// otherwise the most significant bit of the message is extracted first (see `bootstrap_with_function_sign_extraction`).
+    /// If the input ciphertext still has some padding, a regular bootstrap is computed.
     ///
     /// # Argument
-    /// * `ct` - an LWE struct containing the second LWE for the multiplication
-    /// * `bsk` - the bootstrapping key used to evaluate the function
-    /// * `n_self` - the index of the ciphertext to multiply in the self struct
-    /// * `n_ct` - the index of the ciphertext to multiply in the ct struct
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the output encoder
     ///
     /// # Output
     /// * a LWE struct
-    /// * NotEnoughPaddingError - if one of the input does not have at least 2 bits of padding
+    /// * DimensionError - if the keys and the input ciphertext have incompatible dimensions
+    /// * PrecisionError - if the input encoder has no bit of precision
     ///
     /// # Example
     /// ```rust
     /// use concrete::*;
     ///
     /// // params
-    /// let (min_1, max_1): (f64, f64) = (-150., 204.);
-    /// let min_2: f64 = 30.;
-    /// let max_2: f64 = min_2 + max_1 - min_1;
-    ///
-    /// let precision = 4;
-    /// let padding = 2;
-    /// let level: usize = 3;
-    /// let base_log: usize = 3;
-    ///
-    /// // encoder
-    /// let encoder_1 = Encoder::new(min_1, max_1, precision, padding).unwrap();
-    /// let encoder_2 = Encoder::new(min_2, max_2, precision, padding).unwrap();
-    ///
-    /// // generate a secret key
-    /// let secret_key = LWESecretKey::new(&LWE128_650);
+    /// let (min, max): (f64, f64) = (0., 7.);
+    /// let precision = 3;
+    /// let padding = 0;
     ///
-    /// // two lists of messages
-    /// let message_1: f64 = -127.;
-    /// let message_2: f64 = 72.7;
+    /// // encoders
+    /// let encoder_input = Encoder::new(min, max, precision, padding).unwrap();
+    /// let encoder_output = Encoder::new(0., 7., precision, 1).unwrap();
     ///
-    /// // generate secret keys
+    /// // secret keys
     /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
     /// let secret_key_before = LWESecretKey::new(&LWE128_630);
     /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
     ///
-    /// // bootstrapping key
-    /// let bootstrapping_key =
-    ///     LWEBSK::new(&secret_key_before, &rlwe_secret_key, base_log, level);
-    ///
-    /// // a list of messages that we encrypt
-    /// let ciphertext_1 =
-    ///     LWE::encode_encrypt(&secret_key_before, message_1, &encoder_1).unwrap();
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 7, 3);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 2, 7);
     ///
-    /// let ciphertext_2 =
-    ///     LWE::encode_encrypt(&secret_key_before, message_2, &encoder_2).unwrap();
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, 5., &encoder_input).unwrap();
     ///
-    /// let ciphertext_out = ciphertext_1
-    ///     .mul_from_bootstrap(&ciphertext_2, &bootstrapping_key)
+    /// let ciphertext_out = ciphertext
+    ///     .bootstrap_with_function_full_domain(&bsk, &ksk, |x| (x * x) % 8., &encoder_output)
     ///     .unwrap();
     /// ```
-    pub fn mul_from_bootstrap(
+    pub fn bootstrap_with_function_full_domain<F: Fn(f64) -> f64>(
         &self,
-        ct: &crate::LWE,
         bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        f: F,
+        encoder_output: &crate::Encoder,
     ) -> Result<crate::LWE, CryptoAPIError> {
-        // clone twice from self
-        let mut ct1 = self.clone();
-        let mut ct2 = self.clone();
-
-        // return an error if nb_bit_precision < 2
-        if ct1.encoder.nb_bit_precision < 2 {
-            return Err(NotEnoughPaddingError!(ct1.encoder.nb_bit_precision, 2));
+        if self.encoder.nb_bit_padding > 0 {
+            return self.bootstrap_with_function(bsk, f, encoder_output);
         }
 
-        // extract once from ct
-        let input2 = ct.clone();
-
-        // compute the addition and the subtraction
-        ct1.add_with_padding_inplace(&input2)?;
-        ct2.sub_with_padding_inplace(&input2)?;
-
-        // create the output encoder
-        let mut encoder_out1 = ct1.encoder.new_square_divided_by_four(2)?;
-        let mut encoder_out2 = ct2.encoder.new_square_divided_by_four(2)?;
-
-        // set the same deltas
-        if encoder_out1.delta < encoder_out2.delta {
-            encoder_out1.delta = encoder_out2.delta;
+        if bsk
+            .check_negacyclic_function(&self.encoder, encoder_output, &f)
+            .is_ok()
+        {
+            self.bootstrap_with_negacyclic_function(bsk, f, encoder_output)
         } else {
-            encoder_out2.delta = encoder_out1.delta;
+            self.bootstrap_with_function_sign_extraction(bsk, ksk, f, encoder_output)
         }
+    }
 
-        // bootstrap
-        let mut square1 = ct1.bootstrap_with_function(bsk, |x| (x * x) / 4., &encoder_out1)?;
+    /// Compute a bootstrap and apply a negacyclic function to an LWE ciphertext,
+    /// i.e. a function such that f(x + delta / 2) is encoded as -f(x) with the output encoder.
+    /// The bits of padding of the input are all removed, so the input can have no padding at all.
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `f` - the negacyclic function to apply
+    /// * `encoder_output` - the output encoder
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the bootstrapping key and the input ciphertext have incompatible dimensions
+    /// * NotNegacyclicError - if the function is not negacyclic
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // the input interval [-1, 1[ covers the whole torus
+    /// let encoder_input = Encoder::new(-1., 0.75, 3, 0).unwrap();
+    /// let encoder_output = Encoder::new(-2., 1., 2, 0).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 7, 3);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, -0.5, &encoder_input).unwrap();
+    ///
+    /// // the sign function is negacyclic
+    /// let ciphertext_out = ciphertext
+    ///     .bootstrap_with_negacyclic_function(&bsk, |x| if x < 0. { -1. } else { 1. }, &encoder_output)
+    ///     .unwrap();
+    /// ```
+    pub fn bootstrap_with_negacyclic_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // check bsk compatibility
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        }
+
+        // remove all the padding
+        let mut input = self.clone();
+        input.remove_padding_inplace(self.encoder.nb_bit_padding)?;
+
+        // generate the look up table (throw error if the function is not negacyclic)
+        let lut = bsk.generate_full_domain_look_up_table(&input.encoder, encoder_output, f)?;
+
+        // compute the bootstrap
+        let result = bsk.bootstrap_raw(&input.ciphertext, &lut);
+
+        // construct the output
+        self.bootstrap_output(bsk, result, encoder_output, 0)
+    }
+
+    /// Compute a bootstrap and apply an arbitrary function to an LWE ciphertext without padding.
+    /// The most significant bit of the message is first extracted with a sign bootstrap,
+    /// then the remaining bits and the extracted bit are packed into a ciphertext with one bit of padding,
+    /// which is finally bootstrapped with the look up table of the function.
+    /// It costs three bootstraps and two key switches.
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the output encoder
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertext have incompatible dimensions
+    /// * PrecisionError - if the input encoder has no bit of precision
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_input = Encoder::new(0., 7., 3, 0).unwrap();
+    /// let encoder_output = Encoder::new(0., 7., 3, 1).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 7, 3);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 2, 7);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, 6., &encoder_input).unwrap();
+    ///
+    /// let ciphertext_out = ciphertext
+    ///     .bootstrap_with_function_sign_extraction(&bsk, &ksk, |x| 7. - x, &encoder_output)
+    ///     .unwrap();
+    /// ```
+    pub fn bootstrap_with_function_sign_extraction<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // check bsk compatibility
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        }
+        // check ksk compatibility
+        if ksk.dimension_before != bsk.dimension * bsk.polynomial_size {
+            return Err(DimensionError!(
+                ksk.dimension_before,
+                bsk.dimension * bsk.polynomial_size
+            ));
+        }
+        if ksk.dimension_after != self.dimension {
+            return Err(DimensionError!(ksk.dimension_after, self.dimension));
+        }
+
+        // remove all the padding
+        let mut input = self.clone();
+        input.remove_padding_inplace(self.encoder.nb_bit_padding)?;
+        let precision = input.encoder.nb_bit_precision;
+        if precision == 0 {
+            return Err(PrecisionError!());
+        }
+
+        // move the messages to the center of their cells so the sign is well defined
+        let half_cell: Torus = 1 << (<Torus as Numeric>::BITS - precision - 1);
+        let update =
+            input.ciphertext.as_tensor().as_slice()[self.dimension].wrapping_add(half_cell);
+        input.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] = update;
+
+        // sign bootstrap: encrypts 1/8 if the msb is 0 and -1/8 otherwise
+        let lut = vec![1 << (<Torus as Numeric>::BITS - 3); bsk.polynomial_size];
+        let sign_output = bsk.bootstrap_raw(&input.ciphertext, &lut);
+        let mut sign = LweCiphertext::allocate(0, LweSize(self.dimension + 1));
+        ksk.ciphertexts
+            .keyswitch_ciphertext(&mut sign, &sign_output);
+
+        // remove the msb from the input: input - 2 * sign + 1/4
+        let mut low = input.ciphertext.clone();
+        let mut sign_times_two = sign.clone();
+        sign_times_two.update_with_scalar_mul(Cleartext(2));
+        low.update_with_sub(&sign_times_two);
+        let update = low.as_tensor().as_slice()[self.dimension]
+            .wrapping_add(1 << (<Torus as Numeric>::BITS - 2));
+        low.as_mut_tensor().as_mut_slice()[self.dimension] = update;
+
+        // the msb encoded in the second bit: 1/8 - sign
+        let mut msb = sign;
+        msb.update_with_neg();
+        let update = msb.as_tensor().as_slice()[self.dimension]
+            .wrapping_add(1 << (<Torus as Numeric>::BITS - 3));
+        msb.as_mut_tensor().as_mut_slice()[self.dimension] = update;
+
+        // divide the remaining bits by two with a bootstrap (the low part has one bit of padding)
+        let lut = bsk.generate_cell_look_up_table(1 << (precision - 1), |cell| {
+            ((2 * cell + 1) as Torus) << (<Torus as Numeric>::BITS - precision - 2)
+        });
+        let half_output = bsk.bootstrap_raw(&low, &lut);
+        let mut packed = LweCiphertext::allocate(0, LweSize(self.dimension + 1));
+        ksk.ciphertexts
+            .keyswitch_ciphertext(&mut packed, &half_output);
+
+        // pack the msb back: the message is now encoded with one bit of padding
+        packed.update_with_add(&msb);
+
+        // final bootstrap with the look up table of the function
+        let granularity = input.encoder.get_granularity();
+        let values = (0..(1 << precision))
+            .map(|cell| {
+                encoder_output.encode_outside_interval_operators(f(
+                    input.encoder.o + cell as f64 * granularity
+                ))
+            })
+            .collect::<Result<Vec<Torus>, CryptoAPIError>>()?;
+        let lut = bsk.generate_cell_look_up_table(1 << precision, |cell| values[cell]);
+        let result = bsk.bootstrap_raw(&packed, &lut);
+
+        // construct the output
+        self.bootstrap_output(bsk, result, encoder_output, 1)
+    }
+
+    /// Build the output of a bootstrap from the bootstrapped ciphertext:
+    /// compute the new variance with the NPE and update the output encoder accordingly
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key used
+    /// * `result` - the output ciphertext of the bootstrap
+    /// * `encoder_output` - the encoder of the output
+    /// * `nb_bit_padding` - the number of bits of padding of the input of the bootstrap
+    fn bootstrap_output(
+        &self,
+        bsk: &crate::LWEBSK,
+        result: LweCiphertext<Vec<Torus>>,
+        encoder_output: &crate::Encoder,
+        nb_bit_padding: usize,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // compute the new variance (without the drift)
+        let new_var = <Torus as npe::Cross>::bootstrap(
+            self.dimension,
+            bsk.dimension,
+            bsk.level,
+            bsk.base_log,
+            bsk.polynomial_size,
+            bsk.variance,
+        );
+
+        // create the output encoder
+        let new_encoder_output = bootstrap_output_encoder(
+            self.dimension,
+            self.encoder.nb_bit_precision,
+            bsk.polynomial_size,
+            new_var,
+            encoder_output,
+            nb_bit_padding,
+        )?;
+
+        // construct the output
+        let lwe = crate::LWE {
+            variance: new_var,
+            ciphertext: result,
+            dimension: bsk.polynomial_size * bsk.dimension,
+            encoder: new_encoder_output,
+        };
+
+        Ok(lwe)
+    }
+
+    /// Multiply two LWE ciphertexts thanks to two bootstrapping procedures
+    /// need to have 2 bits of padding at least
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct containing the second LWE for the multiplication
+    /// * `bsk` - the bootstrapping key used to evaluate the function
+    /// * `n_self` - the index of the ciphertext to multiply in the self struct
+    /// * `n_ct` - the index of the ciphertext to multiply in the ct struct
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * NotEnoughPaddingError - if one of the input does not have at least 2 bits of padding
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // params
+    /// let (min_1, max_1): (f64, f64) = (-150., 204.);
+    /// let min_2: f64 = 30.;
+    /// let max_2: f64 = min_2 + max_1 - min_1;
+    ///
+    /// let precision = 4;
+    /// let padding = 2;
+    /// let level: usize = 3;
+    /// let base_log: usize = 3;
+    ///
+    /// // encoder
+    /// let encoder_1 = Encoder::new(min_1, max_1, precision, padding).unwrap();
+    /// let encoder_2 = Encoder::new(min_2, max_2, precision, padding).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = LWESecretKey::new(&LWE128_650);
+    ///
+    /// // two lists of messages
+    /// let message_1: f64 = -127.;
+    /// let message_2: f64 = 72.7;
+    ///
+    /// // generate secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bootstrapping_key =
+    ///     LWEBSK::new(&secret_key_before, &rlwe_secret_key, base_log, level);
+    ///
+    /// // a list of messages that we encrypt
+    /// let ciphertext_1 =
+    ///     LWE::encode_encrypt(&secret_key_before, message_1, &encoder_1).unwrap();
+    ///
+    /// let ciphertext_2 =
+    ///     LWE::encode_encrypt(&secret_key_before, message_2, &encoder_2).unwrap();
+    ///
+    /// let ciphertext_out = ciphertext_1
+    ///     .mul_from_bootstrap(&ciphertext_2, &bootstrapping_key)
+    ///     .unwrap();
+    /// ```
+    pub fn mul_from_bootstrap(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // clone twice from self
+        let mut ct1 = self.clone();
+        let mut ct2 = self.clone();
+
+        // return an error if nb_bit_precision < 2
+        if ct1.encoder.nb_bit_precision < 2 {
+            return Err(NotEnoughPaddingError!(ct1.encoder.nb_bit_precision, 2));
+        }
+
+        // extract once from ct
+        let input2 = ct.clone();
+
+        // compute the addition and the subtraction
+        ct1.add_with_padding_inplace(&input2)?;
+        ct2.sub_with_padding_inplace(&input2)?;
+
+        // create the output encoder
+        let mut encoder_out1 = ct1.encoder.new_square_divided_by_four(2)?;
+        let mut encoder_out2 = ct2.encoder.new_square_divided_by_four(2)?;
+
+        // set the same deltas
+        if encoder_out1.delta < encoder_out2.delta {
+            encoder_out1.delta = encoder_out2.delta;
+        } else {
+            encoder_out2.delta = encoder_out1.delta;
+        }
+
+        // bootstrap
+        let mut square1 = ct1.bootstrap_with_function(bsk, |x| (x * x) / 4., &encoder_out1)?;
         let square2 = ct2.bootstrap_with_function(bsk, |x| (x * x) / 4., &encoder_out2)?;
 
-        // subtract
-        square1.sub_with_padding_inplace(&square2)?;
+        // subtract
+        square1.sub_with_padding_inplace(&square2)?;
+
+        Ok(square1)
+    }
+
+    /// Compute a bootstrap applying a function of two variables to the messages of two LWE ciphertexts.
+    /// The messages are seen as their indices on the grids of their encoders: the index of ct is moved above
+    /// the index of self by multiplying ct by a power of two, the sum of the two ciphertexts encrypts the
+    /// concatenation of the indices, and a single bootstrap evaluates the 2D look up table.
+    /// self must have enough padding to receive the bits of ct, i.e. at least nb_bit_precision(ct) + 1 bits,
+    /// and the concatenation must fit in the look up table of the bootstrapping key.
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct with at least one bit of padding
+    /// * `bsk` - the bootstrapping key
+    /// * `f` - the function to apply, self's message being its first argument
+    /// * `encoder_output` - the output encoder
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the bootstrapping key and the input ciphertexts have incompatible dimensions
+    /// * NotEnoughPaddingError - if self has less than nb_bit_precision(ct) + 1 bits of padding or ct has no bit of padding
+    /// * LookUpTableSizeError - if the concatenated messages have more values than the polynomial size
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders: self has room for the 2 bits of ct in its padding
+    /// let encoder_1 = Encoder::new_rounding_context(0., 3., 2, 3).unwrap();
+    /// let encoder_2 = Encoder::new_rounding_context(0., 3., 2, 1).unwrap();
+    /// let encoder_output = Encoder::new_rounding_context(0., 3., 2, 1).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_630);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping key
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 7, 3);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext_1 = LWE::encode_encrypt(&secret_key_before, 3., &encoder_1).unwrap();
+    /// let ciphertext_2 = LWE::encode_encrypt(&secret_key_before, 2., &encoder_2).unwrap();
+    ///
+    /// // multiplication modulo 4
+    /// let ciphertext_out = ciphertext_1
+    ///     .bootstrap_bivariate(&ciphertext_2, &bsk, |x, y| (x * y) % 4., &encoder_output)
+    ///     .unwrap();
+    /// assert_eq!(ciphertext_out.decrypt_decode_round(&secret_key_after).unwrap(), 2.);
+    /// ```
+    pub fn bootstrap_bivariate<F: Fn(f64, f64) -> f64>(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // check the dimensions
+        if self.dimension != ct.dimension {
+            return Err(DimensionError!(self.dimension, ct.dimension));
+        }
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        }
+
+        // check that the concatenation fits in the padding and in the look up table
+        let (nb_bit_1, nb_bit_2) = (self.encoder.nb_bit_precision, ct.encoder.nb_bit_precision);
+        if self.encoder.nb_bit_padding < nb_bit_2 + 1 {
+            return Err(NotEnoughPaddingError!(
+                self.encoder.nb_bit_padding,
+                nb_bit_2 + 1
+            ));
+        }
+        if ct.encoder.nb_bit_padding == 0 {
+            return Err(NotEnoughPaddingError!(ct.encoder.nb_bit_padding, 1));
+        }
+        let nb_cells: usize = 1 << (nb_bit_1 + nb_bit_2);
+        if nb_cells > bsk.polynomial_size {
+            return Err(LookUpTableSizeError!(nb_cells, bsk.polynomial_size));
+        }
+
+        // multiply both ciphertexts so the index of ct lands above the index of self, with one bit of padding left
+        let mut low = self.clone();
+        low.remove_padding_inplace(self.encoder.nb_bit_padding - nb_bit_2 - 1)?;
+        let mut high = ct.clone();
+        high.remove_padding_inplace(ct.encoder.nb_bit_padding - 1)?;
+
+        // add them and encode the concatenated index
+        low.ciphertext.update_with_add(&high.ciphertext);
+        low.variance = npe::add_ciphertexts(low.variance, high.variance);
+        low.encoder = crate::Encoder {
+            o: 0.,
+            delta: nb_cells as f64,
+            nb_bit_precision: nb_bit_1 + nb_bit_2,
+            nb_bit_padding: 1,
+            round: true,
+            failure_probability: self.encoder.failure_probability,
+        };
+
+        // bootstrap with the 2D look up table
+        let (granularity_1, granularity_2) =
+            (self.encoder.get_granularity(), ct.encoder.get_granularity());
+        low.bootstrap_with_function(
+            bsk,
+            |x| {
+                let index = (x.round() as usize) % nb_cells;
+                f(
+                    self.encoder.o + (index % (1 << nb_bit_1)) as f64 * granularity_1,
+                    ct.encoder.o + (index >> nb_bit_1) as f64 * granularity_2,
+                )
+            },
+            encoder_output,
+        )
+    }
+
+    /// Re-encode an LWE ciphertext with a new encoder thanks to a bootstrap followed by a key switch,
+    /// so the output is encrypted under the same key as the input.
+    /// Nothing is computed if the encoders have the same interval and the same padding.
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    /// * `encoder` - the new encoder, its interval must contain the interval of the input encoder
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertext have incompatible dimensions
+    pub fn reencode(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        encoder: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        if deltas_eq!(self.encoder.o, encoder.o)
+            && deltas_eq!(self.encoder.delta, encoder.delta)
+            && self.encoder.nb_bit_padding == encoder.nb_bit_padding
+        {
+            return Ok(self.clone());
+        }
+        self.bootstrap_with_function_full_domain(bsk, ksk, |x| x, encoder)?
+            .keyswitch(ksk)
+    }
+
+    /// Check that a bootstrapping key and a key switching key bring a ciphertext back to its own key
+    fn check_bootstrap_keyswitch_keys(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<(), CryptoAPIError> {
+        if self.dimension != bsk.get_lwe_dimension() {
+            return Err(DimensionError!(self.dimension, bsk.get_lwe_dimension()));
+        }
+        if ksk.dimension_before != bsk.dimension * bsk.polynomial_size {
+            return Err(DimensionError!(
+                ksk.dimension_before,
+                bsk.dimension * bsk.polynomial_size
+            ));
+        }
+        if ksk.dimension_after != self.dimension {
+            return Err(DimensionError!(ksk.dimension_after, self.dimension));
+        }
+        Ok(())
+    }
+
+    /// Re-encode two ciphertexts with a common encoder having at least two bits of padding
+    /// (see `Encoder::new_covering`), so their difference keeps one bit of padding for a bootstrap
+    fn align_for_comparison(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<(crate::LWE, crate::LWE), CryptoAPIError> {
+        if self.dimension != ct.dimension {
+            return Err(DimensionError!(self.dimension, ct.dimension));
+        }
+        self.check_bootstrap_keyswitch_keys(bsk, ksk)?;
+
+        let encoder = crate::Encoder::new_covering(&[&self.encoder, &ct.encoder])?;
+        Ok((
+            self.reencode(bsk, ksk, &encoder)?,
+            ct.reencode(bsk, ksk, &encoder)?,
+        ))
+    }
+
+    /// Return the output encoder of a rounding context for a function computed on self,
+    /// keeping the failure probability of the encoder of self
+    fn rounding_output_encoder(
+        &self,
+        min: f64,
+        max: f64,
+        nb_bit_precision: usize,
+        nb_bit_padding: usize,
+    ) -> Result<crate::Encoder, CryptoAPIError> {
+        let mut encoder =
+            crate::Encoder::new_rounding_context(min, max, nb_bit_precision, nb_bit_padding)?;
+        encoder.failure_probability = self.encoder.failure_probability;
+        Ok(encoder)
+    }
+
+    /// Compute self - ct between two aligned ciphertexts and bootstrap it with a function of the difference
+    /// and of the granularity of the messages, the output is key switched back to the input key
+    fn bootstrap_difference<F: Fn(f64, f64) -> f64>(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        // the difference lives in [-delta, delta[ with the smallest granularity of the inputs
+        let difference = self.sub_with_padding_exact(ct)?;
+        let granularity = difference.encoder.get_granularity();
+        difference
+            .bootstrap_with_function(bsk, |d| f(d, granularity), encoder_output)?
+            .keyswitch(ksk)
+    }
+
+    /// Compare two LWE ciphertexts with any encoders: the result encrypts 1 if
+    /// the message of self is greater than the message of ct and 0 otherwise.
+    /// The messages are compared with a tolerance of half the granularity,
+    /// the ciphertexts are first re-encoded with a bootstrap if their encoders are not compatible.
+    /// The output is encrypted under the input key with the encoder [0, 1] with one bit of padding,
+    /// and its variance is computed with the NPE.
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertexts have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_1 = Encoder::new_rounding_context(0., 7., 3, 1).unwrap();
+    /// let encoder_2 = Encoder::new_rounding_context(4., 11., 3, 2).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_750);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 4);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 2, 7);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext_1 = LWE::encode_encrypt(&secret_key_before, 6., &encoder_1).unwrap();
+    /// let ciphertext_2 = LWE::encode_encrypt(&secret_key_before, 5., &encoder_2).unwrap();
+    ///
+    /// let ciphertext_out = ciphertext_1
+    ///     .greater_than(&ciphertext_2, &bsk, &ksk)
+    ///     .unwrap();
+    /// assert_eq!(ciphertext_out.decrypt_decode_round(&secret_key_before).unwrap(), 1.);
+    /// ```
+    pub fn greater_than(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let (ct_1, ct_2) = self.align_for_comparison(ct, bsk, ksk)?;
+        ct_1.bootstrap_difference(
+            &ct_2,
+            bsk,
+            ksk,
+            |d, granularity| if d >= granularity / 2. { 1. } else { 0. },
+            &self.rounding_output_encoder(0., 1., 1, 1)?,
+        )
+    }
+
+    /// Compare two LWE ciphertexts with any encoders: the result encrypts 1 if
+    /// the message of self is less than the message of ct and 0 otherwise.
+    /// See `greater_than` for the management of the encoders.
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertexts have incompatible dimensions
+    pub fn less_than(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let (ct_1, ct_2) = self.align_for_comparison(ct, bsk, ksk)?;
+        ct_1.bootstrap_difference(
+            &ct_2,
+            bsk,
+            ksk,
+            |d, granularity| if d <= -granularity / 2. { 1. } else { 0. },
+            &self.rounding_output_encoder(0., 1., 1, 1)?,
+        )
+    }
 
-        Ok(square1)
+    /// Compare two LWE ciphertexts with any encoders: the result encrypts 1 if
+    /// the messages are equal up to half the granularity and 0 otherwise.
+    /// See `greater_than` for the management of the encoders.
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertexts have incompatible dimensions
+    pub fn equal(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let (ct_1, ct_2) = self.align_for_comparison(ct, bsk, ksk)?;
+        ct_1.bootstrap_difference(
+            &ct_2,
+            bsk,
+            ksk,
+            |d, granularity| if d.abs() < granularity / 2. { 1. } else { 0. },
+            &self.rounding_output_encoder(0., 1., 1, 1)?,
+        )
+    }
+
+    /// Compute the sign of the message of an LWE ciphertext: the result encrypts -1, 0 or 1,
+    /// 0 being returned for messages closer to 0 than half the granularity.
+    /// The input can have no padding at all (see `bootstrap_with_function_full_domain`).
+    /// The output is encrypted under the input key with the encoder [-2, 1] with one bit of padding,
+    /// and its variance is computed with the NPE.
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertext have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new_rounding_context(-4., 3., 3, 1).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_750);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 4);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 2, 7);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, -3., &encoder).unwrap();
+    ///
+    /// let ciphertext_out = ciphertext.sign(&bsk, &ksk).unwrap();
+    /// assert_eq!(ciphertext_out.decrypt_decode_round(&secret_key_before).unwrap(), -1.);
+    /// ```
+    pub fn sign(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        self.check_bootstrap_keyswitch_keys(bsk, ksk)?;
+        let tolerance = self.encoder.get_granularity() / 2.;
+        self.bootstrap_with_function_full_domain(
+            bsk,
+            ksk,
+            |x| {
+                if x >= tolerance {
+                    1.
+                } else if x <= -tolerance {
+                    -1.
+                } else {
+                    0.
+                }
+            },
+            &self.rounding_output_encoder(-2., 1., 2, 1)?,
+        )?
+        .keyswitch(ksk)
+    }
+
+    /// Compute the maximum of the messages of two LWE ciphertexts as ct + relu(self - ct) with one bootstrap.
+    /// The ciphertexts are first re-encoded with a bootstrap if their encoders are not compatible,
+    /// the output is encrypted under the input key with their common encoder (see `Encoder::new_covering`),
+    /// and its variance is computed with the NPE.
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertexts have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new_rounding_context(0., 7., 3, 2).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_750);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 4);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 2, 7);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext_1 = LWE::encode_encrypt(&secret_key_before, 2., &encoder).unwrap();
+    /// let ciphertext_2 = LWE::encode_encrypt(&secret_key_before, 5., &encoder).unwrap();
+    ///
+    /// let ciphertext_out = ciphertext_1.max(&ciphertext_2, &bsk, &ksk).unwrap();
+    /// assert_eq!(ciphertext_out.decrypt_decode_round(&secret_key_before).unwrap(), 5.);
+    /// ```
+    pub fn max(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let (ct_1, mut res) = self.align_for_comparison(ct, bsk, ksk)?;
+        let relu = ct_1.relu_of_difference(&res, bsk, ksk)?;
+        res.ciphertext.update_with_add(&relu.ciphertext);
+        res.merge_noise_and_precision(&relu)?;
+        Ok(res)
+    }
+
+    /// Compute the minimum of the messages of two LWE ciphertexts as self - relu(self - ct) with one bootstrap.
+    /// See `max` for the management of the encoders.
+    ///
+    /// # Argument
+    /// * `ct` - an LWE struct
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a LWE struct
+    /// * DimensionError - if the keys and the input ciphertexts have incompatible dimensions
+    pub fn min(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let (mut res, ct_2) = self.align_for_comparison(ct, bsk, ksk)?;
+        let relu = res.relu_of_difference(&ct_2, bsk, ksk)?;
+        res.ciphertext.update_with_sub(&relu.ciphertext);
+        res.merge_noise_and_precision(&relu)?;
+        Ok(res)
+    }
+
+    /// Compute relu(self - ct) between two aligned ciphertexts, encoded with the delta and the padding
+    /// of the inputs and an offset of zero, so it can be added to or subtracted from an input
+    fn relu_of_difference(
+        &self,
+        ct: &crate::LWE,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        let encoder_output = crate::Encoder {
+            o: 0.,
+            delta: self.encoder.delta,
+            nb_bit_precision: usize::max(
+                self.encoder.nb_bit_precision,
+                ct.encoder.nb_bit_precision,
+            ),
+            nb_bit_padding: self.encoder.nb_bit_padding,
+            round: self.encoder.round,
+            failure_probability: self.encoder.failure_probability,
+        };
+        self.bootstrap_difference(ct, bsk, ksk, |d, _| f64::max(d, 0.), &encoder_output)
+    }
+
+    /// Update the variance and the precision of self after adding or subtracting the ciphertext ct
+    /// without changing the encoder
+    fn merge_noise_and_precision(&mut self, ct: &crate::LWE) -> Result<(), CryptoAPIError> {
+        self.variance = npe::add_ciphertexts(self.variance, ct.variance);
+        self.encoder.nb_bit_precision =
+            usize::min(self.encoder.nb_bit_precision, ct.encoder.nb_bit_precision);
+        self.encoder.update_precision_from_variance(self.variance)?;
+        Ok(())
+    }
+
+    /// Extract the bits of the message of an LWE ciphertext, from the least significant one.
+    /// The message is seen as its index on the grid of the encoder, i.e. the integer (m - o) / granularity
+    /// in [0, 2^nb_bit_precision), so the messages have to be on this grid (e.g. with a rounding context).
+    /// At each step the ciphertext is multiplied by a power of two so the current bit lands on the most significant
+    /// bit of the torus, the bit is computed with a bootstrap followed by a key switch, and it is subtracted
+    /// from the ciphertext before the next step.
+    /// The failure probability is bounded with the NPE (see `concrete_npe::bit_extraction`).
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    /// * `n_bits` - the number of bits to extract
+    ///
+    /// # Output
+    /// * a VectorLWE struct with n_bits ciphertexts of 0 or 1, the bit i being encoded with
+    ///   nb_bit_precision + nb_bit_padding - 1 - i bits of padding so it stays in place on the torus
+    /// * DimensionError - if the keys and the input ciphertext have incompatible dimensions
+    /// * BitExtractionError - if n_bits is zero or larger than the precision of the input ciphertext
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new_rounding_context(0., 15., 4, 1).unwrap();
+    ///
+    /// // secret keys, the noise of the key switch is amplified by the extraction
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_1024);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 5);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 3, 8);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, 6., &encoder).unwrap();
+    ///
+    /// let bits = ciphertext.extract_bits(&bsk, &ksk, 4).unwrap();
+    /// assert_eq!(
+    ///     bits.decrypt_decode_round(&secret_key_before).unwrap(),
+    ///     vec![0., 1., 1., 0.]
+    /// );
+    /// ```
+    pub fn extract_bits(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        n_bits: usize,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        if n_bits == 0 || n_bits > self.encoder.nb_bit_precision {
+            return Err(BitExtractionError!(n_bits, self.encoder.nb_bit_precision));
+        }
+        let nb_bit_message = self.encoder.nb_bit_precision + self.encoder.nb_bit_padding;
+        let delta_log = <Torus as Numeric>::BITS - nb_bit_message;
+
+        // the bits stay in place
+        let positions: Vec<Torus> = (0..n_bits).map(|i| 1 << (delta_log + i)).collect();
+        let mut bits = self.extract_bits_raw(bsk, ksk, &positions)?;
+        for (i, bit) in bits.iter_mut().enumerate() {
+            bit.encoder = self.rounding_output_encoder(0., 1., 1, nb_bit_message - 1 - i)?;
+            bit.encoder.update_precision_from_variance(bit.variance)?;
+        }
+
+        // concatenate the bits
+        let mut container: Vec<Torus> = Vec::with_capacity(n_bits * (self.dimension + 1));
+        for bit in bits.iter() {
+            container.extend_from_slice(bit.ciphertext.as_tensor().as_slice());
+        }
+        Ok(crate::VectorLWE {
+            ciphertexts: crypto::lwe::LweList::from_container(
+                container,
+                LweSize(self.dimension + 1),
+            ),
+            variances: bits.iter().map(|bit| bit.variance).collect(),
+            dimension: self.dimension,
+            nb_ciphertexts: n_bits,
+            encoders: bits.into_iter().map(|bit| bit.encoder).collect(),
+        })
+    }
+
+    /// Compute a bootstrap with an arbitrary function on an LWE ciphertext whose precision is too large
+    /// for a single look up table.
+    /// The message, seen as its index on the grid of the input encoder, is split into its bits (see `extract_bits`):
+    /// the least significant ones make a digit small enough for a look up table, and the function is evaluated
+    /// with one bootstrap on this digit for each possible value of the most significant bits.
+    /// The right output is then selected with a tree of multiplexers, one bootstrap each, driven by the
+    /// most significant bits.
+    /// The size of the digit is the largest one not affected by the drift of the bootstrap, so the evaluation costs
+    /// about 2 * nb_bit_precision + 2^(nb_bit_precision - nb_bit_digit + 1) bootstraps.
+    /// The multiplexers need 2 more bits than the output precision in their look up tables.
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the output encoder, with at most 3 bits of padding
+    ///
+    /// # Output
+    /// * a LWE struct encrypted under the input key
+    /// * DimensionError - if the keys and the input ciphertext have incompatible dimensions
+    /// * InvalidEncoderError - if the output encoder is not valid or has more than 3 bits of padding
+    /// * LookUpTableSizeError - if the output precision is too large for the multiplexers
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_input = Encoder::new_rounding_context(0., 31., 5, 1).unwrap();
+    /// let encoder_output = Encoder::new_rounding_context(0., 3., 2, 1).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_1024);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 5);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 3, 8);
+    ///
+    /// // encode and encrypt
+    /// let ciphertext = LWE::encode_encrypt(&secret_key_before, 27., &encoder_input).unwrap();
+    ///
+    /// let ciphertext_out = ciphertext
+    ///     .bootstrap_with_function_large_precision(&bsk, &ksk, |x| (x / 8.).floor(), &encoder_output)
+    ///     .unwrap();
+    /// assert_eq!(ciphertext_out.decrypt_decode_round(&secret_key_before).unwrap(), 3.);
+    /// ```
+    pub fn bootstrap_with_function_large_precision<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<crate::LWE, CryptoAPIError> {
+        self.check_bootstrap_keyswitch_keys(bsk, ksk)?;
+        if !encoder_output.is_valid() || encoder_output.nb_bit_padding > 3 {
+            return Err(InvalidEncoderError!(
+                encoder_output.nb_bit_precision,
+                encoder_output.delta
+            ));
+        }
+
+        // the outputs are computed with 3 bits of padding to leave room for the multiplexers
+        let nb_bit_input = self.encoder.nb_bit_precision;
+        let nb_bit_output = encoder_output.nb_bit_precision;
+        let output_log = <Torus as Numeric>::BITS - 3 - nb_bit_output;
+        if 1 << (nb_bit_output + 2) > bsk.polynomial_size {
+            return Err(LookUpTableSizeError!(
+                1 << (nb_bit_output + 2),
+                bsk.polynomial_size
+            ));
+        }
+
+        // the largest digit not affected by the drift (see bootstrap_output)
+        let nb_rounding_noise_bit: usize =
+            (npe::lwe::log2_rounding_noise(self.dimension)).ceil() as usize + 1;
+        let nb_bit_digit = usize::min(
+            i32::max(
+                bsk.get_polynomial_size_log() as i32 - nb_rounding_noise_bit as i32,
+                1,
+            ) as usize,
+            nb_bit_input,
+        );
+        if nb_bit_output + 2 + nb_rounding_noise_bit > bsk.get_polynomial_size_log() {
+            println!(
+                "{}: the multiplexers need {} bit(s) but only {} bit(s) are not affected by the noise. Consider increasing the polynomial size of the RLWE secret key.",
+                "Risk of error during bootstrap due to the rounding".red().bold(),
+                nb_bit_output + 2,
+                bsk.get_polynomial_size_log() as i32 - nb_rounding_noise_bit as i32
+            );
+        }
+
+        // extract the bits: the digit bits are moved in a digit with one bit of padding,
+        // the other ones in the selection bit of the multiplexers
+        let positions: Vec<Torus> = (0..nb_bit_input)
+            .map(|i| {
+                if i < nb_bit_digit {
+                    1 << (<Torus as Numeric>::BITS - 1 - nb_bit_digit + i)
+                } else {
+                    1 << (output_log + nb_bit_output + 1)
+                }
+            })
+            .collect();
+        let bits = self.extract_bits_raw(bsk, ksk, &positions)?;
+
+        // build the digit, shifted by half a cell
+        let mut digit = bits[0].clone();
+        for bit in bits[1..nb_bit_digit].iter() {
+            digit.ciphertext.update_with_add(&bit.ciphertext);
+            digit.variance = npe::add_ciphertexts(digit.variance, bit.variance);
+        }
+        digit.add_to_body(1 << (<Torus as Numeric>::BITS - 2 - nb_bit_digit));
+
+        // encode an output on the torus with 3 bits of padding
+        let encode_output = |message: f64| -> Torus {
+            let index = ((message - encoder_output.o) / encoder_output.delta
+                * f64::powi(2., nb_bit_output as i32))
+            .round();
+            let index = f64::min(
+                f64::max(index, 0.),
+                f64::powi(2., nb_bit_output as i32) - 1.,
+            );
+            (index as Torus) << output_log
+        };
+
+        // evaluate the function on the digit for each value of the most significant bits
+        let granularity = self.encoder.get_granularity();
+        let mut outputs: Vec<crate::LWE> = (0..(1 << (nb_bit_input - nb_bit_digit)))
+            .map(|high: usize| {
+                let lut = bsk.generate_cell_look_up_table(1 << nb_bit_digit, |cell| {
+                    let index = (high << nb_bit_digit) + cell;
+                    encode_output(f(self.encoder.o + index as f64 * granularity))
+                });
+                digit.bootstrap_keyswitch_raw(bsk, ksk, &lut)
+            })
+            .collect();
+
+        // tree of multiplexers: output_0 + bit * (output_1 - output_0)
+        let lut = bsk.generate_cell_look_up_table(1 << (nb_bit_output + 2), |cell| {
+            if cell >= 1 << (nb_bit_output + 1) {
+                ((cell as Torus).wrapping_sub(3 << nb_bit_output)) << output_log
+            } else {
+                0
+            }
+        });
+        for bit in bits[nb_bit_digit..].iter() {
+            outputs = outputs
+                .chunks(2)
+                .map(|pair| {
+                    // pack the difference of the outputs and the bit, shifted by half a cell
+                    let mut packed = pair[1].clone();
+                    packed.ciphertext.update_with_sub(&pair[0].ciphertext);
+                    packed.ciphertext.update_with_add(&bit.ciphertext);
+                    packed
+                        .add_to_body((1 << (output_log + nb_bit_output)) + (1 << (output_log - 1)));
+
+                    let mut res = pair[0].clone();
+                    let selected = packed.bootstrap_keyswitch_raw(bsk, ksk, &lut);
+                    res.ciphertext.update_with_add(&selected.ciphertext);
+                    res.variance = npe::add_ciphertexts(res.variance, selected.variance);
+                    res
+                })
+                .collect();
+        }
+
+        // set the output encoder and remove the padding in excess
+        let mut res = outputs.remove(0);
+        res.encoder = crate::Encoder {
+            nb_bit_padding: 3,
+            ..encoder_output.clone()
+        };
+        res.encoder.update_precision_from_variance(res.variance)?;
+        if encoder_output.nb_bit_padding < 3 {
+            res.remove_padding_inplace(3 - encoder_output.nb_bit_padding)?;
+        }
+        Ok(res)
+    }
+
+    /// Extract the bits of the message of self (see `extract_bits`), the bit i being output as bit * positions[i]
+    /// with the variance of a bootstrap followed by a key switch.
+    /// A second bootstrap is computed for each bit whose position is not its position in the message.
+    fn extract_bits_raw(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        positions: &[Torus],
+    ) -> Result<Vec<crate::LWE>, CryptoAPIError> {
+        self.check_bootstrap_keyswitch_keys(bsk, ksk)?;
+        let n_bits = positions.len();
+        if n_bits == 0 || n_bits > self.encoder.nb_bit_precision {
+            return Err(BitExtractionError!(n_bits, self.encoder.nb_bit_precision));
+        }
+
+        let nb_bit_message = self.encoder.nb_bit_precision + self.encoder.nb_bit_padding;
+        let delta_log = <Torus as Numeric>::BITS - nb_bit_message;
+
+        // notification of a problem
+        let failure_probability = npe::bit_extraction::failure_probability(
+            self.variance,
+            self.get_bootstrap_keyswitch_variance(bsk, ksk),
+            self.dimension,
+            bsk.polynomial_size,
+            nb_bit_message,
+            n_bits,
+        );
+        if failure_probability > BIT_EXTRACTION_MAX_FAILURE_PROBABILITY {
+            println!(
+                "{}: the extraction of {} bit(s) fails with a probability up to {:e}. Consider increasing the number of levels of the keys.",
+                "Risk of error during bit extraction".red().bold(),
+                n_bits,
+                failure_probability
+            );
+        }
+
+        // bootstrap to -position/2 or position/2, key switch and add position/2
+        let bootstrap_bit = |shifted: &crate::LWE, position: Torus| -> crate::LWE {
+            let lut = bsk.generate_cell_look_up_table(1, |_| (position >> 1).wrapping_neg());
+            let mut bit = shifted.bootstrap_keyswitch_raw(bsk, ksk, &lut);
+            bit.add_to_body(position >> 1);
+            bit
+        };
+
+        let mut current = self.clone();
+        let mut bits: Vec<crate::LWE> = Vec::with_capacity(n_bits);
+        for (i, position) in positions.iter().enumerate() {
+            // move the bit i on the most significant bit and center the two possible phases
+            let mut shifted = current.clone();
+            shifted
+                .ciphertext
+                .as_mut_tensor()
+                .update_with_scalar_shl(&(nb_bit_message - 1 - i));
+            shifted.add_to_body(1 << (<Torus as Numeric>::BITS - 2));
+
+            // the bit times 2^(delta_log + i), subtracted from the current ciphertext
+            let bit = bootstrap_bit(&shifted, 1 << (delta_log + i));
+            current.ciphertext.update_with_sub(&bit.ciphertext);
+            current.variance = npe::add_ciphertexts(current.variance, bit.variance);
+
+            if *position == 1 << (delta_log + i) {
+                bits.push(bit);
+            } else {
+                bits.push(bootstrap_bit(&shifted, *position));
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Compute a bootstrap with a raw look up table followed by a key switch, the output having the
+    /// encoder of self and the variance of a bootstrap followed by a key switch
+    fn bootstrap_keyswitch_raw(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        lut: &[Torus],
+    ) -> crate::LWE {
+        let mut ciphertext = LweCiphertext::allocate(0, LweSize(ksk.dimension_after + 1));
+        ksk.ciphertexts
+            .keyswitch_ciphertext(&mut ciphertext, &bsk.bootstrap_raw(&self.ciphertext, lut));
+        crate::LWE {
+            ciphertext,
+            variance: self.get_bootstrap_keyswitch_variance(bsk, ksk),
+            dimension: ksk.dimension_after,
+            encoder: self.encoder.clone(),
+        }
+    }
+
+    /// Return the variance of the output of a bootstrap of self followed by a key switch
+    fn get_bootstrap_keyswitch_variance(&self, bsk: &crate::LWEBSK, ksk: &crate::LWEKSK) -> f64 {
+        <Torus as npe::LWE>::key_switch(
+            bsk.dimension * bsk.polynomial_size,
+            ksk.level,
+            ksk.base_log,
+            ksk.variance,
+            <Torus as npe::Cross>::bootstrap(
+                self.dimension,
+                bsk.dimension,
+                bsk.level,
+                bsk.base_log,
+                bsk.polynomial_size,
+                bsk.variance,
+            ),
+        )
+    }
+
+    /// Add a torus element to the body of the ciphertext
+    fn add_to_body(&mut self, value: Torus) {
+        let update = self
+            .ciphertext
+            .as_tensor()
+            .get_element(self.dimension)
+            .wrapping_add(value);
+        *self
+            .ciphertext
+            .as_mut_tensor()
+            .get_element_mut(self.dimension) = update;
     }
 
     /// Return the size of one LWE ciphertext with the parameters of self
@@ -2044,6 +3423,72 @@ impl LWE {
     }
 }
 
+/// Compute the encoder of the output of a bootstrap, the precision being reduced if the output
+/// noise or the drift due to the rounding of the input overlap the message
+///
+/// # Arguments
+/// * `dimension` - the dimension of the input of the bootstrap
+/// * `nb_bit_precision` - the precision of the input of the bootstrap
+/// * `polynomial_size` - the size of the polynomials of the bootstrapping key
+/// * `new_var` - the variance of the output of the bootstrap
+/// * `encoder_output` - the requested output encoder
+/// * `nb_bit_padding` - the number of bits of padding of the input during the bootstrap
+///
+/// # Output
+/// * the output encoder
+pub(crate) fn bootstrap_output_encoder(
+    dimension: usize,
+    nb_bit_precision: usize,
+    polynomial_size: usize,
+    new_var: f64,
+    encoder_output: &crate::Encoder,
+    nb_bit_padding: usize,
+) -> Result<crate::Encoder, CryptoAPIError> {
+    let polynomial_size_log = f64::log2(polynomial_size as f64) as usize;
+
+    // create the output encoder
+    let mut new_encoder_output: crate::Encoder = encoder_output.clone();
+
+    // update the precision in case of the output noise (without drift) is too big and overlap the message
+    let nb_bit_overlap: usize = new_encoder_output.update_precision_from_variance(new_var)?;
+
+    // println!("call to npe : {}", new_var);
+    if nb_bit_overlap > 0 {
+        println!(
+            "{}: {} bit(s) of precision lost over {} bit(s) of message originally. Consider increasing the number of level and/or decreasing the log base.",
+            "Loss of precision during bootstrap".red().bold(),
+            nb_bit_overlap, nb_bit_precision
+        );
+    }
+
+    // calls the NPE to find out the amount of noise after rounding the input ciphertext (drift)
+    let nb_rounding_noise_bit: usize =
+        (npe::lwe::log2_rounding_noise(dimension)).ceil() as usize + 1;
+
+    // deals with the drift error
+    if nb_rounding_noise_bit + nb_bit_padding + new_encoder_output.nb_bit_precision
+        > polynomial_size_log + 1
+    {
+        let nb_bit_loss =
+            nb_bit_padding + new_encoder_output.nb_bit_precision + nb_rounding_noise_bit
+                - polynomial_size_log
+                - 1;
+
+        new_encoder_output.nb_bit_precision = i32::max(
+            new_encoder_output.nb_bit_precision as i32 - nb_bit_loss as i32,
+            0i32,
+        ) as usize;
+        // drift
+        println!(
+            "{}: {} bit(s) of precision lost over {} bit(s) of message originally ({} bits are affected by the noise). Consider increasing the polynomial size of the RLWE secret key.",
+            "Loss of precision during bootstrap due to the rounding".red().bold(),
+            nb_bit_loss, nb_bit_precision,nb_rounding_noise_bit
+        );
+    }
+
+    Ok(new_encoder_output)
+}
+
 /// Print needed pieces of information about an LWE
 impl fmt::Display for LWE {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -708,3 +708,395 @@ fn test_valid_lookup_table() {
         assert_eq_granularity!(cst, decryption_zero, ciphertext_output_zero.encoder);
     }
 }
+
+#[test]
+fn test_encode_encrypt_x_bootstrap_with_negacyclic_function_x_decrypt() {
+    // settings: the input interval covers the whole torus
+    let precision: usize = 3;
+    let encoder_input = crate::Encoder::new(-1., 0.75, precision, 0).unwrap();
+    let encoder_output = crate::Encoder::new(-2., 1., 2, 0).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 7, 3);
+
+    // the sign function is negacyclic
+    let f = |x: f64| if x < 0. { -1. } else { 1. };
+
+    for _ in 0..50 {
+        // a random message on the grid of the input encoder
+        let index: usize = random_index!(1 << precision);
+        let message: f64 = encoder_input.o + index as f64 * encoder_input.get_granularity();
+
+        // encode and encrypt
+        let ciphertext_input =
+            crate::LWE::encode_encrypt(&secret_key_input, message, &encoder_input).unwrap();
+
+        // bootstrap
+        let ciphertext_output = ciphertext_input
+            .bootstrap_with_negacyclic_function(&bsk, f, &encoder_output)
+            .unwrap();
+
+        // decrypt
+        let decryption = ciphertext_output
+            .decrypt_decode_round(&secret_key_output)
+            .unwrap();
+        assert_eq!(f(message), decryption);
+    }
+}
+
+#[test]
+fn test_bootstrap_with_negacyclic_function_not_negacyclic() {
+    // settings
+    let encoder_input = crate::Encoder::new(0., 7., 3, 0).unwrap();
+    let encoder_output = crate::Encoder::new(0., 7., 3, 1).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 7, 3);
+
+    // encode and encrypt
+    let ciphertext_input =
+        crate::LWE::encode_encrypt(&secret_key_input, 2., &encoder_input).unwrap();
+
+    // the identity is not negacyclic
+    let result = ciphertext_input.bootstrap_with_negacyclic_function(&bsk, |x| x, &encoder_output);
+    assert!(matches!(
+        result,
+        Err(crate::CryptoAPIError::NotNegacyclicError { .. })
+    ));
+}
+
+#[test]
+fn test_encode_encrypt_x_bootstrap_with_function_full_domain_x_decrypt() {
+    // settings: no bit of padding on the input
+    let (min, max) = (0., 7.);
+    let precision: usize = 3;
+    let encoder_input = crate::Encoder::new(min, max, precision, 0).unwrap();
+    let encoder_output = crate::Encoder::new(min, max, precision, 1).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping and key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 7, 3);
+    let ksk = crate::LWEKSK::new(&secret_key_output, &secret_key_input, 2, 7);
+
+    // a function which is not negacyclic
+    let f = |x: f64| (x * x) % 8.;
+
+    for _ in 0..20 {
+        // a random message on the grid of the input encoder
+        let message: f64 = random_index!(1 << precision) as f64;
+
+        // encode and encrypt
+        let ciphertext_input =
+            crate::LWE::encode_encrypt(&secret_key_input, message, &encoder_input).unwrap();
+
+        // bootstrap
+        let ciphertext_output = ciphertext_input
+            .bootstrap_with_function_full_domain(&bsk, &ksk, f, &encoder_output)
+            .unwrap();
+
+        // decrypt
+        let decryption = ciphertext_output
+            .decrypt_decode_round(&secret_key_output)
+            .unwrap();
+        assert_eq!(f(message), decryption);
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_comparisons_x_decrypt() {
+    // settings: two encoders with different intervals and paddings
+    let encoder_1 = crate::Encoder::new_rounding_context(0., 7., 3, 1).unwrap();
+    let encoder_2 = crate::Encoder::new_rounding_context(2., 9., 3, 2).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_750);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping and key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 4);
+    let ksk = crate::LWEKSK::new(&secret_key_output, &secret_key_input, 2, 7);
+
+    for _ in 0..10 {
+        // random messages on the grids of the encoders, equal once in a while
+        let message_1: f64 = random_index!(8) as f64;
+        let message_2: f64 = if random_index!(4) == 0 && message_1 >= 2. {
+            message_1
+        } else {
+            (random_index!(8) + 2) as f64
+        };
+
+        // encode and encrypt
+        let ciphertext_1 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_1, &encoder_1).unwrap();
+        let ciphertext_2 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_2, &encoder_2).unwrap();
+
+        // decrypt with the input key
+        let decrypt = |ct: crate::LWE| ct.decrypt_decode_round(&secret_key_input).unwrap();
+
+        assert_eq!(
+            (message_1 > message_2) as usize as f64,
+            decrypt(ciphertext_1.greater_than(&ciphertext_2, &bsk, &ksk).unwrap())
+        );
+        assert_eq!(
+            (message_1 < message_2) as usize as f64,
+            decrypt(ciphertext_1.less_than(&ciphertext_2, &bsk, &ksk).unwrap())
+        );
+        assert_eq!(
+            (message_1 == message_2) as usize as f64,
+            decrypt(ciphertext_1.equal(&ciphertext_2, &bsk, &ksk).unwrap())
+        );
+        assert_eq!(
+            f64::max(message_1, message_2),
+            decrypt(ciphertext_1.max(&ciphertext_2, &bsk, &ksk).unwrap())
+        );
+        assert_eq!(
+            f64::min(message_1, message_2),
+            decrypt(ciphertext_1.min(&ciphertext_2, &bsk, &ksk).unwrap())
+        );
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_sign_x_decrypt() {
+    // settings: the sign is computed with and without padding
+    let encoders = [
+        crate::Encoder::new_rounding_context(-4., 3., 3, 1).unwrap(),
+        crate::Encoder::new_rounding_context(-4., 3., 3, 0).unwrap(),
+    ];
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_750);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping and key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 4);
+    let ksk = crate::LWEKSK::new(&secret_key_output, &secret_key_input, 2, 7);
+
+    for encoder in encoders.iter() {
+        for _ in 0..10 {
+            // a random message on the grid of the encoder
+            let message: f64 = random_index!(8) as f64 - 4.;
+
+            // encode, encrypt and compute the sign
+            let ciphertext =
+                crate::LWE::encode_encrypt(&secret_key_input, message, encoder).unwrap();
+            let sign = ciphertext.sign(&bsk, &ksk).unwrap();
+
+            // decrypt with the input key
+            let decryption = sign.decrypt_decode_round(&secret_key_input).unwrap();
+            assert_eq!(message.signum() * (message != 0.) as usize as f64, decryption);
+        }
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_extract_bits_x_decrypt() {
+    // settings: messages in [-8, 7] on the grid of the encoder
+    let encoder = crate::Encoder::new_rounding_context(-8., 7., 4, 1).unwrap();
+
+    // secret keys: the noise of the key switch is multiplied by up to 2^(2 * (precision - 1))
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_1024);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping and key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 5);
+    let ksk = crate::LWEKSK::new(&secret_key_output, &secret_key_input, 3, 8);
+
+    for _ in 0..10 {
+        // a random message on the grid of the encoder
+        let index = random_index!(16);
+        let message: f64 = index as f64 - 8.;
+
+        // encode, encrypt and extract the bits
+        let ciphertext = crate::LWE::encode_encrypt(&secret_key_input, message, &encoder).unwrap();
+        let bits = ciphertext.extract_bits(&bsk, &ksk, 4).unwrap();
+        assert_eq!(bits.nb_ciphertexts, 4);
+
+        // decrypt with the input key, the bits are the ones of the index of the message
+        let decryption = bits.decrypt_decode_round(&secret_key_input).unwrap();
+        for (i, bit) in decryption.iter().enumerate() {
+            assert_eq!(((index >> i) & 1) as f64, *bit);
+        }
+    }
+
+    // the number of bits must be in [1, precision]
+    let ciphertext = crate::LWE::encode_encrypt(&secret_key_input, 0., &encoder).unwrap();
+    assert!(ciphertext.extract_bits(&bsk, &ksk, 0).is_err());
+    assert!(ciphertext.extract_bits(&bsk, &ksk, 5).is_err());
+}
+
+#[test]
+fn test_encode_encrypt_x_bootstrap_with_function_large_precision_x_decrypt() {
+    // settings: 7 bits of input, more than a look up table of the keys can hold
+    let encoder_input = crate::Encoder::new_rounding_context(0., 127., 7, 1).unwrap();
+    let encoder_output = crate::Encoder::new_rounding_context(0., 3., 2, 1).unwrap();
+    let f = |x: f64| ((x * 5.) % 4.).floor();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_1024);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping and key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 5);
+    let ksk = crate::LWEKSK::new(&secret_key_output, &secret_key_input, 3, 8);
+
+    for _ in 0..3 {
+        // a random message on the grid of the encoder
+        let message: f64 = random_index!(128) as f64;
+
+        // encode, encrypt and evaluate the function
+        let ciphertext =
+            crate::LWE::encode_encrypt(&secret_key_input, message, &encoder_input).unwrap();
+        let ciphertext_out = ciphertext
+            .bootstrap_with_function_large_precision(&bsk, &ksk, f, &encoder_output)
+            .unwrap();
+        assert_eq!(ciphertext_out.dimension, ciphertext.dimension);
+
+        // decrypt with the input key
+        let decryption = ciphertext_out
+            .decrypt_decode_round(&secret_key_input)
+            .unwrap();
+        assert_eq!(f(message), decryption);
+    }
+
+    // the multiplexers cannot handle the output precision
+    let ciphertext = crate::LWE::encode_encrypt(&secret_key_input, 0., &encoder_input).unwrap();
+    let encoder_output = crate::Encoder::new_rounding_context(0., 511., 9, 1).unwrap();
+    assert!(ciphertext
+        .bootstrap_with_function_large_precision(&bsk, &ksk, f, &encoder_output)
+        .is_err());
+}
+
+#[test]
+fn test_encode_encrypt_x_bootstrap_bivariate_x_decrypt() {
+    // settings: a 2D look up table of 4 x 4 values
+    let encoder_1 = crate::Encoder::new_rounding_context(0., 3., 2, 3).unwrap();
+    let encoder_2 = crate::Encoder::new_rounding_context(-2., 1., 2, 1).unwrap();
+    let encoder_output = crate::Encoder::new_rounding_context(0., 15., 4, 1).unwrap();
+    let table: [[f64; 4]; 4] = [
+        [3., 1., 4., 1.],
+        [5., 9., 2., 6.],
+        [5., 3., 5., 8.],
+        [9., 7., 9., 3.],
+    ];
+    let f = |x: f64, y: f64| table[x as usize][(y + 2.) as usize];
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_630);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping key
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 7, 3);
+
+    for _ in 0..10 {
+        // random messages on the grids of the encoders
+        let message_1: f64 = random_index!(4) as f64;
+        let message_2: f64 = random_index!(4) as f64 - 2.;
+
+        // encode, encrypt and evaluate the table
+        let ciphertext_1 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_1, &encoder_1).unwrap();
+        let ciphertext_2 =
+            crate::LWE::encode_encrypt(&secret_key_input, message_2, &encoder_2).unwrap();
+        let ciphertext_out = ciphertext_1
+            .bootstrap_bivariate(&ciphertext_2, &bsk, f, &encoder_output)
+            .unwrap();
+
+        // decrypt with the output key
+        let decryption = ciphertext_out
+            .decrypt_decode_round(&secret_key_output)
+            .unwrap();
+        assert_eq!(f(message_1, message_2), decryption);
+    }
+
+    // not enough padding to receive the bits of the second ciphertext
+    let ciphertext_1 = crate::LWE::encode_encrypt(&secret_key_input, 0., &encoder_1).unwrap();
+    let ciphertext_2 = crate::LWE::encode_encrypt(&secret_key_input, 0., &encoder_2).unwrap();
+    assert!(ciphertext_2
+        .bootstrap_bivariate(&ciphertext_1, &bsk, f, &encoder_output)
+        .is_err());
+
+    // too many values for the look up table, on a noiseless ciphertext so the precision is kept
+    let mut ciphertext_3 = crate::LWE::zero(secret_key_input.dimension).unwrap();
+    ciphertext_3.encoder = crate::Encoder::new_rounding_context(0., 511., 9, 3).unwrap();
+    assert!(ciphertext_3
+        .bootstrap_bivariate(&ciphertext_2, &bsk, f, &encoder_output)
+        .is_err());
+}
+
+#[test]
+fn test_encode_encrypt_x_operators_x_decrypt() {
+    use crate::traits::{GenericNeg, GenericPolicyOps, OperatorPolicy};
+
+    // settings: the messages stay in the intervals of the centered operations
+    let encoder = crate::Encoder::new(-5., 5., 6, 4).unwrap();
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_1024);
+    let message_1: f64 = random_message!(-1.5, 1.5);
+    let message_2: f64 = random_message!(-1.5, 1.5);
+    let ciphertext_1 = crate::LWE::encode_encrypt(&secret_key, message_1, &encoder).unwrap();
+    let ciphertext_2 = crate::LWE::encode_encrypt(&secret_key, message_2, &encoder).unwrap();
+
+    let decrypt = |ct: &crate::LWE| ct.decrypt_decode(&secret_key).unwrap();
+
+    // additions and subtractions between ciphertexts
+    let ct = &ciphertext_1 + &ciphertext_2;
+    assert_eq_granularity!(message_1 + message_2, decrypt(&ct), ct.encoder);
+    let ct = &ciphertext_1 - &ciphertext_2;
+    assert_eq_granularity!(message_1 - message_2, decrypt(&ct), ct.encoder);
+    let mut ct = ciphertext_1.clone();
+    ct += &ciphertext_2;
+    assert_eq_granularity!(message_1 + message_2, decrypt(&ct), ct.encoder);
+    let mut ct = ciphertext_1.clone();
+    ct -= &ciphertext_2;
+    assert_eq_granularity!(message_1 - message_2, decrypt(&ct), ct.encoder);
+
+    for policy in [
+        OperatorPolicy::Padding,
+        OperatorPolicy::Centered,
+        OperatorPolicy::Exact,
+    ]
+    .iter()
+    {
+        let ct = ciphertext_1.add_with_policy(&ciphertext_2, *policy).unwrap();
+        assert_eq_granularity!(message_1 + message_2, decrypt(&ct), ct.encoder);
+        let mut ct = ciphertext_1.clone();
+        ct.sub_with_policy_inplace(&ciphertext_2, *policy).unwrap();
+        assert_eq_granularity!(message_1 - message_2, decrypt(&ct), ct.encoder);
+    }
+
+    // operations with constants
+    let ct = -&ciphertext_1;
+    assert_eq_granularity!(-message_1, decrypt(&ct), ct.encoder);
+    let ct = &ciphertext_1 * 3;
+    assert_eq_granularity!(3. * message_1, decrypt(&ct), ct.encoder);
+    let ct = &ciphertext_1 + 2.5;
+    assert_eq_granularity!(message_1 + 2.5, decrypt(&ct), ct.encoder);
+    let mut ct = ciphertext_1.clone();
+    ct *= 2;
+    ct -= 2.5;
+    assert_eq_granularity!(2. * message_1 - 2.5, decrypt(&ct), ct.encoder);
+
+    // the checked variants return the errors
+    let ciphertext_3 = crate::LWE::zero(630).unwrap();
+    assert!(GenericAdd::add(&ciphertext_1, &ciphertext_3).is_err());
+    assert!(GenericNeg::neg(&ciphertext_1).is_ok());
+}
@@ -3,23 +3,31 @@ use std::fmt;
 use backtrace::Backtrace;
 use colored::Colorize;
 
-use concrete_core::crypto::LweDimension;
-use concrete_core::math::decomposition::{DecompositionBaseLog, DecompositionLevelCount};
-use concrete_core::math::polynomial::PolynomialSize;
+use concrete_commons::parameters::{
+    DecompositionBaseLog, DecompositionLevelCount, GlweSize, LweSize, PolynomialSize,
+};
 use concrete_core::{
-    crypto::{bootstrap::BootstrapKey, GlweSize},
-    math::tensor::{AsMutTensor, AsRefTensor},
-    math::{fft::Complex64, tensor::Tensor},
+    crypto::{
+        bootstrap::{Bootstrap, FourierBootstrapKey, StandardBootstrapKey},
+        glwe::GlweCiphertext,
+        lwe::LweCiphertext,
+    },
+    math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor, IntoTensor},
+    math::{
+        fft::{AlignedVec, Complex64},
+        tensor::Tensor,
+    },
 };
 
 use crate::error::CryptoAPIError;
 use crate::Torus;
-use concrete_commons::{Numeric, StandardDev};
-use concrete_core::math::random::EncryptionRandomGenerator;
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::numeric::Numeric;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct LWEBSK {
-    pub ciphertexts: BootstrapKey<Vec<Complex64>>,
+    pub ciphertexts: Tensor<AlignedVec<Complex64>>,
     pub variance: f64,
     pub dimension: usize,
     pub polynomial_size: usize,
@@ -34,6 +42,19 @@ impl LWEBSK {
             / (usize::pow(self.dimension + 1, 2) * self.level * self.polynomial_size)
     }
 
+    /// Return a view of the key as a bootstrapping key in the fourier domain
+    ///
+    /// The fft buffers live in the view, so the key itself can be shared between threads
+    pub(crate) fn fourier_key(&self) -> FourierBootstrapKey<&[Complex64], Torus> {
+        FourierBootstrapKey::from_container(
+            self.ciphertexts.as_slice(),
+            GlweSize(self.dimension + 1),
+            PolynomialSize(self.polynomial_size),
+            DecompositionLevelCount(self.level),
+            DecompositionBaseLog(self.base_log),
+        )
+    }
+
     /// Return the log2 of the polynomial size of the RLWE involved in the bootstrap
     pub fn get_polynomial_size_log(&self) -> usize {
         f64::log2(self.polynomial_size as f64) as usize
@@ -54,65 +75,174 @@ impl LWEBSK {
         encoder_output: &crate::Encoder,
         f: F,
     ) -> Result<Vec<Torus>, CryptoAPIError> {
+        functional_look_up_table(self.polynomial_size, encoder_input, encoder_output, f)
+    }
+
+    /// Build a lookup table for the identity function from two encoders
+    ///
+    /// # Argument
+    /// * `encoder_input` - the encoder of the input (of the bootstrap)
+    /// * `encoder_output` - the encoder of the output (of the bootstrap)
+    ///
+    /// # Output
+    /// * a slice of Torus containing the lookup table
+    pub fn generate_identity_look_up_table(
+        &self,
+        encoder_input: &crate::Encoder,
+        encoder_output: &crate::Encoder,
+    ) -> Result<Vec<Torus>, CryptoAPIError> {
+        self.generate_functional_look_up_table(encoder_input, encoder_output, |x| x)
+    }
+
+    /// Check that a function is negacyclic with respect to two encoders, meaning that
+    /// f(x + delta / 2) is encoded as the opposite of f(x) for every message x of the input interval
+    ///
+    /// # Argument
+    /// * `encoder_input` - the encoder of the input (of the bootstrap)
+    /// * `encoder_output` - the encoder of the output (of the bootstrap)
+    /// * `f` - a function
+    ///
+    /// # Output
+    /// * NotNegacyclicError - if the function is not negacyclic
+    /// * PrecisionError - if the input encoder has no bit of precision
+    pub fn check_negacyclic_function<F: Fn(f64) -> f64>(
+        &self,
+        encoder_input: &crate::Encoder,
+        encoder_output: &crate::Encoder,
+        f: F,
+    ) -> Result<(), CryptoAPIError> {
         // check that precision != 0
         if encoder_input.nb_bit_precision == 0 {
             return Err(PrecisionError!());
         }
 
-        // check that the input encoder has at least 1 bit of padding
-        if encoder_input.nb_bit_padding == 0 {
-            return Err(NotEnoughPaddingError!(encoder_input.nb_bit_padding, 1));
+        // half of the output granularity on the torus
+        let tolerance: Torus = 1
+            << (<Torus as Numeric>::BITS
+                - encoder_output.nb_bit_padding
+                - encoder_output.nb_bit_precision
+                - 1);
+
+        // the messages of the input interval
+        let nb_messages: usize = 1 << encoder_input.nb_bit_precision;
+        let granularity = encoder_input.get_granularity();
+
+        for i in 0..(nb_messages >> 1) {
+            let encoded = encoder_output
+                .encode_outside_interval_operators(f(encoder_input.o + i as f64 * granularity))?;
+            let encoded_opposite = encoder_output.encode_outside_interval_operators(f(
+                encoder_input.o + (i + (nb_messages >> 1)) as f64 * granularity,
+            ))?;
+
+            // f(x) + f(x + delta / 2) should be zero on the torus
+            let sum = encoded.wrapping_add(encoded_opposite);
+            if Torus::min(sum, sum.wrapping_neg()) > tolerance {
+                return Err(NotNegacyclicError!(i));
+            }
         }
+        Ok(())
+    }
 
-        // clone the input encoder and set nb_bit_padding to 1
-        let mut encoder_input_clone = encoder_input.clone();
-        encoder_input_clone.nb_bit_padding = 1;
-
-        // allocation of the result
-        let mut result: Vec<Torus> = vec![0; self.polynomial_size];
+    /// Build a lookup table of a negacyclic function over the whole torus,
+    /// which means that the input of the bootstrap does not need any bit of padding
+    ///
+    /// # Argument
+    /// * `encoder_input` - the encoder of the input (of the bootstrap)
+    /// * `encoder_output` - the encoder of the output (of the bootstrap)
+    /// * `f` - a negacyclic function
+    ///
+    /// # Output
+    /// * a slice of Torus containing the lookup table
+    /// * NotNegacyclicError - if the function is not negacyclic
+    pub fn generate_full_domain_look_up_table<F: Fn(f64) -> f64>(
+        &self,
+        encoder_input: &crate::Encoder,
+        encoder_output: &crate::Encoder,
+        f: F,
+    ) -> Result<Vec<Torus>, CryptoAPIError> {
+        // the opposite half of the torus is computed by the bootstrap itself
+        self.check_negacyclic_function(encoder_input, encoder_output, &f)?;
 
-        // find the right index to start storing -val_i instead of val_i
-        let minus_start_index: usize =
-            self.polynomial_size - (self.polynomial_size >> (1 + encoder_input.nb_bit_precision));
+        // the first half of the torus contains 2^(nb_bit_precision - 1) messages
+        let nb_messages: usize = 1 << encoder_input.nb_bit_precision;
+        let granularity = encoder_input.get_granularity();
 
+        let mut result: Vec<Torus> = vec![0; self.polynomial_size];
         for (i, res) in result.iter_mut().enumerate() {
-            // create a valid encoding from i
-            let shift: usize = <Torus as Numeric>::BITS - self.get_polynomial_size_log() - 1;
-            let encoded: Torus = (i as Torus) << shift;
-
-            // decode the encoding
-            let decoded: f64 = encoder_input_clone.decode_core(encoded)?;
-
-            // apply the function
-            let f_decoded: f64 = f(decoded);
+            // index of the closest message
+            let index: usize =
+                ((i * nb_messages) as f64 / (2 * self.polynomial_size) as f64).round() as usize;
 
-            // encode the result
-            let output_encoded: Torus =
-                encoder_output.encode_outside_interval_operators(f_decoded)?;
-
-            *res = if i < minus_start_index {
-                output_encoded
-            } else {
-                output_encoded.wrapping_neg()
-            };
+            *res = encoder_output.encode_outside_interval_operators(f(
+                encoder_input.o + index as f64 * granularity
+            ))?;
         }
         Ok(result)
     }
 
-    /// Build a lookup table for the identity function from two encoders
+    /// Build a lookup table where the half torus is split into `nb_cells` cells of the same size
+    /// and every coefficient of a cell is set to the value `f(cell_index)`
     ///
     /// # Argument
-    /// * `encoder_input` - the encoder of the input (of the bootstrap)
-    /// * `encoder_output` - the encoder of the output (of the bootstrap)
+    /// * `nb_cells` - the number of cells in the half torus
+    /// * `f` - a function returning the Torus value of a cell from its index
     ///
     /// # Output
     /// * a slice of Torus containing the lookup table
-    pub fn generate_identity_look_up_table(
+    pub fn generate_cell_look_up_table<F: Fn(usize) -> Torus>(
         &self,
-        encoder_input: &crate::Encoder,
-        encoder_output: &crate::Encoder,
-    ) -> Result<Vec<Torus>, CryptoAPIError> {
-        self.generate_functional_look_up_table(encoder_input, encoder_output, |x| x)
+        nb_cells: usize,
+        f: F,
+    ) -> Vec<Torus> {
+        (0..self.polynomial_size)
+            .map(|i| {
+                f(usize::min(
+                    i * nb_cells / self.polynomial_size,
+                    nb_cells - 1,
+                ))
+            })
+            .collect()
+    }
+
+    /// Compute a bootstrap of a raw LWE ciphertext with a raw lookup table
+    ///
+    /// # Argument
+    /// * `ciphertext` - the LWE ciphertext to bootstrap
+    /// * `lut` - the lookup table, with polynomial_size coefficients
+    ///
+    /// # Output
+    /// * the LWE ciphertext encrypting the lookup table value, under the key derived from the RLWE
+    ///   secret key
+    pub fn bootstrap_raw(
+        &self,
+        ciphertext: &LweCiphertext<Vec<Torus>>,
+        lut: &[Torus],
+    ) -> LweCiphertext<Vec<Torus>> {
+        // build the trivial accumulator
+        let mut accumulator = GlweCiphertext::allocate(
+            0_u64,
+            PolynomialSize(self.polynomial_size),
+            GlweSize(self.dimension + 1),
+        );
+        accumulator
+            .as_mut_tensor()
+            .as_mut_slice()
+            .get_mut(
+                (self.dimension * self.polynomial_size)
+                    ..((self.dimension + 1) * self.polynomial_size),
+            )
+            .unwrap()
+            .copy_from_slice(lut);
+
+        // allocate the result
+        let mut result =
+            LweCiphertext::allocate(0, LweSize(self.dimension * self.polynomial_size + 1));
+
+        // compute the bootstrap
+        self.fourier_key()
+            .bootstrap(&mut result, ciphertext, &accumulator);
+
+        result
     }
 
     /// Create a valid bootstrapping key
@@ -130,9 +260,39 @@ impl LWEBSK {
         sk_output: &crate::RLWESecretKey,
         base_log: usize,
         level: usize,
+    ) -> LWEBSK {
+        LWEBSK::new_with_generator(
+            sk_input,
+            sk_output,
+            base_log,
+            level,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Create a valid bootstrapping key with a provided encryption generator
+    ///
+    /// A generator restored from an exported state allows to resume or reproduce the
+    /// generation of a key.
+    ///
+    /// # Argument
+    /// * `sk_before` - an LWE secret key (input for the bootstrap)
+    /// * `sk_after` - an LWE secret key (output for the bootstrap)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    ///
+    /// # Output
+    /// * an LWEBSK
+    pub fn new_with_generator(
+        sk_input: &crate::LWESecretKey,
+        sk_output: &crate::RLWESecretKey,
+        base_log: usize,
+        level: usize,
+        generator: &mut EncryptionRandomGenerator,
     ) -> LWEBSK {
         // allocation for the bootstrapping key
-        let mut coef_bsk = BootstrapKey::allocate(
+        let mut coef_bsk = StandardBootstrapKey::allocate(
             0_u64,
             GlweSize(sk_output.val.key_size().0 + 1),
             sk_output.val.polynomial_size(),
@@ -144,9 +304,9 @@ impl LWEBSK {
             &sk_input.val,
             &sk_output.val,
             StandardDev::from_standard_dev(sk_output.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
+            generator,
         );
-        let mut fourier_bsk = BootstrapKey::allocate(
+        let mut fourier_bsk = FourierBootstrapKey::allocate(
             Complex64::new(0., 0.),
             GlweSize(sk_output.val.key_size().0 + 1),
             sk_output.val.polynomial_size(),
@@ -157,7 +317,7 @@ impl LWEBSK {
         fourier_bsk.fill_with_forward_fourier(&coef_bsk);
 
         LWEBSK {
-            ciphertexts: fourier_bsk,
+            ciphertexts: fourier_bsk.into_tensor(),
             variance: f64::powi(sk_output.std_dev, 2),
             dimension: sk_output.dimension,
             polynomial_size: sk_output.polynomial_size,
@@ -183,7 +343,7 @@ impl LWEBSK {
         level: usize,
     ) -> LWEBSK {
         // allocation for the bootstrapping key
-        let fourier_bsk = BootstrapKey::allocate(
+        let fourier_bsk: FourierBootstrapKey<_, Torus> = FourierBootstrapKey::allocate(
             Complex64::new(0., 0.),
             GlweSize(sk_output.val.key_size().0 + 1),
             sk_output.val.polynomial_size(),
@@ -193,7 +353,7 @@ impl LWEBSK {
         );
 
         LWEBSK {
-            ciphertexts: fourier_bsk,
+            ciphertexts: fourier_bsk.into_tensor(),
             variance: f64::powi(sk_output.std_dev, 2),
             dimension: sk_output.dimension,
             polynomial_size: sk_output.polynomial_size,
@@ -210,7 +370,7 @@ impl LWEBSK {
         *tensor.get_element_mut(2) = self.polynomial_size as u64;
         *tensor.get_element_mut(3) = self.base_log as u64;
         *tensor.get_element_mut(4) = self.level as u64;
-        *tensor.get_element_mut(5) = self.ciphertexts.key_size().0 as u64;
+        *tensor.get_element_mut(5) = self.get_lwe_dimension() as u64;
 
         for (mut couple, c) in tensor
             .get_sub_mut(6..(self.ciphertexts.as_tensor().len() * 2 + 6))
@@ -233,14 +393,7 @@ impl LWEBSK {
             polynomial_size: *tensor.get_element(2) as usize,
             base_log: *tensor.get_element(3) as usize,
             level: *tensor.get_element(4) as usize,
-            ciphertexts: BootstrapKey::allocate(
-                Complex64::new(0., 0.),
-                GlweSize(*tensor.get_element(1) as usize + 1),
-                PolynomialSize(*tensor.get_element(2) as usize),
-                DecompositionLevelCount(*tensor.get_element(4) as usize),
-                DecompositionBaseLog(*tensor.get_element(3) as usize),
-                LweDimension(*tensor.get_element(5) as usize),
-            ),
+            ciphertexts: Tensor::from_container(AlignedVec::new((tensor.len() - 6) / 2)),
         };
 
         for (couple, c) in tensor
@@ -296,3 +449,67 @@ impl fmt::Display for LWEBSK {
         writeln!(f, "{}", to_be_print)
     }
 }
+
+/// Build the lookup table of a function from two encoders for a bootstrap with a given polynomial size
+///
+/// # Arguments
+/// * `polynomial_size` - the size of the polynomials of the accumulator
+/// * `encoder_input` - the encoder of the input (of the bootstrap)
+/// * `encoder_output` - the encoder of the output (of the bootstrap)
+/// * `f` - a function
+///
+/// # Output
+/// * a vector of Torus containing the lookup table
+pub(crate) fn functional_look_up_table<F: Fn(f64) -> f64>(
+    polynomial_size: usize,
+    encoder_input: &crate::Encoder,
+    encoder_output: &crate::Encoder,
+    f: F,
+) -> Result<Vec<Torus>, CryptoAPIError> {
+    // check that precision != 0
+    if encoder_input.nb_bit_precision == 0 {
+        return Err(PrecisionError!());
+    }
+
+    // check that the input encoder has at least 1 bit of padding
+    if encoder_input.nb_bit_padding == 0 {
+        return Err(NotEnoughPaddingError!(encoder_input.nb_bit_padding, 1));
+    }
+
+    // clone the input encoder and set nb_bit_padding to 1
+    let mut encoder_input_clone = encoder_input.clone();
+    encoder_input_clone.nb_bit_padding = 1;
+
+    // log2 of the polynomial size
+    let polynomial_size_log = f64::log2(polynomial_size as f64) as usize;
+
+    // allocation of the result
+    let mut result: Vec<Torus> = vec![0; polynomial_size];
+
+    // find the right index to start storing -val_i instead of val_i
+    let minus_start_index: usize =
+        polynomial_size - (polynomial_size >> (1 + encoder_input.nb_bit_precision));
+
+    for (i, res) in result.iter_mut().enumerate() {
+        // create a valid encoding from i
+        let shift: usize = <Torus as Numeric>::BITS - polynomial_size_log - 1;
+        let encoded: Torus = (i as Torus) << shift;
+
+        // decode the encoding
+        let decoded: f64 = encoder_input_clone.decode_core(encoded)?;
+
+        // apply the function
+        let f_decoded: f64 = f(decoded);
+
+        // encode the result
+        let output_encoded: Torus =
+            encoder_output.encode_outside_interval_operators(f_decoded)?;
+
+        *res = if i < minus_start_index {
+            output_encoded
+        } else {
+            output_encoded.wrapping_neg()
+        };
+    }
+    Ok(result)
+}
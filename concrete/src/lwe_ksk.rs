@@ -1,8 +1,7 @@
 use crate::Torus;
-use concrete_commons::StandardDev;
-use concrete_core::crypto::LweDimension;
-use concrete_core::math::decomposition::{DecompositionBaseLog, DecompositionLevelCount};
-use concrete_core::math::random::EncryptionRandomGenerator;
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::parameters::{DecompositionBaseLog, DecompositionLevelCount, LweDimension};
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
 use concrete_core::{
     crypto,
     math::tensor::Tensor,
@@ -70,6 +69,32 @@ impl LWEKSK {
         sk_after: &crate::LWESecretKey,
         base_log: usize,
         level: usize,
+    ) -> LWEKSK {
+        LWEKSK::new_with_generator(
+            sk_before,
+            sk_after,
+            base_log,
+            level,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Generate a valid LWE key switching key with a provided encryption generator
+    /// # Argument
+    /// * `sk_before` - an LWE secret key (input for the key switch)
+    /// * `sk_after` - an LWE secret key (output for the key switch)
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    ///
+    /// # Output
+    /// * an LWEKSK
+    pub fn new_with_generator(
+        sk_before: &crate::LWESecretKey,
+        sk_after: &crate::LWESecretKey,
+        base_log: usize,
+        level: usize,
+        generator: &mut EncryptionRandomGenerator,
     ) -> LWEKSK {
         let mut ksk_ciphertexts = crypto::lwe::LweKeyswitchKey::allocate(
             0_u64,
@@ -83,7 +108,7 @@ impl LWEKSK {
             &sk_before.val,
             &sk_after.val,
             StandardDev::from_standard_dev(sk_after.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
+            generator,
         );
 
         LWEKSK {
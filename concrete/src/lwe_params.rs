@@ -1,6 +1,13 @@
 //! lwe_params module describing the LWEParams structure
+//!
+//! The security of the constants below, taken from the LWE estimator, can be checked with the
+//! estimator of `concrete_npe::security` through `LWEParams::estimated_security_bits`.
 
 use super::{read_from_file, write_to_file};
+use crate::error::CryptoAPIError;
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_npe::security::{estimate_security, CostModel, SecretDistribution, SecurityEstimate};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
@@ -175,10 +182,64 @@ impl LWEParams {
         }
     }
 
+    /// Instantiate a new LWEParams after checking its security with a binary secret key
+    /// # Arguments
+    /// * `dimension` -the size of an LWE mask
+    /// * `log2_std_dev` -the log2 of the standard deviation used for the error normal distribution
+    /// * `min_security_bits` -the smallest accepted number of bits of security
+    /// # Output
+    /// * a new instantiation of an LWEParams
+    /// * InsecureParametersError if the estimated security is below `min_security_bits`
+    /// # Example
+    /// ```rust
+    /// use concrete::LWEParams;
+    ///
+    /// assert!(LWEParams::new_with_security(630, -14, 128).is_ok());
+    /// assert!(LWEParams::new_with_security(630, -20, 128).is_err());
+    /// ```
+    pub fn new_with_security(
+        dimension: usize,
+        log2_std_dev: i32,
+        min_security_bits: usize,
+    ) -> Result<LWEParams, CryptoAPIError> {
+        let params = LWEParams::new(dimension, log2_std_dev);
+        let security_bits = params.estimated_security_bits();
+        if security_bits < min_security_bits as f64 {
+            return Err(InsecureParametersError!(
+                dimension,
+                log2_std_dev,
+                security_bits,
+                min_security_bits
+            ));
+        }
+        Ok(params)
+    }
+
     pub fn get_std_dev(&self) -> f64 {
         f64::powi(2., self.log2_std_dev)
     }
 
+    /// Return the estimated number of bits of security with a binary secret key, i.e. the cost of the
+    /// cheapest lattice attack in the cost model of the LWE estimator used for the constants
+    pub fn estimated_security_bits(&self) -> f64 {
+        self.estimate_security(SecretDistribution::Binary, CostModel::Sieve)
+            .log2_cost
+    }
+
+    /// Return the cheapest lattice attack (primal uSVP, dual or hybrid)
+    /// # Arguments
+    /// * `secret` -the distribution of the secret key
+    /// * `cost_model` -the cost model of BKZ
+    /// # Output
+    /// * the estimate of the cheapest attack
+    pub fn estimate_security(
+        &self,
+        secret: SecretDistribution,
+        cost_model: CostModel,
+    ) -> SecurityEstimate {
+        estimate_security(self.dimension, self.log2_std_dev as f64, secret, cost_model)
+    }
+
     pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
         write_to_file(path, self)
     }
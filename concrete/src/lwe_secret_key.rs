@@ -1,10 +1,11 @@
 use super::{read_from_file, write_to_file};
 use crate::error::CryptoAPIError;
+use crate::Torus;
 use backtrace::Backtrace;
 use colored::Colorize;
-use concrete_core::crypto::LweDimension;
-use concrete_core::math::polynomial::PolynomialSize;
-use concrete_core::math::random::RandomGenerator;
+use concrete_commons::key_kinds::BinaryKeyKind;
+use concrete_commons::parameters::{LweDimension, PolynomialSize};
+use concrete_core::crypto::secret::generators::SecretRandomGenerator;
 use concrete_core::{
     crypto::secret::{GlweSecretKey, LweSecretKey},
     math::tensor::IntoTensor,
@@ -15,11 +16,32 @@ use std::fmt;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct LWESecretKey {
-    pub val: LweSecretKey<Vec<bool>>,
+    pub val: LweSecretKey<BinaryKeyKind, Vec<Torus>>,
     pub dimension: usize,
     pub std_dev: f64,
 }
 
+/// Container of a binary secret key in the format saved before the keys were stored as torus
+/// elements, i.e. a list of booleans
+#[derive(Deserialize)]
+pub(crate) struct LegacyBinaryKey {
+    tensor: Vec<bool>,
+}
+
+impl LegacyBinaryKey {
+    pub(crate) fn to_torus_container(&self) -> Vec<Torus> {
+        self.tensor.iter().map(|bit| *bit as Torus).collect()
+    }
+}
+
+/// An LWESecretKey in the format saved before the keys were stored as torus elements
+#[derive(Deserialize)]
+struct LegacyLWESecretKey {
+    val: LegacyBinaryKey,
+    dimension: usize,
+    std_dev: f64,
+}
+
 impl LWESecretKey {
     /// Generate a new secret key from an LWEParams
     /// # Argument
@@ -27,15 +49,35 @@ impl LWESecretKey {
     /// # Output
     /// * a new LWESecretKey
     pub fn new(params: &crate::LWEParams) -> LWESecretKey {
-        let val = LweSecretKey::generate(
-            LweDimension(params.dimension),
-            &mut RandomGenerator::new(None),
-        );
-        LWESecretKey {
-            val,
-            dimension: params.dimension,
-            std_dev: params.get_std_dev(),
-        }
+        LWESecretKey::new_with_generator(params, &mut SecretRandomGenerator::new(None))
+    }
+
+    /// Generate a new secret key from an LWEParams with a provided random generator
+    ///
+    /// A generator created with a seed, or restored from an exported state, makes the key
+    /// generation reproducible.
+    /// # Argument
+    /// * `p` - an LWEParams instance
+    /// * `generator` - the random generator sampling the key
+    /// # Output
+    /// * a new LWESecretKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::SecretRandomGenerator;
+    ///
+    /// let mut generator_1 = SecretRandomGenerator::new(Some(42));
+    /// let mut generator_2 = SecretRandomGenerator::new(Some(42));
+    /// let sk_1 = LWESecretKey::new_with_generator(&LWE128_630, &mut generator_1);
+    /// let sk_2 = LWESecretKey::new_with_generator(&LWE128_630, &mut generator_2);
+    /// assert_eq!(sk_1, sk_2);
+    /// ```
+    pub fn new_with_generator(
+        params: &crate::LWEParams,
+        generator: &mut SecretRandomGenerator,
+    ) -> LWESecretKey {
+        LWESecretKey::new_raw_with_generator(params.dimension, params.get_std_dev(), generator)
     }
 
     /// Generate a new secret key from a raw dimension (i.e. without a LWEParams input)
@@ -45,7 +87,26 @@ impl LWESecretKey {
     /// # Output
     /// * a new LWESecretKey
     pub fn new_raw(dimension: usize, std_dev: f64) -> LWESecretKey {
-        let val = LweSecretKey::generate(LweDimension(dimension), &mut RandomGenerator::new(None));
+        LWESecretKey::new_raw_with_generator(
+            dimension,
+            std_dev,
+            &mut SecretRandomGenerator::new(None),
+        )
+    }
+
+    /// Generate a new secret key from a raw dimension with a provided random generator
+    /// # Argument
+    /// * `dimension` s the length the LWE mask
+    /// * `std_dev` - the standard deviation for the encryption
+    /// * `generator` - the random generator sampling the key
+    /// # Output
+    /// * a new LWESecretKey
+    pub fn new_raw_with_generator(
+        dimension: usize,
+        std_dev: f64,
+        generator: &mut SecretRandomGenerator,
+    ) -> LWESecretKey {
+        let val = LweSecretKey::generate_binary(LweDimension(dimension), generator);
         LWESecretKey {
             val,
             dimension,
@@ -66,7 +127,7 @@ impl LWESecretKey {
             return Err(LweToRlweError!(self.dimension, polynomial_size));
         }
         Ok(crate::RLWESecretKey {
-            val: GlweSecretKey::from_container(
+            val: GlweSecretKey::binary_from_container(
                 self.val.clone().into_tensor().into_container(),
                 PolynomialSize(polynomial_size),
             ),
@@ -87,8 +148,20 @@ impl LWESecretKey {
         write_to_file(path, self)
     }
 
+    /// Load a secret key saved with `save`
+    ///
+    /// Keys saved in the former format, whose coefficients were stored as booleans, are
+    /// converted on the fly.
     pub fn load(path: &str) -> Result<LWESecretKey, Box<dyn Error>> {
-        read_from_file(path)
+        read_from_file(path).or_else(|err| {
+            read_from_file::<_, LegacyLWESecretKey>(path)
+                .map(|legacy| LWESecretKey {
+                    val: LweSecretKey::binary_from_container(legacy.val.to_torus_container()),
+                    dimension: legacy.dimension,
+                    std_dev: legacy.std_dev,
+                })
+                .map_err(|_| err)
+        })
     }
 }
 
@@ -0,0 +1,261 @@
+//! noise measurement module: actual noise of ciphertexts, measured with the secret key
+//!
+//! The `variance` of a ciphertext is only the prediction of `concrete-npe`. When debugging a set of
+//! parameters, the `measure_noise` methods of `LWE`, `VectorLWE` and `VectorRLWE` decrypt the phase of
+//! a ciphertext with its secret key and subtract the encoding of its message, the message being either
+//! provided or the closest one representable with the precision of the encoder. The resulting
+//! `NoiseMeasurement` gives the error in torus units and in bits, together with the number of bits left
+//! between the noise and the least significant bit of the message. A `NoiseHistogram` aggregates many
+//! measurements.
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::{DecompositionBaseLog, DecompositionLevelCount};
+use concrete_core::math::decomposition::SignedDecomposer;
+
+use crate::Torus;
+
+/// Structure describing the measured noise of a single ciphertext
+///
+/// # Attributes
+/// * `error` - the difference between the phase and the encoded message, as a torus element in [-1/2, 1/2[
+/// * `log2_error` - the log2 of the absolute value of the error, minus infinity if there is no error
+/// * `nb_bit_noise` - the number of least significant bits of the torus affected by the error
+/// * `margin` - the number of bits between the error and the least significant bit of the message, negative if the error overlaps the message
+/// * `variance` - the variance predicted for the ciphertext
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseMeasurement {
+    pub error: f64,
+    pub log2_error: f64,
+    pub nb_bit_noise: usize,
+    pub margin: i32,
+    pub variance: f64,
+}
+
+impl NoiseMeasurement {
+    /// Instantiate a new NoiseMeasurement from an error and the encoder of the ciphertext
+    ///
+    /// # Arguments
+    /// * `error` - the difference between the phase and the encoded message, as a torus element in [-1/2, 1/2[
+    /// * `encoder` - the encoder of the ciphertext
+    /// * `variance` - the variance predicted for the ciphertext
+    ///
+    /// # Output
+    /// * a new NoiseMeasurement
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let encoder = Encoder::new(0., 1., 4, 2).unwrap();
+    /// let measurement = NoiseMeasurement::new(f64::powi(2., -40), &encoder, f64::powi(2., -80));
+    ///
+    /// // the error affects the 25 least significant bits of the torus
+    /// assert_eq!(measurement.nb_bit_noise, 25);
+    /// assert_eq!(measurement.margin, 64 - 25 - 6);
+    /// ```
+    pub fn new(error: f64, encoder: &crate::Encoder, variance: f64) -> NoiseMeasurement {
+        let log2_error = f64::log2(error.abs());
+        let nb_bit_noise = f64::max(log2_error + <Torus as Numeric>::BITS as f64 + 1., 0.).floor();
+        let nb_bit_noise = usize::min(nb_bit_noise as usize, <Torus as Numeric>::BITS);
+        NoiseMeasurement {
+            error,
+            log2_error,
+            nb_bit_noise,
+            margin: <Torus as Numeric>::BITS as i32
+                - nb_bit_noise as i32
+                - (encoder.nb_bit_precision + encoder.nb_bit_padding) as i32,
+            variance,
+        }
+    }
+
+    /// Return true if the error overlaps the message, i.e. if the margin is negative
+    pub fn overlaps_message(&self) -> bool {
+        self.margin < 0
+    }
+}
+
+/// Print needed pieces of information about a NoiseMeasurement
+impl fmt::Display for NoiseMeasurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "error = {:e} (2^{:.2}), {} bit(s) of noise, margin = {} bit(s), predicted std dev = 2^{:.2}",
+            self.error,
+            self.log2_error,
+            self.nb_bit_noise,
+            self.margin,
+            f64::log2(self.variance) / 2.
+        )
+    }
+}
+
+/// Compute the closest encoding of a phase with the precision and the padding of an encoder
+///
+/// # Arguments
+/// * `phase` - the phase of a ciphertext
+/// * `encoder` - the encoder of the ciphertext
+///
+/// # Output
+/// * the closest multiple of the granularity of the encoder, on the torus
+pub(crate) fn closest_encoding(phase: Torus, encoder: &crate::Encoder) -> Torus {
+    let nb_bit = encoder.nb_bit_precision + encoder.nb_bit_padding;
+    if nb_bit >= <Torus as Numeric>::BITS {
+        // every torus element is a valid encoding
+        phase
+    } else {
+        SignedDecomposer::new(DecompositionBaseLog(nb_bit), DecompositionLevelCount(1))
+            .closest_representable(phase)
+    }
+}
+
+/// Structure aggregating noise measurements into a histogram of their number of bits of noise
+///
+/// # Attributes
+/// * `counts` - the number of measurements for each number of bits of noise, from 0 to the number of bits of the torus
+/// * `nb_measurements` - the number of measurements
+/// * `sum` - the sum of the errors
+/// * `sum_of_squares` - the sum of the squares of the errors
+/// * `max_abs_error` - the largest absolute value of the errors
+/// * `min_margin` - the smallest margin, None if there is no measurement
+/// * `nb_overlaps` - the number of measurements whose error overlaps the message
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoiseHistogram {
+    pub counts: Vec<usize>,
+    pub nb_measurements: usize,
+    pub sum: f64,
+    pub sum_of_squares: f64,
+    pub max_abs_error: f64,
+    pub min_margin: Option<i32>,
+    pub nb_overlaps: usize,
+}
+
+impl NoiseHistogram {
+    /// Instantiate an empty NoiseHistogram
+    pub fn new() -> NoiseHistogram {
+        NoiseHistogram {
+            counts: vec![0; <Torus as Numeric>::BITS + 1],
+            nb_measurements: 0,
+            sum: 0.,
+            sum_of_squares: 0.,
+            max_abs_error: 0.,
+            min_margin: None,
+            nb_overlaps: 0,
+        }
+    }
+
+    /// Instantiate a NoiseHistogram from a slice of measurements
+    ///
+    /// # Argument
+    /// * `measurements` - a slice of NoiseMeasurement
+    ///
+    /// # Output
+    /// * a new NoiseHistogram
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt some messages
+    /// let secret_key = LWESecretKey::new(&LWE128_630);
+    /// let encoder = Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    /// let messages: Vec<f64> = (0..16).map(|m| m as f64).collect();
+    /// let ciphertexts = VectorLWE::encode_encrypt(&secret_key, &messages, &encoder).unwrap();
+    ///
+    /// // measure their noise
+    /// let measurements = ciphertexts.measure_noise(&secret_key).unwrap();
+    /// let histogram = NoiseHistogram::from_measurements(&measurements);
+    /// assert_eq!(histogram.nb_measurements, 16);
+    /// assert_eq!(histogram.nb_overlaps, 0);
+    /// println!("{}", histogram);
+    /// ```
+    pub fn from_measurements(measurements: &[NoiseMeasurement]) -> NoiseHistogram {
+        let mut histogram = NoiseHistogram::new();
+        histogram.extend(measurements);
+        histogram
+    }
+
+    /// Add a measurement to the histogram
+    ///
+    /// # Argument
+    /// * `measurement` - a NoiseMeasurement
+    pub fn add(&mut self, measurement: &NoiseMeasurement) {
+        self.counts[measurement.nb_bit_noise] += 1;
+        self.nb_measurements += 1;
+        self.sum += measurement.error;
+        self.sum_of_squares += measurement.error * measurement.error;
+        self.max_abs_error = f64::max(self.max_abs_error, measurement.error.abs());
+        self.min_margin = Some(match self.min_margin {
+            Some(margin) => i32::min(margin, measurement.margin),
+            None => measurement.margin,
+        });
+        if measurement.overlaps_message() {
+            self.nb_overlaps += 1;
+        }
+    }
+
+    /// Add several measurements to the histogram
+    ///
+    /// # Argument
+    /// * `measurements` - a slice of NoiseMeasurement
+    pub fn extend(&mut self, measurements: &[NoiseMeasurement]) {
+        for measurement in measurements.iter() {
+            self.add(measurement);
+        }
+    }
+
+    /// Return the mean of the errors, 0 if there is no measurement
+    pub fn mean(&self) -> f64 {
+        if self.nb_measurements == 0 {
+            return 0.;
+        }
+        self.sum / self.nb_measurements as f64
+    }
+
+    /// Return the empirical variance of the errors, 0 if there are less than two measurements
+    pub fn variance(&self) -> f64 {
+        if self.nb_measurements < 2 {
+            return 0.;
+        }
+        let n = self.nb_measurements as f64;
+        (self.sum_of_squares - self.sum * self.sum / n) / (n - 1.)
+    }
+}
+
+impl Default for NoiseHistogram {
+    fn default() -> Self {
+        NoiseHistogram::new()
+    }
+}
+
+/// Print the summary of the measurements followed by one bar per number of bits of noise
+impl fmt::Display for NoiseHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max_count = self.counts.iter().copied().max().unwrap_or(0);
+        writeln!(
+            f,
+            "{} measurement(s), mean = {:e}, std dev = 2^{:.2}, max |error| = 2^{:.2}, min margin = {}, {} overlap(s)",
+            self.nb_measurements,
+            self.mean(),
+            f64::log2(self.variance()) / 2.,
+            f64::log2(self.max_abs_error),
+            match self.min_margin {
+                Some(margin) => margin.to_string(),
+                None => "-".to_string(),
+            },
+            self.nb_overlaps
+        )?;
+        for (nb_bit, count) in self.counts.iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            let width = usize::max(50 * count / max_count, 1);
+            writeln!(f, "{:>3} | {:<50} {}", nb_bit, "#".repeat(width), count)?;
+        }
+        Ok(())
+    }
+}
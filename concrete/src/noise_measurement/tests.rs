@@ -0,0 +1,105 @@
+use super::*;
+
+#[test]
+fn test_noise_measurement_new() {
+    let encoder = crate::Encoder::new(0., 1., 5, 3).unwrap();
+
+    // no error at all
+    let measurement = NoiseMeasurement::new(0., &encoder, 0.);
+    assert_eq!(measurement.nb_bit_noise, 0);
+    assert_eq!(measurement.margin, 64 - 8);
+    assert!(!measurement.overlaps_message());
+
+    // an error of one unit of the torus
+    let measurement = NoiseMeasurement::new(-f64::powi(2., -64), &encoder, 0.);
+    assert_eq!(measurement.log2_error, -64.);
+    assert_eq!(measurement.nb_bit_noise, 1);
+
+    // an error reaching the message
+    let measurement = NoiseMeasurement::new(0.6 * f64::powi(2., -7), &encoder, 0.);
+    assert_eq!(measurement.nb_bit_noise, 57);
+    assert_eq!(measurement.margin, -1);
+    assert!(measurement.overlaps_message());
+}
+
+#[test]
+fn test_closest_encoding() {
+    let encoder = crate::Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    let encoded = encoder.encode_core(6.).unwrap();
+    assert_eq!(
+        closest_encoding(encoded.wrapping_add(1 << 40), &encoder),
+        encoded
+    );
+    assert_eq!(
+        closest_encoding(encoded.wrapping_sub(1 << 40), &encoder),
+        encoded
+    );
+}
+
+#[test]
+fn test_measure_noise_lwe() {
+    let sk = crate::LWESecretKey::new(&crate::LWE128_630);
+    let encoder = crate::Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    let messages: Vec<f64> = (0..1000).map(|_| random_index!(16) as f64).collect();
+    let ct = crate::VectorLWE::encode_encrypt(&sk, &messages, &encoder).unwrap();
+
+    // the closest encodings are the messages
+    let measurements = ct.measure_noise(&sk).unwrap();
+    assert_eq!(
+        measurements,
+        ct.measure_noise_with_messages(&sk, &messages).unwrap()
+    );
+    let single = crate::LWE::encode_encrypt(&sk, messages[0], &encoder).unwrap();
+    assert_eq!(
+        single.measure_noise(&sk).unwrap(),
+        single.measure_noise_with_message(&sk, messages[0]).unwrap()
+    );
+
+    // the measured variance is close to the predicted one
+    let histogram = NoiseHistogram::from_measurements(&measurements);
+    assert_eq!(histogram.nb_measurements, 1000);
+    assert_eq!(histogram.nb_overlaps, 0);
+    let ratio = histogram.variance() / sk.get_variance();
+    assert!(ratio > 0.8 && ratio < 1.2, "ratio = {}", ratio);
+
+    // wrong key
+    let other_sk = crate::LWESecretKey::new(&crate::LWE128_1024);
+    assert!(ct.measure_noise(&other_sk).is_err());
+}
+
+#[test]
+fn test_measure_noise_vector_rlwe() {
+    let sk = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let encoder = crate::Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    let messages: Vec<f64> = (0..10).map(|_| random_index!(16) as f64).collect();
+    let ct = crate::VectorRLWE::encode_encrypt(&sk, &messages, &encoder).unwrap();
+
+    // only the coefficients with a valid encoder are measured
+    let measurements = ct.measure_noise(&sk).unwrap();
+    assert_eq!(measurements.len(), ct.nb_valid());
+    for measurement in measurements.iter() {
+        assert!(!measurement.overlaps_message());
+        assert_eq!(measurement.variance, sk.get_variance());
+    }
+}
+
+#[test]
+fn test_noise_histogram() {
+    let encoder = crate::Encoder::new(0., 1., 5, 3).unwrap();
+    let mut histogram = NoiseHistogram::new();
+    assert_eq!(histogram.min_margin, None);
+    assert_eq!(histogram.mean(), 0.);
+
+    histogram.extend(&[
+        NoiseMeasurement::new(f64::powi(2., -40), &encoder, 0.),
+        NoiseMeasurement::new(-f64::powi(2., -40), &encoder, 0.),
+        NoiseMeasurement::new(f64::powi(2., -30), &encoder, 0.),
+    ]);
+    assert_eq!(histogram.nb_measurements, 3);
+    assert_eq!(histogram.counts[25], 2);
+    assert_eq!(histogram.counts[35], 1);
+    assert_eq!(histogram.max_abs_error, f64::powi(2., -30));
+    assert_eq!(histogram.min_margin, Some(64 - 35 - 8));
+    assert_eq!(histogram.mean(), f64::powi(2., -30) / 3.);
+    assert_eq!(histogram.nb_overlaps, 0);
+}
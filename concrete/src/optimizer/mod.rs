@@ -0,0 +1,114 @@
+//! optimizer module: search of secure parameters for a target precision
+//!
+//! The search is done by `concrete_npe::optimizer` over the LWE dimension, the RLWE dimension, the
+//! polynomial size and the decompositions of the bootstrapping and key switching keys, the noise being
+//! estimated with concrete-npe and the security with the LWE estimator points of `lwe_params`.
+//! The cheapest parameters such that a sum of bootstrapped ciphertexts can be key switched and
+//! bootstrapped with a failure probability below the target are returned as `LWEParams` and `RLWEParams`.
+
+#[cfg(test)]
+mod tests;
+
+use crate::error::CryptoAPIError;
+use crate::{read_from_file, write_to_file};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_npe as npe;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// Structure describing the parameters found by the optimizer
+/// # Attributes
+/// * `lwe_params` - the parameters of the LWE secret key, input of the bootstrap
+/// * `rlwe_params` - the parameters of the RLWE secret key used in the bootstrapping key
+/// * `bsk_base_log` - the log2 of the decomposition base of the bootstrapping key
+/// * `bsk_level` - the number of levels of the decomposition of the bootstrapping key
+/// * `ksk_base_log` - the log2 of the decomposition base of the key switching key
+/// * `ksk_level` - the number of levels of the decomposition of the key switching key
+/// * `cost` - the estimated cost of a bootstrap followed by a key switch
+/// * `failure_probability` - the estimated probability that a bootstrap outputs a wrong value
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OptimizedParams {
+    pub lwe_params: crate::LWEParams,
+    pub rlwe_params: crate::RLWEParams,
+    pub bsk_base_log: usize,
+    pub bsk_level: usize,
+    pub ksk_base_log: usize,
+    pub ksk_level: usize,
+    pub cost: f64,
+    pub failure_probability: f64,
+}
+
+impl OptimizedParams {
+    /// Search the cheapest secure parameters for a target precision
+    ///
+    /// # Arguments
+    /// * `nb_bit_precision` - the number of bits of precision of the messages
+    /// * `nb_bit_padding` - the number of bits of padding of the messages
+    /// * `nb_additions` - the number of additions of bootstrapped ciphertexts between two bootstraps
+    /// * `failure_probability` - the largest probability that a bootstrap outputs a wrong value
+    /// * `security_level` - the number of bits of security, 80 or 128
+    ///
+    /// # Output
+    /// * the parameters
+    /// * OptimizerError - if no parameters reach the target
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // 3 bits of precision, sums of 4 ciphertexts, a failure every 2^40 bootstraps
+    /// let params = OptimizedParams::new(3, 1, 3, f64::powi(2., -40), 128).unwrap();
+    ///
+    /// // ready to use keys
+    /// let sk_rlwe = RLWESecretKey::new(&params.rlwe_params);
+    /// let sk_in = LWESecretKey::new(&params.lwe_params);
+    /// let bsk = LWEBSK::new(&sk_in, &sk_rlwe, params.bsk_base_log, params.bsk_level);
+    /// ```
+    pub fn new(
+        nb_bit_precision: usize,
+        nb_bit_padding: usize,
+        nb_additions: usize,
+        failure_probability: f64,
+        security_level: usize,
+    ) -> Result<OptimizedParams, CryptoAPIError> {
+        let params = npe::optimizer::optimize(
+            nb_bit_precision,
+            nb_bit_padding,
+            nb_additions,
+            failure_probability,
+            security_level,
+        )
+        .ok_or_else(|| {
+            OptimizerError!(
+                nb_bit_precision,
+                nb_bit_padding,
+                failure_probability,
+                security_level
+            )
+        })?;
+
+        Ok(OptimizedParams {
+            lwe_params: crate::LWEParams::new(params.lwe_dimension, params.lwe_log2_std_dev),
+            rlwe_params: crate::RLWEParams::new(
+                params.polynomial_size,
+                params.rlwe_dimension,
+                params.rlwe_log2_std_dev,
+            )?,
+            bsk_base_log: params.bsk_base_log,
+            bsk_level: params.bsk_level,
+            ksk_base_log: params.ksk_base_log,
+            ksk_level: params.ksk_level,
+            cost: params.cost,
+            failure_probability: params.failure_probability,
+        })
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<OptimizedParams, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_optimized_params_bootstrap_keyswitch() {
+    let params = OptimizedParams::new(2, 1, 0, f64::powi(2., -20), 128).unwrap();
+    assert!(params.failure_probability <= f64::powi(2., -20));
+
+    // keys
+    let sk_rlwe = crate::RLWESecretKey::new(&params.rlwe_params);
+    let sk_in = crate::LWESecretKey::new(&params.lwe_params);
+    let sk_out = sk_rlwe.to_lwe_secret_key();
+    let bsk = crate::LWEBSK::new(&sk_in, &sk_rlwe, params.bsk_base_log, params.bsk_level);
+    let ksk = crate::LWEKSK::new(&sk_out, &sk_in, params.ksk_base_log, params.ksk_level);
+
+    // bootstrap, key switch and bootstrap again
+    let encoder = crate::Encoder::new_rounding_context(0., 3., 2, 1).unwrap();
+    for message in 0..4 {
+        let ct = crate::LWE::encode_encrypt(&sk_in, message as f64, &encoder).unwrap();
+        let res = ct
+            .bootstrap_with_function(&bsk, |x| (x + 1.) % 4., &encoder)
+            .unwrap()
+            .keyswitch(&ksk)
+            .unwrap()
+            .bootstrap_with_function(&bsk, |x| (x + 1.) % 4., &encoder)
+            .unwrap();
+        assert_eq!(
+            res.decrypt_decode_round(&sk_out).unwrap(),
+            ((message + 2) % 4) as f64
+        );
+    }
+}
+
+#[test]
+fn test_optimized_params_errors() {
+    // too much precision
+    assert!(OptimizedParams::new(20, 1, 0, f64::powi(2., -40), 128).is_err());
+
+    // unsupported security level
+    assert!(OptimizedParams::new(2, 1, 0, f64::powi(2., -40), 100).is_err());
+}
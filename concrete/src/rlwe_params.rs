@@ -3,6 +3,7 @@ use super::{read_from_file, write_to_file};
 use crate::error::CryptoAPIError;
 use backtrace::Backtrace;
 use colored::Colorize;
+use concrete_npe::security::{estimate_security, CostModel, SecretDistribution, SecurityEstimate};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
@@ -167,10 +168,66 @@ impl RLWEParams {
         })
     }
 
+    /// Instantiate a new RLWEParams after checking its security with a binary secret key
+    /// # Arguments
+    /// * `polynomial_size` - the number of coefficients in a polynomial
+    /// * `dimension` - the size of an RLWE mask
+    /// * `log2_std_dev` - the log2 of the standard deviation used for the error normal distribution
+    /// * `min_security_bits` - the smallest accepted number of bits of security
+    /// # Output
+    /// * a new instantiation of an RLWEParams
+    /// * NotPowerOfTwoError if `polynomial_size` is not a power of 2
+    /// * InsecureParametersError if the estimated security is below `min_security_bits`
+    pub fn new_with_security(
+        polynomial_size: usize,
+        dimension: usize,
+        log2_std_dev: i32,
+        min_security_bits: usize,
+    ) -> Result<RLWEParams, CryptoAPIError> {
+        let params = RLWEParams::new(polynomial_size, dimension, log2_std_dev)?;
+        let security_bits = params.estimated_security_bits();
+        if security_bits < min_security_bits as f64 {
+            return Err(InsecureParametersError!(
+                dimension * polynomial_size,
+                log2_std_dev,
+                security_bits,
+                min_security_bits
+            ));
+        }
+        Ok(params)
+    }
+
     pub fn get_std_dev(&self) -> f64 {
         f64::powi(2., self.log2_std_dev)
     }
 
+    /// Return the estimated number of bits of security with a binary secret key, the RLWE
+    /// secret key being seen as an LWE secret key of size dimension * polynomial_size
+    pub fn estimated_security_bits(&self) -> f64 {
+        self.estimate_security(SecretDistribution::Binary, CostModel::Sieve)
+            .log2_cost
+    }
+
+    /// Return the cheapest lattice attack (primal uSVP, dual or hybrid), the algebraic structure
+    /// being ignored
+    /// # Arguments
+    /// * `secret` - the distribution of the secret key
+    /// * `cost_model` - the cost model of BKZ
+    /// # Output
+    /// * the estimate of the cheapest attack
+    pub fn estimate_security(
+        &self,
+        secret: SecretDistribution,
+        cost_model: CostModel,
+    ) -> SecurityEstimate {
+        estimate_security(
+            self.dimension * self.polynomial_size,
+            self.log2_std_dev as f64,
+            secret,
+            cost_model,
+        )
+    }
+
     pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
         write_to_file(path, self)
     }
@@ -1,23 +1,31 @@
 use super::{read_from_file, write_to_file};
-use concrete_core::crypto::GlweDimension;
-use concrete_core::math::polynomial::PolynomialSize;
-use concrete_core::math::random::RandomGenerator;
-use concrete_core::{
-    crypto::secret::{GlweSecretKey, LweSecretKey},
-    math::tensor::IntoTensor,
-};
+use crate::lwe_secret_key::LegacyBinaryKey;
+use crate::Torus;
+use concrete_commons::key_kinds::BinaryKeyKind;
+use concrete_commons::parameters::{GlweDimension, PolynomialSize};
+use concrete_core::crypto::secret::generators::SecretRandomGenerator;
+use concrete_core::crypto::secret::GlweSecretKey;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct RLWESecretKey {
-    pub val: GlweSecretKey<Vec<bool>>,
+    pub val: GlweSecretKey<BinaryKeyKind, Vec<Torus>>,
     pub polynomial_size: usize,
     pub dimension: usize,
     pub std_dev: f64,
 }
 
+/// An RLWESecretKey in the format saved before the keys were stored as torus elements
+#[derive(Deserialize)]
+struct LegacyRLWESecretKey {
+    val: LegacyBinaryKey,
+    polynomial_size: usize,
+    dimension: usize,
+    std_dev: f64,
+}
+
 impl RLWESecretKey {
     /// Generate a new secret key from an RLWEParams
     /// # Argument
@@ -25,17 +33,25 @@ impl RLWESecretKey {
     /// # Output
     /// * a new RLWESecretKey
     pub fn new(params: &crate::RLWEParams) -> RLWESecretKey {
-        let val = GlweSecretKey::generate(
-            GlweDimension(params.dimension),
-            PolynomialSize(params.polynomial_size),
-            &mut RandomGenerator::new(None),
-        );
-        RLWESecretKey {
-            val,
-            polynomial_size: params.polynomial_size,
-            dimension: params.dimension,
-            std_dev: params.get_std_dev(),
-        }
+        RLWESecretKey::new_with_generator(params, &mut SecretRandomGenerator::new(None))
+    }
+
+    /// Generate a new secret key from an RLWEParams with a provided random generator
+    /// # Argument
+    /// * `params` - an RLWEParams instance
+    /// * `generator` - the random generator sampling the key
+    /// # Output
+    /// * a new RLWESecretKey
+    pub fn new_with_generator(
+        params: &crate::RLWEParams,
+        generator: &mut SecretRandomGenerator,
+    ) -> RLWESecretKey {
+        RLWESecretKey::new_raw_with_generator(
+            params.polynomial_size,
+            params.dimension,
+            params.get_std_dev(),
+            generator,
+        )
     }
 
     /// Generate a new secret key from a raw dimension (i.e. without a RLWEParams input)
@@ -45,10 +61,32 @@ impl RLWESecretKey {
     /// # Output
     /// * a new RLWESecretKey
     pub fn new_raw(polynomial_size: usize, dimension: usize, std_dev: f64) -> RLWESecretKey {
-        let val = GlweSecretKey::generate(
+        RLWESecretKey::new_raw_with_generator(
+            polynomial_size,
+            dimension,
+            std_dev,
+            &mut SecretRandomGenerator::new(None),
+        )
+    }
+
+    /// Generate a new secret key from a raw dimension with a provided random generator
+    /// # Argument
+    /// * `polynomial_size` - the size of the polynomial
+    /// * `dimension` - the length the LWE mask
+    /// * `std_dev` - the standard deviation for the encryption
+    /// * `generator` - the random generator sampling the key
+    /// # Output
+    /// * a new RLWESecretKey
+    pub fn new_raw_with_generator(
+        polynomial_size: usize,
+        dimension: usize,
+        std_dev: f64,
+        generator: &mut SecretRandomGenerator,
+    ) -> RLWESecretKey {
+        let val = GlweSecretKey::generate_binary(
             GlweDimension(dimension),
             PolynomialSize(polynomial_size),
-            &mut RandomGenerator::new(None),
+            generator,
         );
         RLWESecretKey {
             val,
@@ -63,7 +101,7 @@ impl RLWESecretKey {
     /// * an LWE secret key
     pub fn to_lwe_secret_key(&self) -> crate::LWESecretKey {
         crate::LWESecretKey {
-            val: LweSecretKey::from_container(self.val.clone().into_tensor().into_container()),
+            val: self.val.clone().into_lwe_secret_key(),
             dimension: self.dimension * self.polynomial_size,
             std_dev: self.std_dev,
         }
@@ -80,8 +118,24 @@ impl RLWESecretKey {
         write_to_file(path, self)
     }
 
+    /// Load a secret key saved with `save`
+    ///
+    /// Keys saved in the former format, whose coefficients were stored as booleans, are
+    /// converted on the fly.
     pub fn load(path: &str) -> Result<RLWESecretKey, Box<dyn Error>> {
-        read_from_file(path)
+        read_from_file(path).or_else(|err| {
+            read_from_file::<_, LegacyRLWESecretKey>(path)
+                .map(|legacy| RLWESecretKey {
+                    val: GlweSecretKey::binary_from_container(
+                        legacy.val.to_torus_container(),
+                        PolynomialSize(legacy.polynomial_size),
+                    ),
+                    polynomial_size: legacy.polynomial_size,
+                    dimension: legacy.dimension,
+                    std_dev: legacy.std_dev,
+                })
+                .map_err(|_| err)
+        })
     }
 }
 
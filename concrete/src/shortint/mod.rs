@@ -0,0 +1,1100 @@
+//! shortint module: exact small integers with a message space and a carry space packed in one LWE
+//!
+//! A ShortInt encrypts a value in [0, message_modulus * carry_modulus[ with one bit of padding.
+//! The lower part of the value (modulo message_modulus) is the message, the upper part is the carry.
+//! Linear operations are exact and consume carry space, bootstraps apply look up tables
+//! on the whole value and can be used to clear the carry.
+
+#[cfg(test)]
+mod tests;
+
+use crate::error::CryptoAPIError;
+use crate::{read_from_file, write_to_file, Torus};
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::LweSize;
+use concrete_core::{
+    crypto::{
+        encoding::{Cleartext, Plaintext},
+        lwe::LweCiphertext,
+        secret::generators::{EncryptionRandomGenerator, SecretRandomGenerator},
+    },
+    math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor},
+};
+use concrete_npe as npe;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// Structure describing the parameters of short integers
+/// # Attributes
+/// * `lwe_params` - the parameters of the LWE secret key encrypting the short integers
+/// * `rlwe_params` - the parameters of the RLWE secret key used in the bootstrapping key
+/// * `bsk_base_log` - the log2 of the decomposition base of the bootstrapping key
+/// * `bsk_level` - the number of levels of the decomposition of the bootstrapping key
+/// * `ksk_base_log` - the log2 of the decomposition base of the key switching key
+/// * `ksk_level` - the number of levels of the decomposition of the key switching key
+/// * `message_modulus` - the size of the message space (a power of two)
+/// * `carry_modulus` - the size of the carry space (a power of two)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShortIntParams {
+    pub lwe_params: crate::LWEParams,
+    pub rlwe_params: crate::RLWEParams,
+    pub bsk_base_log: usize,
+    pub bsk_level: usize,
+    pub ksk_base_log: usize,
+    pub ksk_level: usize,
+    pub message_modulus: usize,
+    pub carry_modulus: usize,
+}
+
+/// 128 bits of security, 1 bit of message and 1 bit of carry (about 11 standard deviations of margin with concrete-npe)
+pub const SHORTINT128_MESSAGE_1_CARRY_1: ShortIntParams = ShortIntParams {
+    lwe_params: crate::LWE128_688,
+    rlwe_params: crate::RLWE128_1024_1,
+    bsk_base_log: 6,
+    bsk_level: 4,
+    ksk_base_log: 2,
+    ksk_level: 7,
+    message_modulus: 2,
+    carry_modulus: 2,
+};
+
+/// 128 bits of security, 2 bits of message and 2 bits of carry (about 7 standard deviations of margin with concrete-npe)
+pub const SHORTINT128_MESSAGE_2_CARRY_2: ShortIntParams = ShortIntParams {
+    lwe_params: crate::LWE128_800,
+    rlwe_params: crate::RLWE128_2048_1,
+    bsk_base_log: 6,
+    bsk_level: 4,
+    ksk_base_log: 2,
+    ksk_level: 8,
+    message_modulus: 4,
+    carry_modulus: 4,
+};
+
+impl ShortIntParams {
+    /// Return the variance of the noise after a bootstrap followed by a key switch, computed with concrete-npe
+    ///
+    /// # Output
+    /// * the output variance of a look up table evaluation
+    pub fn get_bootstrap_output_variance(&self) -> f64 {
+        crate::bootstrap_key_switch_variance(
+            &self.lwe_params,
+            &self.rlwe_params,
+            self.bsk_base_log,
+            self.bsk_level,
+            self.ksk_base_log,
+            self.ksk_level,
+        )
+    }
+
+    /// Return the distance between the input of a bivariate look up table and the border of its cell,
+    /// counted in standard deviations of the noise (including the drift of the bootstrap)
+    ///
+    /// # Output
+    /// * the number of standard deviations
+    pub fn get_nb_std_dev_margin(&self) -> f64 {
+        // the inputs are the outputs of bootstraps, packed as ct_1 * message_modulus + ct_2
+        let var_in = self.get_bootstrap_output_variance();
+        let var_packed = npe::add_ciphertexts(
+            <Torus as npe::LWE>::single_scalar_mul(var_in, self.message_modulus as Torus),
+            var_in,
+        );
+
+        // drift of the rounding of the mask in [0, 2N[
+        let var_drift = crate::bootstrap_drift_variance(
+            self.lwe_params.dimension,
+            self.rlwe_params.polynomial_size,
+        );
+
+        // half of a cell of the torus
+        let half_cell = 1. / (4 * self.message_modulus * self.carry_modulus) as f64;
+        half_cell / f64::sqrt(var_packed + var_drift)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<ShortIntParams, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Structure containing an encrypted short integer.
+///
+/// # Attributes
+/// * `ciphertext` - the LWE ciphertext, its encoder encodes the integers of [0, message_modulus * carry_modulus[
+/// * `message_modulus` - the size of the message space
+/// * `carry_modulus` - the size of the carry space
+/// * `degree` - an upper bound on the encrypted value (message and carry)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShortInt {
+    pub ciphertext: crate::LWE,
+    pub message_modulus: usize,
+    pub carry_modulus: usize,
+    pub degree: usize,
+}
+
+impl ShortInt {
+    /// Create a noiseless encryption of a value, which can be decrypted with any key of the right dimension
+    ///
+    /// # Arguments
+    /// * `value` - the value to encrypt, in [0, message_modulus * carry_modulus[
+    /// * `dimension` - the length the LWE mask
+    /// * `message_modulus` - the size of the message space
+    /// * `carry_modulus` - the size of the carry space
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * MessageTooBigError - if the value does not fit in the message and carry spaces
+    pub fn trivial(
+        value: usize,
+        dimension: usize,
+        message_modulus: usize,
+        carry_modulus: usize,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        let modulus = message_modulus * carry_modulus;
+        if value >= modulus {
+            return Err(MessageTooBigError!(value as f64, modulus as f64));
+        }
+
+        let mut ciphertext = crate::LWE::zero(dimension)?;
+        ciphertext.encoder = ShortInt::get_encoder(message_modulus, carry_modulus)?;
+        ciphertext.ciphertext.as_mut_tensor().as_mut_slice()[dimension] =
+            value as Torus * ShortInt::get_delta(message_modulus, carry_modulus);
+
+        Ok(ShortInt {
+            ciphertext,
+            message_modulus,
+            carry_modulus,
+            degree: value,
+        })
+    }
+
+    /// Return the encoder of the value space [0, message_modulus * carry_modulus[ with one bit of padding
+    pub fn get_encoder(
+        message_modulus: usize,
+        carry_modulus: usize,
+    ) -> Result<crate::Encoder, CryptoAPIError> {
+        let modulus = message_modulus * carry_modulus;
+        crate::Encoder::new_rounding_context(
+            0.,
+            (modulus - 1) as f64,
+            modulus.trailing_zeros() as usize,
+            1,
+        )
+    }
+
+    /// Return the scaling factor between a value and its plaintext
+    pub fn get_delta(message_modulus: usize, carry_modulus: usize) -> Torus {
+        (1 << (<Torus as Numeric>::BITS - 1)) / (message_modulus * carry_modulus) as Torus
+    }
+
+    /// Return the maximum degree that can be stored without overflowing the carry space
+    pub fn get_max_degree(&self) -> usize {
+        self.message_modulus * self.carry_modulus - 1
+    }
+
+    /// Return true if the carry space may be non-empty
+    pub fn has_carry(&self) -> bool {
+        self.degree >= self.message_modulus
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<ShortInt, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+impl fmt::Display for ShortInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "ShortInt {{\n         -> message_modulus = {}\n         -> carry_modulus = {}\n         -> degree = {}\n         -> variance = {}\n}}",
+            self.message_modulus, self.carry_modulus, self.degree, self.ciphertext.variance
+        )
+    }
+}
+
+/// Structure containing the secret keys needed to encrypt and decrypt short integers.
+///
+/// # Attributes
+/// * `params` - the parameters used to generate the keys
+/// * `lwe_secret_key` - the LWE secret key encrypting the short integers
+/// * `rlwe_secret_key` - the RLWE secret key used in the bootstrap
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ShortIntClientKey {
+    pub params: ShortIntParams,
+    pub lwe_secret_key: crate::LWESecretKey,
+    pub rlwe_secret_key: crate::RLWESecretKey,
+}
+
+impl ShortIntClientKey {
+    /// Generate a new client key from a ShortIntParams
+    ///
+    /// # Argument
+    /// * `params` - a ShortIntParams instance
+    ///
+    /// # Output
+    /// * a new ShortIntClientKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    /// ```
+    pub fn new(params: &ShortIntParams) -> ShortIntClientKey {
+        ShortIntClientKey::new_with_generator(params, &mut SecretRandomGenerator::new(None))
+    }
+
+    /// Generate a new client key from a ShortIntParams with a provided random generator
+    ///
+    /// # Arguments
+    /// * `params` - a ShortIntParams instance
+    /// * `generator` - the random generator sampling the LWE and the RLWE secret keys
+    ///
+    /// # Output
+    /// * a new ShortIntClientKey
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::SecretRandomGenerator;
+    ///
+    /// let client_key_1 = ShortIntClientKey::new_with_generator(
+    ///     &SHORTINT128_MESSAGE_1_CARRY_1,
+    ///     &mut SecretRandomGenerator::new(Some(42)),
+    /// );
+    /// let client_key_2 = ShortIntClientKey::new_with_generator(
+    ///     &SHORTINT128_MESSAGE_1_CARRY_1,
+    ///     &mut SecretRandomGenerator::new(Some(42)),
+    /// );
+    /// assert_eq!(client_key_1, client_key_2);
+    /// ```
+    pub fn new_with_generator(
+        params: &ShortIntParams,
+        generator: &mut SecretRandomGenerator,
+    ) -> ShortIntClientKey {
+        ShortIntClientKey {
+            params: params.clone(),
+            lwe_secret_key: crate::LWESecretKey::new_with_generator(&params.lwe_params, generator),
+            rlwe_secret_key: crate::RLWESecretKey::new_with_generator(
+                &params.rlwe_params,
+                generator,
+            ),
+        }
+    }
+
+    /// Generate the server key (bootstrapping and key switching keys) matching the client key
+    ///
+    /// # Output
+    /// * a new ShortIntServerKey
+    pub fn get_server_key(&self) -> ShortIntServerKey {
+        self.get_server_key_with_generator(&mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Generate the server key matching the client key with a provided encryption generator
+    ///
+    /// # Argument
+    /// * `generator` - the encryption generator sampling the masks and the noises of the keys
+    ///
+    /// # Output
+    /// * a new ShortIntServerKey
+    pub fn get_server_key_with_generator(
+        &self,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> ShortIntServerKey {
+        let bsk = crate::LWEBSK::new_with_generator(
+            &self.lwe_secret_key,
+            &self.rlwe_secret_key,
+            self.params.bsk_base_log,
+            self.params.bsk_level,
+            generator,
+        );
+        let ksk = crate::LWEKSK::new_with_generator(
+            &self.rlwe_secret_key.to_lwe_secret_key(),
+            &self.lwe_secret_key,
+            self.params.ksk_base_log,
+            self.params.ksk_level,
+            generator,
+        );
+        ShortIntServerKey {
+            bsk,
+            ksk,
+            message_modulus: self.params.message_modulus,
+            carry_modulus: self.params.carry_modulus,
+        }
+    }
+
+    /// Encrypt a message, the carry space being empty (the degree is message_modulus - 1)
+    ///
+    /// # Argument
+    /// * `message` - the message to encrypt, in [0, message_modulus[
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * MessageTooBigError - if the message does not fit in the message space
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    /// let ct = client_key.encrypt(1).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct).unwrap(), 1);
+    /// ```
+    pub fn encrypt(&self, message: usize) -> Result<ShortInt, CryptoAPIError> {
+        self.encrypt_with_generator(message, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt a message with a provided encryption generator, the carry space being empty
+    ///
+    /// # Arguments
+    /// * `message` - the message to encrypt, in [0, message_modulus[
+    /// * `generator` - the encryption generator sampling the mask and the noise
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * MessageTooBigError - if the message does not fit in the message space
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    ///
+    /// // two generators with the same seeds produce the same ciphertexts
+    /// let ct_1 = client_key
+    ///     .encrypt_with_generator(1, &mut EncryptionRandomGenerator::new_deterministic(1, 2))
+    ///     .unwrap();
+    /// let ct_2 = client_key
+    ///     .encrypt_with_generator(1, &mut EncryptionRandomGenerator::new_deterministic(1, 2))
+    ///     .unwrap();
+    /// assert_eq!(ct_1, ct_2);
+    /// ```
+    pub fn encrypt_with_generator(
+        &self,
+        message: usize,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        if message >= self.params.message_modulus {
+            return Err(MessageTooBigError!(
+                message as f64,
+                self.params.message_modulus as f64
+            ));
+        }
+        let mut res = self.encrypt_with_carry_with_generator(message, generator)?;
+        res.degree = self.params.message_modulus - 1;
+        Ok(res)
+    }
+
+    /// Encrypt a value which can use the carry space
+    ///
+    /// # Argument
+    /// * `value` - the value to encrypt, in [0, message_modulus * carry_modulus[
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * MessageTooBigError - if the value does not fit in the message and carry spaces
+    pub fn encrypt_with_carry(&self, value: usize) -> Result<ShortInt, CryptoAPIError> {
+        self.encrypt_with_carry_with_generator(value, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt a value which can use the carry space with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `value` - the value to encrypt, in [0, message_modulus * carry_modulus[
+    /// * `generator` - the encryption generator sampling the mask and the noise
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * MessageTooBigError - if the value does not fit in the message and carry spaces
+    pub fn encrypt_with_carry_with_generator(
+        &self,
+        value: usize,
+        generator: &mut EncryptionRandomGenerator,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        let (message_modulus, carry_modulus) =
+            (self.params.message_modulus, self.params.carry_modulus);
+        let mut res = ShortInt::trivial(
+            value,
+            self.lwe_secret_key.dimension,
+            message_modulus,
+            carry_modulus,
+        )?;
+        res.ciphertext.encrypt_raw_with_generator(
+            &self.lwe_secret_key,
+            value as Torus * ShortInt::get_delta(message_modulus, carry_modulus),
+            generator,
+        )?;
+
+        // the degree must not leak the value
+        res.degree = message_modulus * carry_modulus - 1;
+        Ok(res)
+    }
+
+    /// Decrypt a ShortInt and return its whole value (message and carry)
+    ///
+    /// # Argument
+    /// * `ct` - a ShortInt
+    ///
+    /// # Output
+    /// * the value in [0, message_modulus * carry_modulus[
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    pub fn decrypt_message_and_carry(&self, ct: &ShortInt) -> Result<usize, CryptoAPIError> {
+        // check dimensions
+        if ct.ciphertext.dimension != self.lwe_secret_key.dimension {
+            return Err(DimensionError!(
+                ct.ciphertext.dimension,
+                self.lwe_secret_key.dimension
+            ));
+        }
+
+        // compute the phase
+        let mut plaintext = Plaintext(0 as Torus);
+        self.lwe_secret_key
+            .val
+            .decrypt_lwe(&mut plaintext, &ct.ciphertext.ciphertext);
+
+        // round to the closest multiple of delta
+        let delta = ShortInt::get_delta(ct.message_modulus, ct.carry_modulus);
+        let value = plaintext.0.wrapping_add(delta / 2) / delta;
+        Ok(value as usize % (ct.message_modulus * ct.carry_modulus))
+    }
+
+    /// Decrypt a ShortInt and return its message (the value modulo message_modulus)
+    ///
+    /// # Argument
+    /// * `ct` - a ShortInt
+    ///
+    /// # Output
+    /// * the message in [0, message_modulus[
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    pub fn decrypt(&self, ct: &ShortInt) -> Result<usize, CryptoAPIError> {
+        Ok(self.decrypt_message_and_carry(ct)? % ct.message_modulus)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        write_to_file(path, self)
+    }
+
+    pub fn load(path: &str) -> Result<ShortIntClientKey, Box<dyn Error>> {
+        read_from_file(path)
+    }
+}
+
+/// Structure containing the public keys needed to compute on short integers.
+///
+/// # Attributes
+/// * `bsk` - the bootstrapping key
+/// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+/// * `message_modulus` - the size of the message space
+/// * `carry_modulus` - the size of the carry space
+#[derive(Debug, PartialEq)]
+pub struct ShortIntServerKey {
+    pub bsk: crate::LWEBSK,
+    pub ksk: crate::LWEKSK,
+    pub message_modulus: usize,
+    pub carry_modulus: usize,
+}
+
+impl ShortIntServerKey {
+    /// Check that a ciphertext can be used with this server key
+    fn check_ciphertext(&self, ct: &ShortInt) -> Result<(), CryptoAPIError> {
+        if ct.ciphertext.dimension != self.bsk.get_lwe_dimension() {
+            return Err(DimensionError!(
+                ct.ciphertext.dimension,
+                self.bsk.get_lwe_dimension()
+            ));
+        }
+        if ct.message_modulus != self.message_modulus {
+            return Err(ModulusMismatchError!(
+                ct.message_modulus,
+                self.message_modulus
+            ));
+        }
+        if ct.carry_modulus != self.carry_modulus {
+            return Err(ModulusMismatchError!(ct.carry_modulus, self.carry_modulus));
+        }
+        Ok(())
+    }
+
+    /// Check that a degree fits in the message and carry spaces
+    fn check_degree(&self, degree: usize) -> Result<(), CryptoAPIError> {
+        let max_degree = self.message_modulus * self.carry_modulus - 1;
+        if degree > max_degree {
+            return Err(CarryOverflowError!(degree, max_degree));
+        }
+        Ok(())
+    }
+
+    /// Check a degree computed with checked arithmetic, an overflow of usize being reported as
+    /// a carry overflow
+    fn checked_degree(&self, degree: Option<usize>) -> Result<usize, CryptoAPIError> {
+        let max_degree = self.message_modulus * self.carry_modulus - 1;
+        match degree {
+            Some(degree) if degree <= max_degree => Ok(degree),
+            _ => Err(CarryOverflowError!(degree.unwrap_or(usize::MAX), max_degree)),
+        }
+    }
+
+    /// Return the smallest multiple of message_modulus greater or equal to the degree,
+    /// used to compute an opposite without going below zero
+    fn get_negation_offset(&self, degree: usize) -> usize {
+        degree.div_ceil(self.message_modulus) * self.message_modulus
+    }
+
+    /// Add a constant value to the body of a ciphertext
+    fn add_to_body(&self, ct: &mut ShortInt, value: usize) {
+        let dimension = ct.ciphertext.dimension;
+        let update = ct.ciphertext.ciphertext.as_tensor().as_slice()[dimension].wrapping_add(
+            value as Torus * ShortInt::get_delta(self.message_modulus, self.carry_modulus),
+        );
+        ct.ciphertext.ciphertext.as_mut_tensor().as_mut_slice()[dimension] = update;
+    }
+
+    /// Compute ct_1 + ct_2, the carry of the result can be non-empty
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt
+    /// * `ct_2` - a ShortInt
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * CarryOverflowError - if the sum can overflow the carry space
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(1).unwrap();
+    /// let ct_2 = client_key.encrypt(1).unwrap();
+    ///
+    /// let ct_res = server_key.add(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt_message_and_carry(&ct_res).unwrap(), 2);
+    /// ```
+    pub fn add(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.check_ciphertext(ct_1)?;
+        self.check_ciphertext(ct_2)?;
+        self.check_degree(ct_1.degree + ct_2.degree)?;
+
+        let mut res = ct_1.clone();
+        res.ciphertext
+            .ciphertext
+            .update_with_add(&ct_2.ciphertext.ciphertext);
+        res.ciphertext.variance =
+            npe::add_ciphertexts(ct_1.ciphertext.variance, ct_2.ciphertext.variance);
+        res.degree = ct_1.degree + ct_2.degree;
+        Ok(res)
+    }
+
+    /// Compute the opposite of ct modulo message_modulus
+    /// The result is offset by the smallest multiple of message_modulus greater than the degree of ct,
+    /// so the value stays positive
+    ///
+    /// # Argument
+    /// * `ct` - a ShortInt
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * CarryOverflowError - if the result can overflow the carry space
+    pub fn neg(&self, ct: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let offset = self.get_negation_offset(ct.degree);
+        self.check_degree(offset)?;
+
+        let mut res = ct.clone();
+        res.ciphertext.ciphertext.update_with_neg();
+        self.add_to_body(&mut res, offset);
+        res.degree = offset;
+        Ok(res)
+    }
+
+    /// Compute ct_1 - ct_2 modulo message_modulus, the carry of the result can be non-empty
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt
+    /// * `ct_2` - a ShortInt
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * CarryOverflowError - if the difference can overflow the carry space
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(0).unwrap();
+    /// let ct_2 = client_key.encrypt(1).unwrap();
+    ///
+    /// let ct_res = server_key.sub(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 1);
+    /// ```
+    pub fn sub(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.sub_with_offset(ct_1, ct_2, self.get_negation_offset(ct_2.degree))
+    }
+
+    /// Compute ct_1 - ct_2 + offset, the offset keeping the result positive
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt
+    /// * `ct_2` - a ShortInt
+    /// * `offset` - a positive integer at least as large as the degree of ct_2
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * CarryOverflowError - if the offset is smaller than the degree of ct_2 or if the result
+    ///   can overflow the carry space
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(1).unwrap();
+    /// let ct_2 = client_key.encrypt(2).unwrap();
+    ///
+    /// let ct_res = server_key.sub_with_offset(&ct_1, &ct_2, 3).unwrap();
+    /// assert_eq!(client_key.decrypt_message_and_carry(&ct_res).unwrap(), 2);
+    /// ```
+    pub fn sub_with_offset(
+        &self,
+        ct_1: &ShortInt,
+        ct_2: &ShortInt,
+        offset: usize,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        self.check_ciphertext(ct_1)?;
+        self.check_ciphertext(ct_2)?;
+        if offset < ct_2.degree {
+            return Err(CarryOverflowError!(ct_2.degree, offset));
+        }
+        self.check_degree(ct_1.degree + offset)?;
+
+        let mut res = ct_1.clone();
+        res.ciphertext
+            .ciphertext
+            .update_with_sub(&ct_2.ciphertext.ciphertext);
+        res.ciphertext.variance =
+            npe::add_ciphertexts(ct_1.ciphertext.variance, ct_2.ciphertext.variance);
+        self.add_to_body(&mut res, offset);
+        res.degree = ct_1.degree + offset;
+        Ok(res)
+    }
+
+    /// Compute ct + scalar, the carry of the result can be non-empty
+    ///
+    /// # Arguments
+    /// * `ct` - a ShortInt
+    /// * `scalar` - a positive integer
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * CarryOverflowError - if the sum can overflow the carry space
+    pub fn scalar_add(&self, ct: &ShortInt, scalar: usize) -> Result<ShortInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let degree = self.checked_degree(ct.degree.checked_add(scalar))?;
+
+        let mut res = ct.clone();
+        self.add_to_body(&mut res, scalar);
+        res.degree = degree;
+        Ok(res)
+    }
+
+    /// Compute ct * scalar, the carry of the result can be non-empty
+    ///
+    /// # Arguments
+    /// * `ct` - a ShortInt
+    /// * `scalar` - a positive integer
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * CarryOverflowError - if the product can overflow the carry space
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct = client_key.encrypt(3).unwrap();
+    ///
+    /// let ct_res = server_key.scalar_mul(&ct, 5).unwrap();
+    /// assert_eq!(client_key.decrypt_message_and_carry(&ct_res).unwrap(), 15);
+    /// ```
+    pub fn scalar_mul(&self, ct: &ShortInt, scalar: usize) -> Result<ShortInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let degree = self.checked_degree(ct.degree.checked_mul(scalar))?;
+
+        let mut res = ct.clone();
+        res.ciphertext
+            .ciphertext
+            .update_with_scalar_mul(Cleartext(scalar as Torus));
+        res.ciphertext.variance =
+            <Torus as npe::LWE>::single_scalar_mul(ct.ciphertext.variance, scalar as Torus);
+        res.degree = degree;
+        Ok(res)
+    }
+
+    /// Bootstrap an LWE ciphertext with a raw look up table and key switch the result back to the input key,
+    /// the output variance is computed with concrete-npe
+    ///
+    /// # Arguments
+    /// * `ct` - an LWE ciphertext
+    /// * `lut` - the look up table, with polynomial_size coefficients
+    ///
+    /// # Output
+    /// * a new LWE with the encoder of the input
+    pub fn bootstrap_keyswitch_raw(&self, ct: &crate::LWE, lut: &[Torus]) -> crate::LWE {
+        // bootstrap
+        let bootstrapped = self.bsk.bootstrap_raw(&ct.ciphertext, lut);
+        let var_bootstrap = <Torus as npe::Cross>::bootstrap(
+            ct.dimension,
+            self.bsk.dimension,
+            self.bsk.level,
+            self.bsk.base_log,
+            self.bsk.polynomial_size,
+            self.bsk.variance,
+        );
+
+        // key switch
+        let mut ciphertext = LweCiphertext::allocate(0, LweSize(self.ksk.dimension_after + 1));
+        self.ksk
+            .ciphertexts
+            .keyswitch_ciphertext(&mut ciphertext, &bootstrapped);
+
+        crate::LWE {
+            ciphertext,
+            variance: <Torus as npe::LWE>::key_switch(
+                self.bsk.dimension * self.bsk.polynomial_size,
+                self.ksk.level,
+                self.ksk.base_log,
+                self.ksk.variance,
+                var_bootstrap,
+            ),
+            dimension: self.ksk.dimension_after,
+            encoder: ct.encoder.clone(),
+        }
+    }
+
+    /// Bootstrap a ciphertext with a look up table over the whole value space and key switch the result
+    fn bootstrap_keyswitch(&self, ct: &ShortInt, values: &[usize]) -> ShortInt {
+        let delta = ShortInt::get_delta(self.message_modulus, self.carry_modulus);
+
+        // move the values to the center of their cells
+        let mut input = ct.clone();
+        let dimension = input.ciphertext.dimension;
+        let update =
+            input.ciphertext.ciphertext.as_tensor().as_slice()[dimension].wrapping_add(delta / 2);
+        input.ciphertext.ciphertext.as_mut_tensor().as_mut_slice()[dimension] = update;
+
+        let lut = self
+            .bsk
+            .generate_cell_look_up_table(values.len(), |cell| values[cell] as Torus * delta);
+        input.ciphertext = self.bootstrap_keyswitch_raw(&input.ciphertext, &lut);
+        input.degree = values.iter().cloned().max().unwrap_or(0);
+        input
+    }
+
+    /// Apply a function to the whole value (message and carry) of a ciphertext with a bootstrap
+    ///
+    /// # Arguments
+    /// * `ct` - a ShortInt
+    /// * `f` - the function to apply, its outputs are reduced modulo message_modulus * carry_modulus
+    ///
+    /// # Output
+    /// * a new ShortInt
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct = client_key.encrypt(1).unwrap();
+    ///
+    /// let ct_res = server_key.apply_lookup_table(&ct, |x| 3 - x).unwrap();
+    /// assert_eq!(client_key.decrypt_message_and_carry(&ct_res).unwrap(), 2);
+    /// ```
+    pub fn apply_lookup_table<F: Fn(usize) -> usize>(
+        &self,
+        ct: &ShortInt,
+        f: F,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        self.check_ciphertext(ct)?;
+        let modulus = self.message_modulus * self.carry_modulus;
+
+        // the values above the degree are never reached
+        let values: Vec<usize> = (0..modulus)
+            .map(|x| if x <= ct.degree { f(x) % modulus } else { 0 })
+            .collect();
+        Ok(self.bootstrap_keyswitch(ct, &values))
+    }
+
+    /// Pack two ciphertexts as ct_1 * message_modulus + ct_2 and apply a bivariate function with a single bootstrap
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt with an empty carry space
+    /// * `ct_2` - a ShortInt with an empty carry space
+    /// * `f` - the function to apply to the messages, its outputs are reduced modulo message_modulus * carry_modulus
+    ///
+    /// # Output
+    /// * a new ShortInt
+    /// * CarryOverflowError - if the messages cannot be packed in one ciphertext
+    pub fn apply_bivariate_lookup_table<F: Fn(usize, usize) -> usize>(
+        &self,
+        ct_1: &ShortInt,
+        ct_2: &ShortInt,
+        f: F,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        self.check_ciphertext(ct_1)?;
+        self.check_ciphertext(ct_2)?;
+        if ct_2.degree >= self.message_modulus {
+            return Err(CarryOverflowError!(ct_2.degree, self.message_modulus - 1));
+        }
+
+        // pack the two messages
+        let packed = self.add(&self.scalar_mul(ct_1, self.message_modulus)?, ct_2)?;
+
+        let modulus = self.message_modulus * self.carry_modulus;
+        let values: Vec<usize> = (0..modulus)
+            .map(|x| {
+                let (x_1, x_2) = (x / self.message_modulus, x % self.message_modulus);
+                if x_1 <= ct_1.degree && x_2 <= ct_2.degree {
+                    f(x_1, x_2) % modulus
+                } else {
+                    0
+                }
+            })
+            .collect();
+        Ok(self.bootstrap_keyswitch(&packed, &values))
+    }
+
+    /// Extract the message of a ciphertext (the value modulo message_modulus) with a bootstrap
+    ///
+    /// # Argument
+    /// * `ct` - a ShortInt
+    ///
+    /// # Output
+    /// * a new ShortInt with an empty carry space
+    pub fn message_extract(&self, ct: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        let message_modulus = self.message_modulus;
+        self.apply_lookup_table(ct, |x| x % message_modulus)
+    }
+
+    /// Extract the carry of a ciphertext (the value divided by message_modulus) with a bootstrap
+    ///
+    /// # Argument
+    /// * `ct` - a ShortInt
+    ///
+    /// # Output
+    /// * a new ShortInt containing the carry as a message
+    pub fn carry_extract(&self, ct: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        let message_modulus = self.message_modulus;
+        self.apply_lookup_table(ct, |x| x / message_modulus)
+    }
+
+    /// Empty the carry space of a ciphertext with a bootstrap, which also resets its noise
+    ///
+    /// # Argument
+    /// * `ct` - a ShortInt
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(1).unwrap();
+    /// let mut ct_res = server_key.add(&ct_1, &ct_1).unwrap();
+    ///
+    /// server_key.clear_carry(&mut ct_res).unwrap();
+    /// assert_eq!(client_key.decrypt_message_and_carry(&ct_res).unwrap(), 0);
+    /// assert_eq!(ct_res.degree, 1);
+    /// ```
+    pub fn clear_carry(&self, ct: &mut ShortInt) -> Result<(), CryptoAPIError> {
+        *ct = self.message_extract(ct)?;
+        Ok(())
+    }
+
+    /// Compute ct_1 + ct_2, clearing the carries of the inputs first if there is not enough carry space
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt, its carry can be cleared
+    /// * `ct_2` - a ShortInt, its carry can be cleared
+    ///
+    /// # Output
+    /// * a new ShortInt
+    pub fn smart_add(
+        &self,
+        ct_1: &mut ShortInt,
+        ct_2: &mut ShortInt,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        if self.check_degree(ct_1.degree + ct_2.degree).is_err() {
+            self.clear_carry(ct_1)?;
+            if self.check_degree(ct_1.degree + ct_2.degree).is_err() {
+                self.clear_carry(ct_2)?;
+            }
+        }
+        self.add(ct_1, ct_2)
+    }
+
+    /// Compute ct_1 - ct_2 modulo message_modulus, clearing the carries of the inputs first
+    /// if there is not enough carry space
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt, its carry can be cleared
+    /// * `ct_2` - a ShortInt, its carry can be cleared
+    ///
+    /// # Output
+    /// * a new ShortInt
+    pub fn smart_sub(
+        &self,
+        ct_1: &mut ShortInt,
+        ct_2: &mut ShortInt,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        let offset = self.get_negation_offset(ct_2.degree);
+        if self.check_degree(ct_1.degree + offset).is_err() {
+            self.clear_carry(ct_1)?;
+            let offset = self.get_negation_offset(ct_2.degree);
+            if self.check_degree(ct_1.degree + offset).is_err() {
+                self.clear_carry(ct_2)?;
+            }
+        }
+        self.sub(ct_1, ct_2)
+    }
+
+    /// Compute the message of ct_1 * ct_2 modulo message_modulus
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt with an empty carry space
+    /// * `ct_2` - a ShortInt with an empty carry space
+    ///
+    /// # Output
+    /// * a new ShortInt
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2);
+    /// let server_key = client_key.get_server_key();
+    ///
+    /// let ct_1 = client_key.encrypt(3).unwrap();
+    /// let ct_2 = client_key.encrypt(2).unwrap();
+    ///
+    /// let ct_res = server_key.mul_lsb(&ct_1, &ct_2).unwrap();
+    /// assert_eq!(client_key.decrypt(&ct_res).unwrap(), 2);
+    /// ```
+    pub fn mul_lsb(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        let message_modulus = self.message_modulus;
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| (x * y) % message_modulus)
+    }
+
+    /// Compute the carry of ct_1 * ct_2, i.e. (ct_1 * ct_2) / message_modulus
+    ///
+    /// # Arguments
+    /// * `ct_1` - a ShortInt with an empty carry space
+    /// * `ct_2` - a ShortInt with an empty carry space
+    ///
+    /// # Output
+    /// * a new ShortInt
+    pub fn mul_msb(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        let message_modulus = self.message_modulus;
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| (x * y) / message_modulus)
+    }
+
+    /// Compute ct_1 == ct_2 as an encrypted 0 or 1
+    pub fn equal(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| (x == y) as usize)
+    }
+
+    /// Compute ct_1 > ct_2 as an encrypted 0 or 1
+    pub fn greater(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| (x > y) as usize)
+    }
+
+    /// Compute ct_1 >= ct_2 as an encrypted 0 or 1
+    pub fn greater_or_equal(
+        &self,
+        ct_1: &ShortInt,
+        ct_2: &ShortInt,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| (x >= y) as usize)
+    }
+
+    /// Compute ct_1 < ct_2 as an encrypted 0 or 1
+    pub fn less(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| (x < y) as usize)
+    }
+
+    /// Compute ct_1 <= ct_2 as an encrypted 0 or 1
+    pub fn less_or_equal(
+        &self,
+        ct_1: &ShortInt,
+        ct_2: &ShortInt,
+    ) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| (x <= y) as usize)
+    }
+
+    /// Compute the bitwise and of the messages of ct_1 and ct_2
+    pub fn bitand(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| x & y)
+    }
+
+    /// Compute the bitwise or of the messages of ct_1 and ct_2
+    pub fn bitor(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| x | y)
+    }
+
+    /// Compute the bitwise xor of the messages of ct_1 and ct_2
+    pub fn bitxor(&self, ct_1: &ShortInt, ct_2: &ShortInt) -> Result<ShortInt, CryptoAPIError> {
+        self.apply_bivariate_lookup_table(ct_1, ct_2, |x, y| x ^ y)
+    }
+
+    /// Save the bootstrapping key and the key switching key in two files,
+    /// the moduli are stored in the parameters of the client key
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    pub fn save(&self, path_bsk: &str, path_ksk: &str) {
+        self.bsk.save(path_bsk);
+        self.ksk.save(path_ksk);
+    }
+
+    /// Load a server key saved with `ShortIntServerKey::save`
+    ///
+    /// # Arguments
+    /// * `path_bsk` - the path of the bootstrapping key file
+    /// * `path_ksk` - the path of the key switching key file
+    /// * `params` - the parameters used to generate the keys
+    ///
+    /// # Output
+    /// * a ShortIntServerKey
+    pub fn load(path_bsk: &str, path_ksk: &str, params: &ShortIntParams) -> ShortIntServerKey {
+        ShortIntServerKey {
+            bsk: crate::LWEBSK::load(path_bsk),
+            ksk: crate::LWEKSK::load(path_ksk),
+            message_modulus: params.message_modulus,
+            carry_modulus: params.carry_modulus,
+        }
+    }
+}
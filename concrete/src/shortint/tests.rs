@@ -0,0 +1,206 @@
+use super::*;
+
+#[test]
+fn test_default_params_npe_margin() {
+    assert!(SHORTINT128_MESSAGE_1_CARRY_1.get_nb_std_dev_margin() > 6.);
+    assert!(SHORTINT128_MESSAGE_2_CARRY_2.get_nb_std_dev_margin() > 6.);
+}
+
+#[test]
+fn test_encrypt_x_decrypt() {
+    let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2);
+
+    for value in 0..16 {
+        let ct = client_key.encrypt_with_carry(value).unwrap();
+        assert_eq!(value, client_key.decrypt_message_and_carry(&ct).unwrap());
+        assert_eq!(value % 4, client_key.decrypt(&ct).unwrap());
+        assert_eq!(15, ct.degree);
+
+        let ct_trivial =
+            ShortInt::trivial(value, client_key.lwe_secret_key.dimension, 4, 4).unwrap();
+        assert_eq!(
+            value,
+            client_key.decrypt_message_and_carry(&ct_trivial).unwrap()
+        );
+    }
+
+    // the message must fit in the message space
+    assert!(client_key.encrypt(4).is_err());
+    assert!(client_key.encrypt_with_carry(16).is_err());
+}
+
+#[test]
+fn test_with_generator_reproducible() {
+    use concrete_core::crypto::secret::generators::{
+        EncryptionRandomGenerator, SecretRandomGenerator,
+    };
+
+    // the same seeds give the same keys and ciphertexts
+    let client_key_1 = ShortIntClientKey::new_with_generator(
+        &SHORTINT128_MESSAGE_1_CARRY_1,
+        &mut SecretRandomGenerator::new(Some(1)),
+    );
+    let client_key_2 = ShortIntClientKey::new_with_generator(
+        &SHORTINT128_MESSAGE_1_CARRY_1,
+        &mut SecretRandomGenerator::new(Some(1)),
+    );
+    assert_eq!(client_key_1, client_key_2);
+
+    let server_key_1 = client_key_1
+        .get_server_key_with_generator(&mut EncryptionRandomGenerator::new_deterministic(2, 3));
+    let server_key_2 = client_key_2
+        .get_server_key_with_generator(&mut EncryptionRandomGenerator::new_deterministic(2, 3));
+    assert_eq!(server_key_1, server_key_2);
+
+    let mut generator_1 = EncryptionRandomGenerator::new_deterministic(4, 5);
+    let mut generator_2 = EncryptionRandomGenerator::new_deterministic(4, 5);
+    for value in 0..4 {
+        let ct_1 = client_key_1
+            .encrypt_with_carry_with_generator(value, &mut generator_1)
+            .unwrap();
+        let ct_2 = client_key_2
+            .encrypt_with_carry_with_generator(value, &mut generator_2)
+            .unwrap();
+        assert_eq!(ct_1, ct_2);
+        assert_eq!(value, client_key_1.decrypt_message_and_carry(&ct_1).unwrap());
+    }
+}
+
+#[test]
+fn test_linear_operations() {
+    let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2);
+    let server_key = client_key.get_server_key();
+
+    for m_1 in 0..4 {
+        for m_2 in 0..4 {
+            let ct_1 = client_key.encrypt(m_1).unwrap();
+            let ct_2 = client_key.encrypt(m_2).unwrap();
+
+            // add
+            let ct_res = server_key.add(&ct_1, &ct_2).unwrap();
+            assert_eq!(
+                m_1 + m_2,
+                client_key.decrypt_message_and_carry(&ct_res).unwrap()
+            );
+            assert_eq!(6, ct_res.degree);
+
+            // sub
+            let ct_res = server_key.sub(&ct_1, &ct_2).unwrap();
+            assert_eq!((m_1 + 4 - m_2) % 4, client_key.decrypt(&ct_res).unwrap());
+
+            // neg
+            let ct_res = server_key.neg(&ct_2).unwrap();
+            assert_eq!((4 - m_2) % 4, client_key.decrypt(&ct_res).unwrap());
+
+            // scalar operations
+            let ct_res = server_key.scalar_add(&ct_1, m_2).unwrap();
+            assert_eq!(
+                m_1 + m_2,
+                client_key.decrypt_message_and_carry(&ct_res).unwrap()
+            );
+            let ct_res = server_key.scalar_mul(&ct_1, m_2 + 1).unwrap();
+            assert_eq!(
+                m_1 * (m_2 + 1),
+                client_key.decrypt_message_and_carry(&ct_res).unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_carry_overflow() {
+    let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    let server_key = client_key.get_server_key();
+
+    // the degree of 1 + 1 + 1 is 3, a fourth addition overflows
+    let ct = client_key.encrypt(1).unwrap();
+    let mut ct_res = server_key.add(&ct, &ct).unwrap();
+    ct_res = server_key.add(&ct_res, &ct).unwrap();
+    assert_eq!(3, ct_res.degree);
+    assert!(server_key.add(&ct_res, &ct).is_err());
+    assert!(server_key.scalar_mul(&ct_res, 2).is_err());
+
+    // scalars overflowing usize are reported as carry overflows
+    assert!(server_key.scalar_add(&ct_res, usize::MAX).is_err());
+    assert!(server_key.scalar_mul(&ct_res, usize::MAX).is_err());
+
+    // smart add clears the carry first
+    let mut ct_1 = ct.clone();
+    let ct_res = server_key.smart_add(&mut ct_res, &mut ct_1).unwrap();
+    assert_eq!(0, client_key.decrypt(&ct_res).unwrap());
+}
+
+#[test]
+fn test_extract_x_clear_carry() {
+    let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2);
+    let server_key = client_key.get_server_key();
+
+    for value in 0..16 {
+        let mut ct = client_key.encrypt_with_carry(value).unwrap();
+
+        let ct_message = server_key.message_extract(&ct).unwrap();
+        assert_eq!(
+            value % 4,
+            client_key.decrypt_message_and_carry(&ct_message).unwrap()
+        );
+
+        let ct_carry = server_key.carry_extract(&ct).unwrap();
+        assert_eq!(
+            value / 4,
+            client_key.decrypt_message_and_carry(&ct_carry).unwrap()
+        );
+
+        server_key.clear_carry(&mut ct).unwrap();
+        assert_eq!(
+            value % 4,
+            client_key.decrypt_message_and_carry(&ct).unwrap()
+        );
+        assert!(!ct.has_carry());
+    }
+}
+
+#[test]
+fn test_bivariate_operations() {
+    let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2);
+    let server_key = client_key.get_server_key();
+
+    for m_1 in 0..4 {
+        for m_2 in 0..4 {
+            let ct_1 = client_key.encrypt(m_1).unwrap();
+            let ct_2 = client_key.encrypt(m_2).unwrap();
+
+            let decrypt = |ct: ShortInt| client_key.decrypt_message_and_carry(&ct).unwrap();
+            assert_eq!(
+                (m_1 * m_2) % 4,
+                decrypt(server_key.mul_lsb(&ct_1, &ct_2).unwrap())
+            );
+            assert_eq!(
+                (m_1 * m_2) / 4,
+                decrypt(server_key.mul_msb(&ct_1, &ct_2).unwrap())
+            );
+            assert_eq!(
+                (m_1 == m_2) as usize,
+                decrypt(server_key.equal(&ct_1, &ct_2).unwrap())
+            );
+            assert_eq!(
+                (m_1 > m_2) as usize,
+                decrypt(server_key.greater(&ct_1, &ct_2).unwrap())
+            );
+            assert_eq!(
+                (m_1 >= m_2) as usize,
+                decrypt(server_key.greater_or_equal(&ct_1, &ct_2).unwrap())
+            );
+            assert_eq!(
+                (m_1 < m_2) as usize,
+                decrypt(server_key.less(&ct_1, &ct_2).unwrap())
+            );
+            assert_eq!(
+                (m_1 <= m_2) as usize,
+                decrypt(server_key.less_or_equal(&ct_1, &ct_2).unwrap())
+            );
+            assert_eq!(m_1 & m_2, decrypt(server_key.bitand(&ct_1, &ct_2).unwrap()));
+            assert_eq!(m_1 | m_2, decrypt(server_key.bitor(&ct_1, &ct_2).unwrap()));
+            assert_eq!(m_1 ^ m_2, decrypt(server_key.bitxor(&ct_1, &ct_2).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,1233 @@
+//! simulation module: noise-only simulation of the LWE ciphertexts
+//!
+//! A simulated ciphertext does not have any mask: it only stores its phase, i.e. the encoding of its
+//! message plus some noise, together with the variance of that noise and the encoder, as a trivial LWE
+//! ciphertext of dimension zero. The linear operations are thus computed with the very same code as the
+//! real ones and the variances are updated with the same formulas of `concrete-npe`.
+//! Key switches and bootstraps sample the noise they add, and a bootstrap also reproduces the rounding of
+//! the mask of its input, so that the decryption of a simulated ciphertext follows the same distribution as
+//! the decryption of a real one. Setting the `noise` attribute of a `SimulatedSecretKey` to false disables
+//! the sampling: the phases are then the exact encodings of the messages and only the variances are tracked.
+//!
+//! Simulated keys only store parameters, so that they are generated instantly. The traits
+//! `LWEOperations` and `VectorLWEOperations` allow the same code to run on real and simulated ciphertexts.
+
+#[cfg(test)]
+mod tests;
+
+use backtrace::Backtrace;
+use colored::Colorize;
+use concrete_commons::numeric::Numeric;
+use concrete_commons::parameters::LweSize;
+use concrete_core::crypto::lwe::{LweCiphertext, LweList};
+use concrete_core::math::random::RandomGenerator;
+use concrete_core::math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor};
+use concrete_npe as npe;
+
+use crate::error::CryptoAPIError;
+use crate::traits::{
+    GenericAdd, GenericMul, GenericNeg, GenericPolicyOps, GenericSub, LWEOperations,
+    OperatorPolicy, VectorLWEOperations,
+};
+use crate::Torus;
+
+/// Structure describing a simulated LWE secret key
+///
+/// # Attributes
+/// * `dimension` - the length of the LWE mask
+/// * `std_dev` - the standard deviation of the noise of the encryptions
+/// * `noise` - true if the noise is sampled, false if only the variances are tracked
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedSecretKey {
+    pub dimension: usize,
+    pub std_dev: f64,
+    pub noise: bool,
+}
+
+impl SimulatedSecretKey {
+    /// Instantiate a simulated LWE secret key from LWE parameters, the noise being sampled
+    ///
+    /// # Argument
+    /// * `params` - the LWE parameters
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let secret_key = SimulatedSecretKey::new(&LWE128_1024);
+    /// ```
+    pub fn new(params: &crate::LWEParams) -> SimulatedSecretKey {
+        SimulatedSecretKey::new_raw(params.dimension, params.get_std_dev())
+    }
+
+    /// Instantiate a simulated LWE secret key from a dimension and a standard deviation, the noise being sampled
+    ///
+    /// # Arguments
+    /// * `dimension` - the length of the LWE mask
+    /// * `std_dev` - the standard deviation of the noise of the encryptions
+    pub fn new_raw(dimension: usize, std_dev: f64) -> SimulatedSecretKey {
+        SimulatedSecretKey {
+            dimension,
+            std_dev,
+            noise: true,
+        }
+    }
+
+    /// Instantiate the simulated LWE secret key obtained from an RLWE secret key
+    /// (see `RLWESecretKey::to_lwe_secret_key`)
+    ///
+    /// # Argument
+    /// * `params` - the RLWE parameters
+    pub fn new_from_rlwe_params(params: &crate::RLWEParams) -> SimulatedSecretKey {
+        SimulatedSecretKey::new_raw(
+            params.dimension * params.polynomial_size,
+            params.get_std_dev(),
+        )
+    }
+
+    /// Return the variance of the error distribution associated with the secret key
+    pub fn get_variance(&self) -> f64 {
+        f64::powi(self.std_dev, 2i32)
+    }
+}
+
+/// Structure describing a simulated bootstrapping key
+///
+/// # Attributes
+/// * `lwe_dimension` - the dimension of the LWE ciphertexts it bootstraps
+/// * `dimension` - the dimension of the RLWE secret key
+/// * `polynomial_size` - the size of the polynomials of the RLWE secret key
+/// * `base_log` - the log2 of the decomposition base
+/// * `level` - the number of levels of the decomposition
+/// * `variance` - the variance of the noise of the key
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedBSK {
+    pub lwe_dimension: usize,
+    pub dimension: usize,
+    pub polynomial_size: usize,
+    pub base_log: usize,
+    pub level: usize,
+    pub variance: f64,
+}
+
+impl SimulatedBSK {
+    /// Instantiate a simulated bootstrapping key
+    ///
+    /// # Arguments
+    /// * `sk_input` - the simulated LWE secret key of the input of the bootstrap
+    /// * `params_output` - the RLWE parameters of the output of the bootstrap
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let secret_key = SimulatedSecretKey::new(&LWE128_630);
+    /// let bsk = SimulatedBSK::new(&secret_key, &RLWE128_1024_1, 5, 5);
+    /// ```
+    pub fn new(
+        sk_input: &SimulatedSecretKey,
+        params_output: &crate::RLWEParams,
+        base_log: usize,
+        level: usize,
+    ) -> SimulatedBSK {
+        SimulatedBSK {
+            lwe_dimension: sk_input.dimension,
+            dimension: params_output.dimension,
+            polynomial_size: params_output.polynomial_size,
+            base_log,
+            level,
+            variance: f64::powi(params_output.get_std_dev(), 2),
+        }
+    }
+
+    /// Instantiate the simulated bootstrapping key with the same parameters as a real one
+    ///
+    /// # Argument
+    /// * `bsk` - a bootstrapping key
+    pub fn new_from_bsk(bsk: &crate::LWEBSK) -> SimulatedBSK {
+        SimulatedBSK {
+            lwe_dimension: bsk.get_lwe_dimension(),
+            dimension: bsk.dimension,
+            polynomial_size: bsk.polynomial_size,
+            base_log: bsk.base_log,
+            level: bsk.level,
+            variance: bsk.variance,
+        }
+    }
+}
+
+/// Structure describing a simulated key switching key
+///
+/// # Attributes
+/// * `dimension_before` - the dimension of the input of the key switch
+/// * `dimension_after` - the dimension of the output of the key switch
+/// * `base_log` - the log2 of the decomposition base
+/// * `level` - the number of levels of the decomposition
+/// * `variance` - the variance of the noise of the key
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedKSK {
+    pub dimension_before: usize,
+    pub dimension_after: usize,
+    pub base_log: usize,
+    pub level: usize,
+    pub variance: f64,
+}
+
+impl SimulatedKSK {
+    /// Instantiate a simulated key switching key
+    ///
+    /// # Arguments
+    /// * `sk_before` - the simulated LWE secret key of the input of the key switch
+    /// * `sk_after` - the simulated LWE secret key of the output of the key switch
+    /// * `base_log` - the log2 of the decomposition base
+    /// * `level` - the number of levels of the decomposition
+    pub fn new(
+        sk_before: &SimulatedSecretKey,
+        sk_after: &SimulatedSecretKey,
+        base_log: usize,
+        level: usize,
+    ) -> SimulatedKSK {
+        SimulatedKSK {
+            dimension_before: sk_before.dimension,
+            dimension_after: sk_after.dimension,
+            base_log,
+            level,
+            variance: sk_after.get_variance(),
+        }
+    }
+
+    /// Instantiate the simulated key switching key with the same parameters as a real one
+    ///
+    /// # Argument
+    /// * `ksk` - a key switching key
+    pub fn new_from_ksk(ksk: &crate::LWEKSK) -> SimulatedKSK {
+        SimulatedKSK {
+            dimension_before: ksk.dimension_before,
+            dimension_after: ksk.dimension_after,
+            base_log: ksk.base_log,
+            level: ksk.level,
+            variance: ksk.variance,
+        }
+    }
+}
+
+/// Structure containing a single simulated LWE ciphertext.
+///
+/// # Attributes
+/// * `phase` - a trivial LWE ciphertext (of dimension zero) whose body is the phase of the simulated
+///   ciphertext, with its variance and its encoder
+/// * `dimension` - the length of the simulated LWE mask
+/// * `noise` - true if the noise is sampled, false if only the variance is tracked
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedLWE {
+    pub phase: crate::LWE,
+    pub dimension: usize,
+    pub noise: bool,
+}
+
+impl GenericAdd<f64, CryptoAPIError> for SimulatedLWE {
+    fn add(&self, right: f64) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(right)
+    }
+    fn add_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        *self = self.add_constant_dynamic_encoder(right)?;
+        Ok(())
+    }
+}
+
+impl GenericAdd<&SimulatedLWE, CryptoAPIError> for SimulatedLWE {
+    fn add(&self, right: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.add_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn add_inplace(&mut self, right: &SimulatedLWE) -> Result<(), CryptoAPIError> {
+        *self = GenericAdd::add(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericSub<f64, CryptoAPIError> for SimulatedLWE {
+    fn sub(&self, right: f64) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(-right)
+    }
+    fn sub_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        *self = self.add_constant_dynamic_encoder(-right)?;
+        Ok(())
+    }
+}
+
+impl GenericSub<&SimulatedLWE, CryptoAPIError> for SimulatedLWE {
+    fn sub(&self, right: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.sub_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn sub_inplace(&mut self, right: &SimulatedLWE) -> Result<(), CryptoAPIError> {
+        *self = GenericSub::sub(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericPolicyOps<CryptoAPIError> for SimulatedLWE {
+    fn add_with_policy_inplace(
+        &mut self,
+        right: &SimulatedLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        *self = match policy {
+            OperatorPolicy::Padding => self.add_with_padding(right)?,
+            OperatorPolicy::Centered => self.add_centered(right)?,
+            OperatorPolicy::Exact => self.add_with_padding_exact(right)?,
+        };
+        Ok(())
+    }
+
+    fn sub_with_policy_inplace(
+        &mut self,
+        right: &SimulatedLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        *self = match policy {
+            OperatorPolicy::Padding => self.sub_with_padding(right)?,
+            OperatorPolicy::Centered => self.add_centered(&right.opposite()?)?,
+            OperatorPolicy::Exact => self.sub_with_padding_exact(right)?,
+        };
+        Ok(())
+    }
+}
+
+impl GenericMul<i32, CryptoAPIError> for SimulatedLWE {
+    fn mul(&self, right: i32) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.mul_constant_static_encoder(right)
+    }
+    fn mul_inplace(&mut self, right: i32) -> Result<(), CryptoAPIError> {
+        *self = self.mul_constant_static_encoder(right)?;
+        Ok(())
+    }
+}
+
+impl GenericNeg<CryptoAPIError> for SimulatedLWE {
+    fn neg(&self) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.opposite()
+    }
+    fn neg_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        *self = self.opposite()?;
+        Ok(())
+    }
+}
+
+impl_operators!(SimulatedLWE);
+
+impl SimulatedLWE {
+    /// Encode a message and simulate its encryption, the noise being sampled if the key asks for it
+    ///
+    /// # Arguments
+    /// * `sk` - a simulated LWE secret key
+    /// * `message` - a message as f64
+    /// * `encoder` - an Encoder
+    ///
+    /// # Output
+    /// * a SimulatedLWE
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let encoder = Encoder::new(-2., 6., 4, 4).unwrap();
+    /// let secret_key = SimulatedSecretKey::new(&LWE128_1024);
+    ///
+    /// let ciphertext = SimulatedLWE::encode_encrypt(&secret_key, -1., &encoder).unwrap();
+    /// ```
+    pub fn encode_encrypt(
+        sk: &SimulatedSecretKey,
+        message: f64,
+        encoder: &crate::Encoder,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        let mut plaintext = encoder.encode_core(message)?;
+        let mut result_encoder: crate::Encoder = encoder.clone();
+        let nb_bit_overlap: usize =
+            result_encoder.update_precision_from_variance(sk.get_variance())?;
+
+        // notification of a problem
+        if nb_bit_overlap > 0 {
+            println!(
+                "{}: {} bit(s) with {} bit(s) of message originally. Consider increasing the dimension the reduce the amount of noise needed.",
+                "Loss of precision during encrypt".red().bold(),
+                nb_bit_overlap, encoder.nb_bit_precision
+            );
+        }
+
+        // add the noise of the encryption
+        if sk.noise {
+            let mut generator = RandomGenerator::new(None);
+            plaintext = plaintext.wrapping_add(sample_noise(&mut generator, sk.get_variance()));
+        }
+
+        Ok(SimulatedLWE {
+            phase: new_trivial_lwe(plaintext, sk.get_variance(), result_encoder),
+            dimension: sk.dimension,
+            noise: sk.noise,
+        })
+    }
+
+    /// Decode the phase of the simulated ciphertext
+    ///
+    /// # Argument
+    /// * `sk` - a simulated LWE secret key
+    ///
+    /// # Output
+    /// * a f64
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    pub fn decrypt_decode(&self, sk: &SimulatedSecretKey) -> Result<f64, CryptoAPIError> {
+        // check dimensions
+        if sk.dimension != self.dimension {
+            return Err(DimensionError!(self.dimension, sk.dimension));
+        }
+        self.phase.encoder.decode_single(self.get_phase())
+    }
+
+    /// Decode the phase of the simulated ciphertext as if the encoder was in a rounding context
+    ///
+    /// # Argument
+    /// * `sk` - a simulated LWE secret key
+    ///
+    /// # Output
+    /// * a f64
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    pub fn decrypt_decode_round(&self, sk: &SimulatedSecretKey) -> Result<f64, CryptoAPIError> {
+        // check dimensions
+        if sk.dimension != self.dimension {
+            return Err(DimensionError!(self.dimension, sk.dimension));
+        }
+        let mut enc_round = self.phase.encoder.clone();
+        enc_round.round = true;
+        enc_round.decode_single(self.get_phase())
+    }
+
+    /// Return the phase of the simulated ciphertext
+    pub fn get_phase(&self) -> Torus {
+        self.phase.ciphertext.as_tensor().as_slice()[0]
+    }
+
+    /// Return the variance of the noise of the simulated ciphertext
+    pub fn get_variance(&self) -> f64 {
+        self.phase.variance
+    }
+
+    /// Return the encoder of the simulated ciphertext
+    pub fn get_encoder(&self) -> &crate::Encoder {
+        &self.phase.encoder
+    }
+
+    /// Add a small message to the simulated ciphertext without changing its encoder
+    /// (see `LWE::add_constant_static_encoder`)
+    pub fn add_constant_static_encoder(
+        &self,
+        message: f64,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        Ok(self.with_phase(self.phase.add_constant_static_encoder(message)?))
+    }
+
+    /// Add a message to the simulated ciphertext by changing its encoder
+    /// (see `LWE::add_constant_dynamic_encoder`)
+    pub fn add_constant_dynamic_encoder(
+        &self,
+        message: f64,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        Ok(self.with_phase(self.phase.add_constant_dynamic_encoder(message)?))
+    }
+
+    /// Add two simulated ciphertexts, the center of the output encoder being the sum of the
+    /// centers of the input encoders (see `LWE::add_centered`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn add_centered(&self, ct: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phase(self.phase.add_centered(&ct.phase)?))
+    }
+
+    /// Add two simulated ciphertexts by eating one bit of padding (see `LWE::add_with_padding`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn add_with_padding(&self, ct: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phase(self.phase.add_with_padding(&ct.phase)?))
+    }
+
+    /// Add two simulated ciphertexts by eating one bit of padding and adding one bit of precision
+    /// (see `LWE::add_with_padding_exact`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn add_with_padding_exact(
+        &self,
+        ct: &SimulatedLWE,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phase(self.phase.add_with_padding_exact(&ct.phase)?))
+    }
+
+    /// Subtract two simulated ciphertexts by eating one bit of padding (see `LWE::sub_with_padding`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn sub_with_padding(&self, ct: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phase(self.phase.sub_with_padding(&ct.phase)?))
+    }
+
+    /// Subtract two simulated ciphertexts by eating one bit of padding and adding one bit of
+    /// precision (see `LWE::sub_with_padding_exact`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn sub_with_padding_exact(
+        &self,
+        ct: &SimulatedLWE,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phase(self.phase.sub_with_padding_exact(&ct.phase)?))
+    }
+
+    /// Multiply the simulated ciphertext by a small integer without changing its encoder
+    /// (see `LWE::mul_constant_static_encoder`)
+    pub fn mul_constant_static_encoder(
+        &self,
+        message: i32,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        Ok(self.with_phase(self.phase.mul_constant_static_encoder(message)?))
+    }
+
+    /// Compute the opposite of the simulated ciphertext (see `LWE::opposite`)
+    pub fn opposite(&self) -> Result<SimulatedLWE, CryptoAPIError> {
+        Ok(self.with_phase(self.phase.opposite()?))
+    }
+
+    /// Simulate a key switch, the noise it adds being sampled
+    ///
+    /// # Argument
+    /// * `ksk` - a simulated key switching key
+    ///
+    /// # Output
+    /// * a SimulatedLWE
+    /// * DimensionError - if the key and the ciphertext have incompatible dimensions
+    pub fn keyswitch(&self, ksk: &SimulatedKSK) -> Result<SimulatedLWE, CryptoAPIError> {
+        // check ksk compatibility
+        if self.dimension != ksk.dimension_before {
+            return Err(DimensionError!(self.dimension, ksk.dimension_before));
+        }
+
+        // calls the NPE to find out the amount of noise after KS
+        let variance = <Torus as npe::LWE>::key_switch(
+            self.dimension,
+            ksk.level,
+            ksk.base_log,
+            ksk.variance,
+            self.phase.variance,
+        );
+
+        let mut res = self.clone();
+        res.dimension = ksk.dimension_after;
+        res.phase.variance = variance;
+
+        // add the noise of the key switch
+        if self.noise {
+            let mut generator = RandomGenerator::new(None);
+            let noise = sample_noise(&mut generator, variance - self.phase.variance);
+            res.set_phase(self.get_phase().wrapping_add(noise));
+        }
+
+        // update the precision
+        let nb_bit_overlap: usize = res.phase.encoder.update_precision_from_variance(variance)?;
+
+        // notification of a problem
+        if nb_bit_overlap > 0 {
+            println!(
+                "{}: {} bit(s) lost, with {} bit(s) of message originally",
+                "Loss of precision during key switch".red().bold(),
+                nb_bit_overlap,
+                self.phase.encoder.nb_bit_precision
+            );
+        }
+
+        Ok(res)
+    }
+
+    /// Simulate a bootstrap
+    ///
+    /// # Argument
+    /// * `bsk` - a simulated bootstrapping key
+    ///
+    /// # Output
+    /// * a SimulatedLWE
+    /// * DimensionError - if the key and the ciphertext have incompatible dimensions
+    pub fn bootstrap(&self, bsk: &SimulatedBSK) -> Result<SimulatedLWE, CryptoAPIError> {
+        self.bootstrap_with_function(bsk, |x| x, &self.phase.encoder)
+    }
+
+    /// Simulate a bootstrap applying an arbitrary function to the message.
+    /// The look up table is the one of `LWE::bootstrap_with_function` and the index read in it is
+    /// computed as in the blind rotation, from a random mask and a random binary key, so that the
+    /// drift due to the rounding of the input is reproduced exactly, the output noise being sampled.
+    ///
+    /// # Arguments
+    /// * `bsk` - a simulated bootstrapping key
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the output encoder
+    ///
+    /// # Output
+    /// * a SimulatedLWE
+    /// * DimensionError - if the key and the ciphertext have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoders
+    /// let encoder_input = Encoder::new(-150., 204., 4, 1).unwrap();
+    /// let encoder_output = Encoder::new(0., 204., 4, 1).unwrap();
+    ///
+    /// // simulated keys
+    /// let secret_key = SimulatedSecretKey::new(&LWE128_630);
+    /// let bsk = SimulatedBSK::new(&secret_key, &RLWE128_1024_1, 3, 3);
+    ///
+    /// let ciphertext = SimulatedLWE::encode_encrypt(&secret_key, -106.276, &encoder_input).unwrap();
+    /// let ciphertext_out = ciphertext
+    ///     .bootstrap_with_function(&bsk, |x| f64::max(0., x), &encoder_output)
+    ///     .unwrap();
+    /// ```
+    pub fn bootstrap_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &SimulatedBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        // check bsk compatibility
+        if self.dimension != bsk.lwe_dimension {
+            return Err(DimensionError!(self.dimension, bsk.lwe_dimension));
+        }
+
+        // generate the look up table (throw error if a bit of padding is missing)
+        let lut = crate::lwe_bsk::functional_look_up_table(
+            bsk.polynomial_size,
+            &self.phase.encoder,
+            encoder_output,
+            f,
+        )?;
+
+        // remove the padding but one bit
+        let mut input = self.clone();
+        if input.phase.encoder.nb_bit_padding > 1 {
+            let nb = input.phase.encoder.nb_bit_padding - 1;
+            input.phase.remove_padding_inplace(nb)?;
+        }
+
+        // read the look up table
+        let mut generator = RandomGenerator::new(None);
+        let index = input.sample_blind_rotation_index(bsk.polynomial_size, &mut generator);
+        let mut output = if index < bsk.polynomial_size {
+            lut[index]
+        } else {
+            lut[index - bsk.polynomial_size].wrapping_neg()
+        };
+
+        // compute the new variance (without the drift)
+        let variance = <Torus as npe::Cross>::bootstrap(
+            self.dimension,
+            bsk.dimension,
+            bsk.level,
+            bsk.base_log,
+            bsk.polynomial_size,
+            bsk.variance,
+        );
+
+        // add the noise of the bootstrap
+        if self.noise {
+            output = output.wrapping_add(sample_noise(&mut generator, variance));
+        }
+
+        // create the output encoder
+        let encoder = crate::lwe::bootstrap_output_encoder(
+            self.dimension,
+            self.phase.encoder.nb_bit_precision,
+            bsk.polynomial_size,
+            variance,
+            encoder_output,
+            1,
+        )?;
+
+        Ok(SimulatedLWE {
+            phase: new_trivial_lwe(output, variance, encoder),
+            dimension: bsk.dimension * bsk.polynomial_size,
+            noise: self.noise,
+        })
+    }
+
+    /// Return the index in [0, 2N[ of the look up table read by a blind rotation of the ciphertext:
+    /// the phase is masked by a random mask and a random binary key, and the mask and the body are
+    /// rounded as in the blind rotation, the rounding being exact without noise
+    fn sample_blind_rotation_index(
+        &self,
+        polynomial_size: usize,
+        generator: &mut RandomGenerator,
+    ) -> usize {
+        let two_n = 2 * polynomial_size;
+        if !self.noise {
+            return modulus_switch(self.get_phase(), polynomial_size);
+        }
+
+        // body = <a, s> + phase and index = round(body) - sum_i round(a_i) * s_i
+        let mut body: Torus = self.get_phase();
+        let mut mask_index: usize = 0;
+        for _ in 0..self.dimension {
+            let a: Torus = generator.random_uniform();
+            let s: Torus = generator.random_uniform_binary();
+            if s == 1 {
+                body = body.wrapping_add(a);
+                mask_index = (mask_index + modulus_switch(a, polynomial_size)) % two_n;
+            }
+        }
+        (modulus_switch(body, polynomial_size) + two_n - mask_index) % two_n
+    }
+
+    /// Check that two simulated ciphertexts have the same dimension
+    fn check_dimensions(&self, ct: &SimulatedLWE) -> Result<(), CryptoAPIError> {
+        if self.dimension != ct.dimension {
+            return Err(DimensionError!(self.dimension, ct.dimension));
+        }
+        Ok(())
+    }
+
+    /// Return a simulated ciphertext with the same dimension and a new phase
+    fn with_phase(&self, phase: crate::LWE) -> SimulatedLWE {
+        SimulatedLWE {
+            phase,
+            dimension: self.dimension,
+            noise: self.noise,
+        }
+    }
+
+    /// Overwrite the phase of the simulated ciphertext
+    fn set_phase(&mut self, phase: Torus) {
+        self.phase.ciphertext.as_mut_tensor().as_mut_slice()[0] = phase;
+    }
+}
+
+impl LWEOperations for SimulatedLWE {
+    type SecretKey = SimulatedSecretKey;
+    type BootstrapKey = SimulatedBSK;
+    type KeySwitchKey = SimulatedKSK;
+
+    fn encode_encrypt(
+        sk: &SimulatedSecretKey,
+        message: f64,
+        encoder: &crate::Encoder,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::encode_encrypt(sk, message, encoder)
+    }
+    fn decrypt_decode(&self, sk: &SimulatedSecretKey) -> Result<f64, CryptoAPIError> {
+        SimulatedLWE::decrypt_decode(self, sk)
+    }
+    fn decrypt_decode_round(&self, sk: &SimulatedSecretKey) -> Result<f64, CryptoAPIError> {
+        SimulatedLWE::decrypt_decode_round(self, sk)
+    }
+    fn get_encoder(&self) -> &crate::Encoder {
+        SimulatedLWE::get_encoder(self)
+    }
+    fn get_variance(&self) -> f64 {
+        SimulatedLWE::get_variance(self)
+    }
+    fn add_constant_static_encoder(&self, message: f64) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::add_constant_static_encoder(self, message)
+    }
+    fn add_constant_dynamic_encoder(&self, message: f64) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::add_constant_dynamic_encoder(self, message)
+    }
+    fn add_centered(&self, ct: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::add_centered(self, ct)
+    }
+    fn add_with_padding(&self, ct: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::add_with_padding(self, ct)
+    }
+    fn sub_with_padding(&self, ct: &SimulatedLWE) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::sub_with_padding(self, ct)
+    }
+    fn mul_constant_static_encoder(&self, message: i32) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::mul_constant_static_encoder(self, message)
+    }
+    fn opposite(&self) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::opposite(self)
+    }
+    fn keyswitch(&self, ksk: &SimulatedKSK) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::keyswitch(self, ksk)
+    }
+    fn bootstrap_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &SimulatedBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<SimulatedLWE, CryptoAPIError> {
+        SimulatedLWE::bootstrap_with_function(self, bsk, f, encoder_output)
+    }
+}
+
+/// Structure containing a list of simulated LWE ciphertexts.
+///
+/// # Attributes
+/// * `phases` - a list of trivial LWE ciphertexts (of dimension zero) whose bodies are the phases of
+///   the simulated ciphertexts, with their variances and their encoders
+/// * `dimension` - the length of the simulated LWE masks
+/// * `noise` - true if the noise is sampled, false if only the variances are tracked
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedVectorLWE {
+    pub phases: crate::VectorLWE,
+    pub dimension: usize,
+    pub noise: bool,
+}
+
+impl GenericAdd<f64, CryptoAPIError> for SimulatedVectorLWE {
+    fn add(&self, right: f64) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(&vec![right; self.phases.nb_ciphertexts])
+    }
+    fn add_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        *self = GenericAdd::add(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericAdd<&SimulatedVectorLWE, CryptoAPIError> for SimulatedVectorLWE {
+    fn add(&self, right: &SimulatedVectorLWE) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.add_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn add_inplace(&mut self, right: &SimulatedVectorLWE) -> Result<(), CryptoAPIError> {
+        *self = GenericAdd::add(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericSub<f64, CryptoAPIError> for SimulatedVectorLWE {
+    fn sub(&self, right: f64) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(&vec![-right; self.phases.nb_ciphertexts])
+    }
+    fn sub_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        *self = GenericSub::sub(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericSub<&SimulatedVectorLWE, CryptoAPIError> for SimulatedVectorLWE {
+    fn sub(&self, right: &SimulatedVectorLWE) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.sub_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn sub_inplace(&mut self, right: &SimulatedVectorLWE) -> Result<(), CryptoAPIError> {
+        *self = GenericSub::sub(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericPolicyOps<CryptoAPIError> for SimulatedVectorLWE {
+    fn add_with_policy_inplace(
+        &mut self,
+        right: &SimulatedVectorLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        self.check_dimensions(right)?;
+        let phases = match policy {
+            OperatorPolicy::Padding => self.phases.add_with_padding(&right.phases)?,
+            OperatorPolicy::Centered => self.phases.add_centered(&right.phases)?,
+            OperatorPolicy::Exact => self.phases.add_with_padding_exact(&right.phases)?,
+        };
+        *self = self.with_phases(phases);
+        Ok(())
+    }
+
+    fn sub_with_policy_inplace(
+        &mut self,
+        right: &SimulatedVectorLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        self.check_dimensions(right)?;
+        let phases = match policy {
+            OperatorPolicy::Padding => self.phases.sub_with_padding(&right.phases)?,
+            OperatorPolicy::Centered => self.phases.add_centered(&right.phases.opposite()?)?,
+            OperatorPolicy::Exact => self.phases.sub_with_padding_exact(&right.phases)?,
+        };
+        *self = self.with_phases(phases);
+        Ok(())
+    }
+}
+
+impl GenericMul<i32, CryptoAPIError> for SimulatedVectorLWE {
+    fn mul(&self, right: i32) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.mul_constant_static_encoder(&vec![right; self.phases.nb_ciphertexts])
+    }
+    fn mul_inplace(&mut self, right: i32) -> Result<(), CryptoAPIError> {
+        *self = GenericMul::mul(self, right)?;
+        Ok(())
+    }
+}
+
+impl GenericNeg<CryptoAPIError> for SimulatedVectorLWE {
+    fn neg(&self) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        Ok(self.with_phases(self.phases.opposite()?))
+    }
+    fn neg_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        self.phases.opposite_inplace()
+    }
+}
+
+impl_operators!(SimulatedVectorLWE);
+
+impl SimulatedVectorLWE {
+    /// Encode messages and simulate their encryption, the noise being sampled if the key asks for it
+    ///
+    /// # Arguments
+    /// * `sk` - a simulated LWE secret key
+    /// * `messages` - a list of messages as f64
+    /// * `encoder` - an Encoder
+    ///
+    /// # Output
+    /// * a SimulatedVectorLWE
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// let encoder = Encoder::new(-2., 6., 4, 4).unwrap();
+    /// let secret_key = SimulatedSecretKey::new(&LWE128_1024);
+    ///
+    /// let ciphertexts =
+    ///     SimulatedVectorLWE::encode_encrypt(&secret_key, &[-1., 2., 5.5], &encoder).unwrap();
+    /// let messages = ciphertexts.decrypt_decode(&secret_key).unwrap();
+    /// ```
+    pub fn encode_encrypt(
+        sk: &SimulatedSecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        let mut cts: Vec<SimulatedLWE> = Vec::with_capacity(messages.len());
+        for m in messages.iter() {
+            cts.push(SimulatedLWE::encode_encrypt(sk, *m, encoder)?);
+        }
+        SimulatedVectorLWE::from_simulated_lwes(&cts)
+    }
+
+    /// Build a list of simulated ciphertexts from single ones
+    ///
+    /// # Argument
+    /// * `cts` - a slice of SimulatedLWE
+    ///
+    /// # Output
+    /// * a SimulatedVectorLWE
+    /// * ZeroCiphertextsInStructureError - if the slice is empty
+    /// * DimensionError - if the ciphertexts have different dimensions
+    pub fn from_simulated_lwes(cts: &[SimulatedLWE]) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        if cts.is_empty() {
+            return Err(ZeroCiphertextsInStructureError!(0));
+        }
+        let dimension = cts[0].dimension;
+        for ct in cts.iter() {
+            cts[0].check_dimensions(ct)?;
+        }
+        let bodies: Vec<Torus> = cts.iter().map(|ct| ct.get_phase()).collect();
+        Ok(SimulatedVectorLWE {
+            phases: crate::VectorLWE {
+                ciphertexts: LweList::from_container(bodies, LweSize(1)),
+                variances: cts.iter().map(|ct| ct.get_variance()).collect(),
+                dimension: 0,
+                nb_ciphertexts: cts.len(),
+                encoders: cts.iter().map(|ct| ct.get_encoder().clone()).collect(),
+            },
+            dimension,
+            noise: cts.iter().any(|ct| ct.noise),
+        })
+    }
+
+    /// Return the n-th simulated ciphertext
+    ///
+    /// # Argument
+    /// * `n` - the index of the ciphertext
+    ///
+    /// # Output
+    /// * a SimulatedLWE
+    /// * IndexError - if the requested ciphertext does not exist
+    pub fn get_nth(&self, n: usize) -> Result<SimulatedLWE, CryptoAPIError> {
+        if n >= self.phases.nb_ciphertexts {
+            return Err(IndexError!(self.phases.nb_ciphertexts, n));
+        }
+        Ok(SimulatedLWE {
+            phase: new_trivial_lwe(
+                self.phases.ciphertexts.as_tensor().as_slice()[n],
+                self.phases.variances[n],
+                self.phases.encoders[n].clone(),
+            ),
+            dimension: self.dimension,
+            noise: self.noise,
+        })
+    }
+
+    /// Decode the phases of the simulated ciphertexts
+    ///
+    /// # Argument
+    /// * `sk` - a simulated LWE secret key
+    ///
+    /// # Output
+    /// * a vector of f64
+    /// * DimensionError - if the ciphertexts and the key have incompatible dimensions
+    pub fn decrypt_decode(&self, sk: &SimulatedSecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        (0..self.phases.nb_ciphertexts)
+            .map(|n| self.get_nth(n)?.decrypt_decode(sk))
+            .collect()
+    }
+
+    /// Decode the phases of the simulated ciphertexts as if the encoders were in a rounding context
+    ///
+    /// # Argument
+    /// * `sk` - a simulated LWE secret key
+    ///
+    /// # Output
+    /// * a vector of f64
+    /// * DimensionError - if the ciphertexts and the key have incompatible dimensions
+    pub fn decrypt_decode_round(
+        &self,
+        sk: &SimulatedSecretKey,
+    ) -> Result<Vec<f64>, CryptoAPIError> {
+        (0..self.phases.nb_ciphertexts)
+            .map(|n| self.get_nth(n)?.decrypt_decode_round(sk))
+            .collect()
+    }
+
+    /// Return a list containing only the n-th simulated ciphertext (see `VectorLWE::extract_nth`)
+    pub fn extract_nth(&self, n: usize) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        Ok(self.with_phases(self.phases.extract_nth(n)?))
+    }
+
+    /// Add small messages to the simulated ciphertexts without changing their encoders
+    /// (see `VectorLWE::add_constant_static_encoder`)
+    pub fn add_constant_static_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        Ok(self.with_phases(self.phases.add_constant_static_encoder(messages)?))
+    }
+
+    /// Add messages to the simulated ciphertexts by changing their encoders
+    /// (see `VectorLWE::add_constant_dynamic_encoder`)
+    pub fn add_constant_dynamic_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        Ok(self.with_phases(self.phases.add_constant_dynamic_encoder(messages)?))
+    }
+
+    /// Add two lists of simulated ciphertexts, the centers of the output encoders being the sums of
+    /// the centers of the input encoders (see `VectorLWE::add_centered`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn add_centered(
+        &self,
+        ct: &SimulatedVectorLWE,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phases(self.phases.add_centered(&ct.phases)?))
+    }
+
+    /// Add two lists of simulated ciphertexts by eating one bit of padding
+    /// (see `VectorLWE::add_with_padding`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn add_with_padding(
+        &self,
+        ct: &SimulatedVectorLWE,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phases(self.phases.add_with_padding(&ct.phases)?))
+    }
+
+    /// Subtract two lists of simulated ciphertexts by eating one bit of padding
+    /// (see `VectorLWE::sub_with_padding`)
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    pub fn sub_with_padding(
+        &self,
+        ct: &SimulatedVectorLWE,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        self.check_dimensions(ct)?;
+        Ok(self.with_phases(self.phases.sub_with_padding(&ct.phases)?))
+    }
+
+    /// Multiply the simulated ciphertexts by small integers without changing their encoders
+    /// (see `VectorLWE::mul_constant_static_encoder`)
+    pub fn mul_constant_static_encoder(
+        &self,
+        messages: &[i32],
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        Ok(self.with_phases(self.phases.mul_constant_static_encoder(messages)?))
+    }
+
+    /// Compute the opposite of the n-th simulated ciphertext (see `VectorLWE::opposite_nth`)
+    pub fn opposite_nth(&self, n: usize) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        Ok(self.with_phases(self.phases.opposite_nth(n)?))
+    }
+
+    /// Simulate the key switch of every ciphertext (see `SimulatedLWE::keyswitch`)
+    ///
+    /// # Argument
+    /// * `ksk` - a simulated key switching key
+    ///
+    /// # Output
+    /// * a SimulatedVectorLWE
+    /// * DimensionError - if the key and the ciphertexts have incompatible dimensions
+    pub fn keyswitch(&self, ksk: &SimulatedKSK) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        let mut cts: Vec<SimulatedLWE> = Vec::with_capacity(self.phases.nb_ciphertexts);
+        for n in 0..self.phases.nb_ciphertexts {
+            cts.push(self.get_nth(n)?.keyswitch(ksk)?);
+        }
+        SimulatedVectorLWE::from_simulated_lwes(&cts)
+    }
+
+    /// Simulate the bootstrap of the n-th ciphertext (see `SimulatedLWE::bootstrap_with_function`)
+    ///
+    /// # Arguments
+    /// * `bsk` - a simulated bootstrapping key
+    /// * `f` - the function to apply
+    /// * `encoder_output` - the output encoder
+    /// * `n` - the index of the ciphertext to bootstrap
+    ///
+    /// # Output
+    /// * a SimulatedVectorLWE containing a single ciphertext
+    /// * IndexError - if the requested ciphertext does not exist
+    /// * DimensionError - if the key and the ciphertexts have incompatible dimensions
+    pub fn bootstrap_nth_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &SimulatedBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        let ct = self
+            .get_nth(n)?
+            .bootstrap_with_function(bsk, f, encoder_output)?;
+        SimulatedVectorLWE::from_simulated_lwes(&[ct])
+    }
+
+    /// Check that two lists of simulated ciphertexts have the same dimension
+    fn check_dimensions(&self, ct: &SimulatedVectorLWE) -> Result<(), CryptoAPIError> {
+        if self.dimension != ct.dimension {
+            return Err(DimensionError!(self.dimension, ct.dimension));
+        }
+        Ok(())
+    }
+
+    /// Return a list of simulated ciphertexts with the same dimension and new phases
+    fn with_phases(&self, phases: crate::VectorLWE) -> SimulatedVectorLWE {
+        SimulatedVectorLWE {
+            phases,
+            dimension: self.dimension,
+            noise: self.noise,
+        }
+    }
+}
+
+impl VectorLWEOperations for SimulatedVectorLWE {
+    type SecretKey = SimulatedSecretKey;
+    type BootstrapKey = SimulatedBSK;
+    type KeySwitchKey = SimulatedKSK;
+
+    fn encode_encrypt(
+        sk: &SimulatedSecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::encode_encrypt(sk, messages, encoder)
+    }
+    fn decrypt_decode(&self, sk: &SimulatedSecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        SimulatedVectorLWE::decrypt_decode(self, sk)
+    }
+    fn decrypt_decode_round(&self, sk: &SimulatedSecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        SimulatedVectorLWE::decrypt_decode_round(self, sk)
+    }
+    fn get_encoders(&self) -> &[crate::Encoder] {
+        &self.phases.encoders
+    }
+    fn get_variances(&self) -> &[f64] {
+        &self.phases.variances
+    }
+    fn extract_nth(&self, n: usize) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::extract_nth(self, n)
+    }
+    fn add_constant_static_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::add_constant_static_encoder(self, messages)
+    }
+    fn add_constant_dynamic_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::add_constant_dynamic_encoder(self, messages)
+    }
+    fn add_centered(&self, ct: &SimulatedVectorLWE) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::add_centered(self, ct)
+    }
+    fn add_with_padding(
+        &self,
+        ct: &SimulatedVectorLWE,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::add_with_padding(self, ct)
+    }
+    fn sub_with_padding(
+        &self,
+        ct: &SimulatedVectorLWE,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::sub_with_padding(self, ct)
+    }
+    fn mul_constant_static_encoder(
+        &self,
+        messages: &[i32],
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::mul_constant_static_encoder(self, messages)
+    }
+    fn opposite_nth(&self, n: usize) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::opposite_nth(self, n)
+    }
+    fn keyswitch(&self, ksk: &SimulatedKSK) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::keyswitch(self, ksk)
+    }
+    fn bootstrap_nth_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &SimulatedBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<SimulatedVectorLWE, CryptoAPIError> {
+        SimulatedVectorLWE::bootstrap_nth_with_function(self, bsk, f, encoder_output, n)
+    }
+}
+
+/// Build a trivial LWE ciphertext of dimension zero whose body is a phase
+fn new_trivial_lwe(phase: Torus, variance: f64, encoder: crate::Encoder) -> crate::LWE {
+    crate::LWE {
+        ciphertext: LweCiphertext::from_container(vec![phase]),
+        variance,
+        dimension: 0,
+        encoder,
+    }
+}
+
+/// Sample a centered normal noise of a given variance as a Torus element
+fn sample_noise(generator: &mut RandomGenerator, variance: f64) -> Torus {
+    let (e, _): (f64, f64) = generator.random_gaussian(0., f64::sqrt(variance));
+    (e * f64::powi(2., <Torus as Numeric>::BITS as i32)).round() as i64 as Torus
+}
+
+/// Round a Torus element to an index in [0, 2N[ as the blind rotation does
+fn modulus_switch(x: Torus, polynomial_size: usize) -> usize {
+    let tmp = x as f64 / f64::powi(2., <Torus as Numeric>::BITS as i32);
+    (tmp * (2 * polynomial_size) as f64).round() as usize % (2 * polynomial_size)
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+/// a few linear operations written once for both backends
+fn linear_operations<T: LWEOperations>(
+    sk: &T::SecretKey,
+    m_1: f64,
+    m_2: f64,
+) -> (f64, f64, crate::Encoder) {
+    let encoder = crate::Encoder::new(-10., 10., 6, 3).unwrap();
+    let ct_1 = T::encode_encrypt(sk, m_1, &encoder).unwrap();
+    let ct_2 = T::encode_encrypt(sk, m_2, &encoder).unwrap();
+    let res = ct_1
+        .add_with_padding(&ct_2)
+        .unwrap()
+        .mul_constant_static_encoder(-2)
+        .unwrap()
+        .add_constant_dynamic_encoder(3.)
+        .unwrap();
+    (
+        res.decrypt_decode(sk).unwrap(),
+        res.get_variance(),
+        res.get_encoder().clone(),
+    )
+}
+
+/// bootstrap with the identity and count the wrong decryptions
+fn count_bootstrap_errors<T: LWEOperations>(
+    sk_input: &T::SecretKey,
+    sk_output: &T::SecretKey,
+    bsk: &T::BootstrapKey,
+    nb_trials: usize,
+) -> usize {
+    let encoder = crate::Encoder::new_rounding_context(0., 15., 4, 1).unwrap();
+    let mut nb_errors: usize = 0;
+    for _ in 0..nb_trials {
+        let message = random_index!(16) as f64;
+        let ct = T::encode_encrypt(sk_input, message, &encoder).unwrap();
+        let res = ct.bootstrap_with_function(bsk, |x| x, &encoder).unwrap();
+        if res.decrypt_decode_round(sk_output).unwrap() != message {
+            nb_errors += 1;
+        }
+    }
+    nb_errors
+}
+
+#[test]
+fn test_simulated_linear_operations() {
+    let sk = crate::LWESecretKey::new(&crate::LWE128_630);
+    let mut sim_sk = SimulatedSecretKey::new(&crate::LWE128_630);
+    sim_sk.noise = false;
+
+    for _ in 0..10 {
+        let (m_1, m_2) = (random_message!(-5., 5.), random_message!(-5., 5.));
+        let (d, variance, encoder) = linear_operations::<crate::LWE>(&sk, m_1, m_2);
+        let (sim_d, sim_variance, sim_encoder) =
+            linear_operations::<SimulatedLWE>(&sim_sk, m_1, m_2);
+
+        // same variances and encoders as the real ciphertexts
+        assert_eq!(variance, sim_variance);
+        assert_eq!(encoder, sim_encoder);
+
+        // same messages
+        assert_with_granularity!([d], [sim_d], [encoder]);
+        assert_with_granularity!([-2. * (m_1 + m_2) + 3.], [sim_d], [sim_encoder]);
+    }
+
+    // the dimensions must match
+    let encoder = crate::Encoder::new(-10., 10., 6, 3).unwrap();
+    let other_sk = SimulatedSecretKey::new(&crate::LWE128_1024);
+    let ct_1 = SimulatedLWE::encode_encrypt(&sim_sk, 1., &encoder).unwrap();
+    let ct_2 = SimulatedLWE::encode_encrypt(&other_sk, 1., &encoder).unwrap();
+    assert!(ct_1.add_with_padding(&ct_2).is_err());
+    assert!(ct_1.decrypt_decode(&other_sk).is_err());
+}
+
+#[test]
+fn test_simulated_bootstrap_keyswitch() {
+    let mut sk_input = SimulatedSecretKey::new(&crate::LWE128_630);
+    sk_input.noise = false;
+    let sk_output = SimulatedSecretKey::new_from_rlwe_params(&crate::RLWE128_1024_1);
+    let bsk = SimulatedBSK::new(&sk_input, &crate::RLWE128_1024_1, 5, 5);
+    let ksk = SimulatedKSK::new(&sk_output, &sk_input, 3, 8);
+
+    let encoder = crate::Encoder::new_rounding_context(0., 7., 3, 2).unwrap();
+    let encoder_output = crate::Encoder::new_rounding_context(0., 7., 3, 1).unwrap();
+    for message in 0..8 {
+        let ct = SimulatedLWE::encode_encrypt(&sk_input, message as f64, &encoder).unwrap();
+
+        // without noise, the bootstrap is exact
+        let res = ct
+            .bootstrap_with_function(&bsk, |x| (x * x) % 8., &encoder_output)
+            .unwrap();
+        assert_eq!(res.dimension, sk_output.dimension);
+        assert_eq!(
+            res.decrypt_decode_round(&sk_output).unwrap(),
+            ((message * message) % 8) as f64
+        );
+        let variance = <Torus as npe::Cross>::bootstrap(630, 1, 5, 5, 1024, bsk.variance);
+        assert_eq!(res.get_variance(), variance);
+
+        // key switch back to the input key
+        let res = res.keyswitch(&ksk).unwrap();
+        assert_eq!(res.dimension, sk_input.dimension);
+        assert_eq!(
+            res.get_variance(),
+            <Torus as npe::LWE>::key_switch(1024, 8, 3, ksk.variance, variance)
+        );
+    }
+
+    // a list of simulated ciphertexts
+    let cts = SimulatedVectorLWE::encode_encrypt(&sk_input, &[1., 2., 3.], &encoder).unwrap();
+    let res = cts
+        .bootstrap_nth_with_function(&bsk, |x| (x * x) % 8., &encoder_output, 2)
+        .unwrap();
+    assert_eq!(res.decrypt_decode_round(&sk_output).unwrap(), vec![1.]);
+    assert_eq!(
+        cts.add_constant_static_encoder(&[1., 1., 1.])
+            .unwrap()
+            .decrypt_decode_round(&sk_input)
+            .unwrap(),
+        vec![2., 3., 4.]
+    );
+
+    // the dimensions must match
+    let ct = SimulatedLWE::encode_encrypt(&sk_input, 1., &encoder).unwrap();
+    assert!(ct.keyswitch(&ksk).is_err());
+    assert!(ct
+        .bootstrap(&SimulatedBSK::new(&sk_output, &crate::RLWE128_1024_1, 5, 5))
+        .is_err());
+}
+
+#[test]
+fn test_simulated_and_real_error_rates() {
+    // a very noisy input key so that about 5% of the bootstraps fail
+    let params = crate::LWEParams::new(256, -7);
+    let (nb_real_trials, nb_simulated_trials) = (500, 10000);
+
+    // real keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let sk_input = crate::LWESecretKey::new(&params);
+    let sk_output = rlwe_secret_key.to_lwe_secret_key();
+    let bsk = crate::LWEBSK::new(&sk_input, &rlwe_secret_key, 5, 5);
+
+    // simulated keys with the same parameters
+    let sim_sk_input = SimulatedSecretKey::new(&params);
+    let sim_sk_output = SimulatedSecretKey::new_from_rlwe_params(&crate::RLWE128_1024_1);
+    let sim_bsk = SimulatedBSK::new_from_bsk(&bsk);
+
+    let real_rate =
+        count_bootstrap_errors::<crate::LWE>(&sk_input, &sk_output, &bsk, nb_real_trials) as f64
+            / nb_real_trials as f64;
+    let simulated_rate = count_bootstrap_errors::<SimulatedLWE>(
+        &sim_sk_input,
+        &sim_sk_output,
+        &sim_bsk,
+        nb_simulated_trials,
+    ) as f64
+        / nb_simulated_trials as f64;
+
+    // the expected rate is erfc(2^-6 / (sqrt(2) * 2^-7)) ~ 4.6%
+    assert!(simulated_rate > 0.02 && simulated_rate < 0.08);
+    assert!(
+        (real_rate - simulated_rate).abs() < 0.04,
+        "real error rate {} != simulated error rate {}",
+        real_rate,
+        simulated_rate
+    );
+}
@@ -0,0 +1,104 @@
+use crate::error::CryptoAPIError;
+use crate::*;
+
+/// Tolerance on the security level, in bits
+const LEVEL_TOLERANCE: f64 = 0.5;
+
+/// Check that the noise of a constant is the smallest one reaching the security level, i.e. that
+/// the constant reaches it and that one bit of noise less does not
+fn assert_reproduces_level(params: &LWEParams, level: f64) {
+    let security_bits = params.estimated_security_bits();
+    assert!(security_bits > level - LEVEL_TOLERANCE, "{}", params);
+    let weaker = LWEParams::new(params.dimension, params.log2_std_dev - 1);
+    assert!(
+        weaker.estimated_security_bits() < level + LEVEL_TOLERANCE,
+        "{}",
+        weaker
+    );
+}
+
+#[test]
+fn test_lwe_params_security() {
+    for params in [
+        LWE128_256,
+        LWE128_512,
+        LWE128_630,
+        LWE128_650,
+        LWE128_688,
+        LWE128_710,
+        LWE128_750,
+        LWE128_800,
+        LWE128_830,
+        LWE128_1024,
+        LWE128_2048,
+    ]
+    .iter()
+    {
+        assert_reproduces_level(params, 128.);
+    }
+    for params in [
+        LWE80_256, LWE80_512, LWE80_630, LWE80_650, LWE80_688, LWE80_710, LWE80_750, LWE80_800,
+        LWE80_830, LWE80_1024, LWE80_2048,
+    ]
+    .iter()
+    {
+        assert_reproduces_level(params, 80.);
+    }
+}
+
+#[test]
+fn test_rlwe_params_security() {
+    // the algebraic structure is ignored, an RLWE constant is the LWE one of dimension k * N
+    for (params, level) in [
+        (RLWE128_256_1, 128.),
+        (RLWE128_512_1, 128.),
+        (RLWE128_1024_1, 128.),
+        (RLWE128_2048_1, 128.),
+        (RLWE128_256_2, 128.),
+        (RLWE128_512_2, 128.),
+        (RLWE128_256_4, 128.),
+        (RLWE80_256_1, 80.),
+        (RLWE80_512_1, 80.),
+        (RLWE80_1024_1, 80.),
+        (RLWE80_2048_1, 80.),
+        (RLWE80_256_2, 80.),
+        (RLWE80_512_2, 80.),
+        (RLWE80_256_4, 80.),
+    ]
+    .iter()
+    {
+        let lwe_params = LWEParams::new(
+            params.dimension * params.polynomial_size,
+            params.log2_std_dev,
+        );
+        assert_eq!(
+            params.estimated_security_bits(),
+            lwe_params.estimated_security_bits()
+        );
+        assert_reproduces_level(&lwe_params, *level);
+    }
+}
+
+#[test]
+fn test_new_with_security() {
+    assert_eq!(
+        LWEParams::new_with_security(630, -14, 128).unwrap(),
+        LWE128_630
+    );
+    assert_eq!(
+        RLWEParams::new_with_security(1024, 1, -25, 128).unwrap(),
+        RLWE128_1024_1
+    );
+
+    // not enough noise
+    match LWEParams::new_with_security(630, -17, 128) {
+        Err(CryptoAPIError::InsecureParametersError { security_bits, .. }) => {
+            assert!(security_bits < 128.)
+        }
+        res => panic!("expected an InsecureParametersError, got {:?}", res),
+    }
+    assert!(RLWEParams::new_with_security(1024, 1, -30, 128).is_err());
+
+    // a weaker level accepts it
+    assert!(LWEParams::new_with_security(630, -17, 100).is_ok());
+}
@@ -7,10 +7,9 @@ fn delete_file<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
 }
 
 use crate::Encoder;
-use concrete_core::crypto::bootstrap::BootstrapKey;
 use concrete_core::crypto::lwe::LweKeyswitchKey;
-use concrete_core::crypto::GlweSize;
-use concrete_core::math::fft::Complex64;
+use concrete_core::math::fft::{AlignedVec, Complex64};
+use concrete_core::math::tensor::{AsRefSlice, AsRefTensor, Tensor};
 
 #[test]
 fn test_encoder_save() {
@@ -44,15 +43,10 @@ fn test_lwebsk_save() {
     // sk_output.key_size: 16
     // sk_input.key_size: 20
 
+    let mut ciphertexts = Tensor::from_container(AlignedVec::new(20 * 4 * 17 * 17 * 4));
+    ciphertexts.fill_with_element(Complex64::new(2., 0.));
     let a = LWEBSK {
-        ciphertexts: BootstrapKey::allocate(
-            Complex64::new(2., 0.),
-            GlweSize(16 + 1),
-            PolynomialSize(4),
-            DecompositionLevelCount(4),
-            DecompositionBaseLog(5),
-            LweDimension(20),
-        ),
+        ciphertexts,
         variance: 0.5,
         dimension: 16,
         polynomial_size: 3 + 1,
@@ -128,6 +122,23 @@ fn test_lwesecretkey_save() {
     assert!(sk1 == sk2, "sk1 != sk2");
 }
 
+#[test]
+fn test_lwesecretkey_load_legacy() {
+    let filename: &str = "lwesk_legacy.json";
+
+    // a key saved with boolean coefficients
+    std::fs::write(
+        filename,
+        r#"{"val":{"tensor":[true,false,false,true]},"dimension":4,"std_dev":0.25}"#,
+    )
+    .unwrap();
+    let sk = LWESecretKey::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert_eq!(sk.val.as_tensor().as_slice(), &[1, 0, 0, 1]);
+    assert_eq!(sk.dimension, 4);
+    assert_eq!(sk.std_dev, 0.25);
+}
+
 use crate::RLWEParams;
 #[test]
 fn test_rlweparams_save() {
@@ -146,9 +157,7 @@ fn test_rlweparams_save() {
 }
 
 use crate::RLWESecretKey;
-use concrete_core::crypto::LweDimension;
-use concrete_core::math::decomposition::{DecompositionBaseLog, DecompositionLevelCount};
-use concrete_core::math::polynomial::PolynomialSize;
+use concrete_commons::parameters::{DecompositionBaseLog, DecompositionLevelCount, LweDimension};
 
 #[test]
 fn test_rlwesecretkey_save() {
@@ -168,6 +177,100 @@ fn test_rlwesecretkey_save() {
     assert!(sk1 == sk2);
 }
 
+#[test]
+fn test_rlwesecretkey_load_legacy() {
+    let filename: &str = "rlwesk_legacy.json";
+
+    // a key saved with boolean coefficients
+    std::fs::write(
+        filename,
+        r#"{"val":{"tensor":[false,true,true,false],"poly_size":2},"polynomial_size":2,"dimension":2,"std_dev":0.25}"#,
+    )
+    .unwrap();
+    let sk = RLWESecretKey::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert_eq!(sk.val.as_tensor().as_slice(), &[0, 1, 1, 0]);
+    assert_eq!(sk.polynomial_size, 2);
+    assert_eq!(sk.dimension, 2);
+}
+
+use crate::{BoolCiphertext, BoolClientKey, BOOL128_DEFAULT};
+
+#[test]
+fn test_boolciphertext_save() {
+    let filename: &str = "boolciphertext.json";
+
+    let client_key = BoolClientKey::new(&BOOL128_DEFAULT);
+    let ct1 = client_key.encrypt(true).unwrap();
+
+    ct1.save(filename).unwrap();
+    let ct2 = BoolCiphertext::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert!(ct1 == ct2);
+    assert!(client_key.decrypt(&ct2).unwrap());
+}
+
+#[test]
+fn test_boolclientkey_save() {
+    let filename: &str = "boolclientkey.json";
+
+    let key1 = BoolClientKey::new(&BOOL128_DEFAULT);
+
+    key1.save(filename).unwrap();
+    let key2 = BoolClientKey::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert!(key1 == key2);
+}
+
+use crate::{ShortInt, ShortIntClientKey, SHORTINT128_MESSAGE_1_CARRY_1};
+
+#[test]
+fn test_shortint_save() {
+    let filename: &str = "shortint.json";
+
+    let client_key = ShortIntClientKey::new(&SHORTINT128_MESSAGE_1_CARRY_1);
+    let ct1 = client_key.encrypt(1).unwrap();
+
+    ct1.save(filename).unwrap();
+    let ct2 = ShortInt::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert!(ct1 == ct2);
+    assert_eq!(client_key.decrypt(&ct2).unwrap(), 1);
+}
+
+use crate::{FheUint8, IntegerClientKey, SHORTINT128_MESSAGE_2_CARRY_2};
+
+#[test]
+fn test_fhe_uint_save() {
+    let filename: &str = "fhe_uint.json";
+
+    let client_key = IntegerClientKey::new(&SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let ct1: FheUint8 = client_key.encrypt(173u8).unwrap();
+
+    ct1.save(filename).unwrap();
+    let ct2 = FheUint8::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert!(ct1 == ct2);
+    assert_eq!(client_key.decrypt(&ct2).unwrap(), 173u8);
+}
+
+use crate::{CrtClientKey, CrtInt, CrtParams};
+
+#[test]
+fn test_crt_int_save() {
+    let filename: &str = "crt_int.json";
+
+    let params = CrtParams::new(&[3, 5, 7, 11, 13], &SHORTINT128_MESSAGE_2_CARRY_2).unwrap();
+    let client_key = CrtClientKey::new(&params).unwrap();
+    let ct1 = client_key.encrypt(12345).unwrap();
+
+    ct1.save(filename).unwrap();
+    let ct2 = CrtInt::load(filename).unwrap();
+    delete_file(filename).unwrap();
+    assert!(ct1 == ct2);
+    assert_eq!(client_key.decrypt(&ct2).unwrap(), 12345);
+}
+
 // use crate::crypto_api::glwe::VectorRLWE;
 // use crate::crypto_api::Plaintext;
 // use crate::crypto_api::{LWEParams, LWESecretKey, RLWEParams, RLWESecretKey, LWEBSK, LWEKSK};
@@ -1,3 +1,20 @@
+//! traits module: homomorphic operations shared by the ciphertext types
+//!
+//! The `Generic*` traits are the checked forms of the homomorphic operations and return a `CryptoAPIError`
+//! when the operands are not compatible. They are implemented by `LWE`, `VectorLWE` and `VectorRLWE` and
+//! back the operators of `std::ops` on references (`&a + &b`, `-&a`, `&a * 3`, `&a + 1.5`, `a += &b`, ...),
+//! which panic instead.
+//! The operators between two ciphertexts follow `OperatorPolicy::Padding` by default, another policy is
+//! selected by wrapping the left operand with `GenericPolicyOps::with_policy` (`a.with_policy(p) + &b`)
+//! or the right operand of an assignment (`a += b.with_policy(p)`), or with the methods of
+//! `GenericPolicyOps`.
+//!
+//! The `LWEOperations` and `VectorLWEOperations` traits gather the operations shared by the ciphertexts and
+//! their noise-only simulations (see the `simulation` module) or their shadow debug versions (see the `debug`
+//! module), so that the same code runs against every backend.
+
+use crate::error::CryptoAPIError;
+
 pub trait HomomorphicAdd<T, U> {
     fn add(&self, left: &U, right: T) -> U;
     fn add_inplace(&self, left: &mut U, right: T);
@@ -17,3 +34,291 @@ pub trait GenericAdd<T, E>: Sized {
     fn add(&self, right: T) -> Result<Self, E>;
     fn add_inplace(&mut self, right: T) -> Result<(), E>;
 }
+
+pub trait GenericSub<T, E>: Sized {
+    fn sub(&self, right: T) -> Result<Self, E>;
+    fn sub_inplace(&mut self, right: T) -> Result<(), E>;
+}
+
+pub trait GenericMul<T, E>: Sized {
+    fn mul(&self, right: T) -> Result<Self, E>;
+    fn mul_inplace(&mut self, right: T) -> Result<(), E>;
+}
+
+pub trait GenericNeg<E>: Sized {
+    fn neg(&self) -> Result<Self, E>;
+    fn neg_inplace(&mut self) -> Result<(), E>;
+}
+
+/// Operations shared by `LWE` and `SimulatedLWE`
+///
+/// # Example
+/// ```rust
+/// use concrete::traits::LWEOperations;
+/// use concrete::*;
+///
+/// // the same computation for both backends
+/// fn double<T: LWEOperations>(sk: &T::SecretKey, message: f64) -> f64 {
+///     let encoder = Encoder::new(0., 10., 4, 2).unwrap();
+///     let ct = T::encode_encrypt(sk, message, &encoder).unwrap();
+///     let res = ct.add_with_padding(&ct).unwrap();
+///     res.decrypt_decode(sk).unwrap()
+/// }
+///
+/// let real = double::<LWE>(&LWESecretKey::new(&LWE128_1024), 3.);
+/// let simulated = double::<SimulatedLWE>(&SimulatedSecretKey::new(&LWE128_1024), 3.);
+/// ```
+pub trait LWEOperations: Sized {
+    type SecretKey;
+    type BootstrapKey;
+    type KeySwitchKey;
+
+    fn encode_encrypt(
+        sk: &Self::SecretKey,
+        message: f64,
+        encoder: &crate::Encoder,
+    ) -> Result<Self, CryptoAPIError>;
+    fn decrypt_decode(&self, sk: &Self::SecretKey) -> Result<f64, CryptoAPIError>;
+    fn decrypt_decode_round(&self, sk: &Self::SecretKey) -> Result<f64, CryptoAPIError>;
+    fn get_encoder(&self) -> &crate::Encoder;
+    fn get_variance(&self) -> f64;
+    fn add_constant_static_encoder(&self, message: f64) -> Result<Self, CryptoAPIError>;
+    fn add_constant_dynamic_encoder(&self, message: f64) -> Result<Self, CryptoAPIError>;
+    fn add_centered(&self, ct: &Self) -> Result<Self, CryptoAPIError>;
+    fn add_with_padding(&self, ct: &Self) -> Result<Self, CryptoAPIError>;
+    fn sub_with_padding(&self, ct: &Self) -> Result<Self, CryptoAPIError>;
+    fn mul_constant_static_encoder(&self, message: i32) -> Result<Self, CryptoAPIError>;
+    fn opposite(&self) -> Result<Self, CryptoAPIError>;
+    fn keyswitch(&self, ksk: &Self::KeySwitchKey) -> Result<Self, CryptoAPIError>;
+    fn bootstrap_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &Self::BootstrapKey,
+        f: F,
+        encoder_output: &crate::Encoder,
+    ) -> Result<Self, CryptoAPIError>;
+}
+
+/// Operations shared by `VectorLWE` and `SimulatedVectorLWE`
+pub trait VectorLWEOperations: Sized {
+    type SecretKey;
+    type BootstrapKey;
+    type KeySwitchKey;
+
+    fn encode_encrypt(
+        sk: &Self::SecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+    ) -> Result<Self, CryptoAPIError>;
+    fn decrypt_decode(&self, sk: &Self::SecretKey) -> Result<Vec<f64>, CryptoAPIError>;
+    fn decrypt_decode_round(&self, sk: &Self::SecretKey) -> Result<Vec<f64>, CryptoAPIError>;
+    fn get_encoders(&self) -> &[crate::Encoder];
+    fn get_variances(&self) -> &[f64];
+    fn extract_nth(&self, n: usize) -> Result<Self, CryptoAPIError>;
+    fn add_constant_static_encoder(&self, messages: &[f64]) -> Result<Self, CryptoAPIError>;
+    fn add_constant_dynamic_encoder(&self, messages: &[f64]) -> Result<Self, CryptoAPIError>;
+    fn add_centered(&self, ct: &Self) -> Result<Self, CryptoAPIError>;
+    fn add_with_padding(&self, ct: &Self) -> Result<Self, CryptoAPIError>;
+    fn sub_with_padding(&self, ct: &Self) -> Result<Self, CryptoAPIError>;
+    fn mul_constant_static_encoder(&self, messages: &[i32]) -> Result<Self, CryptoAPIError>;
+    fn opposite_nth(&self, n: usize) -> Result<Self, CryptoAPIError>;
+    fn keyswitch(&self, ksk: &Self::KeySwitchKey) -> Result<Self, CryptoAPIError>;
+    fn bootstrap_nth_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &Self::BootstrapKey,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<Self, CryptoAPIError>;
+}
+
+/// Policy followed by the additions and the subtractions between two ciphertexts
+///
+/// # Variants
+/// * `Padding` - consume one bit of padding and keep the smallest precision (`add_with_padding`, `sub_with_padding`)
+/// * `Centered` - keep the padding, the intervals being centered on the sum of their centers (`add_centered`)
+/// * `Exact` - consume one bit of padding and add one bit of precision (`add_with_padding_exact`, `sub_with_padding_exact`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorPolicy {
+    Padding,
+    Centered,
+    Exact,
+}
+
+/// Additions and subtractions between two ciphertexts following an explicit `OperatorPolicy`
+///
+/// # Example
+/// ```rust
+/// use concrete::traits::{GenericPolicyOps, OperatorPolicy};
+/// use concrete::{Encoder, LWESecretKey, LWE, LWE128_1024};
+///
+/// let encoder = Encoder::new(-5., 5., 6, 4).unwrap();
+/// let secret_key = LWESecretKey::new(&LWE128_1024);
+/// let ct_1 = LWE::encode_encrypt(&secret_key, 1., &encoder).unwrap();
+/// let ct_2 = LWE::encode_encrypt(&secret_key, -0.5, &encoder).unwrap();
+///
+/// let ct = ct_1.add_with_policy(&ct_2, OperatorPolicy::Centered).unwrap();
+///
+/// // the same with the operators
+/// let ct = ct_1.with_policy(OperatorPolicy::Centered) + &ct_2;
+/// let mut ct = ct_1.clone();
+/// ct -= ct_2.with_policy(OperatorPolicy::Centered);
+/// ```
+pub trait GenericPolicyOps<E>: Sized + Clone {
+    fn add_with_policy_inplace(&mut self, right: &Self, policy: OperatorPolicy) -> Result<(), E>;
+    fn sub_with_policy_inplace(&mut self, right: &Self, policy: OperatorPolicy) -> Result<(), E>;
+
+    fn add_with_policy(&self, right: &Self, policy: OperatorPolicy) -> Result<Self, E> {
+        let mut res = self.clone();
+        res.add_with_policy_inplace(right, policy)?;
+        Ok(res)
+    }
+
+    fn sub_with_policy(&self, right: &Self, policy: OperatorPolicy) -> Result<Self, E> {
+        let mut res = self.clone();
+        res.sub_with_policy_inplace(right, policy)?;
+        Ok(res)
+    }
+
+    /// Wrap a reference to the ciphertext so that the operators follow the given policy
+    fn with_policy(&self, policy: OperatorPolicy) -> WithPolicy<'_, Self> {
+        WithPolicy {
+            ciphertext: self,
+            policy,
+        }
+    }
+}
+
+/// A reference to a ciphertext whose additions and subtractions with the operators of `std::ops`
+/// follow a policy (see `GenericPolicyOps::with_policy`)
+///
+/// # Attributes
+/// * `ciphertext` - the ciphertext
+/// * `policy` - the policy of the operators
+#[derive(Debug, Clone, Copy)]
+pub struct WithPolicy<'a, T> {
+    pub ciphertext: &'a T,
+    pub policy: OperatorPolicy,
+}
+
+impl<'a, T: GenericPolicyOps<CryptoAPIError>> std::ops::Add<&T> for WithPolicy<'a, T> {
+    type Output = T;
+    fn add(self, right: &T) -> T {
+        self.ciphertext
+            .add_with_policy(right, self.policy)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl<'a, T: GenericPolicyOps<CryptoAPIError>> std::ops::Sub<&T> for WithPolicy<'a, T> {
+    type Output = T;
+    fn sub(self, right: &T) -> T {
+        self.ciphertext
+            .sub_with_policy(right, self.policy)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+/// Implement the operators of `std::ops` on a ciphertext type from its `Generic*` traits
+#[macro_export]
+macro_rules! impl_operators {
+    ($T: ty) => {
+        impl std::ops::Add<&$T> for &$T {
+            type Output = $T;
+            fn add(self, right: &$T) -> $T {
+                $crate::traits::GenericAdd::add(self, right).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::Sub<&$T> for &$T {
+            type Output = $T;
+            fn sub(self, right: &$T) -> $T {
+                $crate::traits::GenericSub::sub(self, right).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::Add<f64> for &$T {
+            type Output = $T;
+            fn add(self, right: f64) -> $T {
+                $crate::traits::GenericAdd::add(self, right).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::Sub<f64> for &$T {
+            type Output = $T;
+            fn sub(self, right: f64) -> $T {
+                $crate::traits::GenericSub::sub(self, right).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::Mul<i32> for &$T {
+            type Output = $T;
+            fn mul(self, right: i32) -> $T {
+                $crate::traits::GenericMul::mul(self, right).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::Neg for &$T {
+            type Output = $T;
+            fn neg(self) -> $T {
+                $crate::traits::GenericNeg::neg(self).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::AddAssign<&$T> for $T {
+            fn add_assign(&mut self, right: &$T) {
+                $crate::traits::GenericAdd::add_inplace(self, right)
+                    .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::SubAssign<&$T> for $T {
+            fn sub_assign(&mut self, right: &$T) {
+                $crate::traits::GenericSub::sub_inplace(self, right)
+                    .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::AddAssign<$crate::traits::WithPolicy<'_, $T>> for $T {
+            fn add_assign(&mut self, right: $crate::traits::WithPolicy<'_, $T>) {
+                $crate::traits::GenericPolicyOps::add_with_policy_inplace(
+                    self,
+                    right.ciphertext,
+                    right.policy,
+                )
+                .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::SubAssign<$crate::traits::WithPolicy<'_, $T>> for $T {
+            fn sub_assign(&mut self, right: $crate::traits::WithPolicy<'_, $T>) {
+                $crate::traits::GenericPolicyOps::sub_with_policy_inplace(
+                    self,
+                    right.ciphertext,
+                    right.policy,
+                )
+                .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::AddAssign<f64> for $T {
+            fn add_assign(&mut self, right: f64) {
+                $crate::traits::GenericAdd::add_inplace(self, right)
+                    .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::SubAssign<f64> for $T {
+            fn sub_assign(&mut self, right: f64) {
+                $crate::traits::GenericSub::sub_inplace(self, right)
+                    .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl std::ops::MulAssign<i32> for $T {
+            fn mul_assign(&mut self, right: i32) {
+                $crate::traits::GenericMul::mul_inplace(self, right)
+                    .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+    };
+}
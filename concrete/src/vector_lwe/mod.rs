@@ -8,14 +8,13 @@ use colored::Colorize;
 use itertools::izip;
 use serde::{Deserialize, Serialize};
 
-use concrete_core::math::polynomial::PolynomialSize;
+use concrete_commons::parameters::{CiphertextCount, GlweSize, LweSize, PolynomialSize};
 use concrete_core::{
     crypto::{
-        self,
-        encoding::PlaintextList,
+        bootstrap::Bootstrap,
+        encoding::{Plaintext, PlaintextList},
         glwe::GlweCiphertext,
         lwe::{LweCiphertext, LweList},
-        CiphertextCount, GlweSize, LweSize,
     },
     math::tensor::Tensor,
     math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor, IntoTensor},
@@ -23,9 +22,14 @@ use concrete_core::{
 use concrete_npe as npe;
 
 use crate::error::CryptoAPIError;
+use crate::traits::{
+    GenericAdd, GenericMul, GenericNeg, GenericPolicyOps, GenericSub, OperatorPolicy,
+    VectorLWEOperations,
+};
 use crate::{read_from_file, write_to_file, Torus};
-use concrete_commons::{Numeric, StandardDev};
-use concrete_core::math::random::EncryptionRandomGenerator;
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::numeric::Numeric;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
 
 #[cfg(test)]
 mod tests;
@@ -48,6 +52,153 @@ pub struct VectorLWE {
     pub encoders: Vec<crate::Encoder>,
 }
 
+impl GenericAdd<f64, CryptoAPIError> for VectorLWE {
+    fn add(&self, right: f64) -> Result<VectorLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(&vec![right; self.nb_ciphertexts])
+    }
+    fn add_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        self.add_constant_dynamic_encoder_inplace(&vec![right; self.nb_ciphertexts])
+    }
+}
+
+impl GenericAdd<&VectorLWE, CryptoAPIError> for VectorLWE {
+    fn add(&self, right: &VectorLWE) -> Result<VectorLWE, CryptoAPIError> {
+        self.add_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn add_inplace(&mut self, right: &VectorLWE) -> Result<(), CryptoAPIError> {
+        self.add_with_policy_inplace(right, OperatorPolicy::Padding)
+    }
+}
+
+impl GenericPolicyOps<CryptoAPIError> for VectorLWE {
+    fn add_with_policy_inplace(
+        &mut self,
+        right: &VectorLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        match policy {
+            OperatorPolicy::Padding => self.add_with_padding_inplace(right),
+            OperatorPolicy::Centered => self.add_centered_inplace(right),
+            OperatorPolicy::Exact => self.add_with_padding_exact_inplace(right),
+        }
+    }
+
+    fn sub_with_policy_inplace(
+        &mut self,
+        right: &VectorLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        match policy {
+            OperatorPolicy::Padding => self.sub_with_padding_inplace(right),
+            OperatorPolicy::Centered => self.add_centered_inplace(&right.opposite()?),
+            OperatorPolicy::Exact => self.sub_with_padding_exact_inplace(right),
+        }
+    }
+}
+
+impl GenericSub<f64, CryptoAPIError> for VectorLWE {
+    fn sub(&self, right: f64) -> Result<VectorLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(&vec![-right; self.nb_ciphertexts])
+    }
+    fn sub_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        self.add_constant_dynamic_encoder_inplace(&vec![-right; self.nb_ciphertexts])
+    }
+}
+
+impl GenericSub<&VectorLWE, CryptoAPIError> for VectorLWE {
+    fn sub(&self, right: &VectorLWE) -> Result<VectorLWE, CryptoAPIError> {
+        self.sub_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn sub_inplace(&mut self, right: &VectorLWE) -> Result<(), CryptoAPIError> {
+        self.sub_with_policy_inplace(right, OperatorPolicy::Padding)
+    }
+}
+
+impl GenericMul<i32, CryptoAPIError> for VectorLWE {
+    fn mul(&self, right: i32) -> Result<VectorLWE, CryptoAPIError> {
+        self.mul_constant_static_encoder(&vec![right; self.nb_ciphertexts])
+    }
+    fn mul_inplace(&mut self, right: i32) -> Result<(), CryptoAPIError> {
+        self.mul_constant_static_encoder_inplace(&vec![right; self.nb_ciphertexts])
+    }
+}
+
+impl GenericNeg<CryptoAPIError> for VectorLWE {
+    fn neg(&self) -> Result<VectorLWE, CryptoAPIError> {
+        self.opposite()
+    }
+    fn neg_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        self.opposite_inplace()
+    }
+}
+
+impl_operators!(VectorLWE);
+
+impl VectorLWEOperations for VectorLWE {
+    type SecretKey = crate::LWESecretKey;
+    type BootstrapKey = crate::LWEBSK;
+    type KeySwitchKey = crate::LWEKSK;
+
+    fn encode_encrypt(
+        sk: &crate::LWESecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+    ) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::encode_encrypt(sk, messages, encoder)
+    }
+    fn decrypt_decode(&self, sk: &crate::LWESecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        VectorLWE::decrypt_decode(self, sk)
+    }
+    fn decrypt_decode_round(&self, sk: &crate::LWESecretKey) -> Result<Vec<f64>, CryptoAPIError> {
+        VectorLWE::decrypt_decode_round(self, sk)
+    }
+    fn get_encoders(&self) -> &[crate::Encoder] {
+        &self.encoders
+    }
+    fn get_variances(&self) -> &[f64] {
+        &self.variances
+    }
+    fn extract_nth(&self, n: usize) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::extract_nth(self, n)
+    }
+    fn add_constant_static_encoder(&self, messages: &[f64]) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::add_constant_static_encoder(self, messages)
+    }
+    fn add_constant_dynamic_encoder(
+        &self,
+        messages: &[f64],
+    ) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::add_constant_dynamic_encoder(self, messages)
+    }
+    fn add_centered(&self, ct: &VectorLWE) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::add_centered(self, ct)
+    }
+    fn add_with_padding(&self, ct: &VectorLWE) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::add_with_padding(self, ct)
+    }
+    fn sub_with_padding(&self, ct: &VectorLWE) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::sub_with_padding(self, ct)
+    }
+    fn mul_constant_static_encoder(&self, messages: &[i32]) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::mul_constant_static_encoder(self, messages)
+    }
+    fn opposite_nth(&self, n: usize) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::opposite_nth(self, n)
+    }
+    fn keyswitch(&self, ksk: &crate::LWEKSK) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::keyswitch(self, ksk)
+    }
+    fn bootstrap_nth_with_function<F: Fn(f64) -> f64>(
+        &self,
+        bsk: &crate::LWEBSK,
+        f: F,
+        encoder_output: &crate::Encoder,
+        n: usize,
+    ) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::bootstrap_nth_with_function(self, bsk, f, encoder_output, n)
+    }
+}
+
 impl VectorLWE {
     /// Instantiate a new VectorLWE filled with zeros from a dimension and a number of ciphertexts
     /// `nb_ciphertexts` has to be at least 1.
@@ -228,9 +379,26 @@ impl VectorLWE {
     pub fn encrypt(
         sk: &crate::LWESecretKey,
         plaintexts: &crate::Plaintext,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        VectorLWE::encrypt_with_generator(sk, plaintexts, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt plaintexts from a Plaintext with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an LWESecretKey
+    /// * `plaintexts` - a Plaintext
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    ///
+    /// # Output
+    /// * VectorLWE structure
+    pub fn encrypt_with_generator(
+        sk: &crate::LWESecretKey,
+        plaintexts: &crate::Plaintext,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<crate::VectorLWE, CryptoAPIError> {
         let mut res = VectorLWE::zero(sk.dimension, plaintexts.nb_plaintexts)?;
-        res.encrypt_inplace(sk, plaintexts)?;
+        res.encrypt_inplace_with_generator(sk, plaintexts, generator)?;
         Ok(res)
     }
 
@@ -264,6 +432,54 @@ impl VectorLWE {
         sk: &crate::LWESecretKey,
         messages: &[f64],
         encoder: &crate::Encoder,
+    ) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::encode_encrypt_with_generator(
+            sk,
+            messages,
+            encoder,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encode messages and then directly encrypt the plaintexts into an VectorLWE structure with
+    /// a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `messages` -  a list of messages as u64
+    /// * `encoder` - an Encoder
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    ///
+    /// # Output
+    /// an VectorLWE structure
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    /// use concrete_core::crypto::secret::generators::{
+    ///     EncryptionRandomGenerator, SecretRandomGenerator,
+    /// };
+    ///
+    /// let encoder = Encoder::new(-2., 6., 4, 4).unwrap();
+    /// let messages: Vec<f64> = vec![-1., 2., 0., 5., -0.5];
+    ///
+    /// // a seeded key generation and a deterministic encryption give reproducible fixtures
+    /// let secret_key =
+    ///     LWESecretKey::new_with_generator(&LWE128_1024, &mut SecretRandomGenerator::new(Some(0)));
+    /// let mut generator = EncryptionRandomGenerator::new_deterministic(1, 2);
+    /// let ciphertext = VectorLWE::encode_encrypt_with_generator(
+    ///     &secret_key,
+    ///     &messages,
+    ///     &encoder,
+    ///     &mut generator,
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn encode_encrypt_with_generator(
+        sk: &crate::LWESecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<VectorLWE, CryptoAPIError> {
         let mut plaintexts: Vec<Torus> = vec![0; messages.len()];
         for (pt, m) in plaintexts.iter_mut().zip(messages.iter()) {
@@ -293,7 +509,8 @@ impl VectorLWE {
             nb_ciphertexts: messages.len(),
             encoders: vec![result_encoder; messages.len()],
         };
-        res.encrypt_raw(sk, &plaintexts).unwrap();
+        res.encrypt_raw_with_generator(sk, &plaintexts, generator)
+            .unwrap();
 
         Ok(res)
     }
@@ -332,6 +549,31 @@ impl VectorLWE {
         sk: &crate::LWESecretKey,
         messages: &[f64],
         encoders: &[crate::Encoder],
+    ) -> Result<VectorLWE, CryptoAPIError> {
+        VectorLWE::encode_encrypt_several_encoders_with_generator(
+            sk,
+            messages,
+            encoders,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encode messages with a different encoder for each message and encrypt them with a
+    /// provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `messages` -  a list of messages as u64
+    /// * `encoders` - a list of Encoder elements
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    ///
+    /// # Output
+    /// an VectorLWE structure
+    pub fn encode_encrypt_several_encoders_with_generator(
+        sk: &crate::LWESecretKey,
+        messages: &[f64],
+        encoders: &[crate::Encoder],
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<VectorLWE, CryptoAPIError> {
         let mut plaintexts: Vec<Torus> = vec![0; messages.len()];
         let mut result_encoders: Vec<crate::Encoder> = encoders.to_vec();
@@ -367,7 +609,8 @@ impl VectorLWE {
             nb_ciphertexts: messages.len(),
             encoders: result_encoders,
         };
-        res.encrypt_raw(sk, &plaintexts).unwrap();
+        res.encrypt_raw_with_generator(sk, &plaintexts, generator)
+            .unwrap();
 
         Ok(res)
     }
@@ -403,9 +646,29 @@ impl VectorLWE {
         &mut self,
         sk: &crate::LWESecretKey,
         plaintexts: &crate::Plaintext,
+    ) -> Result<(), CryptoAPIError> {
+        self.encrypt_inplace_with_generator(
+            sk,
+            plaintexts,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encrypt plaintexts from a Plaintext with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `plaintexts` - a list of plaintexts
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    pub fn encrypt_inplace_with_generator(
+        &mut self,
+        sk: &crate::LWESecretKey,
+        plaintexts: &crate::Plaintext,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<(), CryptoAPIError> {
         // encryption
-        self.encrypt_raw(sk, &plaintexts.plaintexts).unwrap();
+        self.encrypt_raw_with_generator(sk, &plaintexts.plaintexts, generator)
+            .unwrap();
 
         for (output_enc, input_enc) in izip!(self.encoders.iter_mut(), plaintexts.encoders.iter()) {
             // copy the Encoders from the Plaintexts to the VectorLWE
@@ -454,6 +717,21 @@ impl VectorLWE {
         &mut self,
         sk: &crate::LWESecretKey,
         plaintexts: &[Torus],
+    ) -> Result<(), CryptoAPIError> {
+        self.encrypt_raw_with_generator(sk, plaintexts, &mut EncryptionRandomGenerator::new(None))
+    }
+
+    /// Encrypt several raw plaintexts with the provided key, standard deviation and encryption
+    /// generator
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `plaintexts` - a list of plaintexts
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    pub fn encrypt_raw_with_generator(
+        &mut self,
+        sk: &crate::LWESecretKey,
+        plaintexts: &[Torus],
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<(), CryptoAPIError> {
         // compute the variance
         let var = sk.get_variance();
@@ -473,7 +751,7 @@ impl VectorLWE {
             &mut self.ciphertexts,
             &PlaintextList::from_container(plaintexts),
             StandardDev::from_standard_dev(sk.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
+            generator,
         );
 
         Ok(())
@@ -579,6 +857,120 @@ impl VectorLWE {
         Ok(tmp)
     }
 
+    /// Measure the noise of each ciphertext with the secret key, the messages being the closest
+    /// ones representable with the precision of the encoders
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// # Output
+    /// * a list of NoiseMeasurement
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * InvalidEncoderError - if an encoder is not valid
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt messages
+    /// let encoder = Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    /// let messages: Vec<f64> = vec![1., 3., 12., 7.];
+    /// let sk = LWESecretKey::new(&LWE128_630);
+    /// let ct = VectorLWE::encode_encrypt(&sk, &messages, &encoder).unwrap();
+    ///
+    /// // measure their noise
+    /// let measurements = ct.measure_noise(&sk).unwrap();
+    /// assert!(measurements.iter().all(|m| !m.overlaps_message()));
+    /// ```
+    pub fn measure_noise(
+        &self,
+        sk: &crate::LWESecretKey,
+    ) -> Result<Vec<crate::NoiseMeasurement>, CryptoAPIError> {
+        // check dimensions
+        if sk.dimension != self.dimension {
+            return Err(DimensionError!(self.dimension, sk.dimension));
+        }
+
+        let mut result: Vec<crate::NoiseMeasurement> = Vec::with_capacity(self.nb_ciphertexts);
+        for (ct, encoder, variance) in izip!(
+            self.ciphertexts.ciphertext_iter(),
+            self.encoders.iter(),
+            self.variances.iter()
+        ) {
+            // check the encoder
+            if !encoder.is_valid() {
+                return Err(InvalidEncoderError!(
+                    encoder.nb_bit_precision,
+                    encoder.delta
+                ));
+            }
+
+            // compute the phase and its closest encoding
+            let mut phase = Plaintext(0);
+            sk.val.decrypt_lwe(&mut phase, &ct);
+            let encoded = Plaintext(crate::noise_measurement::closest_encoding(phase.0, encoder));
+
+            result.push(crate::NoiseMeasurement::new(
+                ct.compute_error(&sk.val, &encoded),
+                encoder,
+                *variance,
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Measure the noise of each ciphertext with the secret key and the messages they encrypt
+    ///
+    /// # Arguments
+    /// * `sk` - an LWE secret key
+    /// * `messages` - the messages encrypted in the ciphertexts
+    /// # Output
+    /// * a list of NoiseMeasurement
+    /// * DimensionError - if the ciphertext and the key have incompatible dimensions
+    /// * NbCTError - if the number of messages and the number of ciphertexts are different
+    /// * MessageOutsideIntervalError - if a message is outside the interval of its encoder
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt messages
+    /// let encoder = Encoder::new(-5., 5., 8, 0).unwrap();
+    /// let messages: Vec<f64> = vec![-3.2, 4.3, 0.12, -1.1, 2.78];
+    /// let sk = LWESecretKey::new(&LWE128_630);
+    /// let ct = VectorLWE::encode_encrypt(&sk, &messages, &encoder).unwrap();
+    ///
+    /// // measure their noise
+    /// let measurements = ct.measure_noise_with_messages(&sk, &messages).unwrap();
+    /// assert!(measurements.iter().all(|m| !m.overlaps_message()));
+    /// ```
+    pub fn measure_noise_with_messages(
+        &self,
+        sk: &crate::LWESecretKey,
+        messages: &[f64],
+    ) -> Result<Vec<crate::NoiseMeasurement>, CryptoAPIError> {
+        // check dimensions
+        if sk.dimension != self.dimension {
+            return Err(DimensionError!(self.dimension, sk.dimension));
+        }
+
+        // check the number of messages
+        if messages.len() != self.nb_ciphertexts {
+            return Err(NbCTError!(messages.len(), self.nb_ciphertexts));
+        }
+
+        let mut result: Vec<crate::NoiseMeasurement> = Vec::with_capacity(self.nb_ciphertexts);
+        for (ct, m, encoder, variance) in izip!(
+            self.ciphertexts.ciphertext_iter(),
+            messages.iter(),
+            self.encoders.iter(),
+            self.variances.iter()
+        ) {
+            let encoded = Plaintext(encoder.encode_core(*m)?);
+            result.push(crate::NoiseMeasurement::new(
+                ct.compute_error(&sk.val, &encoded),
+                encoder,
+                *variance,
+            ));
+        }
+        Ok(result)
+    }
+
     /// Decrypt the list of ciphertexts, meaning compute the phase and directly decode the output as if the encoder was set in round mode
     ///
     /// # Arguments
@@ -1372,6 +1764,120 @@ impl VectorLWE {
         Ok(())
     }
 
+    /// Compute an addition between two VectorLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorLWE struct
+    ///
+    /// # Output
+    /// * a new VectorLWE
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * DeltaError - if the ciphertexts have incompatible deltas
+    /// * PaddingError - if the ciphertexts have incompatible paddings
+    /// * NotEnoughPaddingError - if nb bit of padding is zero
+    pub fn add_with_padding_exact(
+        &self,
+        ct: &crate::VectorLWE,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.add_with_padding_exact_inplace(ct)?;
+        Ok(res)
+    }
+
+    /// Compute an addition between two VectorLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorLWE struct
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * DeltaError - if the ciphertexts have incompatible deltas
+    /// * PaddingError - if the ciphertexts have incompatible paddings
+    /// * NotEnoughPaddingError - if nb bit of padding is zero
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(0., 255., 8, 1).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // encode and encrypt
+    /// let mut ciphertext_1 =
+    ///     VectorLWE::encode_encrypt(&secret_key, &[106., 3.], &encoder).unwrap();
+    /// let ciphertext_2 = VectorLWE::encode_encrypt(&secret_key, &[4., 250.], &encoder).unwrap();
+    ///
+    /// ciphertext_1.add_with_padding_exact_inplace(&ciphertext_2).unwrap();
+    /// assert_eq!(ciphertext_1.encoders[0].nb_bit_precision, 9);
+    /// ```
+    pub fn add_with_padding_exact_inplace(
+        &mut self,
+        ct: &crate::VectorLWE,
+    ) -> Result<(), CryptoAPIError> {
+        let precisions = get_exact_precisions(&self.encoders, &ct.encoders);
+        self.add_with_padding_inplace(ct)?;
+        self.set_precisions(&precisions)
+    }
+
+    /// Compute an subtraction between two VectorLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorLWE struct
+    ///
+    /// # Output
+    /// * a new VectorLWE
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * DeltaError - if the ciphertexts have incompatible deltas
+    /// * PaddingError - if the ciphertexts have incompatible paddings
+    /// * NotEnoughPaddingError - if nb bit of padding is zero
+    pub fn sub_with_padding_exact(
+        &self,
+        ct: &crate::VectorLWE,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.sub_with_padding_exact_inplace(ct)?;
+        Ok(res)
+    }
+
+    /// Compute an subtraction between two VectorLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorLWE struct
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * DeltaError - if the ciphertexts have incompatible deltas
+    /// * PaddingError - if the ciphertexts have incompatible paddings
+    /// * NotEnoughPaddingError - if nb bit of padding is zero
+    pub fn sub_with_padding_exact_inplace(
+        &mut self,
+        ct: &crate::VectorLWE,
+    ) -> Result<(), CryptoAPIError> {
+        let precisions = get_exact_precisions(&self.encoders, &ct.encoders);
+        self.sub_with_padding_inplace(ct)?;
+        self.set_precisions(&precisions)
+    }
+
+    /// Set the precisions of the encoders, then reduce them according to the variances
+    fn set_precisions(&mut self, precisions: &[usize]) -> Result<(), CryptoAPIError> {
+        for (enc, var, precision) in izip!(
+            self.encoders.iter_mut(),
+            self.variances.iter(),
+            precisions.iter()
+        ) {
+            enc.nb_bit_precision = *precision;
+            enc.update_precision_from_variance(*var)?;
+        }
+        Ok(())
+    }
+
     /// Multiply VectorLWE ciphertexts with small integer messages and does not change the encoding but changes the bodies and masks of the ciphertexts
     ///
     /// # Argument
@@ -1713,6 +2219,45 @@ impl VectorLWE {
         Ok(())
     }
 
+    /// Compute the opposite of all the LWE ciphertexts in the structure
+    ///
+    /// # Output
+    /// * a new VectorLWE
+    /// * InvalidEncoderError - if one of the encoders is not valid (i.e. with nb_bit_precision = 0 or delta = 0)
+    pub fn opposite(&self) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.opposite_inplace()?;
+        Ok(res)
+    }
+
+    /// Compute the opposite of all the LWE ciphertexts in the structure
+    ///
+    /// # Output
+    /// * InvalidEncoderError - if one of the encoders is not valid (i.e. with nb_bit_precision = 0 or delta = 0)
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(-150., 204., 6, 1).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = LWESecretKey::new(&LWE128_1024);
+    ///
+    /// // encode and encrypt
+    /// let mut ciphertext =
+    ///     VectorLWE::encode_encrypt(&secret_key, &[-106.276, 104.3], &encoder).unwrap();
+    ///
+    /// ciphertext.opposite_inplace().unwrap();
+    /// ```
+    pub fn opposite_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        for n in 0..self.nb_ciphertexts {
+            self.opposite_nth_inplace(n)?;
+        }
+        Ok(())
+    }
+
     /// Compute the opposite of the n-th LWE ciphertext in the structure
     ///
     /// # Argument
@@ -2078,11 +2623,10 @@ impl VectorLWE {
                 .update_with_scalar_shl(&(self.encoders[n].nb_bit_padding - 1));
 
             // compute the bootstrap
-            crypto::cross::bootstrap(
+            bsk.fourier_key().bootstrap(
                 &mut result,
                 &LweCiphertext::from_container(ct_clone),
-                &bsk.ciphertexts,
-                &mut accumulator,
+                &accumulator,
             );
         } else {
             // compute the bootstrap
@@ -2092,7 +2636,7 @@ impl VectorLWE {
                 .get_sub(n * (self.get_ciphertext_size())..((n + 1) * (self.get_ciphertext_size())))
                 .into_container();
             let ct = LweCiphertext::from_container(ct_view);
-            crypto::cross::bootstrap(&mut result, &ct, &bsk.ciphertexts, &mut accumulator);
+            bsk.fourier_key().bootstrap(&mut result, &ct, &accumulator);
         }
 
         // compute the new variance (without the drift)
@@ -2406,6 +2950,7 @@ impl VectorLWE {
             nb_bit_precision: self.encoders[0].nb_bit_precision,
             nb_bit_padding: self.encoders[0].nb_bit_padding - nb_bit_padding_consumed,
             round: self.encoders[0].round,
+            failure_probability: self.encoders[0].failure_probability,
         };
         new_encoder.update_precision_from_variance(new_var)?;
         Ok(VectorLWE {
@@ -2495,6 +3040,268 @@ impl VectorLWE {
             encoders: vec![new_encoder],
         })
     }
+
+    /// Compute the maximum of the messages of all the LWE ciphertexts with a tournament:
+    /// the ciphertexts are compared two by two with `LWE::max`, which needs log2(nb_ciphertexts) rounds
+    /// of bootstraps. The ciphertexts are first re-encoded with a common encoder if needed (see `Encoder::new_covering`).
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a new VectorLWE containing only one ciphertext, encrypted under the input key
+    /// * ZeroCiphertextsInStructureError - if self contains no ciphertext
+    /// * DimensionError - if the keys and the ciphertexts have incompatible dimensions
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_750);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 4);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 2, 7);
+    ///
+    /// // encode and encrypt
+    /// let messages: Vec<f64> = vec![3., 12., 7., 1., 9.];
+    /// let ciphertexts = VectorLWE::encode_encrypt(&secret_key_before, &messages, &encoder).unwrap();
+    ///
+    /// let ciphertext_max = ciphertexts.max(&bsk, &ksk).unwrap();
+    /// assert_eq!(ciphertext_max.decrypt_decode_round(&secret_key_before).unwrap(), vec![12.]);
+    /// ```
+    pub fn max(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let (max, _) = self.tournament(bsk, ksk, false)?;
+        Ok(VectorLWE::from_lwe(&max))
+    }
+
+    /// Compute the index of the largest message among the LWE ciphertexts with a tournament (see `max`),
+    /// the first index being returned in case of equality.
+    /// Each round of the tournament also selects the index of the winners with one more bootstrap.
+    /// The output encoder is [0, 2^b[ with b = ceil(log2(nb_ciphertexts)) bits of precision and 3 bits of padding.
+    ///
+    /// # Argument
+    /// * `bsk` - the bootstrapping key
+    /// * `ksk` - the key switching key from the output key of the bootstrap to the input key
+    ///
+    /// # Output
+    /// * a new VectorLWE containing only one ciphertext, encrypted under the input key
+    /// * ZeroCiphertextsInStructureError - if self contains no ciphertext
+    /// * DimensionError - if the keys and the ciphertexts have incompatible dimensions
+    /// * LookUpTableSizeError - if the polynomial size is smaller than 4 * 2^b
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    ///
+    /// // secret keys
+    /// let rlwe_secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let secret_key_before = LWESecretKey::new(&LWE128_750);
+    /// let secret_key_after = rlwe_secret_key.to_lwe_secret_key();
+    ///
+    /// // bootstrapping and key switching keys
+    /// let bsk = LWEBSK::new(&secret_key_before, &rlwe_secret_key, 5, 4);
+    /// let ksk = LWEKSK::new(&secret_key_after, &secret_key_before, 2, 7);
+    ///
+    /// // encode and encrypt
+    /// let messages: Vec<f64> = vec![3., 12., 7., 1., 9.];
+    /// let ciphertexts = VectorLWE::encode_encrypt(&secret_key_before, &messages, &encoder).unwrap();
+    ///
+    /// let ciphertext_argmax = ciphertexts.argmax(&bsk, &ksk).unwrap();
+    /// assert_eq!(ciphertext_argmax.decrypt_decode_round(&secret_key_before).unwrap(), vec![1.]);
+    /// ```
+    pub fn argmax(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+    ) -> Result<crate::VectorLWE, CryptoAPIError> {
+        let (_, argmax) = self.tournament(bsk, ksk, true)?;
+        Ok(VectorLWE::from_lwe(&argmax.unwrap()))
+    }
+
+    /// Reduce the ciphertexts two by two with `LWE::max`, and if asked select the index of the winners
+    fn tournament(
+        &self,
+        bsk: &crate::LWEBSK,
+        ksk: &crate::LWEKSK,
+        with_index: bool,
+    ) -> Result<(crate::LWE, Option<crate::LWE>), CryptoAPIError> {
+        if self.nb_ciphertexts == 0 {
+            return Err(ZeroCiphertextsInStructureError!(self.nb_ciphertexts));
+        }
+
+        // re-encode all the ciphertexts with the same encoder
+        let encoders: Vec<&crate::Encoder> = self.encoders.iter().collect();
+        let encoder = crate::Encoder::new_covering(&encoders)?;
+        let mut values = (0..self.nb_ciphertexts)
+            .map(|n| self.get_lwe(n).reencode(bsk, ksk, &encoder))
+            .collect::<Result<Vec<crate::LWE>, CryptoAPIError>>()?;
+
+        // trivial encryptions of the indices
+        let nb_bit_index = usize::max(f64::log2(self.nb_ciphertexts as f64).ceil() as usize, 1);
+        if with_index && (4 << nb_bit_index) > bsk.polynomial_size {
+            return Err(LookUpTableSizeError!(
+                4 << nb_bit_index,
+                bsk.polynomial_size
+            ));
+        }
+        let index_encoder = crate::Encoder {
+            o: 0.,
+            delta: f64::powi(2., nb_bit_index as i32),
+            nb_bit_precision: nb_bit_index,
+            nb_bit_padding: 3,
+            round: true,
+            failure_probability: encoder.failure_probability,
+        };
+        let mut indices = (0..self.nb_ciphertexts)
+            .map(|n| {
+                let mut index = crate::LWE::zero(self.dimension)?;
+                index.ciphertext.as_mut_tensor().as_mut_slice()[self.dimension] =
+                    index_encoder.encode_outside_interval_operators(n as f64)?;
+                index.encoder = index_encoder.clone();
+                Ok(index)
+            })
+            .collect::<Result<Vec<crate::LWE>, CryptoAPIError>>()?;
+
+        // rounds of the tournament
+        while values.len() > 1 {
+            let mut next_values: Vec<crate::LWE> = Vec::with_capacity(values.len().div_ceil(2));
+            let mut next_indices: Vec<crate::LWE> = Vec::with_capacity(values.len().div_ceil(2));
+            for (pair, pair_indices) in values.chunks(2).zip(indices.chunks(2)) {
+                if pair.len() == 1 {
+                    next_values.push(pair[0].clone());
+                    next_indices.push(pair_indices[0].clone());
+                    continue;
+                }
+                next_values.push(pair[0].max(&pair[1], bsk, ksk)?);
+                if with_index {
+                    // the second ciphertext wins only if it is strictly greater
+                    let second_wins = pair[1].greater_than(&pair[0], bsk, ksk)?;
+                    next_indices.push(select_index(
+                        &pair_indices[0],
+                        &pair_indices[1],
+                        &second_wins,
+                        nb_bit_index,
+                        bsk,
+                        ksk,
+                    )?);
+                }
+            }
+            values = next_values;
+            if with_index {
+                indices = next_indices;
+            }
+        }
+
+        Ok((
+            values.remove(0),
+            if with_index {
+                Some(indices.remove(0))
+            } else {
+                None
+            },
+        ))
+    }
+
+    /// Return a copy of the n-th ciphertext as an LWE
+    fn get_lwe(&self, n: usize) -> crate::LWE {
+        let ct_size = self.get_ciphertext_size();
+        crate::LWE {
+            ciphertext: LweCiphertext::from_container(
+                self.ciphertexts
+                    .as_tensor()
+                    .get_sub((n * ct_size)..((n + 1) * ct_size))
+                    .as_slice()
+                    .to_vec(),
+            ),
+            variance: self.variances[n],
+            dimension: self.dimension,
+            encoder: self.encoders[n].clone(),
+        }
+    }
+
+    /// Build a VectorLWE containing a copy of one LWE ciphertext
+    fn from_lwe(ct: &crate::LWE) -> crate::VectorLWE {
+        VectorLWE {
+            ciphertexts: LweList::from_container(
+                ct.ciphertext.as_tensor().as_slice().to_vec(),
+                LweSize(ct.dimension + 1),
+            ),
+            variances: vec![ct.variance],
+            dimension: ct.dimension,
+            nb_ciphertexts: 1,
+            encoders: vec![ct.encoder.clone()],
+        }
+    }
+}
+
+/// Select the encrypted index index_1 or index_2 with one bootstrap, depending on the bit second_wins
+/// (encrypting 0 or 1/4 on the torus).
+/// The indices have nb_bit_index bits of precision and 3 bits of padding, so their difference and the bit
+/// are packed into the half torus as 2^nb_bit_index + (index_2 - index_1) + second_wins * 2^(nb_bit_index + 1)
+/// cells of size 2^-(nb_bit_index + 3).
+fn select_index(
+    index_1: &crate::LWE,
+    index_2: &crate::LWE,
+    second_wins: &crate::LWE,
+    nb_bit_index: usize,
+    bsk: &crate::LWEBSK,
+    ksk: &crate::LWEKSK,
+) -> Result<crate::LWE, CryptoAPIError> {
+    let dimension = index_1.dimension;
+    let shift = <Torus as Numeric>::BITS - nb_bit_index - 3;
+
+    // pack the difference of the indices and the bit, centered in their cells
+    let mut packed = index_2.ciphertext.clone();
+    packed.update_with_sub(&index_1.ciphertext);
+    packed.update_with_add(&second_wins.ciphertext);
+    let update = packed.as_tensor().as_slice()[dimension]
+        .wrapping_add(1 << (<Torus as Numeric>::BITS - 3))
+        .wrapping_add(1 << (shift - 1));
+    packed.as_mut_tensor().as_mut_slice()[dimension] = update;
+
+    // output index_2 - index_1 if the second index wins, 0 otherwise
+    let lut = bsk.generate_cell_look_up_table(4 << nb_bit_index, |cell| {
+        if cell >= 2 << nb_bit_index {
+            (cell as Torus).wrapping_sub(3 << nb_bit_index) << shift
+        } else {
+            0
+        }
+    });
+    let bootstrapped = crate::LWE {
+        ciphertext: bsk.bootstrap_raw(&packed, &lut),
+        variance: <Torus as npe::Cross>::bootstrap(
+            dimension,
+            bsk.dimension,
+            bsk.level,
+            bsk.base_log,
+            bsk.polynomial_size,
+            bsk.variance,
+        ),
+        dimension: bsk.dimension * bsk.polynomial_size,
+        encoder: index_1.encoder.clone(),
+    }
+    .keyswitch(ksk)?;
+
+    // index_1 + (index_2 - index_1) * second_wins
+    let mut res = index_1.clone();
+    res.ciphertext.update_with_add(&bootstrapped.ciphertext);
+    res.variance = npe::add_ciphertexts(res.variance, bootstrapped.variance);
+    Ok(res)
 }
 
 /// Print needed pieces of information about an VectorLWE
@@ -2548,3 +3355,13 @@ impl fmt::Display for VectorLWE {
         writeln!(f, "{}", to_be_print)
     }
 }
+
+/// Return the precisions of exact additions or subtractions between ciphertexts with these encoders:
+/// max(nb1,nb2) + 1
+fn get_exact_precisions(encoders_1: &[crate::Encoder], encoders_2: &[crate::Encoder]) -> Vec<usize> {
+    encoders_1
+        .iter()
+        .zip(encoders_2.iter())
+        .map(|(enc_1, enc_2)| usize::max(enc_1.nb_bit_precision, enc_2.nb_bit_precision) + 1)
+        .collect()
+}
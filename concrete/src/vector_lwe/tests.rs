@@ -815,3 +815,175 @@ fn test_encode_encrypt_several_encoders_x_sum_with_new_min_x_decrypt_decode_roun
     assert_eq_granularity!(clear_sum, d, ct_sum.encoders[0]);
     assert_eq!(precision, ct_sum.encoders[0].nb_bit_precision);
 }
+
+#[test]
+fn test_encode_encrypt_x_max_argmax_x_decrypt() {
+    // settings
+    let nb_ciphertexts: usize = 5;
+    let encoder = crate::Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+
+    // secret keys
+    let rlwe_secret_key = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let secret_key_input = crate::LWESecretKey::new(&crate::LWE128_750);
+    let secret_key_output = rlwe_secret_key.to_lwe_secret_key();
+
+    // bootstrapping and key switching keys
+    let bsk = crate::LWEBSK::new(&secret_key_input, &rlwe_secret_key, 5, 4);
+    let ksk = crate::LWEKSK::new(&secret_key_output, &secret_key_input, 2, 7);
+
+    for _ in 0..3 {
+        // random messages on the grid of the encoder
+        let messages: Vec<f64> = (0..nb_ciphertexts)
+            .map(|_| random_index!(16) as f64)
+            .collect();
+
+        // encode and encrypt
+        let ciphertexts =
+            crate::VectorLWE::encode_encrypt(&secret_key_input, &messages, &encoder).unwrap();
+
+        // the first index of the maximum
+        let max = messages.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let argmax = messages.iter().position(|m| *m == max).unwrap();
+
+        let ciphertext_max = ciphertexts.max(&bsk, &ksk).unwrap();
+        assert_eq!(ciphertext_max.nb_ciphertexts, 1);
+        assert_eq!(
+            vec![max],
+            ciphertext_max
+                .decrypt_decode_round(&secret_key_input)
+                .unwrap()
+        );
+
+        let ciphertext_argmax = ciphertexts.argmax(&bsk, &ksk).unwrap();
+        assert_eq!(
+            vec![argmax as f64],
+            ciphertext_argmax
+                .decrypt_decode_round(&secret_key_input)
+                .unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_encode_encrypt_x_operators_x_decrypt() {
+    use crate::traits::{GenericPolicyOps, OperatorPolicy};
+
+    // settings: the messages stay in the intervals of the centered operations
+    let nb_messages: usize = random_index!(30) + 10;
+    let encoder = crate::Encoder::new(-5., 5., 6, 4).unwrap();
+    let secret_key = crate::LWESecretKey::new(&crate::LWE128_1024);
+    let messages_1: Vec<f64> = random_messages!(-1.5, 1.5, nb_messages);
+    let messages_2: Vec<f64> = random_messages!(-1.5, 1.5, nb_messages);
+    let ciphertext_1 =
+        crate::VectorLWE::encode_encrypt(&secret_key, &messages_1, &encoder).unwrap();
+    let ciphertext_2 =
+        crate::VectorLWE::encode_encrypt(&secret_key, &messages_2, &encoder).unwrap();
+
+    let check = |ct: &crate::VectorLWE, f: &dyn Fn(f64, f64) -> f64| {
+        let decryptions = ct.decrypt_decode(&secret_key).unwrap();
+        for (m1, m2, d, e) in izip!(
+            messages_1.iter(),
+            messages_2.iter(),
+            decryptions.iter(),
+            ct.encoders.iter()
+        ) {
+            assert_eq_granularity!(f(*m1, *m2), d, e);
+        }
+    };
+
+    // operations between ciphertexts
+    check(&(&ciphertext_1 + &ciphertext_2), &|m1, m2| m1 + m2);
+    check(&(&ciphertext_1 - &ciphertext_2), &|m1, m2| m1 - m2);
+    let mut ct = ciphertext_1.clone();
+    ct -= &ciphertext_2;
+    check(&ct, &|m1, m2| m1 - m2);
+
+    for policy in [
+        OperatorPolicy::Padding,
+        OperatorPolicy::Centered,
+        OperatorPolicy::Exact,
+    ]
+    .iter()
+    {
+        let ct = ciphertext_1.add_with_policy(&ciphertext_2, *policy).unwrap();
+        check(&ct, &|m1, m2| m1 + m2);
+        let mut ct = ciphertext_1.clone();
+        ct.sub_with_policy_inplace(&ciphertext_2, *policy).unwrap();
+        check(&ct, &|m1, m2| m1 - m2);
+
+        // same with the operators
+        let ct = ciphertext_1.with_policy(*policy) - &ciphertext_2;
+        assert_eq!(ct, ciphertext_1.sub_with_policy(&ciphertext_2, *policy).unwrap());
+        let mut ct = ciphertext_1.clone();
+        ct += ciphertext_2.with_policy(*policy);
+        assert_eq!(ct, ciphertext_1.add_with_policy(&ciphertext_2, *policy).unwrap());
+    }
+
+    // operations with constants
+    check(&(-&ciphertext_1), &|m1, _| -m1);
+    check(&(&ciphertext_1 * 3), &|m1, _| 3. * m1);
+    let mut ct = ciphertext_1.clone();
+    ct += 2.5;
+    check(&ct, &|m1, _| m1 + 2.5);
+}
+
+#[test]
+fn test_encode_encrypt_with_generator_reproducible() {
+    use concrete_core::crypto::secret::generators::{
+        EncryptionRandomGenerator, SecretRandomGenerator,
+    };
+
+    // random settings for the encoder and some random messages
+    let nb_messages: usize = random_index!(20) + 1;
+    let (min, max) = generate_random_interval!();
+    let (precision, padding) = generate_precision_padding!(8, 8);
+    let encoder = crate::Encoder::new(min, max, precision, padding).unwrap();
+    let messages: Vec<f64> = random_messages!(min, max, nb_messages);
+
+    // the same seeds give the same keys and the same ciphertexts
+    let seed = random_index!(1000) as u128;
+    let encrypt = || {
+        let sk = crate::LWESecretKey::new_with_generator(
+            &crate::LWE128_630,
+            &mut SecretRandomGenerator::new(Some(seed)),
+        );
+        let mut generator = EncryptionRandomGenerator::new_deterministic(seed, seed + 1);
+        let ct = crate::VectorLWE::encode_encrypt_with_generator(
+            &sk,
+            &messages,
+            &encoder,
+            &mut generator,
+        )
+        .unwrap();
+        (sk, ct)
+    };
+    let (sk_1, ct_1) = encrypt();
+    let (sk_2, ct_2) = encrypt();
+    assert_eq!(sk_1, sk_2);
+    assert_eq!(ct_1, ct_2);
+
+    // a restored generator resumes the encryptions where they were exported
+    let mut generator = EncryptionRandomGenerator::new_deterministic(seed, seed + 1);
+    let _ =
+        crate::VectorLWE::encode_encrypt_with_generator(&sk_1, &messages, &encoder, &mut generator)
+            .unwrap();
+    let state = generator.export_state();
+    let ct_3 =
+        crate::VectorLWE::encode_encrypt_with_generator(&sk_1, &messages, &encoder, &mut generator)
+            .unwrap();
+    let ct_4 = crate::VectorLWE::encode_encrypt_with_generator(
+        &sk_1,
+        &messages,
+        &encoder,
+        &mut EncryptionRandomGenerator::from_state(&state),
+    )
+    .unwrap();
+    assert_eq!(ct_3, ct_4);
+    assert_ne!(ct_1, ct_3);
+
+    // the decryptions are still correct
+    let decryptions = ct_4.decrypt_decode(&sk_1).unwrap();
+    for (m, d, e) in izip!(messages.iter(), decryptions.iter(), ct_4.encoders.iter()) {
+        assert_eq_granularity!(m, d, e);
+    }
+}
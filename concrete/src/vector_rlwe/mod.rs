@@ -8,17 +8,21 @@ use colored::Colorize;
 use itertools::izip;
 use serde::{Deserialize, Serialize};
 
-use concrete_core::math::polynomial::PolynomialSize;
+use concrete_commons::parameters::{CiphertextCount, GlweDimension, PolynomialSize};
 use concrete_core::{
-    crypto::{encoding::PlaintextList, glwe::GlweList, CiphertextCount, GlweDimension},
+    crypto::{encoding::PlaintextList, glwe::GlweList},
     math::tensor::{AsMutSlice, AsMutTensor, AsRefSlice, AsRefTensor},
 };
 use concrete_npe as npe;
 
 use crate::error::CryptoAPIError;
+use crate::traits::{
+    GenericAdd, GenericMul, GenericNeg, GenericPolicyOps, GenericSub, OperatorPolicy,
+};
 use crate::{read_from_file, write_to_file, Torus};
-use concrete_commons::{Numeric, StandardDev};
-use concrete_core::math::random::EncryptionRandomGenerator;
+use concrete_commons::dispersion::StandardDev;
+use concrete_commons::numeric::Numeric;
+use concrete_core::crypto::secret::generators::EncryptionRandomGenerator;
 
 #[cfg(test)]
 mod tests;
@@ -46,6 +50,88 @@ pub struct VectorRLWE {
     pub encoders: Vec<crate::Encoder>,
 }
 
+impl GenericAdd<f64, CryptoAPIError> for VectorRLWE {
+    fn add(&self, right: f64) -> Result<VectorRLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(&vec![right; self.nb_valid()])
+    }
+    fn add_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        self.add_constant_dynamic_encoder_inplace(&vec![right; self.nb_valid()])
+    }
+}
+
+impl GenericAdd<&VectorRLWE, CryptoAPIError> for VectorRLWE {
+    fn add(&self, right: &VectorRLWE) -> Result<VectorRLWE, CryptoAPIError> {
+        self.add_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn add_inplace(&mut self, right: &VectorRLWE) -> Result<(), CryptoAPIError> {
+        self.add_with_policy_inplace(right, OperatorPolicy::Padding)
+    }
+}
+
+impl GenericPolicyOps<CryptoAPIError> for VectorRLWE {
+    fn add_with_policy_inplace(
+        &mut self,
+        right: &VectorRLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        match policy {
+            OperatorPolicy::Padding => self.add_with_padding_inplace(right),
+            OperatorPolicy::Centered => self.add_centered_inplace(right),
+            OperatorPolicy::Exact => self.add_with_padding_exact_inplace(right),
+        }
+    }
+
+    fn sub_with_policy_inplace(
+        &mut self,
+        right: &VectorRLWE,
+        policy: OperatorPolicy,
+    ) -> Result<(), CryptoAPIError> {
+        match policy {
+            OperatorPolicy::Padding => self.sub_with_padding_inplace(right),
+            OperatorPolicy::Centered => self.add_centered_inplace(&right.opposite()?),
+            OperatorPolicy::Exact => self.sub_with_padding_exact_inplace(right),
+        }
+    }
+}
+
+impl GenericSub<f64, CryptoAPIError> for VectorRLWE {
+    fn sub(&self, right: f64) -> Result<VectorRLWE, CryptoAPIError> {
+        self.add_constant_dynamic_encoder(&vec![-right; self.nb_valid()])
+    }
+    fn sub_inplace(&mut self, right: f64) -> Result<(), CryptoAPIError> {
+        self.add_constant_dynamic_encoder_inplace(&vec![-right; self.nb_valid()])
+    }
+}
+
+impl GenericSub<&VectorRLWE, CryptoAPIError> for VectorRLWE {
+    fn sub(&self, right: &VectorRLWE) -> Result<VectorRLWE, CryptoAPIError> {
+        self.sub_with_policy(right, OperatorPolicy::Padding)
+    }
+    fn sub_inplace(&mut self, right: &VectorRLWE) -> Result<(), CryptoAPIError> {
+        self.sub_with_policy_inplace(right, OperatorPolicy::Padding)
+    }
+}
+
+impl GenericMul<i32, CryptoAPIError> for VectorRLWE {
+    fn mul(&self, right: i32) -> Result<VectorRLWE, CryptoAPIError> {
+        self.mul_constant_static_encoder(&vec![right; self.nb_ciphertexts])
+    }
+    fn mul_inplace(&mut self, right: i32) -> Result<(), CryptoAPIError> {
+        self.mul_constant_static_encoder_inplace(&vec![right; self.nb_ciphertexts])
+    }
+}
+
+impl GenericNeg<CryptoAPIError> for VectorRLWE {
+    fn neg(&self) -> Result<VectorRLWE, CryptoAPIError> {
+        self.opposite()
+    }
+    fn neg_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        self.opposite_inplace()
+    }
+}
+
+impl_operators!(VectorRLWE);
+
 impl VectorRLWE {
     /// Instantiate a new VectorRLWE filled with zeros from a polynomial size, a dimension and a number of ciphertexts
     ///
@@ -131,6 +217,24 @@ impl VectorRLWE {
     pub fn encrypt_packed(
         sk: &crate::RLWESecretKey,
         plaintexts: &crate::Plaintext,
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        VectorRLWE::encrypt_packed_with_generator(
+            sk,
+            plaintexts,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encrypt several plaintexts into RLWE ciphertexts with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an RLWE secret key
+    /// * `plaintexts` - a list of plaintexts
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    pub fn encrypt_packed_with_generator(
+        sk: &crate::RLWESecretKey,
+        plaintexts: &crate::Plaintext,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<crate::VectorRLWE, CryptoAPIError> {
         // compute the number of RLWE ct required to store all messages
         let nb_rlwe: usize =
@@ -172,7 +276,7 @@ impl VectorRLWE {
         }
 
         // encrypt the plaintexts
-        res.encrypt_packed_raw(sk, &tmp_pt)?;
+        res.encrypt_packed_raw_with_generator(sk, &tmp_pt, generator)?;
 
         Ok(res)
     }
@@ -210,6 +314,28 @@ impl VectorRLWE {
         sk: &crate::RLWESecretKey,
         messages: &[f64],
         encoder: &crate::Encoder,
+    ) -> Result<VectorRLWE, CryptoAPIError> {
+        VectorRLWE::encode_encrypt_packed_with_generator(
+            sk,
+            messages,
+            encoder,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encode and encrypt several messages into RLWE ciphertexts with a provided encryption
+    /// generator
+    ///
+    /// # Arguments
+    /// * `sk` - an RLWE secret key
+    /// * `messages` - a list of messages
+    /// * `encoder` - an encoder
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    pub fn encode_encrypt_packed_with_generator(
+        sk: &crate::RLWESecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<VectorRLWE, CryptoAPIError> {
         // compute the number of RLWE ct required to store all messages
         let nb_rlwe: usize = f64::ceil(messages.len() as f64 / sk.polynomial_size as f64) as usize;
@@ -249,7 +375,7 @@ impl VectorRLWE {
         }
 
         // encrypt the plaintexts
-        res.encrypt_packed_raw(sk, &tmp_pt)?;
+        res.encrypt_packed_raw_with_generator(sk, &tmp_pt, generator)?;
 
         Ok(res)
     }
@@ -288,6 +414,24 @@ impl VectorRLWE {
     pub fn encrypt(
         sk: &crate::RLWESecretKey,
         plaintexts: &crate::Plaintext,
+    ) -> Result<VectorRLWE, CryptoAPIError> {
+        VectorRLWE::encrypt_with_generator(
+            sk,
+            plaintexts,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encrypt n plaintexts into n RLWE ciphertexts with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an RLWE secret key
+    /// * `plaintexts` - a list of plaintexts
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    pub fn encrypt_with_generator(
+        sk: &crate::RLWESecretKey,
+        plaintexts: &crate::Plaintext,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<VectorRLWE, CryptoAPIError> {
         // get the number of ciphertexts to output
         let nb_ciphertexts: usize = plaintexts.nb_plaintexts;
@@ -329,7 +473,7 @@ impl VectorRLWE {
         }
 
         // encrypt the plaintexts
-        res.encrypt_packed_raw(sk, &tmp_pt)?;
+        res.encrypt_packed_raw_with_generator(sk, &tmp_pt, generator)?;
 
         Ok(res)
     }
@@ -367,6 +511,27 @@ impl VectorRLWE {
         sk: &crate::RLWESecretKey,
         messages: &[f64],
         encoder: &crate::Encoder,
+    ) -> Result<VectorRLWE, CryptoAPIError> {
+        VectorRLWE::encode_encrypt_with_generator(
+            sk,
+            messages,
+            encoder,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encode and encrypt n messages into n RLWE ciphertexts with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an RLWE secret key
+    /// * `messages` - a list of messages
+    /// * `encoder` - an encoder
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    pub fn encode_encrypt_with_generator(
+        sk: &crate::RLWESecretKey,
+        messages: &[f64],
+        encoder: &crate::Encoder,
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<VectorRLWE, CryptoAPIError> {
         // get the number of ciphertexts to output
         let nb_ciphertexts: usize = messages.len();
@@ -407,7 +572,7 @@ impl VectorRLWE {
         }
 
         // encrypt the plaintexts
-        res.encrypt_packed_raw(sk, &tmp_pt)?;
+        res.encrypt_packed_raw_with_generator(sk, &tmp_pt, generator)?;
 
         Ok(res)
     }
@@ -425,6 +590,25 @@ impl VectorRLWE {
         &mut self,
         sk: &crate::RLWESecretKey,
         plaintexts: &[Torus],
+    ) -> Result<(), CryptoAPIError> {
+        self.encrypt_packed_raw_with_generator(
+            sk,
+            plaintexts,
+            &mut EncryptionRandomGenerator::new(None),
+        )
+    }
+
+    /// Encrypt several raw plaintexts into RLWE ciphertexts with a provided encryption generator
+    ///
+    /// # Arguments
+    /// * `sk` - an RLWE secret key
+    /// * `plaintexts` - a list of plaintexts
+    /// * `generator` - the encryption generator sampling the masks and the noises
+    pub fn encrypt_packed_raw_with_generator(
+        &mut self,
+        sk: &crate::RLWESecretKey,
+        plaintexts: &[Torus],
+        generator: &mut EncryptionRandomGenerator,
     ) -> Result<(), CryptoAPIError> {
         // the plaintext slice length should be a multiple of polynomial size
         if plaintexts.len() % self.polynomial_size != 0 {
@@ -442,7 +626,7 @@ impl VectorRLWE {
             &mut self.ciphertexts,
             &PlaintextList::from_container(plaintexts),
             StandardDev::from_standard_dev(sk.std_dev),
-            &mut EncryptionRandomGenerator::new(None),
+            generator,
         );
 
         Ok(())
@@ -581,6 +765,86 @@ impl VectorRLWE {
         Ok(result)
     }
 
+    /// Measure the noise of each coefficient with a valid encoder with the secret key, the
+    /// messages being the closest ones representable with the precision of the encoders
+    ///
+    /// # Argument
+    /// * `sk` - an glwe secret key
+    ///
+    /// # Output
+    /// * a list of NoiseMeasurement, in the same order as the output of decrypt_decode
+    /// * PolynomialSizeError - if the polynomial size of the secret key and the polynomial size of the RLWE ciphertext are differents
+    /// * DimensionError - if the dimension of the secret key and the dimension of the RLWE cipertext are differents
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encode and encrypt messages
+    /// let sk = RLWESecretKey::new(&RLWE128_1024_1);
+    /// let encoder = Encoder::new_rounding_context(0., 15., 4, 2).unwrap();
+    /// let messages: Vec<f64> = vec![1., 3., 12., 7.];
+    /// let ct = VectorRLWE::encode_encrypt(&sk, &messages, &encoder).unwrap();
+    ///
+    /// // measure their noise
+    /// let measurements = ct.measure_noise(&sk).unwrap();
+    /// assert_eq!(measurements.len(), 4);
+    /// assert!(measurements.iter().all(|m| !m.overlaps_message()));
+    /// ```
+    pub fn measure_noise(
+        &self,
+        sk: &crate::RLWESecretKey,
+    ) -> Result<Vec<crate::NoiseMeasurement>, CryptoAPIError> {
+        if sk.polynomial_size != self.polynomial_size {
+            return Err(PolynomialSizeError!(
+                sk.polynomial_size,
+                self.polynomial_size
+            ));
+        } else if sk.dimension != self.dimension {
+            return Err(DimensionError!(sk.dimension, self.dimension));
+        }
+
+        let mut result: Vec<crate::NoiseMeasurement> = Vec::with_capacity(self.nb_valid());
+
+        // create a vec with the good size for the phases of one ciphertext
+        let mut tmp_pt: Vec<Torus> = vec![0; self.polynomial_size];
+
+        for (ct, encoders, variances) in izip!(
+            self.ciphertexts.ciphertext_iter(),
+            self.encoders.chunks(self.polynomial_size),
+            self.variances.chunks(self.polynomial_size)
+        ) {
+            // compute the phase
+            sk.val.decrypt_glwe(
+                &mut PlaintextList::from_container(tmp_pt.as_mut_slice()),
+                &ct,
+            );
+
+            // compute the closest encodings of the coefficients with a valid encoder
+            let encoded: Vec<Torus> = izip!(tmp_pt.iter(), encoders.iter())
+                .map(|(pt, encoder)| {
+                    if encoder.is_valid() {
+                        crate::noise_measurement::closest_encoding(*pt, encoder)
+                    } else {
+                        *pt
+                    }
+                })
+                .collect();
+
+            for (error, encoder, variance) in izip!(
+                ct.compute_errors(&sk.val, &PlaintextList::from_container(encoded.as_slice()))
+                    .iter(),
+                encoders.iter(),
+                variances.iter()
+            ) {
+                if encoder.is_valid() {
+                    result.push(crate::NoiseMeasurement::new(*error, encoder, *variance));
+                }
+            }
+        }
+        Ok(result)
+    }
+
     /// Compute the decryption of each ciphertext and returns also the associated encoder
     /// if nb=3 we return the coefficient 0 of the ciphertext 0,
     /// the coefficient 1 of the ciphertext 0 and the coefficient 2 of the ciphertext 0
@@ -693,7 +957,11 @@ impl VectorRLWE {
         let index = n_coeff + n_ciphertext * self.polynomial_size;
 
         // fill the variance and the encoder
-        res.variances[0] = self.variances[index];
+        res.variances[0] = npe::rlwe::sample_extract(
+            &self.variances[(n_ciphertext * self.polynomial_size)
+                ..((n_ciphertext + 1) * self.polynomial_size)],
+            n_coeff,
+        );
         res.encoders[0].copy(&self.encoders[index]);
 
         // compute the index for the body
@@ -937,17 +1205,19 @@ impl VectorRLWE {
             .as_mut_tensor()
             .update_with_wrapping_add(ct.ciphertexts.as_tensor());
 
+        // compute the new variances
+        self.variances = npe::rlwe::add_uncorrelated(&self.variances, &ct.variances);
+
         // correction related to the addition
-        for (mut ciphertext, encoders, encoders_ct, self_variances, ct_variances) in izip!(
+        for (mut ciphertext, encoders, encoders_ct, self_variances) in izip!(
             self.ciphertexts
                 .as_mut_tensor()
                 .subtensor_iter_mut(self.polynomial_size * (self.dimension + 1)),
             self.encoders.chunks_mut(self.polynomial_size),
             ct.encoders.chunks(self.polynomial_size),
-            self.variances.chunks_mut(self.polynomial_size),
-            ct.variances.chunks(self.polynomial_size),
+            self.variances.chunks(self.polynomial_size),
         ) {
-            for (monomial_coeff, encoder, encoder_ct, self_var, ct_var) in izip!(
+            for (monomial_coeff, encoder, encoder_ct, self_var) in izip!(
                 ciphertext
                     .as_mut_slice()
                     .get_mut(
@@ -958,12 +1228,8 @@ impl VectorRLWE {
                     .iter_mut(),
                 encoders.iter_mut(),
                 encoders_ct.iter(),
-                self_variances.iter_mut(),
-                ct_variances.iter()
+                self_variances.iter()
             ) {
-                // compute the new variance
-                *self_var = npe::add_ciphertexts(*self_var, *ct_var);
-
                 // both coefficients contained a message
                 if encoder.is_valid() && encoder_ct.is_valid() {
                     let mut tmp_enc = encoder.clone();
@@ -1061,16 +1327,15 @@ impl VectorRLWE {
             .as_mut_tensor()
             .update_with_wrapping_add(ct.ciphertexts.as_tensor());
 
+        // compute the new variances
+        self.variances = npe::rlwe::add_uncorrelated(&self.variances, &ct.variances);
+
         // update the Encoder list
-        for (self_enc, ct_enc, self_var, ct_var) in izip!(
+        for (self_enc, ct_enc, self_var) in izip!(
             self.encoders.iter_mut(),
             ct.encoders.iter(),
-            self.variances.iter_mut(),
-            ct.variances.iter()
+            self.variances.iter()
         ) {
-            // compute the new variance
-            *self_var = npe::add_ciphertexts(*self_var, *ct_var);
-
             // compute the new encoder
             if self_enc.is_valid() && ct_enc.is_valid() {
                 self_enc.o += ct_enc.o;
@@ -1190,16 +1455,15 @@ impl VectorRLWE {
             }
         }
 
+        // compute the new variances
+        self.variances = npe::rlwe::add_uncorrelated(&self.variances, &ct.variances);
+
         // update the Encoder list
-        for (self_enc, ct_enc, self_var, ct_var) in izip!(
+        for (self_enc, ct_enc, self_var) in izip!(
             self.encoders.iter_mut(),
             ct.encoders.iter(),
-            self.variances.iter_mut(),
-            ct.variances.iter()
+            self.variances.iter()
         ) {
-            // compute the new variance
-            *self_var = npe::add_ciphertexts(*self_var, *ct_var);
-
             // compute the new encoder
             if self_enc.is_valid() && ct_enc.is_valid() {
                 self_enc.o -= ct_enc.o + ct_enc.delta;
@@ -1218,6 +1482,138 @@ impl VectorRLWE {
         Ok(())
     }
 
+    /// Compute an addition between two VectorRLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorRLWE struct
+    ///
+    /// # Output
+    /// * a new VectorRLWE
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * PolynomialSizeError - if the ciphertexts have incompatible polynomial size
+    /// * PaddingError - if the ciphertexts ave incompatible paddings
+    /// * NotEnoughPaddingError - if there is no padding
+    /// * DeltaError - if the ciphertexts have incompatile deltas
+    pub fn add_with_padding_exact(
+        &self,
+        ct: &crate::VectorRLWE,
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.add_with_padding_exact_inplace(ct)?;
+        Ok(res)
+    }
+
+    /// Compute an addition between two VectorRLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorRLWE struct
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * PolynomialSizeError - if the ciphertexts have incompatible polynomial size
+    /// * PaddingError - if the ciphertexts ave incompatible paddings
+    /// * NotEnoughPaddingError - if there is no padding
+    /// * DeltaError - if the ciphertexts have incompatile deltas
+    pub fn add_with_padding_exact_inplace(
+        &mut self,
+        ct: &crate::VectorRLWE,
+    ) -> Result<(), CryptoAPIError> {
+        let precisions = self.get_exact_precisions(ct);
+        self.add_with_padding_inplace(ct)?;
+        self.set_precisions(&precisions)
+    }
+
+    /// Compute a subtraction between two VectorRLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorRLWE struct
+    ///
+    /// # Output
+    /// * a new VectorRLWE
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * PolynomialSizeError - if the ciphertexts have incompatible polynomial size
+    /// * PaddingError - if the ciphertexts ave incompatible paddings
+    /// * NotEnoughPaddingError - if there is no padding
+    /// * DeltaError - if the ciphertexts have incompatile deltas
+    pub fn sub_with_padding_exact(
+        &self,
+        ct: &crate::VectorRLWE,
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.sub_with_padding_exact_inplace(ct)?;
+        Ok(res)
+    }
+
+    /// Compute a subtraction between two VectorRLWE ciphertexts by eating one bit of padding.
+    /// Note that the number of bits of message increases: max(nb1,nb2) + 1
+    ///
+    /// # Argument
+    /// * `ct` - an VectorRLWE struct
+    ///
+    /// # Output
+    /// * DimensionError - if the ciphertexts have incompatible dimensions
+    /// * PolynomialSizeError - if the ciphertexts have incompatible polynomial size
+    /// * PaddingError - if the ciphertexts ave incompatible paddings
+    /// * NotEnoughPaddingError - if there is no padding
+    /// * DeltaError - if the ciphertexts have incompatile deltas
+    pub fn sub_with_padding_exact_inplace(
+        &mut self,
+        ct: &crate::VectorRLWE,
+    ) -> Result<(), CryptoAPIError> {
+        let precisions = self.get_exact_precisions(ct);
+        self.sub_with_padding_inplace(ct)?;
+        self.set_precisions(&precisions)
+    }
+
+    /// Return the precisions of exact additions or subtractions between the coefficients of self and ct:
+    /// max(nb1,nb2) + 1 when both encoders are valid
+    fn get_exact_precisions(&self, ct: &crate::VectorRLWE) -> Vec<Option<usize>> {
+        self.encoders
+            .iter()
+            .zip(ct.encoders.iter())
+            .map(|(enc_1, enc_2)| {
+                if enc_1.is_valid() && enc_2.is_valid() {
+                    Some(usize::max(enc_1.nb_bit_precision, enc_2.nb_bit_precision) + 1)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Set the precisions of the encoders, then reduce them according to the variances
+    fn set_precisions(&mut self, precisions: &[Option<usize>]) -> Result<(), CryptoAPIError> {
+        for (enc, var, precision) in izip!(
+            self.encoders.iter_mut(),
+            self.variances.iter(),
+            precisions.iter()
+        ) {
+            if let Some(precision) = precision {
+                enc.nb_bit_precision = *precision;
+                enc.update_precision_from_variance(*var)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Multiply VectorRLWE ciphertexts with small integer messages and does not change the encoding but changes the bodies and masks of the ciphertexts
+    /// # Argument
+    /// * `messages` - a list of integer messages, one for each RLWE ciphertext
+    ///
+    /// # Output
+    /// * a new VectorRLWE
+    pub fn mul_constant_static_encoder(
+        &self,
+        messages: &[i32],
+    ) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.mul_constant_static_encoder_inplace(messages)?;
+        Ok(res)
+    }
+
     /// Multiply VectorRLWE ciphertexts with small integer messages and does not change the encoding but changes the bodies and masks of the ciphertexts
     /// # Argument
     /// * `messages` - a list of integer messages as Torus elements
@@ -1239,6 +1635,10 @@ impl VectorRLWE {
             // compute the absolute value
             let m_abs = m.abs();
 
+            // call to the NPE to estimate the new variances
+            let new_variances = <Torus as npe::RLWE>::constant_mult(variance_list, m_abs as Torus);
+            variance_list.copy_from_slice(&new_variances);
+
             for (coeff, enc, var) in izip!(
                 ciphertext
                     .as_mut_slice()
@@ -1249,7 +1649,7 @@ impl VectorRLWE {
                     .unwrap()
                     .iter_mut(),
                 encoder_list.iter_mut(),
-                variance_list.iter_mut(),
+                variance_list.iter(),
             ) {
                 if enc.is_valid() {
                     // compute correction
@@ -1259,9 +1659,6 @@ impl VectorRLWE {
                     // apply correction
                     *coeff = coeff.wrapping_sub(cor);
                 }
-                // call to the NPE to estimate the new variance
-                *var = npe::LWE::single_scalar_mul(*var, m_abs as Torus);
-
                 if m_abs != 0 {
                     // update the encoder precision based on the variance
                     enc.update_precision_from_variance(*var)?;
@@ -1271,6 +1668,82 @@ impl VectorRLWE {
         Ok(())
     }
 
+    /// Compute the opposite of all the RLWE ciphertexts in the structure,
+    /// the coefficients with an invalid encoder are left unchanged
+    ///
+    /// # Output
+    /// * a new VectorRLWE
+    pub fn opposite(&self) -> Result<crate::VectorRLWE, CryptoAPIError> {
+        let mut res = self.clone();
+        res.opposite_inplace()?;
+        Ok(res)
+    }
+
+    /// Compute the opposite of all the RLWE ciphertexts in the structure,
+    /// the coefficients with an invalid encoder are left unchanged
+    ///
+    /// # Example
+    /// ```rust
+    /// use concrete::*;
+    ///
+    /// // encoder
+    /// let encoder = Encoder::new(-150., 204., 6, 1).unwrap();
+    ///
+    /// // generate a secret key
+    /// let secret_key = RLWESecretKey::new(&RLWE128_1024_1);
+    ///
+    /// // encode and encrypt
+    /// let mut ciphertext =
+    ///     VectorRLWE::encode_encrypt(&secret_key, &[-106.276, 104.3], &encoder).unwrap();
+    ///
+    /// ciphertext.opposite_inplace().unwrap();
+    /// ```
+    pub fn opposite_inplace(&mut self) -> Result<(), CryptoAPIError> {
+        // compute the opposite
+        self.ciphertexts
+            .as_mut_tensor()
+            .update_with(|a| *a = a.wrapping_neg());
+
+        // add the correction on the coefficients of the bodies
+        for (mut ciphertext, encoder_list) in izip!(
+            self.ciphertexts
+                .as_mut_tensor()
+                .subtensor_iter_mut((self.dimension + 1) * self.polynomial_size),
+            self.encoders.chunks_mut(self.polynomial_size),
+        ) {
+            for (coeff, enc) in izip!(
+                ciphertext
+                    .as_mut_slice()
+                    .get_mut(
+                        (self.dimension * self.polynomial_size)
+                            ..((self.dimension + 1) * self.polynomial_size),
+                    )
+                    .unwrap()
+                    .iter_mut(),
+                encoder_list.iter_mut()
+            ) {
+                if enc.is_valid() {
+                    let mut cor: Torus = 0;
+                    if enc.nb_bit_padding > 0 {
+                        cor = (1 << (<Torus as Numeric>::BITS - enc.nb_bit_padding))
+                            - (1 << (<Torus as Numeric>::BITS
+                                - enc.nb_bit_padding
+                                - enc.nb_bit_precision));
+                    } else {
+                        cor = cor.wrapping_sub(
+                            1 << (<Torus as Numeric>::BITS - enc.nb_bit_precision),
+                        );
+                    }
+                    *coeff = coeff.wrapping_add(cor);
+
+                    // change the encoder
+                    enc.opposite_inplace()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Multiply each VectorRLWE ciphertext with a real constant and do change the encoding and the ciphertexts by consuming some bits of padding
     /// it needs to have the same number of constant than ciphertexts
     /// it also needs that the input encoding all contained zero in their intervals
@@ -1386,6 +1859,10 @@ impl VectorRLWE {
             // compute  the rounding error on c_abs
             let rounding_error = (discret_c_abs - c_abs).abs();
 
+            // call to the NPE to estimate the new variances
+            let new_variances = <Torus as npe::RLWE>::constant_mult(variances, scal);
+            variances.copy_from_slice(&new_variances);
+
             // post mul correction and new encoders
             for (b, encoder, var) in izip!(
                 ciphertext
@@ -1394,10 +1871,8 @@ impl VectorRLWE {
                     .unwrap()
                     .iter_mut(),
                 encoders.iter_mut(),
-                variances.iter_mut()
+                variances.iter()
             ) {
-                // call to the NPE to estimate the new variance
-                *var = npe::LWE::single_scalar_mul(*var, scal);
                 if scal != 0 {
                     // update the encoder precision based on the variance
                     encoder.update_precision_from_variance(*var)?;
@@ -578,3 +578,58 @@ fn test_encode_encrypt_on_cst_x_mul_constant_with_padding_inplace_x_decrypt_with
     }
     assert_eq!(cpt, nb_messages);
 }
+
+#[test]
+fn test_encode_encrypt_packed_x_operators_x_decrypt() {
+    use crate::traits::{GenericPolicyOps, OperatorPolicy};
+
+    // settings: the messages stay in the intervals of the centered operations
+    let nb_messages: usize = random_index!(1023) + 1;
+    let encoder = crate::Encoder::new(-5., 5., 6, 4).unwrap();
+    let sk = crate::RLWESecretKey::new(&crate::RLWE128_1024_1);
+    let messages_1: Vec<f64> = random_messages!(-1.5, 1.5, nb_messages);
+    let messages_2: Vec<f64> = random_messages!(-1.5, 1.5, nb_messages);
+    let ciphertext_1 =
+        crate::VectorRLWE::encode_encrypt_packed(&sk, &messages_1, &encoder).unwrap();
+    let ciphertext_2 =
+        crate::VectorRLWE::encode_encrypt_packed(&sk, &messages_2, &encoder).unwrap();
+
+    let check = |ct: &crate::VectorRLWE, f: &dyn Fn(f64, f64) -> f64| {
+        let decryptions = ct.decrypt_decode(&sk).unwrap();
+        assert_eq!(decryptions.len(), nb_messages);
+        for (m1, m2, d, e) in izip!(
+            messages_1.iter(),
+            messages_2.iter(),
+            decryptions.iter(),
+            ct.encoders.iter()
+        ) {
+            assert_eq_granularity!(f(*m1, *m2), d, e);
+        }
+    };
+
+    // operations between ciphertexts
+    check(&(&ciphertext_1 + &ciphertext_2), &|m1, m2| m1 + m2);
+    check(&(&ciphertext_1 - &ciphertext_2), &|m1, m2| m1 - m2);
+    let mut ct = ciphertext_1.clone();
+    ct += &ciphertext_2;
+    check(&ct, &|m1, m2| m1 + m2);
+
+    for policy in [
+        OperatorPolicy::Padding,
+        OperatorPolicy::Centered,
+        OperatorPolicy::Exact,
+    ]
+    .iter()
+    {
+        let mut ct = ciphertext_1.clone();
+        ct.add_with_policy_inplace(&ciphertext_2, *policy).unwrap();
+        check(&ct, &|m1, m2| m1 + m2);
+        let ct = ciphertext_1.sub_with_policy(&ciphertext_2, *policy).unwrap();
+        check(&ct, &|m1, m2| m1 - m2);
+    }
+
+    // operations with constants
+    check(&(-&ciphertext_1), &|m1, _| -m1);
+    check(&(&ciphertext_1 * 3), &|m1, _| 3. * m1);
+    check(&(&ciphertext_1 - 2.5), &|m1, _| m1 - 2.5);
+}